@@ -48,6 +48,8 @@ impl DemoPlayerPage {
                     speed: &Default::default(),
                     state: &mut self.state,
                     name: "example_demo",
+                    camera: &Default::default(),
+                    stages: &[],
                 },
             ),
             ui_state,
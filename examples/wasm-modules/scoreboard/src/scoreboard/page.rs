@@ -114,6 +114,7 @@ impl Scoreboard {
                     ping: Duration::from_millis(999),
                     ..Default::default()
                 }),
+                stats: None,
             });
 
             if i % 3 == 0 {
@@ -179,6 +180,7 @@ impl Scoreboard {
                     ping: Duration::from_millis(999),
                     ..Default::default()
                 }),
+                stats: None,
             });
             if i % 3 == 0 {
                 blue_stages.insert(
@@ -241,6 +243,7 @@ impl Scoreboard {
                     ping: Duration::from_millis(999),
                     ..Default::default()
                 }),
+                stats: None,
             });
         }
         client_ui::scoreboard::main_frame::render(
@@ -269,6 +272,7 @@ impl Scoreboard {
                                 score_limit: 50,
                                 time_limit: Some(Duration::from_secs(60 * 60)),
                             },
+                            match_over: false,
                         },
                     },
                     character_infos: &character_infos,
@@ -25,6 +25,7 @@ use config::{
 };
 use egui::Color32;
 use game_config::config::ConfigGame;
+use game_interface::types::weapons::WeaponType;
 use hiarc::{hiarc_safer_rc_refcell, Hiarc};
 use ui_base::ui::UiCreator;
 
@@ -36,6 +37,10 @@ pub enum LocalConsoleEvent {
         addresses: Vec<SocketAddr>,
         can_start_local_server: bool,
     },
+    /// Disconnect from the current server
+    Disconnect,
+    /// Connect to the last server again
+    Reconnect,
     /// A bind command was executed
     Bind {
         // The bind was added to the player's profile
@@ -62,9 +67,52 @@ pub enum LocalConsoleEvent {
         name: String,
     },
     LocalPlayerAction(BindActionsLocalPlayer),
+    /// Start a manual demo recording
+    RecordDemo,
+    /// Stop the manual demo recording
+    StopRecordDemo,
+    /// Add a marker at the current time point to all active demo recordings
+    DemoMarker,
+    /// Play the demo at the given path
+    PlayDemo {
+        path: PathBuf,
+    },
+    Screenshot,
+    /// An action of the tool-assisted run mode
+    Tas(TasAction),
     Quit,
 }
 
+/// Changes to the input of the next tick in the tool-assisted run mode.
+#[derive(Debug, Hiarc, Clone, Copy)]
+pub enum TasInputEdit {
+    Dir(i32),
+    Jump(bool),
+    Hook(bool),
+    Fire(bool),
+    CursorX(f64),
+    CursorY(f64),
+    Weapon(WeaponType),
+}
+
+#[derive(Debug, Hiarc)]
+pub enum TasAction {
+    /// Start or stop the tool-assisted run mode
+    Toggle,
+    /// Pause or continue the simulation
+    TogglePause,
+    /// Simulate the given amount of ticks
+    Step { ticks: u64 },
+    /// Go back the given amount of ticks
+    Rewind { ticks: u64 },
+    /// Throw away all inputs after the current tick
+    Cut,
+    /// Edit the input of the next tick
+    Input(TasInputEdit),
+    /// Export the inputs and a demo under the given name
+    Export { name: String },
+}
+
 #[hiarc_safer_rc_refcell]
 #[derive(Debug, Default, Hiarc)]
 pub struct LocalConsoleEvents {
@@ -848,6 +896,300 @@ impl LocalConsoleBuilder {
             allows_partial_cmds: false,
         }));
 
+        let console_events_cmd = console_events.clone();
+        list.push(ConsoleEntry::Cmd(ConsoleEntryCmd {
+            name: "disconnect".into(),
+            usage: "disconnect".into(),
+            description: "Disconnects from the current server.".into(),
+            cmd: Rc::new(move |_, _, _, _| {
+                console_events_cmd.push(LocalConsoleEvent::Disconnect);
+                Ok("".to_string())
+            }),
+            args: Default::default(),
+            allows_partial_cmds: false,
+        }));
+
+        let console_events_cmd = console_events.clone();
+        list.push(ConsoleEntry::Cmd(ConsoleEntryCmd {
+            name: "reconnect".into(),
+            usage: "reconnect".into(),
+            description: "Connects to the last server again.".into(),
+            cmd: Rc::new(move |_, _, _, _| {
+                console_events_cmd.push(LocalConsoleEvent::Reconnect);
+                Ok("".to_string())
+            }),
+            args: Default::default(),
+            allows_partial_cmds: false,
+        }));
+
+        let console_events_cmd = console_events.clone();
+        list.push(ConsoleEntry::Cmd(ConsoleEntryCmd {
+            name: "record".into(),
+            usage: "record".into(),
+            description: "Starts recording a demo of the current game.".into(),
+            cmd: Rc::new(move |_, _, _, _| {
+                console_events_cmd.push(LocalConsoleEvent::RecordDemo);
+                Ok("".to_string())
+            }),
+            args: Default::default(),
+            allows_partial_cmds: false,
+        }));
+
+        let console_events_cmd = console_events.clone();
+        list.push(ConsoleEntry::Cmd(ConsoleEntryCmd {
+            name: "stoprecord".into(),
+            usage: "stoprecord".into(),
+            description: "Stops the demo recording that was started with `record`.".into(),
+            cmd: Rc::new(move |_, _, _, _| {
+                console_events_cmd.push(LocalConsoleEvent::StopRecordDemo);
+                Ok("".to_string())
+            }),
+            args: Default::default(),
+            allows_partial_cmds: false,
+        }));
+
+        let console_events_cmd = console_events.clone();
+        list.push(ConsoleEntry::Cmd(ConsoleEntryCmd {
+            name: "add_demomarker".into(),
+            usage: "add_demomarker".into(),
+            description: "Adds a marker at the current time to all running demo recordings."
+                .into(),
+            cmd: Rc::new(move |_, _, _, _| {
+                console_events_cmd.push(LocalConsoleEvent::DemoMarker);
+                Ok("".to_string())
+            }),
+            args: Default::default(),
+            allows_partial_cmds: false,
+        }));
+
+        let console_events_cmd = console_events.clone();
+        list.push(ConsoleEntry::Cmd(ConsoleEntryCmd {
+            name: "play".into(),
+            usage: "play <file_path>".into(),
+            description: "Plays a demo file, relative paths are looked up in the demos directory."
+                .into(),
+            cmd: Rc::new(move |_, _, _, path| {
+                let (Syn::Text(file_path_str), _) = path
+                    .first()
+                    .ok_or_else(|| anyhow!("expected a file path, but found nothing"))?
+                else {
+                    return Err(anyhow!("Expected a text that represents the file path"));
+                };
+                let mut file_path: PathBuf = file_path_str.into();
+                if file_path.is_relative() && !file_path.starts_with("demos") {
+                    file_path = PathBuf::from("demos").join(file_path);
+                }
+                if file_path.extension().is_none() {
+                    file_path.set_extension("twdemo");
+                }
+                let res = format!("Playing demo {}", file_path.to_string_lossy());
+                console_events_cmd.push(LocalConsoleEvent::PlayDemo { path: file_path });
+                Ok(res)
+            }),
+            args: vec![CommandArg {
+                ty: CommandArgType::Text,
+                user_ty: None,
+            }],
+            allows_partial_cmds: false,
+        }));
+
+        let console_events_cmd = console_events.clone();
+        list.push(ConsoleEntry::Cmd(ConsoleEntryCmd {
+            name: "screenshot".into(),
+            usage: "screenshot".into(),
+            description: "Takes a screenshot and saves it to the screenshots directory.".into(),
+            cmd: Rc::new(move |_, _, _, _| {
+                console_events_cmd.push(LocalConsoleEvent::Screenshot);
+                Ok("".to_string())
+            }),
+            args: Default::default(),
+            allows_partial_cmds: false,
+        }));
+
+        let console_events_cmd = console_events.clone();
+        list.push(ConsoleEntry::Cmd(ConsoleEntryCmd {
+            name: "tas".into(),
+            usage: "tas".into(),
+            description: "Starts or stops the tool-assisted run mode. \
+                Only works on the local server."
+                .into(),
+            cmd: Rc::new(move |_, _, _, _| {
+                console_events_cmd.push(LocalConsoleEvent::Tas(TasAction::Toggle));
+                Ok("".to_string())
+            }),
+            args: Default::default(),
+            allows_partial_cmds: false,
+        }));
+
+        let console_events_cmd = console_events.clone();
+        list.push(ConsoleEntry::Cmd(ConsoleEntryCmd {
+            name: "tas_pause".into(),
+            usage: "tas_pause".into(),
+            description: "Pauses or continues the simulation of the tool-assisted run.".into(),
+            cmd: Rc::new(move |_, _, _, _| {
+                console_events_cmd.push(LocalConsoleEvent::Tas(TasAction::TogglePause));
+                Ok("".to_string())
+            }),
+            args: Default::default(),
+            allows_partial_cmds: false,
+        }));
+
+        let console_events_cmd = console_events.clone();
+        list.push(ConsoleEntry::Cmd(ConsoleEntryCmd {
+            name: "tas_step".into(),
+            usage: "tas_step <ticks>".into(),
+            description: "Simulates the given amount of ticks of the tool-assisted run.".into(),
+            cmd: Rc::new(move |_, _, _, path| {
+                let (Syn::Number(ticks), _) = path
+                    .first()
+                    .ok_or_else(|| anyhow!("expected an amount of ticks, but found nothing"))?
+                else {
+                    return Err(anyhow!("Expected an amount of ticks"));
+                };
+                let ticks: u64 = ticks.parse()?;
+                console_events_cmd.push(LocalConsoleEvent::Tas(TasAction::Step { ticks }));
+                Ok("".to_string())
+            }),
+            args: vec![CommandArg {
+                ty: CommandArgType::Number,
+                user_ty: None,
+            }],
+            allows_partial_cmds: false,
+        }));
+
+        let console_events_cmd = console_events.clone();
+        list.push(ConsoleEntry::Cmd(ConsoleEntryCmd {
+            name: "tas_rewind".into(),
+            usage: "tas_rewind <ticks>".into(),
+            description: "Goes back the given amount of ticks in the tool-assisted run. \
+                The inputs of the following ticks are kept."
+                .into(),
+            cmd: Rc::new(move |_, _, _, path| {
+                let (Syn::Number(ticks), _) = path
+                    .first()
+                    .ok_or_else(|| anyhow!("expected an amount of ticks, but found nothing"))?
+                else {
+                    return Err(anyhow!("Expected an amount of ticks"));
+                };
+                let ticks: u64 = ticks.parse()?;
+                console_events_cmd.push(LocalConsoleEvent::Tas(TasAction::Rewind { ticks }));
+                Ok("".to_string())
+            }),
+            args: vec![CommandArg {
+                ty: CommandArgType::Number,
+                user_ty: None,
+            }],
+            allows_partial_cmds: false,
+        }));
+
+        let console_events_cmd = console_events.clone();
+        list.push(ConsoleEntry::Cmd(ConsoleEntryCmd {
+            name: "tas_cut".into(),
+            usage: "tas_cut".into(),
+            description: "Throws away all inputs after the current tick of the tool-assisted run, \
+                so the following ticks use the live input again."
+                .into(),
+            cmd: Rc::new(move |_, _, _, _| {
+                console_events_cmd.push(LocalConsoleEvent::Tas(TasAction::Cut));
+                Ok("".to_string())
+            }),
+            args: Default::default(),
+            allows_partial_cmds: false,
+        }));
+
+        let console_events_cmd = console_events.clone();
+        list.push(ConsoleEntry::Cmd(ConsoleEntryCmd {
+            name: "tas_input".into(),
+            usage: "tas_input <dir|jump|hook|fire|cursor_x|cursor_y|weapon> <value>".into(),
+            description: "Edits the input of the active player for the next tick \
+                of the tool-assisted run."
+                .into(),
+            cmd: Rc::new(move |_, _, _, path| {
+                let (Syn::Text(field), _) = path
+                    .first()
+                    .ok_or_else(|| anyhow!("expected an input field, but found nothing"))?
+                else {
+                    return Err(anyhow!("Expected the name of an input field"));
+                };
+                let (Syn::Text(val), _) = path
+                    .get(1)
+                    .ok_or_else(|| anyhow!("expected a value, but found nothing"))?
+                else {
+                    return Err(anyhow!("Expected a value"));
+                };
+                let parse_bool = |val: &str| match val {
+                    "1" | "true" | "on" => Ok(true),
+                    "0" | "false" | "off" => Ok(false),
+                    _ => Err(anyhow!("Expected 0 or 1")),
+                };
+                let edit = match field.as_str() {
+                    "dir" => TasInputEdit::Dir(val.parse::<i32>()?.clamp(-1, 1)),
+                    "jump" => TasInputEdit::Jump(parse_bool(val)?),
+                    "hook" => TasInputEdit::Hook(parse_bool(val)?),
+                    "fire" => TasInputEdit::Fire(parse_bool(val)?),
+                    "cursor_x" => TasInputEdit::CursorX(val.parse()?),
+                    "cursor_y" => TasInputEdit::CursorY(val.parse()?),
+                    "weapon" => TasInputEdit::Weapon(match val.as_str() {
+                        "hammer" => WeaponType::Hammer,
+                        "gun" => WeaponType::Gun,
+                        "shotgun" => WeaponType::Shotgun,
+                        "grenade" => WeaponType::Grenade,
+                        "laser" => WeaponType::Laser,
+                        _ => return Err(anyhow!("Unknown weapon {val}")),
+                    }),
+                    _ => return Err(anyhow!("Unknown input field {field}")),
+                };
+                console_events_cmd.push(LocalConsoleEvent::Tas(TasAction::Input(edit)));
+                Ok("".to_string())
+            }),
+            args: vec![
+                CommandArg {
+                    ty: CommandArgType::TextFrom(vec![
+                        "dir".try_into().unwrap(),
+                        "jump".try_into().unwrap(),
+                        "hook".try_into().unwrap(),
+                        "fire".try_into().unwrap(),
+                        "cursor_x".try_into().unwrap(),
+                        "cursor_y".try_into().unwrap(),
+                        "weapon".try_into().unwrap(),
+                    ]),
+                    user_ty: None,
+                },
+                CommandArg {
+                    ty: CommandArgType::Text,
+                    user_ty: None,
+                },
+            ],
+            allows_partial_cmds: false,
+        }));
+
+        let console_events_cmd = console_events.clone();
+        list.push(ConsoleEntry::Cmd(ConsoleEntryCmd {
+            name: "tas_export".into(),
+            usage: "tas_export <name>".into(),
+            description: "Exports the inputs of the tool-assisted run to the tas directory \
+                and records it as demo."
+                .into(),
+            cmd: Rc::new(move |_, _, _, path| {
+                let (Syn::Text(name), _) = path
+                    .first()
+                    .ok_or_else(|| anyhow!("expected a name, but found nothing"))?
+                else {
+                    return Err(anyhow!("Expected a text that represents the name"));
+                };
+                let res = format!("Exporting tool-assisted run {name}");
+                console_events_cmd.push(LocalConsoleEvent::Tas(TasAction::Export {
+                    name: name.clone(),
+                }));
+                Ok(res)
+            }),
+            args: vec![CommandArg {
+                ty: CommandArgType::Text,
+                user_ty: None,
+            }],
+            allows_partial_cmds: false,
+        }));
+
         list.push(ConsoleEntry::Cmd(ConsoleEntryCmd {
             name: "quit".into(),
             usage: "quit the client".into(),
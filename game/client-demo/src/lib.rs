@@ -13,7 +13,7 @@ use std::{
 };
 
 use anyhow::anyhow;
-pub use av_encoder::types::EncoderSettings;
+pub use av_encoder::types::{EncoderKind, EncoderSettings};
 use av_encoder::{traits::AudioVideoEncoder, AvEncoder};
 use base::system::{System, SystemTime, SystemTimeInterface};
 use base_io::{io::Io, runtime::IoRuntimeTask};
@@ -23,7 +23,9 @@ use client_render_game::render_game::{
     RenderForPlayer, RenderGameCreateOptions, RenderGameForPlayer, RenderGameInput,
     RenderGameInterface, RenderGameSettings, RenderModTy, RenderPlayerCameraMode,
 };
-use client_ui::demo_player::user_data::{DemoViewerEvent, DemoViewerUiState, UserData};
+use client_ui::demo_player::user_data::{
+    DemoViewerCamera, DemoViewerEvent, DemoViewerUiState, UserData,
+};
 use config::config::ConfigEngine;
 use demo::{
     recorder::{DemoRecorder, DemoRecorderCreateProps, DemoRecorderCreatePropsBase},
@@ -33,7 +35,15 @@ use demo::{
 use egui::{FontDefinitions, Rect};
 use game_base::{assets_url::HTTP_RESOURCE_URL, game_types::intra_tick_time_to_ratio};
 use game_config::config::{ConfigGame, ConfigMap, ConfigRender, ConfigSoundRender};
-use game_interface::{interface::GameStateInterface, types::game::GameTickType};
+use game_interface::{
+    interface::GameStateInterface,
+    types::{
+        auto_director::{AutoDirector, AutoDirectorCandidate},
+        game::GameTickType,
+        id_types::{PlayerId, StageId},
+        render::stage::StageRenderInfo,
+    },
+};
 use graphics::{
     graphics::graphics::Graphics,
     handles::{
@@ -47,7 +57,7 @@ use graphics_types::rendering::{BlendType, ColorMaskMode, State};
 use hiarc::hi_closure;
 use math::math::vector::{ffixed, ubvec4, vec2};
 use pool::datatypes::{
-    PoolBTreeMap, PoolFxLinkedHashMap, PoolFxLinkedHashSet, PoolVec, PoolVecDeque,
+    PoolBTreeMap, PoolFxHashSet, PoolFxLinkedHashMap, PoolFxLinkedHashSet, PoolVec, PoolVecDeque,
 };
 use pool::mt_datatypes::PoolCow as MtPoolCow;
 use serde::de::DeserializeOwned;
@@ -59,6 +69,9 @@ use sound_backend::sound_backend::SoundBackend;
 use ui::render::{DemoPlayerUiRender, DemoPlayerUiRenderPipe};
 use ui_base::ui::UiCreator;
 
+/// How long the auto director follows a character at least.
+const AUTO_DIRECTOR_MIN_FOLLOW_SECS: u64 = 3;
+
 #[derive(Debug, Clone)]
 pub struct DemoContainer {
     /// The const sized header
@@ -79,6 +92,13 @@ pub struct DemoViewerInner {
     is_closed: bool,
     is_paused: bool,
     speed: ffixed,
+
+    camera: DemoViewerCamera,
+    director: AutoDirector,
+    /// The monotonic tick the auto director was last updated in.
+    director_tick: Option<GameTickType>,
+    /// All stages of the last rendered tick.
+    stages: Vec<StageId>,
 }
 
 impl DemoViewerInner {
@@ -379,6 +399,11 @@ impl DemoViewerImpl {
                 // Always paused
                 is_paused: true,
                 speed: ffixed::from_num(1.0),
+
+                camera: Default::default(),
+                director: Default::default(),
+                director_tick: None,
+                stages: Default::default(),
             },
             should_show_preview: None,
             inner: DemoViewerInner {
@@ -392,6 +417,11 @@ impl DemoViewerImpl {
                 is_closed: false,
                 is_paused: false,
                 speed: ffixed::from_num(1.0),
+
+                camera: Default::default(),
+                director: Default::default(),
+                director_tick: None,
+                stages: Default::default(),
             },
 
             demo_ui: DemoPlayerUiRender::new(graphics, ui_creator),
@@ -424,6 +454,65 @@ impl DemoViewerImpl {
         render.clear_render_state();
     }
 
+    /// Translates the camera picked in the ui to the camera mode
+    /// used for rendering.
+    fn camera_mode(
+        viewer: &mut DemoViewerInner,
+        stages: &PoolFxLinkedHashMap<StageId, StageRenderInfo>,
+        player_id: &PlayerId,
+        monotonic_tick: GameTickType,
+    ) -> RenderPlayerCameraMode {
+        // if no character can be found, stay at the recorded player
+        let fallback_pos = stages
+            .values()
+            .find_map(|stage| stage.world.characters.get(player_id))
+            .map(|character| character.lerped_pos)
+            .unwrap_or_default();
+        let mut character_ids = PoolFxHashSet::new_without_pool();
+        match viewer.camera {
+            DemoViewerCamera::Recorded => return RenderPlayerCameraMode::Default,
+            DemoViewerCamera::Stage(stage_id) => {
+                character_ids.extend(
+                    stages
+                        .get(&stage_id)
+                        .into_iter()
+                        .flat_map(|stage| stage.world.characters.keys().copied()),
+                );
+            }
+            DemoViewerCamera::AutoDirector => {
+                let ticks_passed = viewer
+                    .director_tick
+                    .replace(monotonic_tick)
+                    .map(|tick| monotonic_tick.saturating_sub(tick))
+                    .unwrap_or_default();
+                let candidates = stages.values().flat_map(|stage| {
+                    stage
+                        .world
+                        .characters
+                        .iter()
+                        .map(|(id, character)| AutoDirectorCandidate {
+                            id: *id,
+                            vel: character.lerped_vel,
+                            carries_flag: stage
+                                .world
+                                .ctf_flags
+                                .values()
+                                .any(|flag| flag.owner_id == Some(*id)),
+                        })
+                });
+                character_ids.extend(viewer.director.update(
+                    candidates,
+                    ticks_passed,
+                    viewer.demo.header_ext.ticks_per_second.get() * AUTO_DIRECTOR_MIN_FOLLOW_SECS,
+                ));
+            }
+        }
+        RenderPlayerCameraMode::OnCharacters {
+            character_ids,
+            fallback_pos,
+        }
+    }
+
     fn prepare_render_input<'a>(
         viewer: &mut DemoViewerInner,
         data: &mut DemoStaticData,
@@ -491,7 +580,7 @@ impl DemoViewerImpl {
         let (player_id, _) = local_players.iter().next().unwrap();
         let intra_tick_time = viewer.intra_tick_time(monotonic_tick, prev_tick, next_tick);
 
-        let render_for_player = RenderForPlayer {
+        let mut render_for_player = RenderForPlayer {
             chat_info: None,
             emote_wheel_input: None,
             spectator_selection_input: None,
@@ -515,6 +604,10 @@ impl DemoViewerImpl {
 
         let stages = game.all_stages(intra_tick_ratio);
 
+        viewer.stages.clear();
+        viewer.stages.extend(stages.keys().copied());
+        render_for_player.cam_mode = Self::camera_mode(viewer, &stages, player_id, monotonic_tick);
+
         let scoreboard_info = None; // game.collect_scoreboard_info();
 
         let load_events = !last_monotonic_tick.is_some_and(|tick| tick == monotonic_tick);
@@ -712,6 +805,8 @@ impl DemoViewerImpl {
                     events: &mut self.events,
                     state: &mut self.ui_state,
                     name: &self.demo_name,
+                    camera: &self.inner.camera,
+                    stages: &self.inner.stages,
                 },
             },
             input,
@@ -844,6 +939,11 @@ impl DemoViewerImpl {
                     );
                     self.should_show_preview = Some(rect);
                 }
+                DemoViewerEvent::Camera(camera) => {
+                    self.inner.camera = camera;
+                    self.inner.director = Default::default();
+                    self.inner.director_tick = None;
+                }
                 DemoViewerEvent::Close => {
                     self.inner.is_closed = true;
                 }
@@ -0,0 +1,71 @@
+use anyhow::anyhow;
+
+/// Copies the RGBA image into the RGBA atlas at the given pixel position.
+pub(crate) fn blit_img(
+    atlas: &mut [u8],
+    atlas_width: usize,
+    x: usize,
+    y: usize,
+    img: &[u8],
+    img_width: usize,
+) {
+    let pitch = atlas_width * 4;
+    img.chunks_exact(img_width * 4)
+        .enumerate()
+        .for_each(|(row, chunk)| {
+            let offset = (y + row) * pitch + x * 4;
+            atlas[offset..offset + chunk.len()].copy_from_slice(chunk);
+        });
+}
+
+/// Makes sure a part has exactly the size its slot in the atlas has.
+pub(crate) fn check_part_size(
+    name: &str,
+    data: &[u8],
+    width: u32,
+    height: u32,
+    expected_width: usize,
+    expected_height: usize,
+) -> anyhow::Result<()> {
+    if width as usize != expected_width || height as usize != expected_height {
+        Err(anyhow!(
+            "part {name} has a size of {width}x{height}, \
+            but {expected_width}x{expected_height} was expected"
+        ))
+    } else if data.len() != width as usize * height as usize * 4 {
+        Err(anyhow!(
+            "part {name} has {} bytes of data, but is {width}x{height} RGBA",
+            data.len()
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// Calculates the size of a single segment of the atlas
+/// from a part that spans `segments_x` x `segments_y` segments.
+pub(crate) fn segment_size(
+    name: &str,
+    width: u32,
+    height: u32,
+    segments_x: usize,
+    segments_y: usize,
+) -> anyhow::Result<(usize, usize)> {
+    let (width, height) = (width as usize, height as usize);
+    if width == 0 || height == 0 || width % segments_x != 0 || height % segments_y != 0 {
+        Err(anyhow!(
+            "part {name} with a size of {width}x{height} \
+            can not be divided into {segments_x}x{segments_y} segments"
+        ))
+    } else {
+        Ok((width / segments_x, height / segments_y))
+    }
+}
+
+/// A deterministic, mostly opaque RGBA image for round trip tests.
+#[cfg(test)]
+pub(crate) fn test_atlas(width: u32, height: u32) -> Vec<u8> {
+    (0..width as usize * height as usize * 4)
+        .map(|i| if i % 4 == 3 { 255 } else { (i * 7 % 251) as u8 })
+        .collect()
+}
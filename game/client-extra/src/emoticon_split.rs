@@ -1,6 +1,8 @@
 use anyhow::anyhow;
 
-#[derive(Debug, Clone)]
+use crate::atlas::{blit_img, check_part_size, segment_size};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Emoticon06Part {
     pub data: Vec<u8>,
     pub width: u32,
@@ -17,7 +19,7 @@ impl Emoticon06Part {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
 pub struct Emoticon06ConvertResult {
     pub oop: Emoticon06Part,
     pub exclamation: Emoticon06Part,
@@ -140,3 +142,71 @@ pub fn split_06_emoticon(
         })
     }
 }
+
+/// Packs the 16 individual emoticons back into a 0.6 emoticon texture,
+/// the reverse of [`split_06_emoticon`].
+///
+/// All emoticons must have the same size.
+pub fn pack_06_emoticon(emoticons: &Emoticon06ConvertResult) -> anyhow::Result<Emoticon06Part> {
+    let (segment_width, segment_height) =
+        segment_size("oop", emoticons.oop.width, emoticons.oop.height, 1, 1)?;
+    let width = segment_width * 4;
+    let height = segment_height * 4;
+    let mut data = vec![0; width * height * 4];
+
+    let ordered = [
+        ("oop", &emoticons.oop),
+        ("exclamation", &emoticons.exclamation),
+        ("hearts", &emoticons.hearts),
+        ("drop", &emoticons.drop),
+        ("dotdot", &emoticons.dotdot),
+        ("music", &emoticons.music),
+        ("sorry", &emoticons.sorry),
+        ("ghost", &emoticons.ghost),
+        ("sushi", &emoticons.sushi),
+        ("splattee", &emoticons.splattee),
+        ("deviltee", &emoticons.deviltee),
+        ("zomg", &emoticons.zomg),
+        ("zzz", &emoticons.zzz),
+        ("wtf", &emoticons.wtf),
+        ("eyes", &emoticons.eyes),
+        ("question", &emoticons.question),
+    ];
+    for (index, (name, part)) in ordered.into_iter().enumerate() {
+        check_part_size(
+            name,
+            &part.data,
+            part.width,
+            part.height,
+            segment_width,
+            segment_height,
+        )?;
+        blit_img(
+            &mut data,
+            width,
+            (index % 4) * segment_width,
+            (index / 4) * segment_height,
+            &part.data,
+            part.width as usize,
+        );
+    }
+
+    Ok(Emoticon06Part::new(data, width, height))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::atlas::test_atlas;
+
+    use super::{pack_06_emoticon, split_06_emoticon};
+
+    #[test]
+    fn round_trip() {
+        let atlas = test_atlas(512, 512);
+        let parts = split_06_emoticon(&atlas, 512, 512).unwrap();
+        let packed = pack_06_emoticon(&parts).unwrap();
+        assert_eq!((packed.width, packed.height), (512, 512));
+        // the emoticon atlas has no unused space
+        assert!(packed.data == atlas);
+    }
+}
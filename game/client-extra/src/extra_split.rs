@@ -1,6 +1,8 @@
 use anyhow::anyhow;
 
-#[derive(Debug, Clone)]
+use crate::atlas::{blit_img, check_part_size, segment_size};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Extras06Part {
     pub data: Vec<u8>,
     pub width: u32,
@@ -17,7 +19,7 @@ impl Extras06Part {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
 pub struct Extras06ConvertResult {
     pub snowflake: Extras06Part,
     pub sparkle: Extras06Part,
@@ -79,3 +81,61 @@ pub fn split_06_extras(
         Ok(Extras06ConvertResult { snowflake, sparkle })
     }
 }
+
+/// Packs the individual components back into a 0.6 extras.png,
+/// the reverse of [`split_06_extras`].
+///
+/// Space in the atlas that is not used by any component stays transparent.
+pub fn pack_06_extras(extras: &Extras06ConvertResult) -> anyhow::Result<Extras06Part> {
+    let (segment_width, segment_height) = segment_size(
+        "snowflake",
+        extras.snowflake.width,
+        extras.snowflake.height,
+        2,
+        2,
+    )?;
+    let width = segment_width * 16;
+    let height = segment_height * 16;
+    let mut data = vec![0; width * height * 4];
+
+    let mut blit = |name: &str, part: &Extras06Part, x: usize, y: usize, w: usize, h: usize| {
+        check_part_size(
+            name,
+            &part.data,
+            part.width,
+            part.height,
+            w * segment_width,
+            h * segment_height,
+        )?;
+        blit_img(
+            &mut data,
+            width,
+            x * segment_width,
+            y * segment_height,
+            &part.data,
+            part.width as usize,
+        );
+        anyhow::Ok(())
+    };
+
+    blit("snowflake", &extras.snowflake, 0, 0, 2, 2)?;
+    blit("sparkle", &extras.sparkle, 2, 0, 2, 2)?;
+
+    Ok(Extras06Part::new(data, width, height))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::atlas::test_atlas;
+
+    use super::{pack_06_extras, split_06_extras};
+
+    #[test]
+    fn round_trip() {
+        let atlas = test_atlas(512, 512);
+        let parts = split_06_extras(&atlas, 512, 512).unwrap();
+        let packed = pack_06_extras(&parts).unwrap();
+        assert_eq!((packed.width, packed.height), (512, 512));
+        assert_eq!(split_06_extras(&packed.data, 512, 512).unwrap(), parts);
+    }
+}
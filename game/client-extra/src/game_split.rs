@@ -1,6 +1,8 @@
 use anyhow::anyhow;
 
-#[derive(Debug, Clone)]
+use crate::atlas::{blit_img, check_part_size, segment_size};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Game06Part {
     pub data: Vec<u8>,
     pub width: u32,
@@ -24,7 +26,7 @@ impl Game06Part {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
 pub struct Game06ConvertResult {
     pub cursor_hammer: Game06Part,
     pub cursor_gun: Game06Part,
@@ -644,3 +646,160 @@ pub fn split_06_game(
         })
     }
 }
+
+/// Packs the individual components back into a 0.6 game.png,
+/// the reverse of [`split_06_game`].
+///
+/// Optional components that are missing and space in the atlas
+/// that is not used by any component stay transparent.
+pub fn pack_06_game(game: &Game06ConvertResult) -> anyhow::Result<Game06Part> {
+    let (segment_width, segment_height) = segment_size(
+        "hook_chain",
+        game.hook_chain.width,
+        game.hook_chain.height,
+        1,
+        1,
+    )?;
+    let width = segment_width * 32;
+    let height = segment_height * 16;
+    let mut data = vec![0; width * height * 4];
+
+    let mut blit = |name: &str, part: &Game06Part, x: usize, y: usize, w: usize, h: usize| {
+        check_part_size(
+            name,
+            &part.data,
+            part.width,
+            part.height,
+            w * segment_width,
+            h * segment_height,
+        )?;
+        blit_img(
+            &mut data,
+            width,
+            x * segment_width,
+            y * segment_height,
+            &part.data,
+            part.width as usize,
+        );
+        anyhow::Ok(())
+    };
+
+    blit("health_full", &game.health_full, 21, 0, 2, 2)?;
+    blit("health_empty", &game.health_empty, 23, 0, 2, 2)?;
+    blit("armor_full", &game.armor_full, 21, 2, 2, 2)?;
+    blit("armor_empty", &game.armor_empty, 23, 2, 2, 2)?;
+
+    blit("star1", &game.star1, 15, 0, 2, 2)?;
+    blit("star2", &game.star2, 17, 0, 2, 2)?;
+    blit("star3", &game.star3, 19, 0, 2, 2)?;
+
+    let particles = [
+        (6, 0, 1),
+        (6, 1, 1),
+        (7, 0, 1),
+        (7, 1, 1),
+        (8, 0, 1),
+        (8, 1, 1),
+        (9, 0, 2),
+        (11, 0, 2),
+        (13, 0, 2),
+    ];
+    for (part, (x, y, size)) in game.particles.iter().zip(particles) {
+        blit("particle", part, x, y, size, size)?;
+    }
+
+    blit("cursor_hammer", &game.cursor_hammer, 0, 0, 2, 2)?;
+    blit("weapon_hammer", &game.weapon_hammer, 2, 1, 4, 3)?;
+
+    blit("cursor_gun", &game.cursor_gun, 0, 4, 2, 2)?;
+    blit("weapon_gun", &game.weapon_gun, 2, 4, 4, 2)?;
+    blit("projectile_gun", &game.projectile_gun, 6, 4, 2, 2)?;
+    for (index, muzzle) in game.muzzle_gun.iter().enumerate() {
+        blit("muzzle_gun", muzzle, 8 + index * 4, 4, 4, 2)?;
+    }
+
+    blit("cursor_shotgun", &game.cursor_shotgun, 0, 6, 2, 2)?;
+    blit("weapon_shotgun", &game.weapon_shotgun, 2, 6, 8, 2)?;
+    blit("projectile_shotgun", &game.projectile_shotgun, 10, 6, 2, 2)?;
+    for (index, muzzle) in game.muzzle_shotgun.iter().enumerate() {
+        blit("muzzle_shotgun", muzzle, 12 + index * 4, 6, 4, 2)?;
+    }
+
+    blit("cursor_grenade", &game.cursor_grenade, 0, 8, 2, 2)?;
+    blit("weapon_grenade", &game.weapon_grenade, 2, 8, 7, 2)?;
+    blit("projectile_grenade", &game.projectile_grenade, 10, 8, 2, 2)?;
+
+    blit("cursor_ninja", &game.cursor_ninja, 0, 10, 2, 2)?;
+    blit("weapon_ninja", &game.weapon_ninja, 2, 10, 8, 2)?;
+    for (index, muzzle) in game.muzzle_ninja.iter().enumerate() {
+        blit("muzzle_ninja", muzzle, 25, index * 4, 7, 4)?;
+    }
+
+    blit("cursor_laser", &game.cursor_laser, 0, 12, 2, 2)?;
+    blit("weapon_laser", &game.weapon_laser, 2, 12, 7, 3)?;
+    blit("projectile_laser", &game.projectile_laser, 10, 12, 2, 2)?;
+
+    blit("hook_chain", &game.hook_chain, 2, 0, 1, 1)?;
+    blit("hook_head", &game.hook_head, 3, 0, 2, 1)?;
+
+    blit("pickup_health", &game.pickup_health, 10, 2, 2, 2)?;
+    blit("pickup_armor", &game.pickup_armor, 12, 2, 2, 2)?;
+
+    blit("flag_blue", &game.flag_blue, 12, 8, 4, 8)?;
+    blit("flag_red", &game.flag_red, 16, 8, 4, 8)?;
+
+    let optional_parts = [
+        ("lose_shotgun", &game.lose_shotgun, 15, 2, 2, 2),
+        ("lose_grenade", &game.lose_grenade, 17, 2, 2, 2),
+        ("lose_laser", &game.lose_laser, 19, 2, 2, 2),
+        ("lose_ninja", &game.lose_ninja, 10, 10, 2, 2),
+        (
+            "ninja_bar_full_left",
+            &game.ninja_bar_full_left,
+            21,
+            4,
+            1,
+            2,
+        ),
+        ("ninja_bar_full", &game.ninja_bar_full, 22, 4, 1, 2),
+        ("ninja_bar_empty", &game.ninja_bar_empty, 23, 4, 1, 2),
+        (
+            "ninja_bar_empty_right",
+            &game.ninja_bar_empty_right,
+            24,
+            4,
+            1,
+            2,
+        ),
+    ];
+    for (name, part, x, y, w, h) in optional_parts {
+        if let Some(part) = part {
+            blit(name, part, x, y, w, h)?;
+        }
+    }
+
+    Ok(Game06Part::new(data, width, height))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::atlas::test_atlas;
+
+    use super::{pack_06_game, split_06_game};
+
+    #[test]
+    fn round_trip() {
+        let atlas = test_atlas(1024, 512);
+        let parts = split_06_game(&atlas, 1024, 512).unwrap();
+        let packed = pack_06_game(&parts).unwrap();
+        assert_eq!((packed.width, packed.height), (1024, 512));
+        let repacked_parts = split_06_game(&packed.data, 1024, 512).unwrap();
+        assert_eq!(repacked_parts, parts);
+
+        let mut parts = parts;
+        parts.lose_ninja = None;
+        let packed = pack_06_game(&parts).unwrap();
+        let repacked_parts = split_06_game(&packed.data, 1024, 512).unwrap();
+        assert_eq!(repacked_parts.lose_ninja, None);
+    }
+}
@@ -1,5 +1,6 @@
 #![allow(clippy::erasing_op)]
 #![allow(clippy::identity_op)]
+mod atlas;
 pub mod ddrace_hud_split;
 pub mod emoticon_split;
 pub mod extra_split;
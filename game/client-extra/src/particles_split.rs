@@ -1,6 +1,8 @@
 use anyhow::anyhow;
 
-#[derive(Debug, Clone)]
+use crate::atlas::{blit_img, check_part_size, segment_size};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Particles06Part {
     pub data: Vec<u8>,
     pub width: u32,
@@ -17,7 +19,7 @@ impl Particles06Part {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
 pub struct Particles06ConvertResult {
     pub slice: Particles06Part,
     pub ball: Particles06Part,
@@ -164,3 +166,65 @@ pub fn split_06_particles(
         })
     }
 }
+
+/// Packs the individual components back into a 0.6 particles.png,
+/// the reverse of [`split_06_particles`].
+///
+/// Space in the atlas that is not used by any component stays transparent.
+pub fn pack_06_particles(particles: &Particles06ConvertResult) -> anyhow::Result<Particles06Part> {
+    let (segment_width, segment_height) =
+        segment_size("slice", particles.slice.width, particles.slice.height, 1, 1)?;
+    let width = segment_width * 8;
+    let height = segment_height * 8;
+    let mut data = vec![0; width * height * 4];
+
+    let mut blit = |name: &str, part: &Particles06Part, x: usize, y: usize, w: usize, h: usize| {
+        check_part_size(
+            name,
+            &part.data,
+            part.width,
+            part.height,
+            w * segment_width,
+            h * segment_height,
+        )?;
+        blit_img(
+            &mut data,
+            width,
+            x * segment_width,
+            y * segment_height,
+            &part.data,
+            part.width as usize,
+        );
+        anyhow::Ok(())
+    };
+
+    blit("slice", &particles.slice, 0, 0, 1, 1)?;
+    blit("ball", &particles.ball, 1, 0, 1, 1)?;
+    for (index, splat) in particles.splat.iter().enumerate() {
+        blit("splat", splat, 2 + index, 0, 1, 1)?;
+    }
+
+    blit("smoke", &particles.smoke, 0, 1, 1, 1)?;
+    blit("shell", &particles.shell, 0, 2, 2, 2)?;
+    blit("explosion", &particles.explosion[0], 0, 4, 4, 4)?;
+    blit("airjump", &particles.airjump, 2, 2, 2, 2)?;
+    blit("hit", &particles.hit[0], 4, 1, 2, 2)?;
+
+    Ok(Particles06Part::new(data, width, height))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::atlas::test_atlas;
+
+    use super::{pack_06_particles, split_06_particles};
+
+    #[test]
+    fn round_trip() {
+        let atlas = test_atlas(256, 256);
+        let parts = split_06_particles(&atlas, 256, 256).unwrap();
+        let packed = pack_06_particles(&parts).unwrap();
+        assert_eq!((packed.width, packed.height), (256, 256));
+        assert_eq!(split_06_particles(&packed.data, 256, 256).unwrap(), parts);
+    }
+}
@@ -1,6 +1,8 @@
 use anyhow::anyhow;
 
-#[derive(Debug, Clone)]
+use crate::atlas::{blit_img, check_part_size, segment_size};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Skin06Part {
     pub data: Vec<u8>,
     pub width: u32,
@@ -17,7 +19,7 @@ impl Skin06Part {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
 pub struct Skin06ConvertResult {
     pub body: Skin06Part,
     pub body_outline: Skin06Part,
@@ -141,3 +143,75 @@ pub fn split_06_skin(
         })
     }
 }
+
+/// Packs the individual parts back into a 0.6 skin texture,
+/// the reverse of [`split_06_skin`].
+///
+/// The segment size is taken from the hand,
+/// all other parts must match the layout described in [`split_06_skin`].
+pub fn pack_06_skin(skin: &Skin06ConvertResult) -> anyhow::Result<Skin06Part> {
+    let (segment_width, segment_height) =
+        segment_size("hand", skin.hand.width, skin.hand.height, 1, 1)?;
+    let width = segment_width * 8;
+    let height = segment_height * 4;
+    let mut data = vec![0; width * height * 4];
+
+    let mut blit = |name: &str, part: &Skin06Part, x: usize, y: usize, w: usize, h: usize| {
+        check_part_size(
+            name,
+            &part.data,
+            part.width,
+            part.height,
+            w * segment_width,
+            h * segment_height,
+        )?;
+        blit_img(
+            &mut data,
+            width,
+            x * segment_width,
+            y * segment_height,
+            &part.data,
+            part.width as usize,
+        );
+        anyhow::Ok(())
+    };
+
+    blit("body", &skin.body, 0, 0, 3, 3)?;
+    blit("body_outline", &skin.body_outline, 3, 0, 3, 3)?;
+
+    blit("hand", &skin.hand, 6, 0, 1, 1)?;
+    blit("hand_outline", &skin.hand_outline, 7, 0, 1, 1)?;
+    blit("foot", &skin.foot, 6, 1, 2, 1)?;
+    blit("foot_outline", &skin.foot_outline, 6, 2, 2, 1)?;
+
+    blit("watermark", &skin.watermark, 0, 3, 2, 1)?;
+    blit("eye_normal", &skin.eye_normal, 2, 3, 1, 1)?;
+    blit("eye_angry", &skin.eye_angry, 3, 3, 1, 1)?;
+    blit("eye_pain", &skin.eye_pain, 4, 3, 1, 1)?;
+    blit("eye_happy", &skin.eye_happy, 5, 3, 1, 1)?;
+    blit("eye_dead", &skin.eye_dead, 6, 3, 1, 1)?;
+    blit("eye_surprised", &skin.eye_surprised, 7, 3, 1, 1)?;
+
+    Ok(Skin06Part::new(data, width, height))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::atlas::test_atlas;
+
+    use super::{pack_06_skin, split_06_skin};
+
+    #[test]
+    fn round_trip() {
+        let atlas = test_atlas(256, 128);
+        let parts = split_06_skin(&atlas, 256, 128).unwrap();
+        let packed = pack_06_skin(&parts).unwrap();
+        assert_eq!((packed.width, packed.height), (256, 128));
+        // the skin atlas has no unused space
+        assert!(packed.data == atlas);
+
+        let mut parts = parts;
+        parts.foot.width /= 2;
+        assert!(pack_06_skin(&parts).is_err());
+    }
+}
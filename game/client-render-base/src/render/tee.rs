@@ -1,8 +1,10 @@
 use client_containers::skins::{Skin, SkinMetrics, SkinTextures};
-use game_interface::types::render::character::TeeEye;
+use game_interface::types::{character_info::NetworkSkinInfo, render::character::TeeEye};
 use graphics::{
-    graphics::graphics::Graphics, handles::quad_container::quad_container::QuadContainer,
-    quad_container::Quad, streaming::quad_scope_begin,
+    graphics::graphics::Graphics,
+    handles::{quad_container::quad_container::QuadContainer, texture::texture::TextureContainer},
+    quad_container::Quad,
+    streaming::quad_scope_begin,
 };
 
 use graphics_types::rendering::{ColorRgba, State};
@@ -46,6 +48,59 @@ impl From<ubvec4> for TeeRenderSkinColor {
     }
 }
 
+/// The colors of all parts of a tee.
+#[derive(Debug, Clone, Copy)]
+pub struct TeeRenderSkinColors {
+    pub body: TeeRenderSkinColor,
+    pub marking: TeeRenderSkinColor,
+    pub decoration: TeeRenderSkinColor,
+    pub feet: TeeRenderSkinColor,
+    pub eyes: TeeRenderSkinColor,
+}
+
+impl From<NetworkSkinInfo> for TeeRenderSkinColors {
+    fn from(value: NetworkSkinInfo) -> Self {
+        let part = |color: Option<ubvec4>| {
+            color
+                .map(TeeRenderSkinColor::from)
+                .unwrap_or(TeeRenderSkinColor::Original)
+        };
+        match value {
+            NetworkSkinInfo::Original => Self {
+                body: TeeRenderSkinColor::Original,
+                marking: TeeRenderSkinColor::Original,
+                decoration: TeeRenderSkinColor::Original,
+                feet: TeeRenderSkinColor::Original,
+                eyes: TeeRenderSkinColor::Original,
+            },
+            NetworkSkinInfo::Custom {
+                body_color,
+                feet_color,
+            } => Self {
+                body: body_color.into(),
+                marking: TeeRenderSkinColor::Original,
+                decoration: body_color.into(),
+                feet: feet_color.into(),
+                // like in 0.6 the eyes follow the body color
+                eyes: body_color.into(),
+            },
+            NetworkSkinInfo::Parts {
+                body_color,
+                marking_color,
+                decoration_color,
+                feet_color,
+                eyes_color,
+            } => Self {
+                body: part(body_color),
+                marking: part(marking_color),
+                decoration: part(decoration_color),
+                feet: part(feet_color),
+                eyes: part(eyes_color),
+            },
+        }
+    }
+}
+
 trait RenderSkin {
     fn render_skin(&self, color: &TeeRenderSkinColor) -> &SkinTextures;
 }
@@ -59,10 +114,39 @@ impl RenderSkin for Skin {
     }
 }
 
+/// The skins the individual parts of a tee are rendered from.
+///
+/// Hands are always taken from the body skin.
+#[derive(Debug, Clone, Copy)]
+pub struct TeeRenderSkins<'a> {
+    pub body: &'a Skin,
+    /// `None` renders no marking.
+    pub marking: Option<&'a Skin>,
+    /// `None` renders no decoration.
+    pub decoration: Option<&'a Skin>,
+    pub feet: &'a Skin,
+    pub eyes: &'a Skin,
+}
+
+impl<'a> From<&'a Skin> for TeeRenderSkins<'a> {
+    fn from(skin: &'a Skin) -> Self {
+        Self {
+            body: skin,
+            marking: None,
+            decoration: None,
+            feet: skin,
+            eyes: skin,
+        }
+    }
+}
+
 #[derive(Debug, Hiarc)]
 pub struct TeeRenderInfo {
     pub color_body: TeeRenderSkinColor,
+    pub color_marking: TeeRenderSkinColor,
+    pub color_decoration: TeeRenderSkinColor,
     pub color_feet: TeeRenderSkinColor,
+    pub color_eyes: TeeRenderSkinColor,
 
     pub got_air_jump: bool,
     pub feet_flipped: bool,
@@ -390,30 +474,21 @@ impl RenderTee {
         }
     }
 
-    pub fn render_tee_body(
+    /// Renders a texture that uses the body quad (body, marking & decoration).
+    fn render_tee_body_texture(
         &self,
         state: &State,
         body_pos: &vec2,
         body_scale: &vec2,
         body_rotation: f32,
-        body_color: &TeeRenderSkinColor,
-        alpha: f32,
-        skin: &Skin,
+        color: &ColorRgba,
+        texture: &TextureContainer,
         outline: bool,
     ) {
         let mut quad_scope = quad_scope_begin();
         quad_scope.set_state(state);
         quad_scope.set_rotation(body_rotation * PI * 2.0);
-
-        let render_skin = skin.render_skin(body_color);
-        let body_color = body_color.unwrap(alpha);
-        // draw body
-        quad_scope.set_colors_from_single(body_color.r, body_color.g, body_color.b, body_color.a);
-        let texture = if outline {
-            &render_skin.body_outline
-        } else {
-            &render_skin.body
-        };
+        quad_scope.set_colors_from_single(color.r, color.g, color.b, color.a);
         self.tee_quad_container.render_quad_container_as_sprite(
             if !outline {
                 self.body_offset
@@ -429,6 +504,86 @@ impl RenderTee {
         );
     }
 
+    pub fn render_tee_body(
+        &self,
+        state: &State,
+        body_pos: &vec2,
+        body_scale: &vec2,
+        body_rotation: f32,
+        body_color: &TeeRenderSkinColor,
+        alpha: f32,
+        skin: &Skin,
+        outline: bool,
+    ) {
+        let render_skin = skin.render_skin(body_color);
+        let texture = if outline {
+            &render_skin.body_outline
+        } else {
+            &render_skin.body
+        };
+        self.render_tee_body_texture(
+            state,
+            body_pos,
+            body_scale,
+            body_rotation,
+            &body_color.unwrap(alpha),
+            texture,
+            outline,
+        );
+    }
+
+    /// The marking is drawn on top of the body filling.
+    pub fn render_tee_marking(
+        &self,
+        state: &State,
+        body_pos: &vec2,
+        body_scale: &vec2,
+        body_rotation: f32,
+        marking_color: &TeeRenderSkinColor,
+        alpha: f32,
+        skin: &Skin,
+    ) {
+        let render_skin = skin.render_skin(marking_color);
+        self.render_tee_body_texture(
+            state,
+            body_pos,
+            body_scale,
+            body_rotation,
+            &marking_color.unwrap(alpha),
+            &render_skin.marking,
+            false,
+        );
+    }
+
+    /// The decoration is drawn behind the body.
+    pub fn render_tee_decoration(
+        &self,
+        state: &State,
+        body_pos: &vec2,
+        body_scale: &vec2,
+        body_rotation: f32,
+        decoration_color: &TeeRenderSkinColor,
+        alpha: f32,
+        skin: &Skin,
+        outline: bool,
+    ) {
+        let render_skin = skin.render_skin(decoration_color);
+        let texture = if outline {
+            &render_skin.decoration_outline
+        } else {
+            &render_skin.decoration
+        };
+        self.render_tee_body_texture(
+            state,
+            body_pos,
+            body_scale,
+            body_rotation,
+            &decoration_color.unwrap(alpha),
+            texture,
+            outline,
+        );
+    }
+
     pub fn render_tee_eyes(
         &self,
         state: &State,
@@ -626,7 +781,7 @@ impl RenderTee {
     pub fn render_tee_from_math(
         &self,
         tee_math: &RenderTeeMath,
-        skin: &Skin,
+        skins: &TeeRenderSkins,
         info: &TeeRenderInfo,
         dir: &vec2,
         alpha: f32,
@@ -653,10 +808,10 @@ impl RenderTee {
         } = tee_math;
 
         if let Some(left_hand) = &hand_left {
-            self.render_tee_hand(left_hand, &info.color_body, skin, alpha, state);
+            self.render_tee_hand(left_hand, &info.color_body, skins.body, alpha, state);
         }
         if let Some(right_hand) = &hand_right {
-            self.render_tee_hand(right_hand, &info.color_body, skin, alpha, state);
+            self.render_tee_hand(right_hand, &info.color_body, skins.body, alpha, state);
         }
 
         // first pass we draw the outline
@@ -666,6 +821,20 @@ impl RenderTee {
 
             for f in 0..2 {
                 if f == 1 {
+                    // draw decoration
+                    if let Some(skin) = skins.decoration {
+                        self.render_tee_decoration(
+                            state,
+                            body_pos,
+                            body_scale,
+                            *body_rotation,
+                            &info.color_decoration,
+                            alpha,
+                            skin,
+                            outline == 1,
+                        );
+                    }
+
                     // draw body
                     self.render_tee_body(
                         state,
@@ -674,10 +843,23 @@ impl RenderTee {
                         *body_rotation,
                         &info.color_body,
                         alpha,
-                        skin,
+                        skins.body,
                         outline == 1,
                     );
 
+                    // draw marking
+                    if let Some(skin) = skins.marking.filter(|_| p == 1) {
+                        self.render_tee_marking(
+                            state,
+                            body_pos,
+                            body_scale,
+                            *body_rotation,
+                            &info.color_marking,
+                            alpha,
+                            skin,
+                        );
+                    }
+
                     // draw eyes
                     if p == 1 {
                         self.render_tee_eyes(
@@ -690,10 +872,10 @@ impl RenderTee {
                             eye_right_pos,
                             eye_right_scale,
                             *eye_right_rotation,
-                            &info.color_body,
-                            &info.color_body,
+                            &info.color_eyes,
+                            &info.color_eyes,
                             alpha,
-                            skin,
+                            skins.eyes,
                         );
                     }
                 }
@@ -711,7 +893,7 @@ impl RenderTee {
                     &info.color_feet,
                     &info.color_feet,
                     alpha,
-                    skin,
+                    skins.feet,
                     outline == 1,
                     info.feet_flipped,
                     info.got_air_jump,
@@ -724,7 +906,7 @@ impl RenderTee {
     pub fn render_tee(
         &self,
         anim: &AnimState,
-        skin: &Skin,
+        skins: &TeeRenderSkins,
         info: &TeeRenderInfo,
         hands: &TeeRenderHands,
         dir: &vec2,
@@ -734,7 +916,7 @@ impl RenderTee {
     ) {
         self.render_tee_from_math(
             &RenderTeeMath::new(anim, info, hands, dir, pos),
-            skin,
+            skins,
             info,
             dir,
             alpha,
@@ -1,4 +1,4 @@
-use std::{borrow::Borrow, time::Duration};
+use std::{borrow::Borrow, rc::Rc, time::Duration};
 
 use base::linked_hash_map_view::FxLinkedHashMap;
 use client_containers::{
@@ -23,7 +23,10 @@ use client_render_base::{
         },
         effects::Effects,
         particle_manager::ParticleManager,
-        tee::{RenderTee, RenderTeeHandMath, TeeRenderHands, TeeRenderInfo, TeeRenderSkinColor},
+        tee::{
+            RenderTee, RenderTeeHandMath, TeeRenderHands, TeeRenderInfo, TeeRenderSkinColors,
+            TeeRenderSkins,
+        },
         toolkit::ToolkitRender,
     },
 };
@@ -35,7 +38,7 @@ use pool::datatypes::PoolFxLinkedHashMap;
 use vanilla::collision::collision::Collision;
 
 use game_interface::types::{
-    character_info::{NetworkSkinInfo, MAX_ASSET_NAME_LEN},
+    character_info::MAX_ASSET_NAME_LEN,
     id_types::CharacterId,
     render::character::{CharacterBuff, CharacterDebuff, CharacterInfo, CharacterRenderInfo},
     resource_key::NetworkResourceKey,
@@ -73,6 +76,39 @@ pub struct PlayerRenderPipe<'a> {
     pub phased: bool,
 }
 
+/// The skins of all parts of a single tee.
+struct PlayerSkins {
+    body: Rc<Skin>,
+    marking: Option<Rc<Skin>>,
+    decoration: Option<Rc<Skin>>,
+    feet: Rc<Skin>,
+    eyes: Rc<Skin>,
+}
+
+impl PlayerSkins {
+    fn render_skins(&self) -> TeeRenderSkins<'_> {
+        TeeRenderSkins {
+            body: &self.body,
+            marking: self.marking.as_deref(),
+            decoration: self.decoration.as_deref(),
+            feet: &self.feet,
+            eyes: &self.eyes,
+        }
+    }
+}
+
+impl From<Rc<Skin>> for PlayerSkins {
+    fn from(skin: Rc<Skin>) -> Self {
+        Self {
+            body: skin.clone(),
+            marking: None,
+            decoration: None,
+            feet: skin.clone(),
+            eyes: skin,
+        }
+    }
+}
+
 /// The player component renders all hooks
 /// all weapons, and all players
 pub struct Players {
@@ -163,34 +199,44 @@ impl Players {
 
         const RENDER_TEE_SIZE: f32 = 2.0;
 
-        fn skin_colors(
-            character_info: Option<&CharacterInfo>,
-        ) -> (TeeRenderSkinColor, TeeRenderSkinColor) {
-            if let Some(NetworkSkinInfo::Custom {
-                body_color,
-                feet_color,
-            }) = character_info.map(|character_info| character_info.skin_info)
-            {
-                (body_color.into(), feet_color.into())
-            } else {
-                (TeeRenderSkinColor::Original, TeeRenderSkinColor::Original)
-            }
+        fn skin_colors(character_info: Option<&CharacterInfo>) -> TeeRenderSkinColors {
+            character_info
+                .map(|character_info| character_info.skin_info)
+                .unwrap_or_default()
+                .into()
         }
 
-        fn skin<'a>(
-            character_info: Option<&'a CharacterInfo>,
+        fn skin(
+            character_info: Option<&CharacterInfo>,
             ninja_skin: Option<Option<&NetworkResourceKey<MAX_ASSET_NAME_LEN>>>,
             freeze_skin: Option<Option<&NetworkResourceKey<MAX_ASSET_NAME_LEN>>>,
-            freezes: &'a mut FreezeContainer,
-            ninjas: &'a mut NinjaContainer,
-            skins: &'a mut SkinContainer,
-        ) -> &'a Skin {
+            freezes: &mut FreezeContainer,
+            ninjas: &mut NinjaContainer,
+            skins: &mut SkinContainer,
+        ) -> PlayerSkins {
+            // freeze & ninja skins replace all parts
             if let Some(freeze_skin) = freeze_skin {
-                &freezes.get_or_default_opt(freeze_skin).skin
+                return freezes.get_or_default_opt(freeze_skin).skin.clone().into();
             } else if let Some(ninja_skin) = ninja_skin {
-                &ninjas.get_or_default_opt(ninja_skin).skin
-            } else {
-                skins.get_or_default_opt(character_info.map(|char| &char.info.skin))
+                return ninjas.get_or_default_opt(ninja_skin).skin.clone().into();
+            }
+
+            let info = character_info.map(|char| &char.info);
+            let skin = skins
+                .get_or_default_opt(info.map(|info| &info.skin))
+                .clone();
+            let Some(parts) = info.map(|info| &info.skin_parts) else {
+                return skin.into();
+            };
+            let mut part = |key: &Option<NetworkResourceKey<MAX_ASSET_NAME_LEN>>| {
+                key.as_ref().map(|key| skins.get_or_default(key).clone())
+            };
+            PlayerSkins {
+                body: part(&parts.body).unwrap_or_else(|| skin.clone()),
+                marking: part(&parts.marking),
+                decoration: part(&parts.decoration),
+                feet: part(&parts.feet).unwrap_or_else(|| skin.clone()),
+                eyes: part(&parts.eyes).unwrap_or(skin),
             }
         }
 
@@ -222,7 +268,7 @@ impl Players {
             let freeze_skin = is_freeze.then(|| character_info.map(|char| &char.info.freeze));
             let ninja_skin = is_ninja.then(|| character_info.map(|char| &char.info.ninja));
 
-            let (color_body, _) = skin_colors(character_info);
+            let colors = skin_colors(character_info);
 
             // hook
             let hook_hand = should_render_hook
@@ -240,15 +286,16 @@ impl Players {
             if let Some(hook_hand) = hook_hand {
                 self.tee_renderer.render_tee_hand(
                     &RenderTeeHandMath::new(&pos, RENDER_TEE_SIZE, &hook_hand),
-                    &color_body,
-                    skin(
+                    &colors.body,
+                    &skin(
                         character_info,
                         ninja_skin,
                         freeze_skin,
                         freezes,
                         ninjas,
                         skins,
-                    ),
+                    )
+                    .body,
                     phased_alpha,
                     &state,
                 );
@@ -389,11 +436,14 @@ impl Players {
                 }
             }
 
-            let (color_body, color_feet) = skin_colors(character_info);
+            let colors = skin_colors(character_info);
 
             let tee_render_info = TeeRenderInfo {
-                color_body,
-                color_feet,
+                color_body: colors.body,
+                color_marking: colors.marking,
+                color_decoration: colors.decoration,
+                color_feet: colors.feet,
+                color_eyes: colors.eyes,
                 got_air_jump: character_render_info.has_air_jump,
                 feet_flipped: false,
                 size: RENDER_TEE_SIZE, // yes a tee is 2 tiles big (rendering wise)
@@ -423,7 +473,8 @@ impl Players {
                     Some(*character_id),
                 );
                 if effects.is_rate_10() {
-                    skin.sounds
+                    skin.body
+                        .sounds
                         .skid
                         .random_entry(&mut particle_manager.rng)
                         .play(
@@ -438,7 +489,7 @@ impl Players {
 
             self.tee_renderer.render_tee(
                 &anim_state,
-                skin,
+                &skin.render_skins(),
                 &tee_render_info,
                 &TeeRenderHands {
                     left: None,
@@ -563,6 +563,31 @@ impl RenderGame {
         })
     }
 
+    /// The center between all given characters that are currently rendered.
+    fn characters_center(
+        render_info: &RenderGameInput,
+        character_ids: &PoolFxHashSet<CharacterId>,
+    ) -> Option<vec2> {
+        let positions = character_ids
+            .iter()
+            .filter_map(|character_id| {
+                render_info
+                    .character_infos
+                    .get(character_id)
+                    .and_then(|c| c.stage_id)
+                    .and_then(|id| render_info.stages.get(&id))
+                    .and_then(|s| s.world.characters.get(character_id))
+                    .map(|c| c.lerped_pos)
+            })
+            .collect::<Vec<_>>();
+        (!positions.is_empty()).then(|| {
+            positions
+                .iter()
+                .fold(vec2::default(), |acc, pos| acc + *pos)
+                / positions.len() as f32
+        })
+    }
+
     fn render_ingame(
         &mut self,
 
@@ -650,6 +675,13 @@ impl RenderGame {
                     cam.zoom = p.zoom;
                     pos
                 }
+                RenderPlayerCameraMode::OnCharacters {
+                    ref character_ids,
+                    fallback_pos,
+                } if character_ids.len() > 1 => {
+                    // overview of all spectated characters, e.g. a whole stage
+                    Self::characters_center(render_info, character_ids).unwrap_or(fallback_pos)
+                }
                 RenderPlayerCameraMode::OnCharacters { fallback_pos, .. } => {
                     camera_character_render_info
                         .map(|character| {
@@ -2621,23 +2653,7 @@ impl RenderGameInterface for RenderGame {
                 RenderPlayerCameraMode::OnCharacters {
                     character_ids,
                     fallback_pos,
-                } => Some(
-                    character_ids
-                        .iter()
-                        .next()
-                        .and_then(|character_id| {
-                            input
-                                .character_infos
-                                .get(character_id)
-                                .and_then(|c| {
-                                    c.stage_id
-                                        .and_then(|id| input.stages.get(&id))
-                                        .and_then(|s| s.world.characters.get(character_id))
-                                })
-                                .map(|c| c.lerped_pos)
-                        })
-                        .unwrap_or(*fallback_pos),
-                ),
+                } => Some(Self::characters_center(&input, character_ids).unwrap_or(*fallback_pos)),
             };
             if let Some(cam_pos) = cam_pos {
                 if let Some(listener) = next_sound_listeners.remove(player_id) {
@@ -11,7 +11,7 @@ use ui_base::{
     utils::{add_horizontal_margins, text_sized},
 };
 
-use crate::demo_player::user_data::{DemoViewerEvent, DemoViewerEventExport};
+use crate::demo_player::user_data::{DemoViewerCamera, DemoViewerEvent, DemoViewerEventExport};
 
 use super::user_data::UserData;
 
@@ -251,6 +251,52 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>, ui_state: &m
                             pipe.user_data.events.push(DemoViewerEvent::SpeedFaster);
                         }
 
+                        ui.add_space(15.0);
+
+                        // camera
+                        let camera_name = |camera: &DemoViewerCamera| match camera {
+                            DemoViewerCamera::Recorded => "Recorded player".to_string(),
+                            DemoViewerCamera::Stage(stage_id) => format!(
+                                "Stage {}",
+                                pipe.user_data
+                                    .stages
+                                    .iter()
+                                    .position(|id| id == stage_id)
+                                    .map(|index| index + 1)
+                                    .unwrap_or_default()
+                            ),
+                            DemoViewerCamera::AutoDirector => "Auto director".to_string(),
+                        };
+                        ui.menu_button(
+                            text_sized(
+                                &format!("\u{f030} {}", camera_name(pipe.user_data.camera)),
+                                FONT_SIZE / 1.5,
+                            ),
+                            |ui| {
+                                let cameras =
+                                    [DemoViewerCamera::Recorded, DemoViewerCamera::AutoDirector]
+                                        .into_iter()
+                                        .chain(
+                                            pipe.user_data
+                                                .stages
+                                                .iter()
+                                                .map(|stage_id| DemoViewerCamera::Stage(*stage_id)),
+                                        );
+                                for camera in cameras {
+                                    if ui
+                                        .selectable_label(
+                                            *pipe.user_data.camera == camera,
+                                            camera_name(&camera),
+                                        )
+                                        .clicked()
+                                    {
+                                        pipe.user_data.events.push(DemoViewerEvent::Camera(camera));
+                                        ui.close_menu();
+                                    }
+                                }
+                            },
+                        );
+
                         ui.add_space(15.0);
                         ui.colored_label(Color32::WHITE, pipe.user_data.name);
                     });
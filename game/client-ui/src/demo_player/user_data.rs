@@ -1,6 +1,7 @@
 use std::time::Duration;
 
 use egui::Rect;
+use game_interface::types::id_types::StageId;
 use graphics::handles::{
     canvas::canvas::GraphicsCanvasHandle, stream::stream::GraphicsStreamHandle,
 };
@@ -14,6 +15,18 @@ pub struct DemoViewerEventExport {
     pub remove_chat: bool,
 }
 
+/// Which part of the game the demo viewer camera shows.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum DemoViewerCamera {
+    /// Follows the player that recorded the demo.
+    #[default]
+    Recorded,
+    /// Shows all characters of a stage.
+    Stage(StageId),
+    /// Automatically follows the most interesting character.
+    AutoDirector,
+}
+
 #[derive(Debug, Clone)]
 pub enum DemoViewerEvent {
     ResumeToggle,
@@ -37,6 +50,8 @@ pub enum DemoViewerEvent {
     SkipTo { time: Duration },
     PreviewAt { rect: Rect, time: Duration },
 
+    Camera(DemoViewerCamera),
+
     Close,
 }
 
@@ -59,6 +74,10 @@ pub struct UserData<'a> {
     pub speed: &'a ffixed,
     pub name: &'a str,
 
+    pub camera: &'a DemoViewerCamera,
+    /// All stages that currently exist in the demo.
+    pub stages: &'a [StageId],
+
     pub events: &'a mut Vec<DemoViewerEvent>,
 
     pub state: &'a mut DemoViewerUiState,
@@ -87,6 +87,29 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>, ui_state: &m
                         balance_msg,
                         None,
                     ),
+                    MatchRoundTimeType::Warmup { ticks_left } => (
+                        match ticks_left {
+                            Some(ticks_left) => {
+                                let secs = ticks_left / pipe.user_data.ticks_per_second.get();
+                                let nanos = (ticks_left % pipe.user_data.ticks_per_second.get())
+                                    * tick_time_nanos;
+                                format!(
+                                    "Warmup {}",
+                                    Duration::new(secs, nanos as u32).to_race_string()
+                                )
+                            }
+                            None => "Warmup".to_string(),
+                        },
+                        Color32::LIGHT_YELLOW,
+                        balance_msg,
+                        None,
+                    ),
+                    MatchRoundTimeType::Paused => (
+                        "Paused".to_string(),
+                        Color32::LIGHT_YELLOW,
+                        balance_msg,
+                        None,
+                    ),
                     MatchRoundTimeType::GameOver { winner, .. } => {
                         ("".into(), Color32::WHITE, None, Some(winner))
                     }
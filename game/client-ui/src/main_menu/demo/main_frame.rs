@@ -3,6 +3,7 @@ use std::path::PathBuf;
 use base::duration_ext::DurationToRaceStr;
 use egui::{Align2, ComboBox, DragValue, Grid, Vec2};
 use egui_extras::{Size, StripBuilder};
+use game_config::config::ConfigVideoEncoder;
 use ui_base::{
     style::bg_frame_color,
     types::{UiRenderPipe, UiState},
@@ -18,6 +19,7 @@ fn record_settings(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>) {
     egui::Window::new("Export demo to video")
         .anchor(Align2::CENTER_CENTER, Vec2::default())
         .show(ui.ctx(), |ui| {
+            let ffmpeg_encoder = pipe.user_data.features.ffmpeg_encoder;
             let config = &mut pipe.user_data.config;
             let file_name = config
                 .engine
@@ -51,6 +53,37 @@ fn record_settings(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>) {
                 );
                 ui.end_row();
 
+                ui.label("Encoder:");
+                if !ffmpeg_encoder && config.cl.recorder.encoder == ConfigVideoEncoder::Ffmpeg {
+                    config.cl.recorder.encoder = ConfigVideoEncoder::Auto;
+                }
+                ComboBox::new("encoder_combobox", "")
+                    .selected_text(match config.cl.recorder.encoder {
+                        ConfigVideoEncoder::Auto => "Auto",
+                        ConfigVideoEncoder::Ffmpeg => "ffmpeg (mp4)",
+                        ConfigVideoEncoder::ImageSequence => "Image sequence (png + wav)",
+                    })
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(
+                            &mut config.cl.recorder.encoder,
+                            ConfigVideoEncoder::Auto,
+                            "Auto",
+                        );
+                        if ffmpeg_encoder {
+                            ui.selectable_value(
+                                &mut config.cl.recorder.encoder,
+                                ConfigVideoEncoder::Ffmpeg,
+                                "ffmpeg (mp4)",
+                            );
+                        }
+                        ui.selectable_value(
+                            &mut config.cl.recorder.encoder,
+                            ConfigVideoEncoder::ImageSequence,
+                            "Image sequence (png + wav)",
+                        );
+                    });
+                ui.end_row();
+
                 ui.label("Crf (0 = lossless, 51 = worst):");
                 ui.add(
                     DragValue::new(&mut config.cl.recorder.crf)
@@ -3,5 +3,7 @@ use hiarc::Hiarc;
 #[derive(Debug, Hiarc, Default, Clone, Copy)]
 pub struct EnabledFeatures {
     pub demo_to_video: bool,
+    /// Whether demos can be encoded with ffmpeg.
+    pub ffmpeg_encoder: bool,
     pub spatial_chat: bool,
 }
@@ -262,6 +262,32 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>, ui_state: &m
         ui.add_space(5.0);
 
         let player = &mut config.players[profile_index as usize];
+        if ui
+            .checkbox(&mut player.skin.parts.enabled, "Part based skin")
+            .changed()
+        {
+            pipe.user_data
+                .player_settings_sync
+                .set_player_info_changed();
+        }
+        if player.skin.parts.enabled {
+            if super::parts::render_skin_parts(
+                ui,
+                pipe.user_data.canvas_handle,
+                pipe.user_data.skin_container,
+                pipe.user_data.render_tee,
+                ui_state,
+                &mut pipe.user_data.config.engine,
+                &mut player.skin,
+                player.eyes,
+            ) {
+                pipe.user_data
+                    .player_settings_sync
+                    .set_player_info_changed();
+            }
+            return;
+        }
+
         let name = player.skin.name.clone();
         let eye = player.eyes;
         let skin_info: NetworkSkinInfo = (&player.skin).into();
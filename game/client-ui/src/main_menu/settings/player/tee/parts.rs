@@ -0,0 +1,272 @@
+use std::collections::BTreeMap;
+
+use client_containers::skins::SkinContainer;
+use client_render_base::render::tee::RenderTee;
+use config::{config::ConfigEngine, types::ConfRgb};
+use egui::Grid;
+use egui_extras::{Size, StripBuilder};
+use game_config::config::{ConfigPlayerSkin, ConfigPlayerSkinPart, ConfigTeeEye};
+use game_interface::types::{
+    character_info::{NetworkSkinInfo, NetworkSkinParts, MAX_ASSET_NAME_LEN},
+    resource_key::{NetworkResourceKey, ResourceKey},
+};
+use graphics::handles::canvas::canvas::GraphicsCanvasHandle;
+use math::math::vector::vec2;
+use ui_base::{components::clearable_edit_field::clearable_edit_field, types::UiState};
+
+use crate::utils::render_tee_parts_for_ui;
+
+use super::main_frame::eye_to_render_eye;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SkinPart {
+    Body,
+    Marking,
+    Decoration,
+    Feet,
+    Eyes,
+}
+
+impl SkinPart {
+    const ALL: [Self; 5] = [
+        Self::Body,
+        Self::Marking,
+        Self::Decoration,
+        Self::Feet,
+        Self::Eyes,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Body => "Body",
+            Self::Marking => "Marking",
+            Self::Decoration => "Decoration",
+            Self::Feet => "Feet",
+            Self::Eyes => "Eyes",
+        }
+    }
+
+    fn config(self, skin: &mut ConfigPlayerSkin) -> &mut ConfigPlayerSkinPart {
+        match self {
+            Self::Body => &mut skin.parts.body,
+            Self::Marking => &mut skin.parts.marking,
+            Self::Decoration => &mut skin.parts.decoration,
+            Self::Feet => &mut skin.parts.feet,
+            Self::Eyes => &mut skin.parts.eyes,
+        }
+    }
+
+    fn network_part(
+        self,
+        parts: &mut NetworkSkinParts,
+    ) -> &mut Option<NetworkResourceKey<MAX_ASSET_NAME_LEN>> {
+        match self {
+            Self::Body => &mut parts.body,
+            Self::Marking => &mut parts.marking,
+            Self::Decoration => &mut parts.decoration,
+            Self::Feet => &mut parts.feet,
+            Self::Eyes => &mut parts.eyes,
+        }
+    }
+}
+
+/// Renders the settings of a part based skin, where every
+/// part of the tee can be taken from a different skin.
+///
+/// Returns `true` if the skin was changed.
+pub fn render_skin_parts(
+    ui: &mut egui::Ui,
+    canvas_handle: &GraphicsCanvasHandle,
+    skin_container: &mut SkinContainer,
+    render_tee: &RenderTee,
+    ui_state: &mut UiState,
+    config_engine: &mut ConfigEngine,
+    skin: &mut ConfigPlayerSkin,
+    skin_eye: ConfigTeeEye,
+) -> bool {
+    let mut changed = false;
+    let render_eye = eye_to_render_eye(skin_eye);
+    let selected_part = config_engine
+        .ui
+        .path
+        .query
+        .entry("skin-part".to_string())
+        .or_default()
+        .clone();
+    let mut selected = SkinPart::ALL
+        .into_iter()
+        .find(|part| part.name() == selected_part)
+        .unwrap_or(SkinPart::Body);
+
+    StripBuilder::new(ui)
+        .size(Size::exact(180.0))
+        .size(Size::remainder())
+        .vertical(|mut strip| {
+            strip.cell(|ui| {
+                ui.style_mut().wrap_mode = None;
+                StripBuilder::new(ui)
+                    .size(Size::exact(200.0))
+                    .size(Size::remainder())
+                    .horizontal(|mut strip| {
+                        strip.cell(|ui| {
+                            ui.style_mut().wrap_mode = None;
+                            ui.label("Preview:");
+                            let skin_size = 100.0;
+                            let rect = ui.available_rect_before_wrap();
+                            let pos = vec2::new(
+                                rect.min.x + skin_size / 2.0,
+                                rect.min.y + skin_size / 2.0,
+                            );
+                            let skin_info: NetworkSkinInfo = (&*skin).into();
+                            render_tee_parts_for_ui(
+                                canvas_handle,
+                                skin_container,
+                                render_tee,
+                                ui,
+                                ui_state,
+                                ui.ctx().screen_rect(),
+                                Some(ui.clip_rect()),
+                                &ResourceKey::from_str_lossy(&skin.name),
+                                &(&skin.parts).into(),
+                                Some(&skin_info),
+                                pos,
+                                skin_size,
+                                render_eye,
+                            );
+                            ui.add_space(skin_size);
+                        });
+                        strip.cell(|ui| {
+                            ui.style_mut().wrap_mode = None;
+                            ui.horizontal(|ui| {
+                                for part in SkinPart::ALL {
+                                    if ui.selectable_label(selected == part, part.name()).clicked()
+                                    {
+                                        selected = part;
+                                    }
+                                }
+                            });
+
+                            let part = selected.config(skin);
+                            Grid::new("player-skin-part-options")
+                                .num_columns(2)
+                                .show(ui, |ui| {
+                                    ui.label("Skin:");
+                                    let mut name = part.name.clone();
+                                    clearable_edit_field(ui, &mut name, Some(150.0), Some(24));
+                                    if part.name != name {
+                                        part.name = name;
+                                        changed = true;
+                                    }
+                                    ui.end_row();
+
+                                    ui.label("Custom color:");
+                                    changed |= ui.checkbox(&mut part.custom_color, "").changed();
+                                    ui.end_row();
+
+                                    if part.custom_color {
+                                        ui.label("Color:");
+                                        let color = part.color;
+                                        if selected == SkinPart::Marking {
+                                            let mut rgba = [color.r, color.g, color.b, part.alpha];
+                                            changed |= ui
+                                                .color_edit_button_srgba_unmultiplied(&mut rgba)
+                                                .changed();
+                                            part.alpha = rgba[3];
+                                            part.color = ConfRgb {
+                                                r: rgba[0],
+                                                g: rgba[1],
+                                                b: rgba[2],
+                                            };
+                                        } else {
+                                            let mut rgb = [color.r, color.g, color.b];
+                                            changed |=
+                                                ui.color_edit_button_srgb(&mut rgb).changed();
+                                            part.color = ConfRgb {
+                                                r: rgb[0],
+                                                g: rgb[1],
+                                                b: rgb[2],
+                                            };
+                                        }
+                                        ui.end_row();
+                                    }
+                                });
+                            ui.label(match selected {
+                                SkinPart::Marking | SkinPart::Decoration => {
+                                    "An empty skin name hides this part."
+                                }
+                                SkinPart::Body | SkinPart::Feet | SkinPart::Eyes => {
+                                    "An empty skin name uses the part of the main skin."
+                                }
+                            });
+                        });
+                    });
+            });
+            strip.cell(|ui| {
+                ui.style_mut().wrap_mode = None;
+                let entries = skin_container.entries_index();
+                let entries_sorted = entries.into_iter().collect::<BTreeMap<_, _>>();
+                let skin_info: NetworkSkinInfo = (&*skin).into();
+                let skin_parts: NetworkSkinParts = (&skin.parts).into();
+                let main_skin = ResourceKey::from_str_lossy(&skin.name);
+                let part_name = selected.config(skin).name.clone();
+                let skin_search = config_engine
+                    .ui
+                    .path
+                    .query
+                    .entry("skin-search".to_string())
+                    .or_default();
+                let mut next_name = None;
+                super::super::super::list::list::render(
+                    ui,
+                    entries_sorted.iter().map(|(name, &ty)| (name.as_str(), ty)),
+                    100.0,
+                    |_, name| {
+                        let skin_valid: Result<NetworkResourceKey<MAX_ASSET_NAME_LEN>, _> =
+                            name.try_into();
+                        skin_valid.map(|_| ()).map_err(|err| err.into())
+                    },
+                    |_, name| part_name == name,
+                    |ui, _, name, pos, skin_size| {
+                        // preview the tee as if this skin was picked for the part
+                        let mut skin_parts = skin_parts.clone();
+                        *selected.network_part(&mut skin_parts) = name.try_into().ok();
+                        render_tee_parts_for_ui(
+                            canvas_handle,
+                            skin_container,
+                            render_tee,
+                            ui,
+                            ui_state,
+                            ui.ctx().screen_rect(),
+                            Some(ui.clip_rect()),
+                            &main_skin,
+                            &skin_parts,
+                            Some(&skin_info),
+                            pos,
+                            skin_size,
+                            render_eye,
+                        );
+                    },
+                    |_, name| {
+                        next_name = Some(name.to_string());
+                    },
+                    |_, _| None,
+                    skin_search,
+                    |_| {},
+                );
+                if let Some(next_name) = next_name.take() {
+                    let part = selected.config(skin);
+                    changed |= part.name != next_name;
+                    part.name = next_name;
+                }
+            });
+        });
+
+    *config_engine
+        .ui
+        .path
+        .query
+        .entry("skin-part".to_string())
+        .or_default() = selected.name().to_string();
+
+    changed
+}
@@ -1,4 +1,5 @@
 pub mod footer;
 pub mod list;
 pub mod main_frame;
+pub mod summary;
 pub mod topbar;
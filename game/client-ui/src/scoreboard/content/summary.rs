@@ -0,0 +1,117 @@
+use egui::{Frame, RichText};
+use egui_extras::{Column, TableBuilder};
+use game_interface::types::{
+    id_types::CharacterId,
+    render::scoreboard::{ScoreboardCharacterStats, ScoreboardGameType, ScoreboardScoreType},
+    weapons::WeaponType,
+};
+use ui_base::{
+    style::bg_frame_color,
+    types::{UiRenderPipe, UiState},
+    utils::{add_margins, get_margin},
+};
+
+use crate::scoreboard::user_data::UserData;
+
+const WEAPONS: [(WeaponType, &str); 5] = [
+    (WeaponType::Hammer, "Hammer"),
+    (WeaponType::Gun, "Gun"),
+    (WeaponType::Shotgun, "Shotgun"),
+    (WeaponType::Grenade, "Grenade"),
+    (WeaponType::Laser, "Laser"),
+];
+
+/// Collects all players that have match statistics, red or solo players first.
+pub fn players_with_stats(
+    game: &ScoreboardGameType,
+) -> Vec<(CharacterId, ScoreboardScoreType, ScoreboardCharacterStats)> {
+    let stages: Box<dyn Iterator<Item = _>> = match game {
+        ScoreboardGameType::SidedPlay {
+            red_stages,
+            blue_stages,
+            ..
+        } => Box::new(red_stages.values().chain(blue_stages.values())),
+        ScoreboardGameType::SoloPlay { stages, .. } => Box::new(stages.values()),
+    };
+    stages
+        .flat_map(|stage| stage.characters.iter())
+        .filter_map(|c| c.stats.map(|stats| (c.id, c.score, stats)))
+        .collect()
+}
+
+/// End-of-match summary with the statistics of all players
+pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>, ui_state: &mut UiState) {
+    let character_infos = pipe.user_data.character_infos;
+    let players = players_with_stats(&pipe.user_data.scoreboard.game);
+
+    let res = Frame::default()
+        .fill(bg_frame_color())
+        .rounding(5.0)
+        .inner_margin(get_margin(ui))
+        .show(ui, |ui| {
+            ui.set_width(ui.available_width());
+            ui.set_height(ui.available_height());
+            ui.set_clip_rect(ui.available_rect_before_wrap());
+            add_margins(ui, |ui| {
+                const FONT_SIZE: f32 = 12.0;
+                let headers = [
+                    "Name", "Score", "K/D", "Accuracy", "Flags", "Damage", "Alive",
+                ];
+                TableBuilder::new(ui)
+                    .auto_shrink([false, false])
+                    .column(Column::remainder().at_least(120.0))
+                    .columns(Column::auto(), headers.len() - 1 + WEAPONS.len() + 1)
+                    .header(20.0, |mut row| {
+                        for header in headers
+                            .into_iter()
+                            .chain(WEAPONS.iter().map(|(_, name)| *name))
+                            .chain(["Ninja"])
+                        {
+                            row.col(|ui| {
+                                ui.label(RichText::new(header).size(FONT_SIZE).strong());
+                            });
+                        }
+                    })
+                    .body(|body| {
+                        body.rows(20.0, players.len(), |mut row| {
+                            let (id, score, stats) = &players[row.index()];
+                            let mut col = |text: String| {
+                                row.col(|ui| {
+                                    ui.label(RichText::new(text).size(FONT_SIZE));
+                                });
+                            };
+                            col(character_infos
+                                .get(id)
+                                .map(|c| c.info.name.to_string())
+                                .unwrap_or_default());
+                            col(match score {
+                                ScoreboardScoreType::Points(points) => format!("{points}"),
+                                ScoreboardScoreType::RaceFinishTime(_)
+                                | ScoreboardScoreType::None => "".into(),
+                            });
+                            col(format!("{}/{}", stats.kills(), stats.deaths));
+                            col(stats
+                                .accuracy()
+                                .map(|accuracy| format!("{:.0}%", accuracy * 100.0))
+                                .unwrap_or_else(|| "-".into()));
+                            col(format!(
+                                "{}/{}/{}",
+                                stats.flag_grabs, stats.flag_captures, stats.flag_returns
+                            ));
+                            col(format!("{}/{}", stats.damage_dealt, stats.damage_taken));
+                            let alive = stats.time_alive.as_secs();
+                            col(format!("{}:{:0>2}", alive / 60, alive % 60));
+                            for (weapon, _) in WEAPONS {
+                                let weapon = &stats.weapons[weapon as usize];
+                                col(format!(
+                                    "{} ({}/{})",
+                                    weapon.kills, weapon.hits, weapon.shots
+                                ));
+                            }
+                            col(format!("{}", stats.ninja_kills));
+                        })
+                    });
+            });
+        });
+    ui_state.add_blur_rect(res.response.rect, 5.0);
+}
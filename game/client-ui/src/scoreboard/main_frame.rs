@@ -47,6 +47,25 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>, ui_state: &m
         Vec2::new(allowed_width_spec, allowed_height_spec),
     );
 
+    // after a match the statistics of all players replace the spectators
+    let scoreboard = pipe.user_data.scoreboard;
+    if scoreboard.options.match_over
+        && !super::content::summary::players_with_stats(&scoreboard.game).is_empty()
+    {
+        let extra_offset_x = 10.0;
+        let summary_rect = Rect::from_min_size(
+            Pos2::new(no_spec_rect.min.x + extra_offset_x, offset_y),
+            Vec2::new(
+                no_spec_rect.width() - extra_offset_x * 2.0,
+                allowed_height_spec,
+            ),
+        );
+        ui.allocate_new_ui(egui::UiBuilder::new().max_rect(summary_rect), |ui| {
+            super::content::summary::render(ui, pipe, ui_state)
+        });
+        return;
+    }
+
     ui.allocate_new_ui(egui::UiBuilder::new().max_rect(spec_rect), |ui| {
         super::content::main_frame::render_spectators(ui, pipe, ui_state, available_rect)
     });
@@ -2,7 +2,7 @@ use std::borrow::Borrow;
 
 use egui::{vec2, Align2, Frame, ScrollArea, Vec2, Window};
 
-use game_interface::types::render::character::TeeEye;
+use game_interface::types::{id_types::StageId, render::character::TeeEye};
 use math::math::vector::vec2;
 use ui_base::{
     style::bg_frame_color,
@@ -35,6 +35,39 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>, ui_state: &m
                             .events
                             .push_back(SpectatorSelectionEvent::FreeView);
                     }
+                    if ui.button("Auto director").clicked() {
+                        pipe.user_data
+                            .events
+                            .push_back(SpectatorSelectionEvent::AutoDirector);
+                    }
+
+                    // group the characters by their stages
+                    let mut stages: Vec<(StageId, Vec<&str>)> = Default::default();
+                    for character in pipe.user_data.character_infos.values() {
+                        let Some(stage_id) = character.stage_id else {
+                            continue;
+                        };
+                        let name = character.info.name.as_str();
+                        match stages.iter_mut().find(|(id, _)| *id == stage_id) {
+                            Some((_, names)) => names.push(name),
+                            None => stages.push((stage_id, vec![name])),
+                        }
+                    }
+                    if stages.len() > 1 {
+                        ui.separator();
+                        for (index, (stage_id, names)) in stages.into_iter().enumerate() {
+                            if ui
+                                .button(format!("Stage {}: {}", index + 1, names.join(", ")))
+                                .clicked()
+                            {
+                                pipe.user_data
+                                    .events
+                                    .push_back(SpectatorSelectionEvent::Stage(stage_id));
+                            }
+                        }
+                        ui.separator();
+                    }
+
                     for (id, character) in pipe
                         .user_data
                         .character_infos
@@ -3,7 +3,10 @@ use std::collections::VecDeque;
 use base::linked_hash_map_view::FxLinkedHashMap;
 use client_containers::skins::SkinContainer;
 use client_render_base::render::tee::RenderTee;
-use game_interface::types::{id_types::CharacterId, render::character::CharacterInfo};
+use game_interface::types::{
+    id_types::{CharacterId, StageId},
+    render::character::CharacterInfo,
+};
 use graphics::handles::{
     canvas::canvas::GraphicsCanvasHandle, stream::stream::GraphicsStreamHandle,
 };
@@ -13,6 +16,10 @@ use serde::{Deserialize, Serialize};
 pub enum SpectatorSelectionEvent {
     FreeView,
     Selected(Vec<CharacterId>),
+    /// Spectate all characters of a stage.
+    Stage(StageId),
+    /// Let the game pick the most interesting character.
+    AutoDirector,
 }
 
 pub struct UserData<'a> {
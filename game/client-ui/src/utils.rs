@@ -16,14 +16,20 @@ use client_render_base::{
     render::{
         animation::AnimState,
         default_anim::{base_anim, idle_anim},
-        tee::{offset_to_mid, RenderTee, TeeRenderHands, TeeRenderInfo, TeeRenderSkinColor},
+        tee::{
+            offset_to_mid, RenderTee, TeeRenderHands, TeeRenderInfo, TeeRenderSkinColors,
+            TeeRenderSkins,
+        },
         toolkit::ToolkitRender,
     },
 };
 use egui::Rect;
 use game_interface::types::{
-    character_info::NetworkSkinInfo, emoticons::EmoticonType, render::character::TeeEye,
-    resource_key::ResourceKey, weapons::WeaponType,
+    character_info::{NetworkSkinInfo, NetworkSkinParts, MAX_ASSET_NAME_LEN},
+    emoticons::EmoticonType,
+    render::character::TeeEye,
+    resource_key::{NetworkResourceKey, ResourceKey},
+    weapons::WeaponType,
 };
 use graphics::{
     handles::{
@@ -56,6 +62,28 @@ pub fn rotate(center: &vec2, rotation: f32, points: &mut [vec2]) {
     }
 }
 
+/// The skins of the individual parts of a tee rendered in the ui.
+#[derive(Debug)]
+struct UiTeeSkins {
+    body: Rc<Skin>,
+    marking: Option<Rc<Skin>>,
+    decoration: Option<Rc<Skin>>,
+    feet: Rc<Skin>,
+    eyes: Rc<Skin>,
+}
+
+impl From<Rc<Skin>> for UiTeeSkins {
+    fn from(skin: Rc<Skin>) -> Self {
+        Self {
+            body: skin.clone(),
+            marking: None,
+            decoration: None,
+            feet: skin.clone(),
+            eyes: skin,
+        }
+    }
+}
+
 pub fn render_tee_for_ui_with_skin(
     canvas_handle: &GraphicsCanvasHandle,
     skin: Rc<Skin>,
@@ -68,12 +96,40 @@ pub fn render_tee_for_ui_with_skin(
     pos: vec2,
     size: f32,
     eyes: TeeEye,
+) {
+    render_tee_for_ui_with_skins(
+        canvas_handle,
+        skin.into(),
+        render_tee,
+        ui,
+        ui_state,
+        render_rect,
+        clip_rect,
+        skin_info,
+        pos,
+        size,
+        eyes,
+    )
+}
+
+fn render_tee_for_ui_with_skins(
+    canvas_handle: &GraphicsCanvasHandle,
+    skins: UiTeeSkins,
+    render_tee: &RenderTee,
+    ui: &mut egui::Ui,
+    ui_state: &mut UiState,
+    render_rect: Rect,
+    clip_rect: Option<Rect>,
+    skin_info: Option<&NetworkSkinInfo>,
+    pos: vec2,
+    size: f32,
+    eyes: TeeEye,
 ) {
     #[derive(Debug)]
     struct RenderTeeCb {
         render_rect: Rect,
         clip_rect: Option<Rect>,
-        skin: Rc<Skin>,
+        skins: UiTeeSkins,
         skin_info: Option<NetworkSkinInfo>,
         pos: vec2,
         size: f32,
@@ -88,19 +144,14 @@ pub fn render_tee_for_ui_with_skin(
             anim_state.set(&base_anim(), &Duration::from_millis(0));
             anim_state.add(&idle_anim(), &Duration::from_millis(0), 1.0);
 
-            let (color_body, color_feet) = if let Some(NetworkSkinInfo::Custom {
-                body_color,
-                feet_color,
-            }) = self.skin_info
-            {
-                (body_color.into(), feet_color.into())
-            } else {
-                (TeeRenderSkinColor::Original, TeeRenderSkinColor::Original)
-            };
+            let colors: TeeRenderSkinColors = self.skin_info.unwrap_or_default().into();
 
             let tee_render_info = TeeRenderInfo {
-                color_body,
-                color_feet,
+                color_body: colors.body,
+                color_marking: colors.marking,
+                color_decoration: colors.decoration,
+                color_feet: colors.feet,
+                color_eyes: colors.eyes,
                 got_air_jump: false,
                 feet_flipped: false,
                 size: self.size,
@@ -128,16 +179,24 @@ pub fn render_tee_for_ui_with_skin(
                 );
             }
 
+            let skins = TeeRenderSkins {
+                body: &self.skins.body,
+                marking: self.skins.marking.as_deref(),
+                decoration: self.skins.decoration.as_deref(),
+                feet: &self.skins.feet,
+                eyes: &self.skins.eyes,
+            };
             self.render_tee.render_tee(
                 &anim_state,
-                &self.skin,
+                &skins,
                 &tee_render_info,
                 &TeeRenderHands {
                     left: None,
                     right: None,
                 },
                 &dir,
-                &(self.pos + offset_to_mid(&self.skin.metrics, &anim_state, &tee_render_info)),
+                &(self.pos
+                    + offset_to_mid(&self.skins.body.metrics, &anim_state, &tee_render_info)),
                 self.opacity,
                 &state,
             );
@@ -147,7 +206,7 @@ pub fn render_tee_for_ui_with_skin(
     let cb = RenderTeeCb {
         render_rect,
         clip_rect,
-        skin,
+        skins,
         skin_info: skin_info.copied(),
         pos,
         size,
@@ -190,6 +249,50 @@ pub fn render_tee_for_ui(
     )
 }
 
+/// Like [`render_tee_for_ui`], but the parts of the tee
+/// can be taken from other skins.
+pub fn render_tee_parts_for_ui(
+    canvas_handle: &GraphicsCanvasHandle,
+    skin_container: &mut SkinContainer,
+    render_tee: &RenderTee,
+    ui: &mut egui::Ui,
+    ui_state: &mut UiState,
+    render_rect: Rect,
+    clip_rect: Option<Rect>,
+    skin: &ResourceKey,
+    skin_parts: &NetworkSkinParts,
+    skin_info: Option<&NetworkSkinInfo>,
+    pos: vec2,
+    size: f32,
+    eyes: TeeEye,
+) {
+    let skin = skin_container.get_or_default(skin).clone();
+    let mut part = |key: &Option<NetworkResourceKey<MAX_ASSET_NAME_LEN>>| {
+        key.as_ref()
+            .map(|key| skin_container.get_or_default(key).clone())
+    };
+    let skins = UiTeeSkins {
+        body: part(&skin_parts.body).unwrap_or_else(|| skin.clone()),
+        marking: part(&skin_parts.marking),
+        decoration: part(&skin_parts.decoration),
+        feet: part(&skin_parts.feet).unwrap_or_else(|| skin.clone()),
+        eyes: part(&skin_parts.eyes).unwrap_or(skin),
+    };
+    render_tee_for_ui_with_skins(
+        canvas_handle,
+        skins,
+        render_tee,
+        ui,
+        ui_state,
+        render_rect,
+        clip_rect,
+        skin_info,
+        pos,
+        size,
+        eyes,
+    )
+}
+
 pub fn render_weapon_for_ui(
     canvas_handle: &GraphicsCanvasHandle,
     weapon_container: &mut WeaponContainer,
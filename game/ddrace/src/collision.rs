@@ -0,0 +1,4 @@
+use api_macros::collision_mod;
+
+#[collision_mod("../../")]
+pub mod collision {}
@@ -0,0 +1,4 @@
+use api_macros::config_mod;
+
+#[config_mod("../../")]
+pub mod config {}
@@ -0,0 +1,802 @@
+/// The ddrace game state, a thin layer around the ddrace physics
+/// that adds race results, records and the race related chat commands.
+pub mod ddrace {
+    use std::{
+        num::{NonZeroU32, NonZeroU64},
+        sync::Arc,
+        time::Duration,
+    };
+
+    use base::{
+        hash::{fmt_hash, Hash},
+        network_string::{MtPoolNetworkString, NetworkReducedAsciiString, NetworkString},
+    };
+    use base_io::runtime::{IoRuntime, IoRuntimeTask};
+    use command_parser::parser::{self, CommandType, ParserCache};
+    use game_database::traits::DbInterface;
+    use game_interface::{
+        account_info::MAX_ACCOUNT_NAME_LEN,
+        chat_commands::ChatCommands,
+        client_commands::ClientCommand,
+        events::{
+            EventClientInfo, GameEvents, GameWorldAction, GameWorldEvent,
+            GameWorldNotificationEvent, GameWorldSystemMessage,
+        },
+        ghosts::GhostResult,
+        interface::{
+            GameStateCreate, GameStateCreateOptions, GameStateInterface, GameStateStaticInfo,
+            MAX_MAP_NAME_LEN,
+        },
+        pooling::GamePooling,
+        rcon_entries::ExecRconInput,
+        settings::GameStateSettings,
+        tick_result::TickResult,
+        types::{
+            character_info::NetworkCharacterInfo,
+            emoticons::EmoticonType,
+            id_gen::IdGeneratorIdType,
+            id_types::{CharacterId, PlayerId, StageId},
+            input::CharacterInputInfo,
+            network_stats::PlayerNetworkStats,
+            player_info::{AccountId, PlayerClientInfo, PlayerDropReason, PlayerUniqueId},
+            render::{
+                character::{
+                    CharacterInfo, LocalCharacterDdrace, LocalCharacterRenderInfo, TeeEye,
+                },
+                scoreboard::{
+                    Scoreboard, ScoreboardGameType, ScoreboardGameTypeOptions, ScoreboardScoreType,
+                },
+                stage::StageRenderInfo,
+            },
+            snapshot::{SnapshotClientInfo, SnapshotLocalPlayers},
+            ticks::TickOptions,
+        },
+        vote_commands::{VoteCommand, VoteCommandResult},
+    };
+    use math::math::vector::vec2;
+    use pool::{
+        datatypes::{PoolFxLinkedHashMap, PoolFxLinkedHashSet, PoolVec},
+        mt_datatypes::PoolCow as MtPoolCow,
+    };
+    use rustc_hash::FxHashMap;
+
+    use crate::{
+        config::config::{ConfigGameType, ConfigVanilla},
+        entities::character::character::Character,
+        race::race::{fmt_race_diff, fmt_race_time, RaceState},
+        simulation_pipe::simulation_pipe::GameStagePendingEvents,
+        sql::records::{self, RaceRecord, RaceRecords, TimesEntry, Top5Entry},
+        state::state::GameState,
+    };
+
+    enum RaceQuery {
+        PlayerBest {
+            unique_identifier: PlayerUniqueId,
+            record: Option<RaceRecord>,
+        },
+        Top5 {
+            player_id: PlayerId,
+            entries: Vec<Top5Entry>,
+        },
+        Rank {
+            player_id: PlayerId,
+            player_name: String,
+            rank: Option<(u64, Duration)>,
+        },
+        Times {
+            player_id: PlayerId,
+            times: Vec<TimesEntry>,
+        },
+    }
+
+    /// A finished race that still has to be announced and saved.
+    struct RaceFinish {
+        player_id: PlayerId,
+        unique_identifier: PlayerUniqueId,
+        player_name: String,
+        record: RaceRecord,
+    }
+
+    impl RaceFinish {
+        fn new(id: &CharacterId, character: &Character, time: Duration) -> Self {
+            Self {
+                player_id: *id,
+                unique_identifier: character.player_info.unique_identifier,
+                player_name: character.player_info.player_info.name.to_string(),
+                record: RaceRecord {
+                    time,
+                    checkpoints: character.core.modifications.race.checkpoint_times(),
+                },
+            }
+        }
+    }
+
+    /// The key a player's records are saved under.
+    fn player_key(unique_identifier: &PlayerUniqueId) -> String {
+        match unique_identifier {
+            PlayerUniqueId::Account(account_id) => format!("account:{account_id}"),
+            PlayerUniqueId::CertFingerprint(hash) => format!("cert:{}", fmt_hash(hash)),
+        }
+    }
+
+    fn push_player_msg(
+        player_events: &mut FxHashMap<PlayerId, GameStagePendingEvents>,
+        game_pools: &GamePooling,
+        player_id: &PlayerId,
+        msg: &str,
+    ) {
+        player_events
+            .entry(*player_id)
+            .or_default()
+            .push(system_msg(game_pools, msg));
+    }
+
+    fn system_msg(game_pools: &GamePooling, msg: &str) -> GameWorldEvent {
+        GameWorldEvent::Notification(GameWorldNotificationEvent::System(
+            GameWorldSystemMessage::Custom({
+                let mut s = game_pools.mt_network_string_common_pool.new();
+                s.try_set(msg).unwrap();
+                s
+            }),
+        ))
+    }
+
+    pub struct Ddrace {
+        state: GameState,
+
+        chat_commands: ChatCommands,
+        cache: ParserCache,
+        map_name: String,
+
+        io_rt: IoRuntime,
+        records: Option<RaceRecords>,
+        queries: Vec<IoRuntimeTask<RaceQuery>>,
+
+        /// The best time of the map, used for the checkpoint splits
+        map_best: Option<RaceRecord>,
+        /// The best times of the players that joined the server
+        best_times: FxHashMap<PlayerUniqueId, Duration>,
+    }
+
+    impl Ddrace {
+        fn push_query<F>(&mut self, query: F)
+        where
+            F: std::future::Future<Output = anyhow::Result<RaceQuery>> + Send + 'static,
+        {
+            self.queries.push(self.io_rt.spawn(query));
+        }
+
+        fn handle_chat_commands(&mut self, player_id: &PlayerId, cmds: Vec<CommandType>) {
+            let Some(server_player) = self.state.game.players.player(player_id) else {
+                return;
+            };
+            let Some(character) = self
+                .state
+                .game
+                .stages
+                .get(&server_player.stage_id())
+                .and_then(|stage| stage.world.characters.get(player_id))
+            else {
+                return;
+            };
+            let unique_identifier = character.player_info.unique_identifier;
+            let player_name = character.player_info.player_info.name.to_string();
+            let player_id = *player_id;
+
+            for cmd in cmds {
+                let CommandType::Full(cmd) = cmd else {
+                    continue;
+                };
+                let Some(records) = self.records.clone() else {
+                    if ["top5", "rank", "times"].contains(&cmd.ident.as_str()) {
+                        push_player_msg(
+                            &mut self.state.player_events,
+                            &self.state.game_pools,
+                            &player_id,
+                            "Race records are not available on this server.",
+                        );
+                    }
+                    continue;
+                };
+                let map_name = self.map_name.clone();
+                let key = player_key(&unique_identifier);
+                match cmd.ident.as_str() {
+                    "top5" => self.push_query(async move {
+                        Ok(RaceQuery::Top5 {
+                            player_id,
+                            entries: records.top5(&map_name).await?,
+                        })
+                    }),
+                    "rank" => {
+                        let player_name = player_name.clone();
+                        self.push_query(async move {
+                            Ok(RaceQuery::Rank {
+                                player_id,
+                                player_name,
+                                rank: records.rank(&map_name, &key).await?,
+                            })
+                        })
+                    }
+                    "times" => self.push_query(async move {
+                        Ok(RaceQuery::Times {
+                            player_id,
+                            times: records.times(&map_name, &key).await?,
+                        })
+                    }),
+                    _ => {
+                        // handled by the inner game state
+                    }
+                }
+            }
+        }
+
+        /// Announces finished races and team races and collects
+        /// them for saving.
+        fn race_tick(&mut self) {
+            let stage_0_id = self.state.stage_0_id;
+            let game_pools = &self.state.game_pools;
+            let player_events = &mut self.state.player_events;
+            let map_best = &self.map_best;
+
+            let mut finishes: Vec<RaceFinish> = Default::default();
+            for (stage_id, stage) in self.state.game.stages.iter_mut() {
+                for (id, character) in stage.world.characters.iter_mut() {
+                    let race = &mut character.core.modifications.race;
+                    let Some(checkpoint) = race
+                        .last_checkpoint
+                        .filter(|&cp| race.reported_checkpoint != Some(cp))
+                    else {
+                        continue;
+                    };
+                    race.reported_checkpoint = Some(checkpoint);
+                    let time = race.checkpoint_times()[checkpoint as usize];
+                    let record_time = map_best
+                        .as_ref()
+                        .and_then(|record| record.checkpoints[checkpoint as usize]);
+                    if let (Some(time), Some(record_time)) = (time, record_time) {
+                        push_player_msg(
+                            player_events,
+                            game_pools,
+                            id,
+                            &format!(
+                                "Checkpoint {}: {} ({})",
+                                checkpoint + 1,
+                                fmt_race_time(time),
+                                fmt_race_diff(time, record_time)
+                            ),
+                        );
+                    }
+                }
+
+                if *stage_id == stage_0_id {
+                    for (id, character) in stage.world.characters.iter_mut() {
+                        let race = &mut character.core.modifications.race;
+                        if race.state != RaceState::Finished || race.finish_reported {
+                            continue;
+                        }
+                        race.finish_reported = true;
+                        let finish_time = race.time();
+                        stage.game_pending_events.push(GameWorldEvent::Notification(
+                            GameWorldNotificationEvent::Action(GameWorldAction::RaceFinish {
+                                character: *id,
+                                finish_time,
+                            }),
+                        ));
+                        finishes.push(RaceFinish::new(id, character, finish_time));
+                    }
+                } else {
+                    // a team only finishes when all of its members finished
+                    let characters = &mut stage.world.characters;
+                    let team_finished = !characters.is_empty()
+                        && characters.values().all(|character| {
+                            character.core.modifications.race.state == RaceState::Finished
+                        })
+                        && characters
+                            .values()
+                            .any(|character| !character.core.modifications.race.finish_reported);
+                    if !team_finished {
+                        continue;
+                    }
+                    let finish_time = characters
+                        .values()
+                        .map(|character| character.core.modifications.race.time())
+                        .max()
+                        .unwrap_or_default();
+                    let mut team_characters = game_pools.character_id_pool.new();
+                    for (id, character) in characters.iter_mut() {
+                        character.core.modifications.race.finish_reported = true;
+                        team_characters.push(*id);
+                        finishes.push(RaceFinish::new(id, character, finish_time));
+                    }
+                    stage.game_pending_events.push(GameWorldEvent::Notification(
+                        GameWorldNotificationEvent::Action(GameWorldAction::RaceTeamFinish {
+                            characters: team_characters,
+                            team_name: {
+                                let mut name = MtPoolNetworkString::new_without_pool();
+                                (*name).clone_from(&stage.stage_name);
+                                name
+                            },
+                            finish_time,
+                        }),
+                    ));
+                }
+            }
+
+            for finish in finishes {
+                self.race_finished(finish);
+            }
+        }
+
+        fn race_finished(&mut self, finish: RaceFinish) {
+            let RaceFinish {
+                player_id,
+                unique_identifier,
+                player_name,
+                record,
+            } = finish;
+
+            let mut msg = format!("You finished in {}", fmt_race_time(record.time));
+            match self.best_times.get(&unique_identifier) {
+                Some(best) if record.time < *best => {
+                    msg.push_str(&format!(
+                        ", a new personal record ({})",
+                        fmt_race_diff(record.time, *best)
+                    ));
+                }
+                Some(best) => {
+                    msg.push_str(&format!(" ({})", fmt_race_diff(record.time, *best)));
+                }
+                None => {}
+            }
+            push_player_msg(
+                &mut self.state.player_events,
+                &self.state.game_pools,
+                &player_id,
+                &msg,
+            );
+
+            let best = self
+                .best_times
+                .entry(unique_identifier)
+                .or_insert(record.time);
+            *best = (*best).min(record.time);
+
+            if self
+                .map_best
+                .as_ref()
+                .is_none_or(|map_best| record.time < map_best.time)
+            {
+                if let Some(stage) = self.state.game.stages.get(&self.state.stage_0_id) {
+                    stage.game_pending_events.push(system_msg(
+                        &self.state.game_pools,
+                        &format!(
+                            "{} set a new map record: {}",
+                            player_name,
+                            fmt_race_time(record.time)
+                        ),
+                    ));
+                }
+                self.map_best = Some(record.clone());
+            }
+
+            if let Some(records) = self.records.clone() {
+                let map_name = self.map_name.clone();
+                let key = player_key(&unique_identifier);
+                self.io_rt.spawn_without_lifetime(async move {
+                    records
+                        .insert(&map_name, &key, &player_name, &record)
+                        .await?;
+                    Ok(())
+                });
+            }
+        }
+
+        fn query_tick(&mut self) {
+            for query in std::mem::take(&mut self.queries) {
+                if !query.is_finished() {
+                    self.queries.push(query);
+                    continue;
+                }
+                let query = match query.get_storage() {
+                    Ok(query) => query,
+                    Err(err) => {
+                        log::warn!(target: "sql", "race query failed: {err}");
+                        continue;
+                    }
+                };
+                let (player_id, msg) = match query {
+                    RaceQuery::PlayerBest {
+                        unique_identifier,
+                        record,
+                    } => {
+                        if let Some(record) = record {
+                            let best = self
+                                .best_times
+                                .entry(unique_identifier)
+                                .or_insert(record.time);
+                            *best = (*best).min(record.time);
+                        }
+                        continue;
+                    }
+                    RaceQuery::Top5 { player_id, entries } => {
+                        let msg = if entries.is_empty() {
+                            "Nobody finished this map yet.".to_string()
+                        } else {
+                            let mut msg = format!("Top 5 of {}:", self.map_name);
+                            for (index, entry) in entries.iter().enumerate() {
+                                msg.push_str(&format!(
+                                    "\n{}. {} {}",
+                                    index + 1,
+                                    entry.player_name,
+                                    fmt_race_time(entry.time)
+                                ));
+                            }
+                            msg
+                        };
+                        (player_id, msg)
+                    }
+                    RaceQuery::Rank {
+                        player_id,
+                        player_name,
+                        rank,
+                    } => {
+                        let msg = match rank {
+                            Some((rank, time)) => format!(
+                                "{} is rank {} with a time of {}",
+                                player_name,
+                                rank,
+                                fmt_race_time(time)
+                            ),
+                            None => "You did not finish this map yet.".to_string(),
+                        };
+                        (player_id, msg)
+                    }
+                    RaceQuery::Times { player_id, times } => {
+                        let msg = if times.is_empty() {
+                            "You did not finish this map yet.".to_string()
+                        } else {
+                            let mut msg = "Your last times:".to_string();
+                            for entry in times.iter() {
+                                msg.push_str(&format!(
+                                    "\n{} ({})",
+                                    fmt_race_time(entry.time),
+                                    <chrono::DateTime<chrono::Utc>>::from_timestamp(
+                                        entry.create_time.secs as i64,
+                                        entry.create_time.subsec_nanos
+                                    )
+                                    .unwrap_or_default()
+                                    .format("%Y-%m-%d %H:%M")
+                                ));
+                            }
+                            msg
+                        };
+                        (player_id, msg)
+                    }
+                };
+                push_player_msg(
+                    &mut self.state.player_events,
+                    &self.state.game_pools,
+                    &player_id,
+                    &msg,
+                );
+            }
+        }
+    }
+
+    impl GameStateCreate for Ddrace {
+        fn new(
+            map: Vec<u8>,
+            map_name: NetworkReducedAsciiString<MAX_MAP_NAME_LEN>,
+            mut options: GameStateCreateOptions,
+            io_rt: IoRuntime,
+            db: Arc<dyn DbInterface>,
+        ) -> Result<(Self, GameStateStaticInfo), NetworkString<1024>>
+        where
+            Self: Sized,
+        {
+            // races are played in solo matches that never end,
+            // teams are stages
+            let mut config: ConfigVanilla = options
+                .config
+                .as_ref()
+                .and_then(|config| serde_json::from_slice(config).ok())
+                .unwrap_or_default();
+            config.game_type = ConfigGameType::Dm;
+            config.allow_stages = true;
+            config.score_limit = u64::MAX;
+            config.time_limit_secs = 0;
+            options.config = serde_json::to_vec(&config).ok();
+
+            let records_db = db.clone();
+            let db_task = io_rt.spawn(async move {
+                if !records_db.kinds().is_empty() {
+                    records::setup(records_db.clone()).await?;
+                    RaceRecords::new(records_db).await
+                } else {
+                    Err(anyhow::anyhow!("Databases not active."))
+                }
+            });
+
+            let (state, mut info) =
+                GameState::new(map, map_name.clone(), options, io_rt.clone(), db)?;
+
+            let records = match db_task.get_storage() {
+                Ok(records) => Some(records),
+                Err(err) => {
+                    log::warn!(target: "sql", "failed to prepare race records: {err}");
+                    None
+                }
+            };
+            let map_best = records.as_ref().and_then(|records| {
+                let records = records.clone();
+                let map_name = map_name.to_string();
+                io_rt
+                    .spawn(async move { records.map_best(&map_name).await })
+                    .get_storage()
+                    .ok()
+                    .flatten()
+            });
+
+            info.chat_commands.cmds.extend(
+                ["top5", "rank", "times"]
+                    .into_iter()
+                    .map(|cmd| (cmd.try_into().unwrap(), vec![])),
+            );
+            info.mod_name = "ddrace".try_into().unwrap();
+            info.options.physics_group_name = "ddnet".try_into().unwrap();
+            info.options.allow_stages = true;
+
+            Ok((
+                Self {
+                    state,
+
+                    chat_commands: info.chat_commands.clone(),
+                    cache: Default::default(),
+                    map_name: map_name.to_string(),
+
+                    io_rt,
+                    records,
+                    queries: Default::default(),
+
+                    map_best,
+                    best_times: Default::default(),
+                },
+                info,
+            ))
+        }
+    }
+
+    impl GameStateInterface for Ddrace {
+        fn collect_characters_info(&self) -> PoolFxLinkedHashMap<CharacterId, CharacterInfo> {
+            self.state.collect_characters_info()
+        }
+
+        fn collect_render_ext(&self) -> PoolVec<u8> {
+            self.state.collect_render_ext()
+        }
+
+        fn collect_scoreboard_info(&self) -> Scoreboard {
+            let mut scoreboard = self.state.collect_scoreboard_info();
+
+            let best_time = |id: &CharacterId| {
+                self.state
+                    .game
+                    .stages
+                    .values()
+                    .find_map(|stage| stage.world.characters.get(id))
+                    .and_then(|character| {
+                        self.best_times
+                            .get(&character.player_info.unique_identifier)
+                    })
+                    .map(|time| ScoreboardScoreType::RaceFinishTime(*time))
+                    .unwrap_or(ScoreboardScoreType::None)
+            };
+            if let ScoreboardGameType::SoloPlay { stages, .. } = &mut scoreboard.game {
+                for stage in stages.values_mut() {
+                    for character in stage.characters.iter_mut() {
+                        character.score = best_time(&character.id);
+                    }
+                    // fastest first, players without a time last
+                    stage.characters.sort_by_key(|c| c.score);
+                    stage.score = stage
+                        .characters
+                        .first()
+                        .map(|c| c.score)
+                        .unwrap_or(ScoreboardScoreType::None);
+                }
+            }
+            scoreboard.options.ty = ScoreboardGameTypeOptions::Race { time_limit: None };
+            scoreboard
+        }
+
+        fn all_stages(&self, ratio: f64) -> PoolFxLinkedHashMap<StageId, StageRenderInfo> {
+            self.state.all_stages(ratio)
+        }
+
+        fn collect_character_local_render_info(
+            &self,
+            player_id: &PlayerId,
+        ) -> LocalCharacterRenderInfo {
+            let Some(character) = self
+                .state
+                .game
+                .players
+                .player(player_id)
+                .and_then(|p| self.state.game.stages.get(&p.stage_id()))
+                .and_then(|stage| stage.world.characters.get(player_id))
+            else {
+                // spectators get nothing
+                return LocalCharacterRenderInfo::Unavailable;
+            };
+            let core = &character.core.core;
+            let modifications = &character.core.modifications;
+
+            let mut owned_weapons = PoolFxLinkedHashSet::new_without_pool();
+            owned_weapons.extend(character.reusable_core.weapons.keys().copied());
+
+            LocalCharacterRenderInfo::Ddrace(LocalCharacterDdrace {
+                jumps: (core.jumps - core.jumped_total).max(0) as u32,
+                max_jumps: NonZeroU32::new(core.jumps.max(0) as u32),
+                endless_hook: false,
+                can_hook_others: true,
+                jetpack: false,
+                deep_frozen: modifications.deep_frozen,
+                live_frozen: false,
+                can_finish: true,
+                owned_weapons,
+                disabled_weapons: PoolFxLinkedHashSet::new_without_pool(),
+                tele_weapons: PoolFxLinkedHashSet::new_without_pool(),
+                solo: false,
+                invincible: false,
+                dummy_hammer: false,
+                dummy_copy: false,
+                stage_locked: false,
+                team0_mode: false,
+                can_collide: true,
+                checkpoint: modifications.race.last_checkpoint,
+            })
+        }
+
+        fn get_client_camera_join_pos(&self) -> vec2 {
+            self.state.get_client_camera_join_pos()
+        }
+
+        fn player_join(&mut self, player_info: &PlayerClientInfo) -> PlayerId {
+            let player_id = self.state.player_join(player_info);
+
+            if let Some(records) = self.records.clone() {
+                let unique_identifier = player_info.unique_identifier;
+                let map_name = self.map_name.clone();
+                let key = player_key(&unique_identifier);
+                self.push_query(async move {
+                    Ok(RaceQuery::PlayerBest {
+                        unique_identifier,
+                        record: records.player_best(&map_name, &key).await?,
+                    })
+                });
+            }
+
+            player_id
+        }
+
+        fn player_drop(&mut self, player_id: &PlayerId, reason: PlayerDropReason) {
+            self.state.player_drop(player_id, reason)
+        }
+
+        fn try_overwrite_player_character_info(
+            &mut self,
+            id: &PlayerId,
+            info: &NetworkCharacterInfo,
+            version: NonZeroU64,
+        ) {
+            self.state
+                .try_overwrite_player_character_info(id, info, version)
+        }
+
+        fn account_created(&mut self, account_id: AccountId, cert_fingerprint: Hash) {
+            self.state.account_created(account_id, cert_fingerprint)
+        }
+
+        fn account_renamed(
+            &mut self,
+            account_id: AccountId,
+            new_name: &NetworkReducedAsciiString<MAX_ACCOUNT_NAME_LEN>,
+        ) {
+            self.state.account_renamed(account_id, new_name)
+        }
+
+        fn network_stats(&mut self, stats: PoolFxLinkedHashMap<PlayerId, PlayerNetworkStats>) {
+            self.state.network_stats(stats)
+        }
+
+        fn settings(&self) -> GameStateSettings {
+            self.state.settings()
+        }
+
+        fn client_command(&mut self, player_id: &PlayerId, cmd: ClientCommand) {
+            if let ClientCommand::Chat(cmd) = &cmd {
+                let cmds = parser::parse(&cmd.raw, &self.chat_commands.cmds, &self.cache);
+                self.handle_chat_commands(player_id, cmds);
+            }
+            self.state.client_command(player_id, cmd)
+        }
+
+        fn rcon_command(
+            &mut self,
+            player_id: Option<PlayerId>,
+            cmd: ExecRconInput,
+        ) -> Vec<Result<NetworkString<65536>, NetworkString<65536>>> {
+            self.state.rcon_command(player_id, cmd)
+        }
+
+        fn vote_command(&mut self, cmd: VoteCommand) -> VoteCommandResult {
+            self.state.vote_command(cmd)
+        }
+
+        fn voted_player(&mut self, player_id: Option<PlayerId>) {
+            self.state.voted_player(player_id)
+        }
+
+        fn set_player_inputs(&mut self, inps: PoolFxLinkedHashMap<PlayerId, CharacterInputInfo>) {
+            self.state.set_player_inputs(inps)
+        }
+
+        fn set_player_emoticon(&mut self, player_id: &PlayerId, emoticon: EmoticonType) {
+            self.state.set_player_emoticon(player_id, emoticon)
+        }
+
+        fn set_player_eye(&mut self, player_id: &PlayerId, eye: TeeEye, duration: Duration) {
+            self.state.set_player_eye(player_id, eye, duration)
+        }
+
+        fn tick(&mut self, options: TickOptions) -> TickResult {
+            let res = self.state.tick(options);
+
+            if !options.is_future_tick_prediction {
+                self.race_tick();
+                self.query_tick();
+            }
+
+            res
+        }
+
+        fn snapshot_for(&self, client: SnapshotClientInfo) -> MtPoolCow<'static, [u8]> {
+            self.state.snapshot_for(client)
+        }
+
+        fn build_from_snapshot(
+            &mut self,
+            snapshot: &MtPoolCow<'static, [u8]>,
+        ) -> SnapshotLocalPlayers {
+            self.state.build_from_snapshot(snapshot)
+        }
+
+        fn snapshot_for_hotreload(&self) -> Option<MtPoolCow<'static, [u8]>> {
+            self.state.snapshot_for_hotreload()
+        }
+
+        fn build_from_snapshot_by_hotreload(&mut self, snapshot: &MtPoolCow<'static, [u8]>) {
+            self.state.build_from_snapshot_by_hotreload(snapshot)
+        }
+
+        fn build_from_snapshot_for_prev(&mut self, snapshot: &MtPoolCow<'static, [u8]>) {
+            self.state.build_from_snapshot_for_prev(snapshot)
+        }
+
+        fn build_ghosts_from_snapshot(&self, snapshot: &MtPoolCow<'static, [u8]>) -> GhostResult {
+            self.state.build_ghosts_from_snapshot(snapshot)
+        }
+
+        fn events_for(&self, client: EventClientInfo) -> GameEvents {
+            self.state.events_for(client)
+        }
+
+        fn clear_events(&mut self) {
+            self.state.clear_events()
+        }
+
+        fn sync_event_id(&self, event_id: IdGeneratorIdType) {
+            self.state.sync_event_id(event_id)
+        }
+    }
+}
@@ -0,0 +1,151 @@
+pub mod core;
+pub mod player;
+pub mod pos {
+    pub use ::vanilla::entities::character::pos::*;
+}
+pub mod hook {
+    pub use ::vanilla::entities::character::hook::*;
+}
+pub mod score {
+    pub use ::vanilla::entities::character::score::*;
+}
+
+use api_macros::character_mod;
+
+#[character_mod("../../")]
+pub mod character {
+    use crate::race::race::{
+        clamp_vel, stopper_restrictions, time_checkpoint, CharacterRace, FREEZE_TICKS,
+    };
+
+    #[derive(Debug, Hiarc, Default, Serialize, Deserialize, Copy, Clone)]
+    pub struct CharacterCoreMod {
+        pub race: CharacterRace,
+        /// Deep freeze can only be removed by a deep unfreeze tile.
+        pub deep_frozen: bool,
+
+        /// The directions stopper tiles block in the current tick.
+        #[serde(skip)]
+        move_restrictions: u8,
+        /// The input the physics see while the character is frozen.
+        #[serde(skip)]
+        frozen_input: CharacterInput,
+    }
+
+    impl CharacterCore {
+        fn get_core_mut_and_input(
+            &mut self,
+            reusable_core: &CharacterReusableCore,
+        ) -> (&mut Core, &CharacterInput) {
+            if reusable_core.debuffs.contains_key(&CharacterDebuff::Freeze) {
+                // frozen characters can still aim, but not move
+                let mut input = self.input;
+                input.state.dir.set(0);
+                input.state.hook.set(false);
+                input.state.jump.set(false);
+                input.state.fire.set(false);
+                self.modifications.frozen_input = input;
+                self.core.queued_jumps = 0;
+                self.core.queued_hooks.clicked = 0;
+                (&mut self.core, &self.modifications.frozen_input)
+            } else {
+                (&mut self.core, &self.input)
+            }
+        }
+    }
+
+    impl Character {
+        fn handle_game_layer_tiles(&mut self, tile: &Tile, res: &mut CharacterDamageResult) {
+            let index = tile.index;
+            if index == DdraceTileNum::Death as u8 {
+                self.die(None, GameWorldActionKillWeapon::World, Default::default());
+                *res = CharacterDamageResult::Death;
+            } else if index == DdraceTileNum::Freeze as u8 {
+                self.reusable_core.debuffs.insert(
+                    CharacterDebuff::Freeze,
+                    BuffProps {
+                        remaining_tick: FREEZE_TICKS.into(),
+                        interact_tick: Default::default(),
+                        interact_cursor_dir: Default::default(),
+                        interact_val: 0.0,
+                    },
+                );
+            } else if index == DdraceTileNum::Unfreeze as u8 {
+                if !self.core.modifications.deep_frozen {
+                    self.reusable_core.debuffs.remove(&CharacterDebuff::Freeze);
+                }
+            } else if index == DdraceTileNum::DFreeze as u8 {
+                self.core.modifications.deep_frozen = true;
+            } else if index == DdraceTileNum::DUnfreeze as u8 {
+                self.core.modifications.deep_frozen = false;
+            } else if index == DdraceTileNum::Start as u8 {
+                self.core.modifications.race.start();
+            } else if index == DdraceTileNum::Finish as u8 {
+                self.core.modifications.race.finish();
+            } else if index == DdraceTileNum::WallJump as u8 {
+                let core = &mut self.core.core;
+                if core.vel.y > 0.0 && core.colliding != 0 && core.left_wall {
+                    core.left_wall = false;
+                    core.jumped_total = if core.jumps >= 2 { core.jumps - 2 } else { 0 };
+                    core.jumped = 1;
+                }
+            } else if index == DdraceTileNum::RefillJumps as u8 {
+                let core = &mut self.core.core;
+                core.jumped &= !2;
+                core.jumped_total = 0;
+            } else if let Some(checkpoint) = time_checkpoint(tile) {
+                self.core.modifications.race.checkpoint(checkpoint);
+            } else {
+                self.core.modifications.move_restrictions |= stopper_restrictions(tile);
+            }
+        }
+
+        #[must_use]
+        fn handle_tiles(&mut self, old_pos: vec2, collision: &Collision) -> CharacterDamageResult {
+            let mut res = CharacterDamageResult::None;
+            let mut tele = None;
+            self.core.modifications.move_restrictions = 0;
+            let cur_pos = *self.pos.pos();
+            collision.intersect_line_feedback(&old_pos, &cur_pos, |tile| match tile {
+                // the front layer supports the same race tiles as the game layer
+                HitTile::Game(tile) | HitTile::Front(tile) => {
+                    self.handle_game_layer_tiles(tile, &mut res);
+                }
+                HitTile::Tele(tile) => {
+                    self.handle_tele_layer_tile(tile, &mut tele);
+                }
+                HitTile::Speedup(_) => {}
+                HitTile::Switch(_) => {}
+                HitTile::Tune(_) => {
+                    // tune tiles are handled on the fly where needed
+                }
+            });
+            if matches!(res, CharacterDamageResult::Death) {
+                return res;
+            }
+            if let Some(tile) = tele {
+                self.handle_tele(&tile, collision);
+            }
+            self.core.core.vel = clamp_vel(
+                self.core.modifications.move_restrictions,
+                &self.core.core.vel,
+            );
+            res
+        }
+
+        fn mod_tick(&mut self) {
+            self.core.modifications.race.tick();
+            if self.core.modifications.deep_frozen {
+                self.reusable_core.debuffs.insert(
+                    CharacterDebuff::Freeze,
+                    BuffProps {
+                        remaining_tick: FREEZE_TICKS.into(),
+                        interact_tick: Default::default(),
+                        interact_cursor_dir: Default::default(),
+                        interact_val: 0.0,
+                    },
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,4 @@
+use api_macros::character_core_mod;
+
+#[character_core_mod("../../")]
+pub mod character_core {}
@@ -0,0 +1,4 @@
+use api_macros::player_mod;
+
+#[player_mod("../../")]
+pub mod player {}
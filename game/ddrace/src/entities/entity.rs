@@ -0,0 +1,4 @@
+use api_macros::entity_mod;
+
+#[entity_mod("../../")]
+pub mod entity {}
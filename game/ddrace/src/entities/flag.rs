@@ -0,0 +1,4 @@
+use api_macros::flag_mod;
+
+#[flag_mod("../../")]
+pub mod flag {}
@@ -0,0 +1,4 @@
+use api_macros::laser_mod;
+
+#[laser_mod("../../")]
+pub mod laser {}
@@ -0,0 +1,6 @@
+pub mod character;
+pub mod entity;
+pub mod flag;
+pub mod laser;
+pub mod pickup;
+pub mod projectile;
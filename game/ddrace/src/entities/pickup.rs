@@ -0,0 +1,4 @@
+use api_macros::pickup_mod;
+
+#[pickup_mod("../../")]
+pub mod pickup {}
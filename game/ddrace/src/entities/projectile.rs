@@ -0,0 +1,4 @@
+use api_macros::projectile_mod;
+
+#[projectile_mod("../../")]
+pub mod projectile {}
@@ -0,0 +1,4 @@
+use api_macros::events_mod;
+
+#[events_mod("../../")]
+pub mod events {}
@@ -0,0 +1,4 @@
+use api_macros::game_objects_mod;
+
+#[game_objects_mod("../../")]
+pub mod game_objects {}
@@ -0,0 +1,38 @@
+#![allow(clippy::too_many_arguments)]
+#![allow(clippy::module_inception)]
+#![allow(clippy::multiple_bound_locations)]
+
+pub mod collision;
+pub mod command_chain {
+    pub use ::vanilla::command_chain::*;
+}
+pub mod config;
+/// the ddrace game state, the entry point of this mod
+pub mod ddrace;
+pub mod entities;
+pub mod events;
+pub mod game_objects;
+pub mod match_manager;
+pub mod match_state;
+/// race timing, checkpoints and stopper tiles
+pub mod race;
+pub mod reusable {
+    pub use ::vanilla::reusable::*;
+}
+pub mod simulation_pipe;
+pub mod snapshot;
+pub mod spawns {
+    pub use ::vanilla::spawns::*;
+}
+/// basic sql support and the race records
+pub mod sql {
+    pub use ::vanilla::sql::*;
+    pub mod records;
+}
+pub mod stage;
+pub mod state;
+#[cfg(test)]
+mod teleporters;
+pub mod types;
+pub mod weapons;
+pub mod world;
@@ -0,0 +1,4 @@
+use api_macros::match_manager_mod;
+
+#[match_manager_mod("../../")]
+pub mod match_manager {}
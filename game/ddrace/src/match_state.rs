@@ -0,0 +1,4 @@
+use api_macros::match_state_mod;
+
+#[match_state_mod("../../")]
+pub mod match_state {}
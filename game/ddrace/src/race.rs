@@ -0,0 +1,234 @@
+/// race timing, checkpoints and the tile helpers of the ddrace mod
+pub mod race {
+    use std::time::Duration;
+
+    use game_base::mapdef_06::DdraceTileNum;
+    use game_interface::types::game::GameTickType;
+    use hiarc::Hiarc;
+    use map::map::groups::layers::tiles::{
+        rotation_180, rotation_270, TileBase, TileFlags, ROTATION_90,
+    };
+    use math::math::vector::vec2;
+    use serde::{Deserialize, Serialize};
+
+    use crate::state::state::TICKS_PER_SECOND;
+
+    /// ddrace maps support up to 25 time checkpoints
+    pub const MAX_CHECKPOINTS: usize = (DdraceTileNum::TimeCheckpointLast as usize
+        - DdraceTileNum::TimeCheckpointFirst as usize)
+        + 1;
+
+    /// How long a freeze tile freezes a character.
+    pub const FREEZE_TICKS: GameTickType = TICKS_PER_SECOND * 3;
+
+    pub fn ticks_to_duration(ticks: GameTickType) -> Duration {
+        Duration::from_micros(ticks * 1_000_000 / TICKS_PER_SECOND)
+    }
+
+    /// Formats a race time like `1:23.45`.
+    pub fn fmt_race_time(time: Duration) -> String {
+        let secs = time.as_secs();
+        format!(
+            "{}:{:02}.{:02}",
+            secs / 60,
+            secs % 60,
+            time.subsec_millis() / 10
+        )
+    }
+
+    /// Formats the difference between two race times like `+0.35`/`-1.20`.
+    pub fn fmt_race_diff(time: Duration, other: Duration) -> String {
+        let diff = time.as_secs_f64() - other.as_secs_f64();
+        format!("{}{:.2}", if diff < 0.0 { "-" } else { "+" }, diff.abs())
+    }
+
+    #[derive(Debug, Hiarc, Default, Serialize, Deserialize, Copy, Clone, PartialEq, Eq)]
+    pub enum RaceState {
+        #[default]
+        None,
+        Running,
+        Finished,
+    }
+
+    /// The race of a single character, from the start to the finish tile.
+    #[derive(Debug, Hiarc, Default, Serialize, Deserialize, Copy, Clone)]
+    pub struct CharacterRace {
+        pub state: RaceState,
+        /// Ticks passed since the start tile was left
+        pub ticks: GameTickType,
+        /// The time in ticks at every passed checkpoint, `None` if not passed.
+        pub checkpoints: [Option<GameTickType>; MAX_CHECKPOINTS],
+        pub last_checkpoint: Option<u8>,
+
+        /// The last checkpoint the server informed the player about.
+        pub reported_checkpoint: Option<u8>,
+        /// The server already handled the finish of this race.
+        pub finish_reported: bool,
+    }
+
+    impl CharacterRace {
+        /// (Re-)starts the race, touching the start tile again restarts the time.
+        pub fn start(&mut self) {
+            *self = Self {
+                state: RaceState::Running,
+                ..Default::default()
+            };
+        }
+
+        pub fn tick(&mut self) {
+            if self.state == RaceState::Running {
+                self.ticks += 1;
+            }
+        }
+
+        pub fn checkpoint(&mut self, index: u8) {
+            if self.state == RaceState::Running && self.last_checkpoint != Some(index) {
+                self.checkpoints[index as usize] = Some(self.ticks);
+                self.last_checkpoint = Some(index);
+            }
+        }
+
+        /// Returns `true` if the race was running and is now finished.
+        pub fn finish(&mut self) -> bool {
+            if self.state == RaceState::Running {
+                self.state = RaceState::Finished;
+                true
+            } else {
+                false
+            }
+        }
+
+        pub fn time(&self) -> Duration {
+            ticks_to_duration(self.ticks)
+        }
+
+        pub fn checkpoint_times(&self) -> [Option<Duration>; MAX_CHECKPOINTS] {
+            self.checkpoints.map(|cp| cp.map(ticks_to_duration))
+        }
+    }
+
+    pub mod cannot_move {
+        pub const LEFT: u8 = 1 << 0;
+        pub const RIGHT: u8 = 1 << 1;
+        pub const UP: u8 = 1 << 2;
+        pub const DOWN: u8 = 1 << 3;
+    }
+
+    /// The directions a stopper tile blocks, see [`cannot_move`].
+    pub fn stopper_restrictions(tile: &TileBase) -> u8 {
+        let rot = tile.flags & (TileFlags::XFLIP | TileFlags::YFLIP | TileFlags::ROTATE);
+        let is_rot = |r| rot == r;
+        if tile.index == DdraceTileNum::Stop as u8 {
+            if is_rot(ROTATION_90) {
+                cannot_move::LEFT
+            } else if is_rot(rotation_180()) {
+                cannot_move::UP
+            } else if is_rot(rotation_270()) {
+                cannot_move::RIGHT
+            } else {
+                cannot_move::DOWN
+            }
+        } else if tile.index == DdraceTileNum::StopS as u8 {
+            if is_rot(ROTATION_90) || is_rot(rotation_270()) {
+                cannot_move::LEFT | cannot_move::RIGHT
+            } else {
+                cannot_move::UP | cannot_move::DOWN
+            }
+        } else if tile.index == DdraceTileNum::StopA as u8 {
+            cannot_move::LEFT | cannot_move::RIGHT | cannot_move::UP | cannot_move::DOWN
+        } else {
+            0
+        }
+    }
+
+    pub fn clamp_vel(restrictions: u8, vel: &vec2) -> vec2 {
+        let mut vel = *vel;
+        if vel.x > 0.0 && (restrictions & cannot_move::RIGHT) != 0 {
+            vel.x = 0.0;
+        }
+        if vel.x < 0.0 && (restrictions & cannot_move::LEFT) != 0 {
+            vel.x = 0.0;
+        }
+        if vel.y > 0.0 && (restrictions & cannot_move::DOWN) != 0 {
+            vel.y = 0.0;
+        }
+        if vel.y < 0.0 && (restrictions & cannot_move::UP) != 0 {
+            vel.y = 0.0;
+        }
+        vel
+    }
+
+    /// Returns the checkpoint index of a time checkpoint tile.
+    pub fn time_checkpoint(tile: &TileBase) -> Option<u8> {
+        (DdraceTileNum::TimeCheckpointFirst as u8..=DdraceTileNum::TimeCheckpointLast as u8)
+            .contains(&tile.index)
+            .then(|| tile.index - DdraceTileNum::TimeCheckpointFirst as u8)
+    }
+
+    #[cfg(test)]
+    mod test {
+        use std::time::Duration;
+
+        use game_base::mapdef_06::DdraceTileNum;
+        use map::map::groups::layers::tiles::{TileBase, TileFlags, ROTATION_90};
+        use math::math::vector::vec2;
+
+        use super::{
+            cannot_move, clamp_vel, fmt_race_diff, fmt_race_time, stopper_restrictions,
+            time_checkpoint, CharacterRace, RaceState,
+        };
+
+        #[test]
+        fn race_time() {
+            let mut race = CharacterRace::default();
+            race.tick();
+            assert_eq!(race.ticks, 0);
+
+            race.start();
+            for _ in 0..75 {
+                race.tick();
+            }
+            race.checkpoint(3);
+            race.tick();
+            assert!(race.finish());
+            assert!(!race.finish());
+            race.tick();
+
+            assert_eq!(race.state, RaceState::Finished);
+            assert_eq!(race.time(), Duration::from_millis(1520));
+            assert_eq!(
+                race.checkpoint_times()[3],
+                Some(Duration::from_millis(1500))
+            );
+            assert_eq!(fmt_race_time(race.time()), "0:01.52");
+            assert_eq!(fmt_race_diff(race.time(), Duration::from_secs(2)), "-0.48");
+        }
+
+        #[test]
+        fn stoppers() {
+            let tile = |index: DdraceTileNum, flags: TileFlags| TileBase {
+                index: index as u8,
+                flags,
+            };
+            let down = stopper_restrictions(&tile(DdraceTileNum::Stop, TileFlags::empty()));
+            assert_eq!(down, cannot_move::DOWN);
+            assert_eq!(clamp_vel(down, &vec2::new(1.0, 2.0)), vec2::new(1.0, 0.0));
+            assert_eq!(clamp_vel(down, &vec2::new(1.0, -2.0)), vec2::new(1.0, -2.0));
+            assert_eq!(
+                stopper_restrictions(&tile(DdraceTileNum::StopS, ROTATION_90)),
+                cannot_move::LEFT | cannot_move::RIGHT
+            );
+            assert_eq!(
+                time_checkpoint(&tile(
+                    DdraceTileNum::TimeCheckpointFirst,
+                    TileFlags::empty()
+                )),
+                Some(0)
+            );
+            assert_eq!(
+                time_checkpoint(&tile(DdraceTileNum::Start, TileFlags::empty())),
+                None
+            );
+        }
+    }
+}
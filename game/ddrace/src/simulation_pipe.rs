@@ -0,0 +1,4 @@
+use api_macros::simulation_pipe_mod;
+
+#[simulation_pipe_mod("../../")]
+pub mod simulation_pipe {}
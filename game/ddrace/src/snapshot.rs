@@ -0,0 +1,4 @@
+use api_macros::snapshot_mod;
+
+#[snapshot_mod("../../")]
+pub mod snapshot {}
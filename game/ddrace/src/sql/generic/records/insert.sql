@@ -0,0 +1,9 @@
+INSERT INTO ddrace_race_records (
+    map_name,
+    player_key,
+    player_name,
+    time_micros,
+    checkpoints
+)
+VALUES
+    (?, ?, ?, ?, ?);
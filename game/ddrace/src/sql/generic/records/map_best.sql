@@ -0,0 +1,11 @@
+SELECT
+    ddrace_race_records.time_micros,
+    ddrace_race_records.checkpoints
+FROM
+    ddrace_race_records
+WHERE
+    ddrace_race_records.map_name = ?
+ORDER BY
+    ddrace_race_records.time_micros ASC
+LIMIT
+    1;
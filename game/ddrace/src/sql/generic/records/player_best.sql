@@ -0,0 +1,12 @@
+SELECT
+    ddrace_race_records.time_micros,
+    ddrace_race_records.checkpoints
+FROM
+    ddrace_race_records
+WHERE
+    ddrace_race_records.map_name = ?
+    AND ddrace_race_records.player_key = ?
+ORDER BY
+    ddrace_race_records.time_micros ASC
+LIMIT
+    1;
@@ -0,0 +1,7 @@
+SELECT
+    COUNT(DISTINCT ddrace_race_records.player_key) AS better_players
+FROM
+    ddrace_race_records
+WHERE
+    ddrace_race_records.map_name = ?
+    AND ddrace_race_records.time_micros < ?;
@@ -0,0 +1,13 @@
+SELECT
+    ddrace_race_records.time_micros,
+    ddrace_race_records.create_time
+FROM
+    ddrace_race_records
+WHERE
+    ddrace_race_records.map_name = ?
+    AND ddrace_race_records.player_key = ?
+ORDER BY
+    ddrace_race_records.create_time DESC,
+    ddrace_race_records.id DESC
+LIMIT
+    5;
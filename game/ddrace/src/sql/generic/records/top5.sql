@@ -0,0 +1,13 @@
+SELECT
+    MAX(ddrace_race_records.player_name) AS player_name,
+    MIN(ddrace_race_records.time_micros) AS best_time
+FROM
+    ddrace_race_records
+WHERE
+    ddrace_race_records.map_name = ?
+GROUP BY
+    ddrace_race_records.player_key
+ORDER BY
+    best_time ASC
+LIMIT
+    5;
@@ -0,0 +1,12 @@
+CREATE TABLE ddrace_race_records (
+    id BIGINT NOT NULL AUTO_INCREMENT,
+    map_name VARCHAR(64) NOT NULL,
+    player_key VARCHAR(128) NOT NULL,
+    player_name VARCHAR(64) NOT NULL,
+    time_micros BIGINT NOT NULL,
+    checkpoints TEXT NOT NULL,
+    create_time DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY(id),
+    KEY(map_name, time_micros),
+    KEY(map_name, player_key)
+);
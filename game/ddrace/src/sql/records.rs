@@ -0,0 +1,298 @@
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+use anyhow::anyhow;
+use game_database::{
+    statement::{Statement, StatementBuilder},
+    traits::{DbInterface, DbKind, DbKindExtra, SqlText},
+    types::UnixUtcTimestamp,
+    StatementArgs, StatementResult,
+};
+
+use crate::race::race::MAX_CHECKPOINTS;
+
+#[derive(Debug, StatementArgs)]
+struct InsertArg {
+    map_name: String,
+    player_key: String,
+    player_name: String,
+    time_micros: i64,
+    checkpoints: String,
+}
+
+#[derive(Debug, StatementArgs)]
+struct MapArg {
+    map_name: String,
+}
+
+#[derive(Debug, StatementArgs)]
+struct PlayerArg {
+    map_name: String,
+    player_key: String,
+}
+
+#[derive(Debug, StatementArgs)]
+struct RankArg {
+    map_name: String,
+    time_micros: i64,
+}
+
+#[derive(Debug, StatementResult)]
+struct Top5Result {
+    player_name: String,
+    best_time: i64,
+}
+
+#[derive(Debug, StatementResult)]
+struct BestResult {
+    time_micros: i64,
+    checkpoints: String,
+}
+
+#[derive(Debug, StatementResult)]
+struct RankResult {
+    better_players: i64,
+}
+
+#[derive(Debug, StatementResult)]
+struct TimesResult {
+    time_micros: i64,
+    create_time: UnixUtcTimestamp,
+}
+
+/// A finished race, the best one of a player or a map.
+#[derive(Debug, Clone)]
+pub struct RaceRecord {
+    pub time: Duration,
+    pub checkpoints: [Option<Duration>; MAX_CHECKPOINTS],
+}
+
+impl RaceRecord {
+    fn from_db(time_micros: i64, checkpoints: &str) -> Self {
+        let mut res = Self {
+            time: Duration::from_micros(time_micros.max(0) as u64),
+            checkpoints: [None; MAX_CHECKPOINTS],
+        };
+        // `index:micros` pairs, separated by commas
+        for (index, time) in checkpoints
+            .split(',')
+            .filter_map(|cp| cp.split_once(':'))
+            .filter_map(|(index, time)| {
+                Some((index.parse::<usize>().ok()?, time.parse::<u64>().ok()?))
+            })
+        {
+            if let Some(cp) = res.checkpoints.get_mut(index) {
+                *cp = Some(Duration::from_micros(time));
+            }
+        }
+        res
+    }
+
+    fn checkpoints_to_db(checkpoints: &[Option<Duration>; MAX_CHECKPOINTS]) -> String {
+        checkpoints
+            .iter()
+            .enumerate()
+            .filter_map(|(index, cp)| cp.map(|cp| format!("{}:{}", index, cp.as_micros())))
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Top5Entry {
+    pub player_name: String,
+    pub time: Duration,
+}
+
+#[derive(Debug, Clone)]
+pub struct TimesEntry {
+    pub time: Duration,
+    pub create_time: UnixUtcTimestamp,
+}
+
+/// All statements to read and write the best times of a map.
+#[derive(Clone)]
+pub struct RaceRecords {
+    insert: Arc<Statement<InsertArg, ()>>,
+    top5: Arc<Statement<MapArg, Top5Result>>,
+    map_best: Arc<Statement<MapArg, BestResult>>,
+    player_best: Arc<Statement<PlayerArg, BestResult>>,
+    rank: Arc<Statement<RankArg, RankResult>>,
+    times: Arc<Statement<PlayerArg, TimesResult>>,
+}
+
+impl RaceRecords {
+    pub async fn new(db: Arc<dyn DbInterface>) -> anyhow::Result<Self> {
+        let kinds = db.kinds();
+        let kind = [
+            DbKind::MySql(DbKindExtra::Main),
+            DbKind::Sqlite(DbKindExtra::Main),
+        ]
+        .into_iter()
+        .find(|kind| kinds.contains(kind))
+        .ok_or_else(|| anyhow!("No main db specified"))?;
+
+        let insert = StatementBuilder::<_, InsertArg, ()>::new(
+            kind,
+            include_str!("generic/records/insert.sql"),
+            |arg| {
+                vec![
+                    arg.map_name,
+                    arg.player_key,
+                    arg.player_name,
+                    arg.time_micros,
+                    arg.checkpoints,
+                ]
+            },
+        );
+        let top5 = StatementBuilder::<_, MapArg, Top5Result>::new(
+            kind,
+            include_str!("generic/records/top5.sql"),
+            |arg| vec![arg.map_name],
+        );
+        let map_best = StatementBuilder::<_, MapArg, BestResult>::new(
+            kind,
+            include_str!("generic/records/map_best.sql"),
+            |arg| vec![arg.map_name],
+        );
+        let player_best = StatementBuilder::<_, PlayerArg, BestResult>::new(
+            kind,
+            include_str!("generic/records/player_best.sql"),
+            |arg| vec![arg.map_name, arg.player_key],
+        );
+        let rank = StatementBuilder::<_, RankArg, RankResult>::new(
+            kind,
+            include_str!("generic/records/rank.sql"),
+            |arg| vec![arg.map_name, arg.time_micros],
+        );
+        let times = StatementBuilder::<_, PlayerArg, TimesResult>::new(
+            kind,
+            include_str!("generic/records/times.sql"),
+            |arg| vec![arg.map_name, arg.player_key],
+        );
+
+        Ok(Self {
+            insert: Arc::new(Statement::new(db.clone(), insert).await?),
+            top5: Arc::new(Statement::new(db.clone(), top5).await?),
+            map_best: Arc::new(Statement::new(db.clone(), map_best).await?),
+            player_best: Arc::new(Statement::new(db.clone(), player_best).await?),
+            rank: Arc::new(Statement::new(db.clone(), rank).await?),
+            times: Arc::new(Statement::new(db, times).await?),
+        })
+    }
+
+    pub async fn insert(
+        &self,
+        map_name: &str,
+        player_key: &str,
+        player_name: &str,
+        record: &RaceRecord,
+    ) -> anyhow::Result<u64> {
+        self.insert
+            .execute(InsertArg {
+                map_name: map_name.to_string(),
+                player_key: player_key.to_string(),
+                player_name: player_name.to_string(),
+                time_micros: record.time.as_micros() as i64,
+                checkpoints: RaceRecord::checkpoints_to_db(&record.checkpoints),
+            })
+            .await
+    }
+
+    pub async fn top5(&self, map_name: &str) -> anyhow::Result<Vec<Top5Entry>> {
+        Ok(self
+            .top5
+            .fetch_all(MapArg {
+                map_name: map_name.to_string(),
+            })
+            .await?
+            .into_iter()
+            .map(|res| Top5Entry {
+                player_name: res.player_name,
+                time: Duration::from_micros(res.best_time.max(0) as u64),
+            })
+            .collect())
+    }
+
+    pub async fn map_best(&self, map_name: &str) -> anyhow::Result<Option<RaceRecord>> {
+        Ok(self
+            .map_best
+            .fetch_optional(MapArg {
+                map_name: map_name.to_string(),
+            })
+            .await?
+            .map(|res| RaceRecord::from_db(res.time_micros, &res.checkpoints)))
+    }
+
+    pub async fn player_best(
+        &self,
+        map_name: &str,
+        player_key: &str,
+    ) -> anyhow::Result<Option<RaceRecord>> {
+        Ok(self
+            .player_best
+            .fetch_optional(PlayerArg {
+                map_name: map_name.to_string(),
+                player_key: player_key.to_string(),
+            })
+            .await?
+            .map(|res| RaceRecord::from_db(res.time_micros, &res.checkpoints)))
+    }
+
+    /// The 1-based rank of the player's best time on this map,
+    /// together with the best time. `None` if the player never finished.
+    pub async fn rank(
+        &self,
+        map_name: &str,
+        player_key: &str,
+    ) -> anyhow::Result<Option<(u64, Duration)>> {
+        let Some(best) = self.player_best(map_name, player_key).await? else {
+            return Ok(None);
+        };
+        let res = self
+            .rank
+            .fetch_one(RankArg {
+                map_name: map_name.to_string(),
+                time_micros: best.time.as_micros() as i64,
+            })
+            .await?;
+        Ok(Some((res.better_players.max(0) as u64 + 1, best.time)))
+    }
+
+    /// The last few times of a player on this map, newest first.
+    pub async fn times(&self, map_name: &str, player_key: &str) -> anyhow::Result<Vec<TimesEntry>> {
+        Ok(self
+            .times
+            .fetch_all(PlayerArg {
+                map_name: map_name.to_string(),
+                player_key: player_key.to_string(),
+            })
+            .await?
+            .into_iter()
+            .map(|res| TimesEntry {
+                time: Duration::from_micros(res.time_micros.max(0) as u64),
+                create_time: res.create_time,
+            })
+            .collect())
+    }
+}
+
+pub async fn setup(db: Arc<dyn DbInterface>) -> anyhow::Result<()> {
+    let mut stmts: HashMap<_, Vec<SqlText>> = Default::default();
+    let kinds = db.kinds();
+
+    if kinds.contains(&DbKind::MySql(DbKindExtra::Main)) {
+        stmts
+            .entry(DbKind::MySql(DbKindExtra::Main))
+            .or_default()
+            .push(include_str!("mysql/records/records.sql").into());
+    }
+    if kinds.contains(&DbKind::Sqlite(DbKindExtra::Main)) {
+        stmts
+            .entry(DbKind::Sqlite(DbKindExtra::Main))
+            .or_default()
+            .push(include_str!("sqlite/records/records.sql").into());
+    }
+
+    db.setup("game-server-ddrace", vec![(1, stmts)].into_iter().collect())
+        .await
+}
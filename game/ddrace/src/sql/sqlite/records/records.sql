@@ -0,0 +1,10 @@
+CREATE TABLE ddrace_race_records (
+    id INTEGER AUTO_INCREMENT,
+    map_name TEXT NOT NULL,
+    player_key TEXT NOT NULL,
+    player_name TEXT NOT NULL,
+    time_micros INTEGER NOT NULL,
+    checkpoints TEXT NOT NULL,
+    create_time DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY(id)
+);
@@ -0,0 +1,4 @@
+use api_macros::stage_mod;
+
+#[stage_mod("../../")]
+pub mod stage {}
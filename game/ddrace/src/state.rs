@@ -0,0 +1,4 @@
+use api_macros::state_mod;
+
+#[state_mod("../../")]
+pub mod state {}
@@ -0,0 +1,157 @@
+//! Ticks characters through the tele checkpoints of a small map
+//! that replaces the physics of ctf1.
+
+use std::sync::Arc;
+
+use base_io::{io::create_runtime, runtime::IoRuntime};
+use game_base::mapdef_06::DdraceTileNum;
+use game_database::dummy::DummyDb;
+use game_interface::{
+    interface::{GameStateCreate, GameStateCreateOptions, GameStateInterface},
+    types::{
+        character_info::NetworkCharacterInfo,
+        id_types::PlayerId,
+        network_stats::PlayerNetworkStats,
+        player_info::{PlayerClientInfo, PlayerUniqueId},
+    },
+};
+use map::{
+    map::groups::{
+        layers::{
+            physics::{MapLayerPhysics, MapLayerTilePhysicsBase, MapLayerTilePhysicsTele},
+            tiles::{TeleTile, TileBase},
+        },
+        MapGroupPhysics, MapGroupPhysicsAttr,
+    },
+    types::NonZeroU16MinusOne,
+};
+use math::math::{distance, vector::vec2};
+
+use crate::{
+    collision::collision::Collision, entities::character::character::Character,
+    state::state::GameState,
+};
+
+const W: usize = 30;
+const H: usize = 10;
+/// The row the characters stand in, the row below is solid.
+const ROW: usize = 7;
+
+fn tile_center(x: usize, y: usize) -> vec2 {
+    vec2::new(x as f32 * 32.0 + 16.0, y as f32 * 32.0 + 16.0)
+}
+
+/// Creates a game whose collision is a `W` x `H` map with the given
+/// tele tiles and lets a single player join.
+fn tele_game(tele_tiles: &[(usize, usize, DdraceTileNum, u8)]) -> (GameState, PlayerId) {
+    let mut tiles = vec![TileBase::default(); W * H];
+    for tile in tiles[(ROW + 1) * W..(ROW + 2) * W].iter_mut() {
+        tile.index = DdraceTileNum::Solid as u8;
+    }
+    let mut tele = vec![TeleTile::default(); W * H];
+    for &(x, y, index, number) in tele_tiles {
+        tele[y * W + x] = TeleTile {
+            base: TileBase {
+                index: index as u8,
+                ..Default::default()
+            },
+            number,
+        };
+    }
+    let collision = Collision::new(
+        &MapGroupPhysics {
+            attr: MapGroupPhysicsAttr {
+                width: NonZeroU16MinusOne::new(W as u16).unwrap(),
+                height: NonZeroU16MinusOne::new(H as u16).unwrap(),
+            },
+            layers: vec![
+                MapLayerPhysics::Game(MapLayerTilePhysicsBase { tiles }),
+                MapLayerPhysics::Tele(MapLayerTilePhysicsTele {
+                    base: MapLayerTilePhysicsBase { tiles: tele },
+                    tele_names: Default::default(),
+                }),
+            ],
+        },
+        true,
+    )
+    .unwrap();
+
+    let file = include_bytes!("../../../data/map/maps/ctf1.twmap");
+    let rt = create_runtime();
+    let io_rt = IoRuntime::new(rt);
+    let (mut game, _) = GameState::new(
+        file.to_vec(),
+        "ctf1".try_into().unwrap(),
+        GameStateCreateOptions::default(),
+        io_rt,
+        Arc::new(DummyDb),
+    )
+    .unwrap();
+    // ctf1 is bigger than the test map, so the playfield still fits
+    game.collision = collision;
+
+    let id = game.player_join(&PlayerClientInfo {
+        info: NetworkCharacterInfo::explicit_default(),
+        id: 0,
+        unique_identifier: PlayerUniqueId::Account(0),
+        initial_network_stats: PlayerNetworkStats::default(),
+    });
+    (game, id)
+}
+
+fn character<'a>(game: &'a mut GameState, id: &PlayerId) -> &'a mut Character {
+    let stage_id = game.stage_0_id;
+    game.game
+        .stages
+        .get_mut(&stage_id)
+        .unwrap()
+        .world
+        .characters
+        .get_mut(id)
+        .unwrap()
+}
+
+/// Places the character on the tile with the given velocity.
+fn place(game: &mut GameState, id: &PlayerId, pos: vec2, vel: vec2) {
+    let character = character(game, id);
+    character.pos.move_pos(pos);
+    character.core.core.vel = vel;
+}
+
+fn tick(game: &mut GameState) {
+    game.tick(Default::default());
+    game.clear_events();
+}
+
+#[test]
+fn character_tele_checkpoint() {
+    let check_out = tile_center(24, ROW);
+    let (mut game, id) = tele_game(&[
+        (2, ROW, DdraceTileNum::TeleCheck, 3),
+        (6, ROW, DdraceTileNum::TeleCheckIn, 0),
+        (8, ROW, DdraceTileNum::TeleCheckInEvil, 0),
+        (24, ROW, DdraceTileNum::TeleCheckOut, 3),
+    ]);
+
+    // without a passed checkpoint there is nothing to teleport to
+    place(&mut game, &id, tile_center(6, ROW), vec2::default());
+    tick(&mut game);
+    assert!(distance(character(&mut game, &id).pos.pos(), &tile_center(6, ROW)) < 32.0);
+
+    place(&mut game, &id, tile_center(2, ROW), vec2::default());
+    tick(&mut game);
+    assert_eq!(character(&mut game, &id).core.tele_checkpoint, Some(3));
+
+    place(&mut game, &id, tile_center(6, ROW), vec2::new(5.0, 0.0));
+    tick(&mut game);
+    let character_ref = character(&mut game, &id);
+    assert!(distance(character_ref.pos.pos(), &check_out) < 32.0);
+    // normal checkpoint teleporters keep the velocity
+    assert!(character_ref.core.core.vel.x > 0.0);
+
+    place(&mut game, &id, tile_center(8, ROW), vec2::new(5.0, 0.0));
+    tick(&mut game);
+    let character_ref = character(&mut game, &id);
+    assert!(distance(character_ref.pos.pos(), &check_out) < 1.0);
+    assert_eq!(character_ref.core.core.vel.x, 0.0);
+}
@@ -0,0 +1,4 @@
+use api_macros::types_mod;
+
+#[types_mod("../../")]
+pub mod types {}
@@ -0,0 +1,4 @@
+use api_macros::weapon_def_mod;
+
+#[weapon_def_mod("../../")]
+pub mod weapon_def {}
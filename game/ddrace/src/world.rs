@@ -0,0 +1,4 @@
+use api_macros::world_mod;
+
+#[world_mod("../../")]
+pub mod world {}
@@ -111,12 +111,14 @@ use crate::{
     physics_layers::PhysicsLayerOverlaysDdnet,
     server::EditorServer,
     tab::EditorTab,
+    test_play::{place_test_play_spawn, EditorTestPlay, TEST_PLAY_MAP_NAME},
     tools::{
         auto_saver::AutoSaver,
         quad_layer::{brush::QuadBrush, selection::QuadSelection},
-        sound_layer::brush::SoundBrush,
+        sound_layer::{brush::SoundBrush, selection::SoundSelection},
         tile_layer::{
-            auto_mapper::TileLayerAutoMapper, brush::TileBrush, selection::TileSelection,
+            auto_mapper::TileLayerAutoMapper, brush::TileBrush, fill::TileFill,
+            selection::TileSelection, shape::TileShape,
         },
         tool::{
             ActiveTool, ActiveToolQuads, ActiveToolSounds, ActiveToolTiles, ToolQuadLayer,
@@ -222,6 +224,8 @@ pub struct Editor {
     thread_pool: Arc<rayon::ThreadPool>,
 
     save_tasks: Vec<IoRuntimeTask<()>>,
+    /// The map is written to disk before it can be test played.
+    test_play_task: Option<(IoRuntimeTask<()>, EditorTestPlay)>,
 }
 
 #[derive(Debug, Clone)]
@@ -319,6 +323,8 @@ impl Editor {
                         &overlays,
                     ),
                     selection: TileSelection::new(),
+                    fill: TileFill::default(),
+                    shape: TileShape::new(),
                 },
                 quads: ToolQuadLayer {
                     brush: QuadBrush::new(),
@@ -326,6 +332,7 @@ impl Editor {
                 },
                 sounds: ToolSoundLayer {
                     brush: SoundBrush::new(),
+                    selection: SoundSelection::new(),
                 },
                 active_tool: ActiveTool::Tiles(ActiveToolTiles::Brush),
             },
@@ -370,6 +377,7 @@ impl Editor {
             thread_pool: tp.clone(),
 
             save_tasks: Default::default(),
+            test_play_task: None,
 
             sys,
         };
@@ -1372,8 +1380,13 @@ impl Editor {
     ) -> (Map, HashMap<String, Vec<u8>>, PathBuf) {
         tab.auto_saver.path = Some(path.to_path_buf());
         let map: Map = tab.map.clone().into();
-        let resources = tab
-            .map
+        let resources = Self::map_tab_resources(tab);
+        (map, resources, path.to_path_buf())
+    }
+
+    /// All resource files of the map, keyed by their path.
+    fn map_tab_resources(tab: &EditorTab) -> HashMap<String, Vec<u8>> {
+        tab.map
             .resources
             .images
             .iter()
@@ -1467,8 +1480,7 @@ impl Editor {
                 )
                 .collect::<Vec<_>>()
             }))
-            .collect::<HashMap<_, _>>();
-        (map, resources, path.to_path_buf())
+            .collect::<HashMap<_, _>>()
     }
 
     pub fn save_map_tab(
@@ -1532,6 +1544,51 @@ impl Editor {
         }
     }
 
+    /// Writes the active map to a separate map file,
+    /// which is then played on a local server.
+    /// The editor state itself stays untouched.
+    fn test_play(&mut self, game_mod: String) {
+        if self.test_play_task.is_some() {
+            return;
+        }
+        let Some(tab) = self.tabs.get(&self.active_tab) else {
+            let msg = "No map was loaded to be test played.";
+            log::info!("{msg}");
+            self.notifications_overlay
+                .add_err(msg, Duration::from_secs(10));
+            return;
+        };
+
+        let mut map: Map = tab.map.clone().into();
+        place_test_play_spawn(&mut map, tab.map.groups.user.pos, &game_mod);
+        let resources = Self::map_tab_resources(tab);
+
+        let tp = self.thread_pool.clone();
+        let fs = self.io.fs.clone();
+        let path = format!("map/maps/{TEST_PLAY_MAP_NAME}.twmap");
+        let task = self.io.rt.spawn(async move {
+            fs.create_dir("map/maps".as_ref()).await?;
+            fs.create_dir("map/resources/images".as_ref()).await?;
+            fs.create_dir("map/resources/sounds".as_ref()).await?;
+
+            let mut file: Vec<u8> = Default::default();
+            map.write(&mut file, &tp)?;
+            fs.write_file(path.as_ref(), file).await?;
+
+            for (path, resource) in resources {
+                fs.write_file(path.as_ref(), resource).await?;
+            }
+            Ok(())
+        });
+        self.test_play_task = Some((
+            task,
+            EditorTestPlay {
+                map_name: TEST_PLAY_MAP_NAME.to_string(),
+                game_mod,
+            },
+        ));
+    }
+
     fn save_tab(&mut self, tab: &str) -> bool {
         if let Some((path, tab)) = self
             .tabs
@@ -2133,6 +2190,8 @@ impl Editor {
                     &self.canvas_handle,
                     &tab.map,
                     &self.latest_pointer,
+                    &self.latest_keys_down,
+                    &self.latest_modifiers,
                     &self.current_pointer_pos,
                     &mut tab.client,
                 ),
@@ -2498,6 +2557,9 @@ impl Editor {
                 EditorUiEvent::Minimize => {
                     forced_result = Some(EditorResult::Minimize);
                 }
+                EditorUiEvent::TestPlay { game_mod } => {
+                    self.test_play(game_mod);
+                }
                 EditorUiEvent::Undo => {
                     if let Some(tab) = self.tabs.get(&self.active_tab) {
                         tab.client.undo();
@@ -2585,6 +2647,9 @@ impl Editor {
 pub enum EditorResult {
     Close,
     Minimize,
+    /// The editor wants to test play the map,
+    /// it should be minimized meanwhile.
+    TestPlay(EditorTestPlay),
     PlatformOutput(egui::PlatformOutput),
 }
 
@@ -2693,6 +2758,26 @@ impl EditorInterface for Editor {
         }
         std::mem::swap(&mut self.save_tasks, &mut unfinished_tasks);
 
+        // start the test play as soon as the map was written
+        let mut test_play_result = None;
+        if self
+            .test_play_task
+            .as_ref()
+            .is_some_and(|(task, _)| task.is_finished())
+        {
+            let (task, test_play) = self.test_play_task.take().unwrap();
+            match task.get_storage() {
+                Ok(_) => {
+                    test_play_result = Some(EditorResult::TestPlay(test_play));
+                }
+                Err(err) => {
+                    log::error!("{err}");
+                    self.notifications_overlay
+                        .add_err(err.to_string(), Duration::from_secs(10));
+                }
+            }
+        }
+
         // render the overlay for notifications
         for ev in self.notifications.take() {
             match ev {
@@ -2715,7 +2800,7 @@ impl EditorInterface for Editor {
         }
         self.notifications_overlay.render();
 
-        if let Some(res) = forced_result {
+        if let Some(res) = forced_result.or(test_play_result) {
             res
         } else {
             EditorResult::PlatformOutput(ui_output)
@@ -26,6 +26,19 @@ The property's value is the interpreted as `value = base-value * animation-point
 to calculate the value of the animation points.\
 ";
 
+pub const TEXT_SOUND_SELECTION: &str =
+"\
+# Sound selection\n\
+\n\
+The sound selection is a specialized tool that focuses on making working with existing sound sources easier.\n\n\
+You can change many shared properties at once to many sounds. E.g. the falloff or the shape of the sound sources.\n\
+First select your sound(s) using `left click`:\n\
+- `right click` on a sound's center _point_ to open the property window for all selected sounds.\n\
+- `left click` on a center point to drag all sounds.\n\
+- `ctrl + c` copies the selected sounds, `ctrl + v` pastes them at the cursor.\n\n\
+Press `right click` on no sound to unset the selection.\
+";
+
 pub const TEXT_TILE_BRUSH: &str =
 "\
 # Tile brush\n\
@@ -158,3 +171,27 @@ pub const TEXT_TILE_BRUSH_MIRROR: &str = "\
 \n\
 Mirrors the tile brush horizontal or vertically.
 ";
+
+pub const TEXT_TILE_FILL: &str = "\
+# Tile fill\n\
+\n\
+Fills the clicked area with the current tile brush (`left click`).  \n\
+By default only the connected tiles that equal the clicked tile are replaced, \
+in global mode all equal tiles of the layer are replaced.  \n\
+Bigger brushes are repeated as a pattern.\n\n\
+---\n\n\
+Select the brush the same way as with the tile brush, \
+hold `space` to open the tile picker.\
+";
+
+pub const TEXT_TILE_SHAPES: &str = "\
+# Tile shapes\n\
+\n\
+Draws a rectangle, line or ellipse with the current tile brush.  \n\
+Hold `left click` at the start position and release it at the end position, \
+press `right click` in between to cancel.  \n\
+Rectangles and ellipses can optionally be filled.\n\n\
+---\n\n\
+Select the brush the same way as with the tile brush, \
+hold `space` to open the tile picker.\
+";
@@ -0,0 +1,61 @@
+use game_base::mapdef_06::EEntityTiles;
+use map::map::{groups::layers::physics::MapLayerPhysics, Map};
+use math::math::vector::vec2;
+use serde::{Deserialize, Serialize};
+
+/// The name of the map file the currently edited map is written to
+/// before it is test played.
+pub const TEST_PLAY_MAP_NAME: &str = "editor-test-play";
+
+/// The physics mods the editor offers for test playing.
+pub const TEST_PLAY_GAME_MODS: [&str; 2] = ["vanilla", "ddnet"];
+
+/// Everything the client needs to start a local server
+/// for the map that is test played.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EditorTestPlay {
+    /// The map name without extension inside `map/maps`.
+    pub map_name: String,
+    /// The game mod the local server should load.
+    pub game_mod: String,
+}
+
+/// Replaces the spawns of the game layer by a single spawn at `pos`
+/// (in tiles), so the player spawns where the editor camera was.
+///
+/// The red & blue spawns are only replaced if `game_mod` has no team
+/// based game types, otherwise the teams would lose their own spawns.
+///
+/// Does nothing if `pos` is outside of the map.
+pub fn place_test_play_spawn(map: &mut Map, pos: vec2, game_mod: &str) {
+    let physics = &mut map.groups.physics;
+    let width = physics.attr.width.get() as i32;
+    let height = physics.attr.height.get() as i32;
+    let x = pos.x.floor() as i32;
+    let y = pos.y.floor() as i32;
+    if x < 0 || y < 0 || x >= width || y >= height {
+        return;
+    }
+    let Some(tiles) = physics.layers.iter_mut().find_map(|layer| match layer {
+        MapLayerPhysics::Game(layer) => Some(&mut layer.tiles),
+        _ => None,
+    }) else {
+        return;
+    };
+
+    // vanilla's ctf spawns the players at the spawns of their team
+    let replace_team_spawns = game_mod != "vanilla";
+    for tile in tiles.iter_mut() {
+        if tile.index == EEntityTiles::Spawn as u8
+            || (replace_team_spawns
+                && (tile.index == EEntityTiles::SpawnRed as u8
+                    || tile.index == EEntityTiles::SpawnBlue as u8))
+        {
+            tile.index = 0;
+            tile.flags = Default::default();
+        }
+    }
+    let tile = &mut tiles[(y * width + x) as usize];
+    tile.index = EEntityTiles::Spawn as u8;
+    tile.flags = Default::default();
+}
@@ -1,2 +1,3 @@
 pub mod brush;
+pub mod selection;
 pub mod shared;
@@ -0,0 +1,558 @@
+use std::{
+    collections::{BTreeMap, HashSet},
+    time::Duration,
+};
+
+use client_render_base::map::render_tools::{CanvasType, RenderTools};
+use graphics::handles::{
+    canvas::canvas::GraphicsCanvasHandle, stream::stream::GraphicsStreamHandle,
+};
+use graphics_types::rendering::State;
+use hiarc::Hiarc;
+use map::map::{
+    animations::{AnimPointCurveType, AnimPointPos},
+    groups::layers::design::Sound,
+};
+use math::math::vector::{dvec2, ffixed, fvec2, fvec3, ubvec4, vec2};
+
+use crate::{
+    actions::actions::{
+        ActChangeSoundAttr, ActSoundLayerAddRemSounds, ActSoundLayerAddSounds, EditorAction,
+        EditorActionGroup,
+    },
+    client::EditorClient,
+    map::{EditorLayer, EditorLayerSound, EditorLayerUnionRef, EditorMap, EditorMapInterface},
+    tools::{quad_layer::shared::in_box, shared::in_radius, utils::render_rect},
+    utils::{ui_pos_to_world_pos, UiCanvasSize},
+};
+
+use super::shared::{render_sound_points, SoundPointerDownPoint, SOUND_POINT_RADIUS};
+
+#[derive(Debug, Hiarc)]
+pub enum SoundSelectionPointerDownState {
+    None,
+    /// the selected sounds are dragged, contains the last pointer pos
+    Move(vec2),
+    /// selection of sounds
+    Selection(vec2),
+}
+
+impl SoundSelectionPointerDownState {
+    pub fn is_selection(&self) -> bool {
+        matches!(self, Self::Selection(_))
+    }
+}
+
+#[derive(Debug, Hiarc)]
+pub struct SoundSelectionSounds {
+    pub sounds: BTreeMap<usize, Sound>,
+
+    /// selection x offset
+    pub x: f32,
+    /// selection y offset
+    pub y: f32,
+    /// width of the selection
+    pub w: f32,
+    /// height of the selection
+    pub h: f32,
+
+    pub point: Option<SoundPointerDownPoint>,
+}
+
+impl SoundSelectionSounds {
+    pub fn indices_checked(&mut self, layer: &EditorLayerSound) -> BTreeMap<usize, &mut Sound> {
+        while self
+            .sounds
+            .last_key_value()
+            .is_some_and(|(index, _)| *index >= layer.layer.sounds.len())
+        {
+            self.sounds.pop_last();
+        }
+
+        self.sounds
+            .iter_mut()
+            .map(|(index, sound)| (*index, sound))
+            .collect()
+    }
+}
+
+/// Copied sounds, relative to the top left of their selection
+#[derive(Debug, Hiarc)]
+pub struct SoundSelectionClipboard {
+    pub sounds: Vec<Sound>,
+}
+
+#[derive(Debug, Hiarc)]
+pub struct SoundSelection {
+    pub range: Option<SoundSelectionSounds>,
+    pub pos_offset: dvec2,
+
+    pub pointer_down_state: SoundSelectionPointerDownState,
+
+    pub clipboard: Option<SoundSelectionClipboard>,
+    /// copy & paste only trigger once per key press
+    clipboard_key_down: bool,
+
+    /// to be used to alter the animation using sound properties
+    pub anim_point_pos: AnimPointPos,
+}
+
+impl Default for SoundSelection {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SoundSelection {
+    pub fn new() -> Self {
+        Self {
+            pointer_down_state: SoundSelectionPointerDownState::None,
+            pos_offset: dvec2::default(),
+            range: None,
+
+            clipboard: None,
+            clipboard_key_down: false,
+
+            anim_point_pos: AnimPointPos {
+                time: Duration::ZERO,
+                curve_type: AnimPointCurveType::Linear,
+                value: fvec3::default(),
+            },
+        }
+    }
+
+    fn pointer_world_pos(
+        ui_canvas: &UiCanvasSize,
+        canvas_handle: &GraphicsCanvasHandle,
+        map: &EditorMap,
+        current_pointer_pos: &egui::Pos2,
+    ) -> vec2 {
+        let (offset, parallax) = if let Some(layer) = &map.active_layer() {
+            layer.get_offset_and_parallax()
+        } else {
+            Default::default()
+        };
+        ui_pos_to_world_pos(
+            canvas_handle,
+            ui_canvas,
+            map.groups.user.zoom,
+            vec2::new(current_pointer_pos.x, current_pointer_pos.y),
+            map.groups.user.pos.x,
+            map.groups.user.pos.y,
+            offset.x,
+            offset.y,
+            parallax.x,
+            parallax.y,
+        )
+    }
+
+    fn handle_brush_select(
+        &mut self,
+        ui_canvas: &UiCanvasSize,
+        canvas_handle: &GraphicsCanvasHandle,
+        map: &EditorMap,
+        latest_pointer: &egui::PointerState,
+        current_pointer_pos: &egui::Pos2,
+    ) {
+        let Some(EditorLayerUnionRef::Design {
+            layer: EditorLayer::Sound(layer),
+            ..
+        }) = map.active_layer()
+        else {
+            return;
+        };
+
+        let vec2 {
+            x: mut x1,
+            y: mut y1,
+        } = Self::pointer_world_pos(ui_canvas, canvas_handle, map, current_pointer_pos);
+
+        // check if selection phase ended
+        if let SoundSelectionPointerDownState::Selection(pointer_down) = &self.pointer_down_state {
+            let vec2 {
+                x: mut x0,
+                y: mut y0,
+            } = pointer_down;
+
+            if x0 > x1 {
+                std::mem::swap(&mut x0, &mut x1);
+            }
+            if y0 > y1 {
+                std::mem::swap(&mut y0, &mut y1);
+            }
+
+            // check if any sounds are in the selection
+            let sounds: BTreeMap<usize, Sound> = layer
+                .layer
+                .sounds
+                .iter()
+                .enumerate()
+                .filter(|(_, sound)| {
+                    let point = super::shared::get_sound_point_animated(sound, map, map.user.time);
+                    in_box(&point, x0, y0, x1, y1)
+                })
+                .map(|(s, sound)| (s, *sound))
+                .collect();
+
+            // if there is an selection, apply that
+            if !sounds.is_empty() {
+                self.range = Some(SoundSelectionSounds {
+                    sounds,
+                    x: x0,
+                    y: y0,
+                    w: x1 - x0,
+                    h: y1 - y0,
+
+                    point: None,
+                });
+            } else {
+                self.range = None;
+            }
+
+            if !latest_pointer.primary_down() {
+                self.pointer_down_state = SoundSelectionPointerDownState::None;
+            }
+        } else if latest_pointer.primary_pressed() {
+            self.pointer_down_state = SoundSelectionPointerDownState::Selection(vec2::new(x1, y1));
+        }
+    }
+
+    fn handle_selected(
+        &mut self,
+        ui_canvas: &UiCanvasSize,
+        canvas_handle: &GraphicsCanvasHandle,
+        map: &EditorMap,
+        latest_pointer: &egui::PointerState,
+        current_pointer_pos: &egui::Pos2,
+        client: &mut EditorClient,
+    ) {
+        let Some(EditorLayerUnionRef::Design {
+            layer: EditorLayer::Sound(layer),
+            group_index,
+            layer_index,
+            is_background,
+            ..
+        }) = map.active_layer()
+        else {
+            return;
+        };
+        let range = self.range.as_mut().unwrap();
+
+        let pointer_cur =
+            Self::pointer_world_pos(ui_canvas, canvas_handle, map, current_pointer_pos);
+
+        if let Some(SoundSelectionPointerDownState::Move(pos)) = latest_pointer
+            .primary_down()
+            .then_some(&mut self.pointer_down_state)
+        {
+            let diff = fvec2::new(
+                ffixed::from_num(pointer_cur.x - pos.x),
+                ffixed::from_num(pointer_cur.y - pos.y),
+            );
+            range.x += pointer_cur.x - pos.x;
+            range.y += pointer_cur.y - pos.y;
+            *pos = pointer_cur;
+
+            // move all selected sounds as one group
+            let actions: Vec<_> = range
+                .indices_checked(layer)
+                .into_iter()
+                .map(|(index, sound)| {
+                    sound.pos += diff;
+                    EditorAction::ChangeSoundAttr(ActChangeSoundAttr {
+                        is_background,
+                        group_index,
+                        layer_index,
+                        old_attr: layer.layer.sounds[index],
+                        new_attr: *sound,
+
+                        index,
+                    })
+                })
+                .collect();
+            if !actions.is_empty() && diff != fvec2::default() {
+                client.execute_group(EditorActionGroup {
+                    actions,
+                    identifier: Some(format!(
+                        "sound-selection-move-{is_background}-{group_index}-{layer_index}"
+                    )),
+                });
+            }
+        } else {
+            if !latest_pointer.primary_down() {
+                self.pointer_down_state = SoundSelectionPointerDownState::None;
+            }
+
+            // check if the pointer clicked on one of the selected sound points
+            if latest_pointer.primary_pressed() || latest_pointer.secondary_pressed() {
+                let clicked_sound_point = range.sounds.keys().any(|index| {
+                    layer.layer.sounds.get(*index).is_some_and(|sound| {
+                        let point =
+                            super::shared::get_sound_point_animated(sound, map, map.user.time);
+                        in_radius(&point, &pointer_cur, SOUND_POINT_RADIUS)
+                    })
+                });
+
+                if clicked_sound_point {
+                    if latest_pointer.primary_pressed() {
+                        self.pointer_down_state = SoundSelectionPointerDownState::Move(pointer_cur);
+                    } else {
+                        range.point = Some(SoundPointerDownPoint::Center);
+                    }
+                } else if latest_pointer.secondary_pressed() {
+                    self.range = None;
+                    self.pointer_down_state = SoundSelectionPointerDownState::None;
+                } else {
+                    // start a new selection
+                    self.range = None;
+                    self.pointer_down_state =
+                        SoundSelectionPointerDownState::Selection(pointer_cur);
+                }
+            }
+        }
+    }
+
+    fn handle_clipboard(
+        &mut self,
+        ui_canvas: &UiCanvasSize,
+        canvas_handle: &GraphicsCanvasHandle,
+        map: &EditorMap,
+        latest_keys_down: &HashSet<egui::Key>,
+        latest_modifiers: &egui::Modifiers,
+        current_pointer_pos: &egui::Pos2,
+        client: &mut EditorClient,
+    ) {
+        let copy = latest_modifiers.ctrl && latest_keys_down.contains(&egui::Key::C);
+        let paste = latest_modifiers.ctrl && latest_keys_down.contains(&egui::Key::V);
+        let was_down = std::mem::replace(&mut self.clipboard_key_down, copy || paste);
+        if was_down {
+            return;
+        }
+
+        let Some(EditorLayerUnionRef::Design {
+            layer: EditorLayer::Sound(layer),
+            group_index,
+            layer_index,
+            is_background,
+            ..
+        }) = map.active_layer()
+        else {
+            return;
+        };
+
+        if copy {
+            if let Some(range) = &self.range {
+                let origin = fvec2::new(ffixed::from_num(range.x), ffixed::from_num(range.y));
+                self.clipboard = Some(SoundSelectionClipboard {
+                    sounds: range
+                        .sounds
+                        .keys()
+                        .filter_map(|index| layer.layer.sounds.get(*index))
+                        .map(|sound| Sound {
+                            pos: sound.pos - origin,
+                            ..*sound
+                        })
+                        .collect(),
+                });
+            }
+        } else if paste {
+            if let Some(clipboard) = &self.clipboard {
+                let vec2 { x, y } =
+                    Self::pointer_world_pos(ui_canvas, canvas_handle, map, current_pointer_pos);
+                let origin = fvec2::new(ffixed::from_num(x), ffixed::from_num(y));
+                let index = layer.layer.sounds.len();
+                let sounds: Vec<_> = clipboard
+                    .sounds
+                    .iter()
+                    .map(|sound| Sound {
+                        pos: sound.pos + origin,
+                        ..*sound
+                    })
+                    .collect();
+
+                client.execute(
+                    EditorAction::SoundLayerAddSounds(ActSoundLayerAddSounds {
+                        base: ActSoundLayerAddRemSounds {
+                            is_background,
+                            group_index,
+                            layer_index,
+                            index,
+                            sounds,
+                        },
+                    }),
+                    Some(&format!(
+                        "sound-selection-paste-{is_background}-{group_index}-{layer_index}"
+                    )),
+                );
+            }
+        }
+    }
+
+    fn render_selection(
+        &self,
+        ui_canvas: &UiCanvasSize,
+        canvas_handle: &GraphicsCanvasHandle,
+        stream_handle: &GraphicsStreamHandle,
+        map: &EditorMap,
+        latest_pointer: &egui::PointerState,
+        current_pointer_pos: &egui::Pos2,
+    ) {
+        let layer = map.active_layer();
+        let (offset, parallax) = if let Some(layer) = &layer {
+            layer.get_offset_and_parallax()
+        } else {
+            Default::default()
+        };
+        // if pointer was already down
+        if let SoundSelectionPointerDownState::Selection(pointer_down) = &self.pointer_down_state {
+            if latest_pointer.primary_down() {
+                let pos =
+                    Self::pointer_world_pos(ui_canvas, canvas_handle, map, current_pointer_pos);
+                let pos = egui::pos2(pos.x, pos.y);
+
+                let down_pos = egui::pos2(pointer_down.x, pointer_down.y);
+
+                let rect = egui::Rect::from_min_max(pos, down_pos);
+
+                render_rect(
+                    canvas_handle,
+                    stream_handle,
+                    map,
+                    rect,
+                    ubvec4::new(255, 0, 0, 255),
+                    &parallax,
+                    &offset,
+                );
+            }
+        }
+    }
+
+    fn render_range(
+        &self,
+        canvas_handle: &GraphicsCanvasHandle,
+        stream_handle: &GraphicsStreamHandle,
+        map: &EditorMap,
+    ) {
+        let layer = map.active_layer();
+        let (offset, parallax) = if let Some(layer) = &layer {
+            layer.get_offset_and_parallax()
+        } else {
+            Default::default()
+        };
+
+        let mut state = State::new();
+
+        let range = self.range.as_ref().unwrap();
+
+        let (center, group_attr) = (
+            map.groups.user.pos,
+            layer.map(|layer| layer.get_or_fake_group_attr()),
+        );
+        RenderTools::map_canvas_of_group(
+            CanvasType::Handle(canvas_handle),
+            &mut state,
+            center.x,
+            center.y,
+            group_attr.as_ref(),
+            map.groups.user.zoom,
+        );
+
+        let rect = egui::Rect::from_min_max(
+            egui::pos2(range.x, range.y),
+            egui::pos2(range.x + range.w, range.y + range.h),
+        );
+
+        render_rect(
+            canvas_handle,
+            stream_handle,
+            map,
+            rect,
+            ubvec4::new(0, 0, 255, 255),
+            &parallax,
+            &offset,
+        );
+    }
+
+    pub fn update(
+        &mut self,
+        ui_canvas: &UiCanvasSize,
+        canvas_handle: &GraphicsCanvasHandle,
+        map: &EditorMap,
+        latest_pointer: &egui::PointerState,
+        latest_keys_down: &HashSet<egui::Key>,
+        latest_modifiers: &egui::Modifiers,
+        current_pointer_pos: &egui::Pos2,
+        client: &mut EditorClient,
+    ) {
+        let layer = map.active_layer();
+        if !layer.as_ref().is_some_and(|layer| layer.is_sound_layer()) {
+            return;
+        }
+
+        self.handle_clipboard(
+            ui_canvas,
+            canvas_handle,
+            map,
+            latest_keys_down,
+            latest_modifiers,
+            current_pointer_pos,
+            client,
+        );
+
+        if self.range.is_none() || self.pointer_down_state.is_selection() {
+            self.handle_brush_select(
+                ui_canvas,
+                canvas_handle,
+                map,
+                latest_pointer,
+                current_pointer_pos,
+            );
+        } else {
+            self.handle_selected(
+                ui_canvas,
+                canvas_handle,
+                map,
+                latest_pointer,
+                current_pointer_pos,
+                client,
+            );
+        }
+    }
+
+    pub fn render(
+        &mut self,
+        ui_canvas: &UiCanvasSize,
+        stream_handle: &GraphicsStreamHandle,
+        canvas_handle: &GraphicsCanvasHandle,
+        map: &EditorMap,
+        latest_pointer: &egui::PointerState,
+        current_pointer_pos: &egui::Pos2,
+    ) {
+        let layer = map.active_layer();
+        if !layer.as_ref().is_some_and(|layer| layer.is_sound_layer()) {
+            return;
+        }
+
+        render_sound_points(
+            ui_canvas,
+            layer,
+            current_pointer_pos,
+            stream_handle,
+            canvas_handle,
+            map,
+        );
+
+        if self.range.is_none() || self.pointer_down_state.is_selection() {
+            self.render_selection(
+                ui_canvas,
+                canvas_handle,
+                stream_handle,
+                map,
+                latest_pointer,
+                current_pointer_pos,
+            );
+        } else {
+            self.render_range(canvas_handle, stream_handle, map);
+        }
+    }
+}
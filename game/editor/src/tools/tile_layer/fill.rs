@@ -0,0 +1,195 @@
+use std::collections::VecDeque;
+
+use graphics::handles::{
+    canvas::canvas::GraphicsCanvasHandle, stream::stream::GraphicsStreamHandle,
+};
+use hiarc::Hiarc;
+use math::math::vector::{ivec2, ubvec4};
+
+use crate::{
+    client::EditorClient,
+    map::{EditorLayer, EditorLayerUnionRef, EditorMap, EditorMapInterface, EditorPhysicsLayer},
+    tools::utils::render_rect,
+    utils::UiCanvasSize,
+};
+
+use super::{
+    brush::TileBrush,
+    shared::{apply_brush_to_points, pointer_tile_pos, TILE_VISUAL_SIZE},
+};
+
+/// All points that are filled when starting a fill at `start`.
+///
+/// In contiguous mode all 4-connected tiles that equal the start tile are filled,
+/// in global mode every tile of the layer that equals the start tile.
+pub fn fill_points<T: PartialEq>(
+    tiles: &[T],
+    width: usize,
+    height: usize,
+    start: ivec2,
+    global: bool,
+) -> Vec<ivec2> {
+    if start.x < 0 || start.y < 0 || start.x as usize >= width || start.y as usize >= height {
+        return Vec::new();
+    }
+    let start_tile = &tiles[start.y as usize * width + start.x as usize];
+    let to_point = |index: usize| ivec2::new((index % width) as i32, (index / width) as i32);
+
+    if global {
+        return tiles
+            .iter()
+            .enumerate()
+            .filter(|(_, tile)| *tile == start_tile)
+            .map(|(index, _)| to_point(index))
+            .collect();
+    }
+
+    let mut visited = vec![false; width * height];
+    let mut queue = VecDeque::new();
+    let mut res = Vec::new();
+    let start_index = start.y as usize * width + start.x as usize;
+    visited[start_index] = true;
+    queue.push_back(start_index);
+    while let Some(index) = queue.pop_front() {
+        res.push(to_point(index));
+        let x = index % width;
+        let y = index / width;
+        let neighbours = [
+            (x > 0).then(|| index - 1),
+            (x + 1 < width).then(|| index + 1),
+            (y > 0).then(|| index - width),
+            (y + 1 < height).then(|| index + width),
+        ];
+        for neighbour in neighbours.into_iter().flatten() {
+            if !visited[neighbour] && tiles[neighbour] == *start_tile {
+                visited[neighbour] = true;
+                queue.push_back(neighbour);
+            }
+        }
+    }
+    res
+}
+
+#[derive(Debug, Hiarc, Default)]
+pub struct TileFill {
+    /// Fill all matching tiles of the layer instead of only the connected ones.
+    pub global: bool,
+}
+
+impl TileFill {
+    fn layer_fill_points(&self, layer: &EditorLayerUnionRef, start: ivec2) -> Vec<ivec2> {
+        let (width, height) = layer.get_width_and_height();
+        let (width, height) = (width.get() as usize, height.get() as usize);
+        match layer {
+            EditorLayerUnionRef::Physics { layer, .. } => match layer {
+                EditorPhysicsLayer::Arbitrary(_) => Vec::new(),
+                EditorPhysicsLayer::Game(layer) => {
+                    fill_points(&layer.layer.tiles, width, height, start, self.global)
+                }
+                EditorPhysicsLayer::Front(layer) => {
+                    fill_points(&layer.layer.tiles, width, height, start, self.global)
+                }
+                EditorPhysicsLayer::Tele(layer) => {
+                    fill_points(&layer.layer.base.tiles, width, height, start, self.global)
+                }
+                EditorPhysicsLayer::Speedup(layer) => {
+                    fill_points(&layer.layer.tiles, width, height, start, self.global)
+                }
+                EditorPhysicsLayer::Switch(layer) => {
+                    fill_points(&layer.layer.base.tiles, width, height, start, self.global)
+                }
+                EditorPhysicsLayer::Tune(layer) => {
+                    fill_points(&layer.layer.base.tiles, width, height, start, self.global)
+                }
+            },
+            EditorLayerUnionRef::Design { layer, .. } => match layer {
+                EditorLayer::Tile(layer) => {
+                    fill_points(&layer.layer.tiles, width, height, start, self.global)
+                }
+                _ => Vec::new(),
+            },
+        }
+    }
+
+    pub fn update(
+        &mut self,
+        ui_canvas: &UiCanvasSize,
+        canvas_handle: &GraphicsCanvasHandle,
+        map: &EditorMap,
+        brush: &TileBrush,
+        latest_pointer: &egui::PointerState,
+        current_pointer_pos: &egui::Pos2,
+        client: &mut EditorClient,
+    ) {
+        let Some(layer) = map.active_layer() else {
+            return;
+        };
+        if !layer.is_tile_layer() {
+            return;
+        }
+        let Some(brush) = &brush.brush else {
+            return;
+        };
+
+        if latest_pointer.primary_pressed() {
+            let start =
+                pointer_tile_pos(ui_canvas, canvas_handle, map, &layer, current_pointer_pos);
+            let points = self.layer_fill_points(&layer, start);
+            apply_brush_to_points(&layer, brush, start, &points, client);
+        }
+    }
+
+    pub fn render(
+        &mut self,
+        ui_canvas: &UiCanvasSize,
+        stream_handle: &GraphicsStreamHandle,
+        canvas_handle: &GraphicsCanvasHandle,
+        map: &EditorMap,
+        current_pointer_pos: &egui::Pos2,
+    ) {
+        let Some(layer) = map.active_layer() else {
+            return;
+        };
+        if !layer.is_tile_layer() {
+            return;
+        }
+        let (offset, parallax) = layer.get_offset_and_parallax();
+        let pos = pointer_tile_pos(ui_canvas, canvas_handle, map, &layer, current_pointer_pos);
+        let min = egui::pos2(
+            pos.x as f32 * TILE_VISUAL_SIZE,
+            pos.y as f32 * TILE_VISUAL_SIZE,
+        );
+        render_rect(
+            canvas_handle,
+            stream_handle,
+            map,
+            egui::Rect::from_min_size(min, egui::vec2(TILE_VISUAL_SIZE, TILE_VISUAL_SIZE)),
+            ubvec4::new(0, 255, 255, 255),
+            &parallax,
+            &offset,
+        );
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use math::math::vector::ivec2;
+
+    use super::fill_points;
+
+    #[test]
+    fn contiguous_and_global() {
+        // 0 0 1 0
+        // 0 1 1 0
+        // 1 1 0 0
+        let tiles = [0, 0, 1, 0, 0, 1, 1, 0, 1, 1, 0, 0];
+        let contiguous = fill_points(&tiles, 4, 3, ivec2::new(0, 0), false);
+        assert_eq!(contiguous.len(), 3);
+        assert!(!contiguous.contains(&ivec2::new(3, 0)));
+
+        let global = fill_points(&tiles, 4, 3, ivec2::new(0, 0), true);
+        assert_eq!(global.len(), 7);
+
+        assert!(fill_points(&tiles, 4, 3, ivec2::new(4, 0), false).is_empty());
+    }
+}
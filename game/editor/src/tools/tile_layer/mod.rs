@@ -1,4 +1,6 @@
 pub mod auto_mapper;
 pub mod brush;
+pub mod fill;
 pub mod selection;
+pub mod shape;
 pub mod shared;
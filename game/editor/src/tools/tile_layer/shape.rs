@@ -0,0 +1,227 @@
+use graphics::handles::{
+    canvas::canvas::GraphicsCanvasHandle, stream::stream::GraphicsStreamHandle,
+};
+use hiarc::Hiarc;
+use math::math::vector::{ivec2, ubvec4};
+
+use crate::{
+    client::EditorClient,
+    map::{EditorMap, EditorMapInterface},
+    tools::utils::render_filled_rect,
+    utils::UiCanvasSize,
+};
+
+use super::{
+    brush::TileBrush,
+    shared::{apply_brush_to_points, pointer_tile_pos, TILE_VISUAL_SIZE},
+};
+
+#[derive(Debug, Hiarc, Clone, Copy, PartialEq, Eq)]
+pub enum TileShapeKind {
+    Rect,
+    Line,
+    Ellipse,
+}
+
+/// All tiles on the line between both points (Bresenham).
+pub fn line_points(from: ivec2, to: ivec2) -> Vec<ivec2> {
+    let dx = (to.x - from.x).abs();
+    let dy = -(to.y - from.y).abs();
+    let sx = if from.x < to.x { 1 } else { -1 };
+    let sy = if from.y < to.y { 1 } else { -1 };
+    let mut err = dx + dy;
+    let mut cur = from;
+    let mut res = Vec::new();
+    loop {
+        res.push(cur);
+        if cur == to {
+            break;
+        }
+        let e2 = 2 * err;
+        if e2 >= dy {
+            err += dy;
+            cur.x += sx;
+        }
+        if e2 <= dx {
+            err += dx;
+            cur.y += sy;
+        }
+    }
+    res
+}
+
+fn min_max(from: ivec2, to: ivec2) -> (ivec2, ivec2) {
+    (
+        ivec2::new(from.x.min(to.x), from.y.min(to.y)),
+        ivec2::new(from.x.max(to.x), from.y.max(to.y)),
+    )
+}
+
+/// All tiles of the rectangle spanned by both corners.
+pub fn rect_points(from: ivec2, to: ivec2, filled: bool) -> Vec<ivec2> {
+    let (min, max) = min_max(from, to);
+    (min.y..=max.y)
+        .flat_map(|y| (min.x..=max.x).map(move |x| ivec2::new(x, y)))
+        .filter(|p| filled || p.x == min.x || p.x == max.x || p.y == min.y || p.y == max.y)
+        .collect()
+}
+
+/// All tiles of the ellipse inside the rectangle spanned by both corners.
+pub fn ellipse_points(from: ivec2, to: ivec2, filled: bool) -> Vec<ivec2> {
+    let (min, max) = min_max(from, to);
+    let center_x = (min.x + max.x) as f64 / 2.0;
+    let center_y = (min.y + max.y) as f64 / 2.0;
+    // the tiles themselves have a size, so the radius reaches the outer edge
+    let radius_x = (max.x - min.x) as f64 / 2.0 + 0.5;
+    let radius_y = (max.y - min.y) as f64 / 2.0 + 0.5;
+    let inside = |x: i32, y: i32| {
+        let dx = (x as f64 - center_x) / radius_x;
+        let dy = (y as f64 - center_y) / radius_y;
+        dx * dx + dy * dy <= 1.0
+    };
+    (min.y..=max.y)
+        .flat_map(|y| (min.x..=max.x).map(move |x| ivec2::new(x, y)))
+        .filter(|p| inside(p.x, p.y))
+        .filter(|p| {
+            filled
+                || !inside(p.x - 1, p.y)
+                || !inside(p.x + 1, p.y)
+                || !inside(p.x, p.y - 1)
+                || !inside(p.x, p.y + 1)
+        })
+        .collect()
+}
+
+#[derive(Debug, Hiarc)]
+pub struct TileShape {
+    /// Fill the inside of rectangles and ellipses.
+    pub filled: bool,
+
+    pub pointer_down_tile: Option<ivec2>,
+}
+
+impl Default for TileShape {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TileShape {
+    pub fn new() -> Self {
+        Self {
+            filled: false,
+            pointer_down_tile: None,
+        }
+    }
+
+    fn points(&self, kind: TileShapeKind, from: ivec2, to: ivec2) -> Vec<ivec2> {
+        match kind {
+            TileShapeKind::Rect => rect_points(from, to, self.filled),
+            TileShapeKind::Line => line_points(from, to),
+            TileShapeKind::Ellipse => ellipse_points(from, to, self.filled),
+        }
+    }
+
+    pub fn update(
+        &mut self,
+        ui_canvas: &UiCanvasSize,
+        kind: TileShapeKind,
+        canvas_handle: &GraphicsCanvasHandle,
+        map: &EditorMap,
+        brush: &TileBrush,
+        latest_pointer: &egui::PointerState,
+        current_pointer_pos: &egui::Pos2,
+        client: &mut EditorClient,
+    ) {
+        let Some(layer) = map.active_layer() else {
+            return;
+        };
+        if !layer.is_tile_layer() {
+            return;
+        }
+        let Some(brush) = &brush.brush else {
+            return;
+        };
+
+        let cur = pointer_tile_pos(ui_canvas, canvas_handle, map, &layer, current_pointer_pos);
+        if let Some(from) = self.pointer_down_tile {
+            // cancel the shape
+            if latest_pointer.button_down(egui::PointerButton::Secondary) {
+                self.pointer_down_tile = None;
+            } else if !latest_pointer.primary_down() {
+                let points = self.points(kind, from, cur);
+                apply_brush_to_points(&layer, brush, from, &points, client);
+                self.pointer_down_tile = None;
+            }
+        } else if latest_pointer.primary_pressed() {
+            self.pointer_down_tile = Some(cur);
+        }
+    }
+
+    pub fn render(
+        &mut self,
+        ui_canvas: &UiCanvasSize,
+        kind: TileShapeKind,
+        stream_handle: &GraphicsStreamHandle,
+        canvas_handle: &GraphicsCanvasHandle,
+        map: &EditorMap,
+        current_pointer_pos: &egui::Pos2,
+    ) {
+        let Some(layer) = map.active_layer() else {
+            return;
+        };
+        if !layer.is_tile_layer() {
+            return;
+        }
+        let (offset, parallax) = layer.get_offset_and_parallax();
+        let cur = pointer_tile_pos(ui_canvas, canvas_handle, map, &layer, current_pointer_pos);
+        let points = match self.pointer_down_tile {
+            Some(from) => self.points(kind, from, cur),
+            None => vec![cur],
+        };
+        for p in points {
+            let min = egui::pos2(p.x as f32 * TILE_VISUAL_SIZE, p.y as f32 * TILE_VISUAL_SIZE);
+            render_filled_rect(
+                canvas_handle,
+                stream_handle,
+                map,
+                egui::Rect::from_min_size(min, egui::vec2(TILE_VISUAL_SIZE, TILE_VISUAL_SIZE)),
+                ubvec4::new(0, 255, 255, 100),
+                &parallax,
+                &offset,
+                false,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use math::math::vector::ivec2;
+
+    use super::{ellipse_points, line_points, rect_points};
+
+    #[test]
+    fn shapes() {
+        let line = line_points(ivec2::new(0, 0), ivec2::new(4, 2));
+        assert_eq!(line.first(), Some(&ivec2::new(0, 0)));
+        assert_eq!(line.last(), Some(&ivec2::new(4, 2)));
+        assert_eq!(line.len(), 5);
+
+        assert_eq!(
+            rect_points(ivec2::new(3, 3), ivec2::new(0, 0), false).len(),
+            12
+        );
+        assert_eq!(
+            rect_points(ivec2::new(3, 3), ivec2::new(0, 0), true).len(),
+            16
+        );
+
+        let filled = ellipse_points(ivec2::new(0, 0), ivec2::new(4, 4), true);
+        let outline = ellipse_points(ivec2::new(0, 0), ivec2::new(4, 4), false);
+        assert!(filled.contains(&ivec2::new(2, 2)));
+        assert!(!outline.contains(&ivec2::new(2, 2)));
+        assert!(!filled.contains(&ivec2::new(0, 0)));
+        assert!(outline.iter().all(|p| filled.contains(p)));
+    }
+}
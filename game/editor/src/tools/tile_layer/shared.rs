@@ -1 +1,222 @@
+use graphics::handles::canvas::canvas::GraphicsCanvasHandle;
+use map::{
+    map::groups::layers::tiles::{MapTileLayerPhysicsTiles, MapTileLayerTiles},
+    types::NonZeroU16MinusOne,
+};
+use math::math::vector::{ivec2, vec2};
+
+use crate::{
+    actions::actions::{
+        ActTileLayerReplTilesBase, ActTileLayerReplaceTiles, ActTilePhysicsLayerReplTilesBase,
+        ActTilePhysicsLayerReplaceTiles, EditorAction,
+    },
+    client::EditorClient,
+    map::{EditorLayer, EditorLayerUnionRef, EditorMap, EditorPhysicsLayer},
+    utils::{ui_pos_to_world_pos, UiCanvasSize},
+};
+
+use super::brush::TileBrushTiles;
+
 pub const TILE_VISUAL_SIZE: f32 = 1.0;
+
+/// The tile position of the pointer inside the given layer.
+pub fn pointer_tile_pos(
+    ui_canvas: &UiCanvasSize,
+    canvas_handle: &GraphicsCanvasHandle,
+    map: &EditorMap,
+    layer: &EditorLayerUnionRef,
+    current_pointer_pos: &egui::Pos2,
+) -> ivec2 {
+    let (offset, parallax) = layer.get_offset_and_parallax();
+    let pos = ui_pos_to_world_pos(
+        canvas_handle,
+        ui_canvas,
+        map.groups.user.zoom,
+        vec2::new(current_pointer_pos.x, current_pointer_pos.y),
+        map.groups.user.pos.x,
+        map.groups.user.pos.y,
+        offset.x,
+        offset.y,
+        parallax.x,
+        parallax.y,
+    );
+    ivec2::new(
+        (pos.x / TILE_VISUAL_SIZE).floor() as i32,
+        (pos.y / TILE_VISUAL_SIZE).floor() as i32,
+    )
+}
+
+/// The bounding box of all replaced tiles, together with
+/// the tiles in that box before and after the replacement.
+#[derive(Debug)]
+pub struct ReplacedTiles<T> {
+    pub x: u16,
+    pub y: u16,
+    pub w: NonZeroU16MinusOne,
+    pub h: NonZeroU16MinusOne,
+    pub old_tiles: Vec<T>,
+    pub new_tiles: Vec<T>,
+}
+
+/// Replaces the tiles at the given points with the result of `tile_at`.
+/// Points outside of the layer are ignored.
+///
+/// Returns `None` if no tile would change.
+pub fn replace_tiles<T: Copy + PartialEq>(
+    tiles: &[T],
+    width: u16,
+    height: u16,
+    points: &[ivec2],
+    tile_at: impl Fn(ivec2) -> T,
+) -> Option<ReplacedTiles<T>> {
+    let points: Vec<_> = points
+        .iter()
+        .filter(|p| p.x >= 0 && p.y >= 0 && p.x < width as i32 && p.y < height as i32)
+        .collect();
+
+    let x0 = points.iter().map(|p| p.x).min()?;
+    let y0 = points.iter().map(|p| p.y).min()?;
+    let x1 = points.iter().map(|p| p.x).max()?;
+    let y1 = points.iter().map(|p| p.y).max()?;
+    let w = (x1 - x0 + 1) as usize;
+    let h = (y1 - y0 + 1) as usize;
+
+    let old_tiles: Vec<T> = tiles
+        .chunks_exact(width as usize)
+        .skip(y0 as usize)
+        .take(h)
+        .flat_map(|tiles| tiles[x0 as usize..x0 as usize + w].iter().copied())
+        .collect();
+    let mut new_tiles = old_tiles.clone();
+    for p in points {
+        new_tiles[(p.y - y0) as usize * w + (p.x - x0) as usize] = tile_at(*p);
+    }
+
+    (old_tiles != new_tiles).then(|| ReplacedTiles {
+        x: x0 as u16,
+        y: y0 as u16,
+        w: NonZeroU16MinusOne::new(w as u16).unwrap(),
+        h: NonZeroU16MinusOne::new(h as u16).unwrap(),
+        old_tiles,
+        new_tiles,
+    })
+}
+
+/// The brush tile that belongs to the given point, if the brush
+/// pattern is repeated over the whole layer starting at `origin`.
+fn brush_tile_at<T: Copy>(tiles: &[T], brush: &TileBrushTiles, origin: ivec2, p: ivec2) -> T {
+    let w = brush.w.get() as i32;
+    let h = brush.h.get() as i32;
+    let x = (p.x - origin.x).rem_euclid(w);
+    let y = (p.y - origin.y).rem_euclid(h);
+    tiles[(y * w + x) as usize]
+}
+
+/// Replaces the tiles at the given points with the (repeated) brush pattern
+/// as a single undoable action.
+/// Does nothing if the brush does not fit the layer.
+pub fn apply_brush_to_points(
+    layer: &EditorLayerUnionRef,
+    brush: &TileBrushTiles,
+    origin: ivec2,
+    points: &[ivec2],
+    client: &mut EditorClient,
+) {
+    let (width, height) = layer.get_width_and_height();
+    let (width, height) = (width.get(), height.get());
+
+    macro_rules! replace {
+        ($layer_tiles:expr, $brush_tiles:expr) => {
+            replace_tiles($layer_tiles, width, height, points, |p| {
+                brush_tile_at($brush_tiles, brush, origin, p)
+            })
+        };
+    }
+
+    let action = match layer {
+        EditorLayerUnionRef::Physics {
+            layer, layer_index, ..
+        } => {
+            let MapTileLayerTiles::Physics(brush_tiles) = &brush.tiles else {
+                return;
+            };
+            macro_rules! physics_action {
+                ($kind:ident, $layer_tiles:expr, $brush_tiles:expr) => {
+                    replace!($layer_tiles, $brush_tiles).map(|replaced| {
+                        EditorAction::TilePhysicsLayerReplaceTiles(
+                            ActTilePhysicsLayerReplaceTiles {
+                                base: ActTilePhysicsLayerReplTilesBase {
+                                    layer_index: *layer_index,
+                                    old_tiles: MapTileLayerPhysicsTiles::$kind(replaced.old_tiles),
+                                    new_tiles: MapTileLayerPhysicsTiles::$kind(replaced.new_tiles),
+                                    x: replaced.x,
+                                    y: replaced.y,
+                                    w: replaced.w,
+                                    h: replaced.h,
+                                },
+                            },
+                        )
+                    })
+                };
+            }
+            match (layer, brush_tiles) {
+                (EditorPhysicsLayer::Game(layer), MapTileLayerPhysicsTiles::Game(tiles)) => {
+                    physics_action!(Game, &layer.layer.tiles, tiles)
+                }
+                (EditorPhysicsLayer::Front(layer), MapTileLayerPhysicsTiles::Front(tiles)) => {
+                    physics_action!(Front, &layer.layer.tiles, tiles)
+                }
+                (EditorPhysicsLayer::Tele(layer), MapTileLayerPhysicsTiles::Tele(tiles)) => {
+                    physics_action!(Tele, &layer.layer.base.tiles, tiles)
+                }
+                (EditorPhysicsLayer::Speedup(layer), MapTileLayerPhysicsTiles::Speedup(tiles)) => {
+                    physics_action!(Speedup, &layer.layer.tiles, tiles)
+                }
+                (EditorPhysicsLayer::Switch(layer), MapTileLayerPhysicsTiles::Switch(tiles)) => {
+                    physics_action!(Switch, &layer.layer.base.tiles, tiles)
+                }
+                (EditorPhysicsLayer::Tune(layer), MapTileLayerPhysicsTiles::Tune(tiles)) => {
+                    physics_action!(Tune, &layer.layer.base.tiles, tiles)
+                }
+                _ => None,
+            }
+        }
+        EditorLayerUnionRef::Design {
+            layer,
+            layer_index,
+            group_index,
+            is_background,
+            ..
+        } => {
+            let EditorLayer::Tile(layer) = layer else {
+                return;
+            };
+            let brush_tiles = match &brush.tiles {
+                MapTileLayerTiles::Design(tiles)
+                | MapTileLayerTiles::Physics(MapTileLayerPhysicsTiles::Game(tiles))
+                | MapTileLayerTiles::Physics(MapTileLayerPhysicsTiles::Front(tiles)) => tiles,
+                MapTileLayerTiles::Physics(_) => return,
+            };
+            replace!(&layer.layer.tiles, brush_tiles).map(|replaced| {
+                EditorAction::TileLayerReplaceTiles(ActTileLayerReplaceTiles {
+                    base: ActTileLayerReplTilesBase {
+                        is_background: *is_background,
+                        group_index: *group_index,
+                        layer_index: *layer_index,
+                        old_tiles: replaced.old_tiles,
+                        new_tiles: replaced.new_tiles,
+                        x: replaced.x,
+                        y: replaced.y,
+                        w: replaced.w,
+                        h: replaced.h,
+                    },
+                })
+            })
+        }
+    };
+
+    if let Some(action) = action {
+        // no group identifier, every use of a tool is its own undo step
+        client.execute(action, None);
+    }
+}
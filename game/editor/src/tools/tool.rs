@@ -17,17 +17,46 @@ use crate::{client::EditorClient, map::EditorMap, utils::UiCanvasSize};
 
 use super::{
     quad_layer::{brush::QuadBrush, selection::QuadSelection},
-    sound_layer::brush::SoundBrush,
-    tile_layer::{brush::TileBrush, selection::TileSelection},
+    sound_layer::{brush::SoundBrush, selection::SoundSelection},
+    tile_layer::{
+        brush::TileBrush,
+        fill::TileFill,
+        selection::TileSelection,
+        shape::{TileShape, TileShapeKind},
+    },
 };
 
 #[derive(Debug, Hiarc)]
 pub struct ToolTileLayer {
     pub brush: TileBrush,
     pub selection: TileSelection,
+    pub fill: TileFill,
+    pub shape: TileShape,
 }
 
 impl ToolTileLayer {
+    /// The fill & shape tools draw with the brush, so
+    /// the brush is selected the same way as for the brush tool.
+    fn uses_brush(
+        &self,
+        active_tool: &ActiveToolTiles,
+        latest_keys_down: &HashSet<egui::Key>,
+    ) -> bool {
+        match active_tool {
+            ActiveToolTiles::Brush => true,
+            ActiveToolTiles::Selection => false,
+            ActiveToolTiles::Fill
+            | ActiveToolTiles::Rect
+            | ActiveToolTiles::Line
+            | ActiveToolTiles::Ellipse => {
+                self.shape.pointer_down_tile.is_none()
+                    && (self.brush.brush.is_none()
+                        || self.brush.pointer_down_world_pos.is_some()
+                        || latest_keys_down.contains(&egui::Key::Space))
+            }
+        }
+    }
+
     pub fn update(
         &mut self,
         ui_canvas: &UiCanvasSize,
@@ -47,8 +76,14 @@ impl ToolTileLayer {
         available_rect: &egui::Rect,
         client: &mut EditorClient,
     ) {
-        match active_tool {
-            ActiveToolTiles::Brush => self.brush.update(
+        let shape_kind = match active_tool {
+            ActiveToolTiles::Rect => Some(TileShapeKind::Rect),
+            ActiveToolTiles::Line => Some(TileShapeKind::Line),
+            ActiveToolTiles::Ellipse => Some(TileShapeKind::Ellipse),
+            ActiveToolTiles::Brush | ActiveToolTiles::Selection | ActiveToolTiles::Fill => None,
+        };
+        if self.uses_brush(active_tool, latest_keys_down) {
+            self.brush.update(
                 ui_canvas,
                 tp,
                 graphics_mt,
@@ -64,6 +99,31 @@ impl ToolTileLayer {
                 current_pointer_pos,
                 available_rect,
                 client,
+            );
+            return;
+        }
+        if let Some(kind) = shape_kind {
+            self.shape.update(
+                ui_canvas,
+                kind,
+                canvas_handle,
+                map,
+                &self.brush,
+                latest_pointer,
+                current_pointer_pos,
+                client,
+            );
+            return;
+        }
+        match active_tool {
+            ActiveToolTiles::Fill => self.fill.update(
+                ui_canvas,
+                canvas_handle,
+                map,
+                &self.brush,
+                latest_pointer,
+                current_pointer_pos,
+                client,
             ),
             ActiveToolTiles::Selection => self.selection.update(
                 ui_canvas,
@@ -72,6 +132,10 @@ impl ToolTileLayer {
                 latest_pointer,
                 current_pointer_pos,
             ),
+            ActiveToolTiles::Brush
+            | ActiveToolTiles::Rect
+            | ActiveToolTiles::Line
+            | ActiveToolTiles::Ellipse => {}
         }
     }
 
@@ -90,8 +154,8 @@ impl ToolTileLayer {
         current_pointer_pos: &egui::Pos2,
         available_rect: &egui::Rect,
     ) {
-        match active_tool {
-            ActiveToolTiles::Brush => self.brush.render(
+        if self.uses_brush(active_tool, latest_keys_down) {
+            self.brush.render(
                 ui_canvas,
                 backend_handle,
                 stream_handle,
@@ -103,7 +167,10 @@ impl ToolTileLayer {
                 latest_keys_down,
                 current_pointer_pos,
                 available_rect,
-            ),
+            );
+            return;
+        }
+        match active_tool {
             ActiveToolTiles::Selection => self.selection.render(
                 ui_canvas,
                 stream_handle,
@@ -112,6 +179,38 @@ impl ToolTileLayer {
                 latest_pointer,
                 current_pointer_pos,
             ),
+            ActiveToolTiles::Fill => self.fill.render(
+                ui_canvas,
+                stream_handle,
+                canvas_handle,
+                map,
+                current_pointer_pos,
+            ),
+            ActiveToolTiles::Rect => self.shape.render(
+                ui_canvas,
+                TileShapeKind::Rect,
+                stream_handle,
+                canvas_handle,
+                map,
+                current_pointer_pos,
+            ),
+            ActiveToolTiles::Line => self.shape.render(
+                ui_canvas,
+                TileShapeKind::Line,
+                stream_handle,
+                canvas_handle,
+                map,
+                current_pointer_pos,
+            ),
+            ActiveToolTiles::Ellipse => self.shape.render(
+                ui_canvas,
+                TileShapeKind::Ellipse,
+                stream_handle,
+                canvas_handle,
+                map,
+                current_pointer_pos,
+            ),
+            ActiveToolTiles::Brush => {}
         }
     }
 }
@@ -196,6 +295,7 @@ impl ToolQuadLayer {
 #[derive(Debug, Hiarc)]
 pub struct ToolSoundLayer {
     pub brush: SoundBrush,
+    pub selection: SoundSelection,
 }
 
 impl ToolSoundLayer {
@@ -206,6 +306,8 @@ impl ToolSoundLayer {
         canvas_handle: &GraphicsCanvasHandle,
         map: &EditorMap,
         latest_pointer: &egui::PointerState,
+        latest_keys_down: &HashSet<egui::Key>,
+        latest_modifiers: &egui::Modifiers,
         current_pointer_pos: &egui::Pos2,
         client: &mut EditorClient,
     ) {
@@ -218,6 +320,16 @@ impl ToolSoundLayer {
                 current_pointer_pos,
                 client,
             ),
+            ActiveToolSounds::Selection => self.selection.update(
+                ui_canvas,
+                canvas_handle,
+                map,
+                latest_pointer,
+                latest_keys_down,
+                latest_modifiers,
+                current_pointer_pos,
+                client,
+            ),
         }
     }
 
@@ -240,6 +352,14 @@ impl ToolSoundLayer {
                 latest_pointer,
                 current_pointer_pos,
             ),
+            ActiveToolSounds::Selection => self.selection.render(
+                ui_canvas,
+                stream_handle,
+                canvas_handle,
+                map,
+                latest_pointer,
+                current_pointer_pos,
+            ),
         }
     }
 }
@@ -248,6 +368,10 @@ impl ToolSoundLayer {
 pub enum ActiveToolTiles {
     Brush,
     Selection,
+    Fill,
+    Rect,
+    Line,
+    Ellipse,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -259,6 +383,7 @@ pub enum ActiveToolQuads {
 #[derive(Debug, Clone, Copy)]
 pub enum ActiveToolSounds {
     Brush,
+    Selection,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -70,6 +70,20 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithTab>, ui_st
                     }
                     (res, Some(point), None, None)
                 }
+                ActiveTool::Sounds(ActiveToolSounds::Selection) => {
+                    let selection = &mut pipe.user_data.tools.sounds.selection;
+                    let point = selection.range.as_ref().and_then(|range| range.point);
+                    (
+                        selection
+                            .range
+                            .as_mut()
+                            .map(|range| range.indices_checked(layer))
+                            .unwrap_or_default(),
+                        point,
+                        Some(&mut selection.pos_offset),
+                        Some(&mut selection.anim_point_pos),
+                    )
+                }
                 ActiveTool::Quads(_) | ActiveTool::Tiles(_) => {
                     // ignore
                     (Default::default(), None, None, None)
@@ -523,9 +537,26 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithTab>, ui_st
                         let index = *index;
                         let layer_sound = &layer.layer.sounds[index];
                         // move points by diff
-                            let diff = prop_sound.pos - sound_cmp.pos;
+                        let diff = prop_sound.pos - sound_cmp.pos;
 
-                            sound.pos += diff;
+                        sound.pos += diff;
+
+                        // apply shared properties if changed
+                        if prop_sound.shape != sound_cmp.shape {
+                            sound.shape = prop_sound.shape;
+                        }
+                        if prop_sound.falloff != sound_cmp.falloff {
+                            sound.falloff = prop_sound.falloff;
+                        }
+                        if prop_sound.looped != sound_cmp.looped {
+                            sound.looped = prop_sound.looped;
+                        }
+                        if prop_sound.panning != sound_cmp.panning {
+                            sound.panning = prop_sound.panning;
+                        }
+                        if prop_sound.time_delay != sound_cmp.time_delay {
+                            sound.time_delay = prop_sound.time_delay;
+                        }
 
                         // apply new anims if changed, for the time offset do a difference instead
                         if can_change_pos_anim {
@@ -601,6 +632,9 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithTab>, ui_st
                     ActiveTool::Sounds(ActiveToolSounds::Brush) => {
                         pipe.user_data.tools.sounds.brush.last_selection = None;
                     }
+                    ActiveTool::Sounds(ActiveToolSounds::Selection) => {
+                        pipe.user_data.tools.sounds.selection.range = None;
+                    }
                     ActiveTool::Quads(_) | ActiveTool::Tiles(_) => {
                         // ignore
                     }
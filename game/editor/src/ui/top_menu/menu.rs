@@ -9,6 +9,7 @@ use ui_base::types::UiRenderPipe;
 use crate::{
     explain::TEXT_ANIM_PANEL_AND_PROPS,
     tab::EditorAdminPanelState,
+    test_play::TEST_PLAY_GAME_MODS,
     ui::user_data::{
         EditorMenuDialogJoinProps, EditorMenuDialogMode, EditorMenuHostDialogMode,
         EditorMenuHostNetworkOptions, EditorUiEvent, EditorUiEventHostMap, UserData,
@@ -93,6 +94,28 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>) {
                         }
                     });
 
+                    ui.menu_button("Play", |ui| {
+                        let has_map = pipe.user_data.editor_tabs.active_tab().is_some();
+                        for game_mod in TEST_PLAY_GAME_MODS {
+                            if ui
+                                .add_enabled(
+                                    has_map,
+                                    Button::new(format!("Test play ({game_mod})")),
+                                )
+                                .on_hover_text(
+                                    "Plays the current map on a local server. \
+                                    You spawn at the current camera position.",
+                                )
+                                .clicked()
+                            {
+                                pipe.user_data.ui_events.push(EditorUiEvent::TestPlay {
+                                    game_mod: game_mod.to_string(),
+                                });
+                                ui.close_menu();
+                            }
+                        }
+                    });
+
                     ui.menu_button("Tools", |ui| {
                         if ui
                             .add(
@@ -9,7 +9,8 @@ use crate::{
         ActSoundLayerAddSounds, EditorAction,
     },
     explain::{
-        TEXT_ADD_QUAD, TEXT_ADD_SOUND, TEXT_QUAD_SELECTION, TEXT_TILE_BRUSH, TEXT_TILE_BRUSH_MIRROR,
+        TEXT_ADD_QUAD, TEXT_ADD_SOUND, TEXT_QUAD_SELECTION, TEXT_SOUND_SELECTION, TEXT_TILE_BRUSH,
+        TEXT_TILE_BRUSH_MIRROR, TEXT_TILE_FILL, TEXT_TILE_SHAPES,
     },
     map::{EditorLayer, EditorLayerUnionRef, EditorMapInterface},
     tools::tool::{ActiveTool, ActiveToolQuads, ActiveToolSounds, ActiveToolTiles},
@@ -60,6 +61,82 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithTab>, ui_st
                             if ui.add(btn).clicked() {
                                 *tool = ActiveToolTiles::Selection;
                             }
+                            // fill
+                            let mut btn = Button::new("\u{f575}");
+                            if matches!(tool, ActiveToolTiles::Fill) {
+                                btn = btn.selected(true);
+                            }
+                            if ui
+                                .add(btn)
+                                .on_hover_ui(|ui| {
+                                    let mut cache = egui_commonmark::CommonMarkCache::default();
+                                    egui_commonmark::CommonMarkViewer::new().show(
+                                        ui,
+                                        &mut cache,
+                                        TEXT_TILE_FILL,
+                                    );
+                                })
+                                .clicked()
+                            {
+                                *tool = ActiveToolTiles::Fill;
+                            }
+                            // rect
+                            let mut btn = Button::new("\u{f0c8}");
+                            if matches!(tool, ActiveToolTiles::Rect) {
+                                btn = btn.selected(true);
+                            }
+                            if ui
+                                .add(btn)
+                                .on_hover_ui(|ui| {
+                                    let mut cache = egui_commonmark::CommonMarkCache::default();
+                                    egui_commonmark::CommonMarkViewer::new().show(
+                                        ui,
+                                        &mut cache,
+                                        TEXT_TILE_SHAPES,
+                                    );
+                                })
+                                .clicked()
+                            {
+                                *tool = ActiveToolTiles::Rect;
+                            }
+                            // line
+                            let mut btn = Button::new("\u{f715}");
+                            if matches!(tool, ActiveToolTiles::Line) {
+                                btn = btn.selected(true);
+                            }
+                            if ui
+                                .add(btn)
+                                .on_hover_ui(|ui| {
+                                    let mut cache = egui_commonmark::CommonMarkCache::default();
+                                    egui_commonmark::CommonMarkViewer::new().show(
+                                        ui,
+                                        &mut cache,
+                                        TEXT_TILE_SHAPES,
+                                    );
+                                })
+                                .clicked()
+                            {
+                                *tool = ActiveToolTiles::Line;
+                            }
+                            // ellipse
+                            let mut btn = Button::new("\u{f111}");
+                            if matches!(tool, ActiveToolTiles::Ellipse) {
+                                btn = btn.selected(true);
+                            }
+                            if ui
+                                .add(btn)
+                                .on_hover_ui(|ui| {
+                                    let mut cache = egui_commonmark::CommonMarkCache::default();
+                                    egui_commonmark::CommonMarkViewer::new().show(
+                                        ui,
+                                        &mut cache,
+                                        TEXT_TILE_SHAPES,
+                                    );
+                                })
+                                .clicked()
+                            {
+                                *tool = ActiveToolTiles::Ellipse;
+                            }
                         }
                         ActiveTool::Quads(tool) => {
                             // brush
@@ -99,6 +176,25 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithTab>, ui_st
                             if ui.add(btn).clicked() {
                                 *tool = ActiveToolSounds::Brush;
                             }
+                            // select
+                            let mut btn = Button::new("\u{f45c}");
+                            if matches!(tool, ActiveToolSounds::Selection) {
+                                btn = btn.selected(true);
+                            }
+                            if ui
+                                .add(btn)
+                                .on_hover_ui(|ui| {
+                                    let mut cache = egui_commonmark::CommonMarkCache::default();
+                                    egui_commonmark::CommonMarkViewer::new().show(
+                                        ui,
+                                        &mut cache,
+                                        TEXT_SOUND_SELECTION,
+                                    );
+                                })
+                                .clicked()
+                            {
+                                *tool = ActiveToolSounds::Selection;
+                            }
                         }
                     }
                 });
@@ -110,8 +206,14 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithTab>, ui_st
     let tools = &mut pipe.user_data.tools;
     let res = match &tools.active_tool {
         ActiveTool::Tiles(tool) => {
-            let is_active = (matches!(tool, ActiveToolTiles::Brush)
-                && tools.tiles.brush.brush.is_some())
+            let is_active = (matches!(
+                tool,
+                ActiveToolTiles::Brush
+                    | ActiveToolTiles::Fill
+                    | ActiveToolTiles::Rect
+                    | ActiveToolTiles::Line
+                    | ActiveToolTiles::Ellipse
+            ) && tools.tiles.brush.brush.is_some())
                 || (matches!(tool, ActiveToolTiles::Selection)
                     && tools.tiles.selection.range.is_some());
             egui::TopBottomPanel::top("top_toolbar_tiles_extra")
@@ -137,7 +239,11 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithTab>, ui_st
                                     .clicked()
                                 {
                                     match tool {
-                                        ActiveToolTiles::Brush => {
+                                        ActiveToolTiles::Brush
+                                        | ActiveToolTiles::Fill
+                                        | ActiveToolTiles::Rect
+                                        | ActiveToolTiles::Line
+                                        | ActiveToolTiles::Ellipse => {
                                             if let Some(brush) = &mut tools.tiles.brush.brush {
                                                 mirror_tiles_y(
                                                     pipe.user_data.tp,
@@ -168,7 +274,11 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithTab>, ui_st
                                 let btn = Button::new("\u{f07e}");
                                 if ui.add(btn).clicked() {
                                     match tool {
-                                        ActiveToolTiles::Brush => {
+                                        ActiveToolTiles::Brush
+                                        | ActiveToolTiles::Fill
+                                        | ActiveToolTiles::Rect
+                                        | ActiveToolTiles::Line
+                                        | ActiveToolTiles::Ellipse => {
                                             if let Some(brush) = &mut tools.tiles.brush.brush {
                                                 mirror_tiles_x(
                                                     pipe.user_data.tp,
@@ -196,7 +306,11 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithTab>, ui_st
                                     }
                                 }
                                 match tool {
-                                    ActiveToolTiles::Brush => {
+                                    ActiveToolTiles::Brush
+                                    | ActiveToolTiles::Fill
+                                    | ActiveToolTiles::Rect
+                                    | ActiveToolTiles::Line
+                                    | ActiveToolTiles::Ellipse => {
                                         // rotate -90°
                                         let btn = Button::new("\u{f2ea}");
                                         if ui.add(btn).clicked() {
@@ -277,6 +391,31 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithTab>, ui_st
                                         }
                                     }
                                 }
+                                match tool {
+                                    ActiveToolTiles::Fill => {
+                                        // fill all matching tiles, not only connected ones
+                                        let mut btn = Button::new("\u{f0ac}");
+                                        if tools.tiles.fill.global {
+                                            btn = btn.selected(true);
+                                        }
+                                        if ui.add(btn).clicked() {
+                                            tools.tiles.fill.global = !tools.tiles.fill.global;
+                                        }
+                                    }
+                                    ActiveToolTiles::Rect | ActiveToolTiles::Ellipse => {
+                                        // filled shapes
+                                        let mut btn = Button::new("\u{f5c7}");
+                                        if tools.tiles.shape.filled {
+                                            btn = btn.selected(true);
+                                        }
+                                        if ui.add(btn).clicked() {
+                                            tools.tiles.shape.filled = !tools.tiles.shape.filled;
+                                        }
+                                    }
+                                    ActiveToolTiles::Brush
+                                    | ActiveToolTiles::Selection
+                                    | ActiveToolTiles::Line => {}
+                                }
                             });
                         });
                     });
@@ -62,6 +62,10 @@ pub enum EditorUiEvent {
     Minimize,
     Close,
     ForceClose,
+    /// Play the active map on a local server with the given game mod.
+    TestPlay {
+        game_mod: String,
+    },
     Undo,
     Redo,
     CursorWorldPos {
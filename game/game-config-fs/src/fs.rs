@@ -1,7 +1,7 @@
 use std::path::Path;
 
 use base_io::io::{Io, IoFileSys};
-use game_config::config::ConfigGame;
+use game_config::config::{ConfigGame, GAME_MIGRATIONS};
 
 pub fn save(config: &ConfigGame, io: &Io) {
     let save_str = config.to_json_string();
@@ -19,13 +19,7 @@ pub fn save(config: &ConfigGame, io: &Io) {
 }
 
 pub fn load_in(io: &IoFileSys, path: &Path) -> anyhow::Result<ConfigGame> {
-    let fs = io.fs.clone();
-    let path = path.to_path_buf();
-    let config_file = io
-        .rt
-        .spawn(async move { Ok(fs.read_file(path.as_ref()).await?) });
-    let res = config_file.get_storage()?;
-    ConfigGame::from_json_slice(&res)
+    ConfigGame::from_json_value(config_fs::load_migrated(io, path, &GAME_MIGRATIONS)?)
 }
 
 pub fn load(io: &IoFileSys) -> anyhow::Result<ConfigGame> {
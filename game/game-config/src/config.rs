@@ -1,16 +1,18 @@
 use std::collections::HashMap;
 
 use config::config::ConfigPath;
-use config::{config::ConfigEngine, types::ConfRgb};
+use config::{config::ConfigEngine, migration::ConfigMigrations, types::ConfRgb};
 use config::{config_default, ConfigInterface};
 use game_interface::interface::MAX_MAP_NAME_LEN;
 use game_interface::{
     client_commands::MAX_TEAM_NAME_LEN,
     types::character_info::{
-        NetworkLaserInfo, NetworkSkinInfo, MAX_ASSET_NAME_LEN, MAX_CHARACTER_CLAN_LEN,
-        MAX_CHARACTER_NAME_LEN, MAX_FLAG_NAME_LEN, MAX_LANG_NAME_LEN,
+        NetworkLaserInfo, NetworkSkinInfo, NetworkSkinParts, MAX_ASSET_NAME_LEN,
+        MAX_CHARACTER_CLAN_LEN, MAX_CHARACTER_NAME_LEN, MAX_FLAG_NAME_LEN, MAX_LANG_NAME_LEN,
     },
+    types::resource_key::NetworkResourceKey,
 };
+use math::math::vector::ubvec4;
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 
@@ -146,6 +148,18 @@ pub struct ConfigSpatialChat {
     pub account_certs: HashMap<String, ConfigSpatialChatPerPlayerOptions>,
 }
 
+#[derive(
+    Debug, Clone, Copy, Serialize, Deserialize, ConfigInterface, PartialEq, Eq, PartialOrd, Ord,
+)]
+pub enum ConfigVideoEncoder {
+    /// ffmpeg if the client was built with it, otherwise the image sequence.
+    Auto,
+    /// H.264 encoded mp4 file, only available in builds with ffmpeg.
+    Ffmpeg,
+    /// Lossless PNG images for every frame and a WAV file for the audio.
+    ImageSequence,
+}
+
 #[config_default]
 #[derive(Debug, Clone, Serialize, Deserialize, ConfigInterface)]
 pub struct ConfigDemoRecorder {
@@ -175,6 +189,11 @@ pub struct ConfigDemoRecorder {
     /// 18 is default.
     #[default = 18]
     pub crf: u8,
+    /// The encoder used to export the video.
+    /// The image sequence is written to a directory
+    /// named like the video and works on every platform.
+    #[default = ConfigVideoEncoder::Auto]
+    pub encoder: ConfigVideoEncoder,
     /// Config related to rendering graphics & sound.
     pub render: ConfigRender,
     /// Sound configs used during rendering sound & graphics.
@@ -279,11 +298,15 @@ pub struct ConfigPlayerSkin {
     /// Use the custom/user-defined colors for the skin
     #[default = false]
     pub custom_colors: bool,
+    /// Build the tee from parts of different skins.
+    pub parts: ConfigPlayerSkinParts,
 }
 
 impl From<&ConfigPlayerSkin> for NetworkSkinInfo {
     fn from(value: &ConfigPlayerSkin) -> Self {
-        if value.custom_colors {
+        if value.parts.enabled {
+            (&value.parts).into()
+        } else if value.custom_colors {
             Self::Custom {
                 body_color: value.body_color.into(),
                 feet_color: value.feet_color.into(),
@@ -294,6 +317,80 @@ impl From<&ConfigPlayerSkin> for NetworkSkinInfo {
     }
 }
 
+#[config_default]
+#[derive(Debug, Serialize, Deserialize, ConfigInterface, Clone)]
+pub struct ConfigPlayerSkinPart {
+    /// The skin this part is taken from.
+    /// Empty uses the main skin for body, feet & eyes
+    /// and disables marking & decoration.
+    #[conf_valid(length(max = MAX_ASSET_NAME_LEN))]
+    #[default = ""]
+    pub name: String,
+    #[default = Default::default()]
+    pub color: ConfRgb,
+    /// The opacity of the part, only used for markings.
+    #[default = 255]
+    pub alpha: u8,
+    /// Use the custom/user-defined color for this part
+    #[default = false]
+    pub custom_color: bool,
+}
+
+impl ConfigPlayerSkinPart {
+    fn network_key(&self) -> Option<NetworkResourceKey<MAX_ASSET_NAME_LEN>> {
+        (!self.name.is_empty()).then(|| NetworkResourceKey::from_str_lossy(&self.name))
+    }
+
+    fn network_color(&self) -> Option<ubvec4> {
+        self.custom_color.then(|| {
+            let mut color: ubvec4 = self.color.into();
+            color.w = self.alpha;
+            color
+        })
+    }
+}
+
+#[config_default]
+#[derive(Debug, Serialize, Deserialize, ConfigInterface, Clone)]
+pub struct ConfigPlayerSkinParts {
+    /// Use the part based skin instead of the main skin & its colors.
+    #[default = false]
+    pub enabled: bool,
+    pub body: ConfigPlayerSkinPart,
+    pub marking: ConfigPlayerSkinPart,
+    pub decoration: ConfigPlayerSkinPart,
+    pub feet: ConfigPlayerSkinPart,
+    pub eyes: ConfigPlayerSkinPart,
+}
+
+impl From<&ConfigPlayerSkinParts> for NetworkSkinInfo {
+    fn from(value: &ConfigPlayerSkinParts) -> Self {
+        Self::Parts {
+            body_color: value.body.network_color(),
+            marking_color: value.marking.network_color(),
+            decoration_color: value.decoration.network_color(),
+            feet_color: value.feet.network_color(),
+            eyes_color: value.eyes.network_color(),
+        }
+    }
+}
+
+impl From<&ConfigPlayerSkinParts> for NetworkSkinParts {
+    fn from(value: &ConfigPlayerSkinParts) -> Self {
+        if value.enabled {
+            Self {
+                body: value.body.network_key(),
+                marking: value.marking.network_key(),
+                decoration: value.decoration.network_key(),
+                feet: value.feet.network_key(),
+                eyes: value.eyes.network_key(),
+            }
+        } else {
+            Self::default()
+        }
+    }
+}
+
 #[config_default]
 #[derive(Debug, Serialize, Deserialize, ConfigInterface, Clone)]
 pub struct ConfigPlayerLaser {
@@ -577,6 +674,77 @@ pub struct ConfigServerRenderMod {
     pub required: bool,
 }
 
+#[derive(
+    Debug, Clone, Copy, Serialize, Deserialize, ConfigInterface, PartialEq, Eq, PartialOrd, Ord,
+)]
+pub enum ConfigRconAuthLevel {
+    Moderator,
+    Admin,
+}
+
+/// The external admin console (econ) of the server.
+#[config_default]
+#[derive(Debug, Clone, Serialize, Deserialize, ConfigInterface)]
+pub struct ConfigServerEcon {
+    /// The port of the external console.
+    /// 0 disables the external console.
+    #[default = 0]
+    pub port: u16,
+    /// The address the external console listens on.
+    #[default = "127.0.0.1"]
+    pub bind_addr: String,
+    /// The tokens that allow to log in to the external console,
+    /// together with the auth level the token grants.
+    /// Without any token the external console stays disabled.
+    pub tokens: HashMap<String, ConfigRconAuthLevel>,
+    /// Time in seconds a connection has to authenticate,
+    /// before it is closed.
+    #[conf_valid(range(min = 1, max = 3600))]
+    #[default = 30]
+    pub auth_timeout: u64,
+    /// The maximum number of concurrent connections.
+    #[conf_valid(range(min = 1, max = 1024))]
+    #[default = 8]
+    pub max_connections: u32,
+}
+
+/// The audit log of privileged actions (rcon commands, bans, kicks etc.).
+#[config_default]
+#[derive(Debug, Clone, Serialize, Deserialize, ConfigInterface)]
+pub struct ConfigServerAudit {
+    /// Whether privileged actions are written to the audit log.
+    #[default = true]
+    pub enabled: bool,
+    /// The directory the audit log files are written to.
+    #[default = "audit"]
+    pub path: String,
+    /// The size in bytes after which the audit log file is rotated.
+    #[conf_valid(range(min = 1024, max = 1073741824))]
+    #[default = 10485760]
+    pub max_file_size: u64,
+    /// How many rotated audit log files are kept.
+    #[conf_valid(range(min = 1, max = 1000))]
+    #[default = 5]
+    pub max_files: u32,
+    /// Additionally write the audit log to the main database
+    /// (if a database is configured).
+    #[default = false]
+    pub db: bool,
+}
+
+/// The Prometheus metrics endpoint of the server.
+#[config_default]
+#[derive(Debug, Clone, Serialize, Deserialize, ConfigInterface)]
+pub struct ConfigServerMetrics {
+    /// The port of the http server that serves the metrics under `/metrics`.
+    /// 0 disables the metrics endpoint.
+    #[default = 0]
+    pub port: u16,
+    /// The address the metrics endpoint listens on.
+    #[default = "127.0.0.1"]
+    pub bind_addr: String,
+}
+
 pub const MAX_SERVER_NAME_LEN: usize = 64;
 #[config_default]
 #[derive(Debug, Clone, Serialize, Deserialize, ConfigInterface)]
@@ -683,6 +851,18 @@ pub struct ConfigServer {
     /// traffic.
     #[default = false]
     pub spatial_chat: bool,
+    /// Overrides the minimum auth level that is required to
+    /// execute a rcon command or to change a server variable.
+    /// The key is the name of the command (e.g. `kick_id`).
+    /// Commands that are not listed here keep their default auth level.
+    pub rcon_auth_levels: HashMap<String, ConfigRconAuthLevel>,
+    /// The external admin console, which allows to execute
+    /// rcon commands over a plain TCP connection.
+    pub econ: ConfigServerEcon,
+    /// The audit log of privileged actions.
+    pub audit: ConfigServerAudit,
+    /// The Prometheus metrics endpoint.
+    pub metrics: ConfigServerMetrics,
 }
 
 /// Sound configs used during rendering sound & graphics.
@@ -735,6 +915,10 @@ pub struct ConfigDebug {
     pub client_server_sync_log: ConfigDebugClientServerSyncLog,
 }
 
+/// Migrations of the stored game config, see [`ConfigMigrations`].
+/// New migrations are appended, existing ones must never change.
+pub static GAME_MIGRATIONS: ConfigMigrations = ConfigMigrations::new(&[]);
+
 #[config_default]
 #[derive(Debug, Clone, Serialize, Deserialize, ConfigInterface)]
 pub struct ConfigGame {
@@ -764,8 +948,9 @@ impl ConfigGame {
         Self::default()
     }
 
+    /// Serializes the config for storage, including its version.
     pub fn to_json_string(&self) -> anyhow::Result<String> {
-        Ok(serde_json::to_string_pretty(self)?)
+        GAME_MIGRATIONS.to_json_string(self)
     }
 
     pub fn from_json_string(json_str: &str) -> anyhow::Result<Self> {
@@ -775,6 +960,10 @@ impl ConfigGame {
     pub fn from_json_slice(json: &[u8]) -> anyhow::Result<Self> {
         Ok(serde_json::from_slice(json)?)
     }
+
+    pub fn from_json_value(json: serde_json::Value) -> anyhow::Result<Self> {
+        Ok(serde_json::from_value(json)?)
+    }
 }
 
 #[derive(Debug, Default, Serialize, Deserialize)]
@@ -5,7 +5,10 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     chat_commands::ClientChatCommand,
-    types::{id_types::CharacterId, render::game::game_match::MatchSide},
+    types::{
+        id_types::{CharacterId, StageId},
+        render::game::game_match::MatchSide,
+    },
 };
 
 #[derive(Debug, Hiarc, Clone, Serialize, Deserialize)]
@@ -18,6 +21,13 @@ pub enum ClientCameraMode {
     /// The clients wants to join the freecam and make
     /// himself invisible/phased (similar to /spec in ddrace)
     PhasedFreeCam(FxHashSet<CharacterId>),
+    /// The client wants to spectate all characters of
+    /// a specific stage (a.k.a. ddrace-team).
+    Stage(StageId),
+    /// The client wants the server to automatically pick
+    /// the most interesting character to spectate,
+    /// e.g. the flag carrier.
+    AutoDirector,
 }
 
 pub const MAX_TEAM_NAME_LEN: usize = 24;
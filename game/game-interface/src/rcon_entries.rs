@@ -33,7 +33,13 @@ pub struct RconEntries {
     pub vars: HashMap<NetworkString<65536>, RconEntry>,
 }
 
-#[derive(Debug, Hiarc, Default, Clone, Copy, Serialize, Deserialize)]
+/// The auth level of a client.
+///
+/// The variants are ordered by their privileges,
+/// so a higher auth level compares greater than a lower one.
+#[derive(
+    Debug, Hiarc, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize,
+)]
 pub enum AuthLevel {
     #[default]
     None,
@@ -17,6 +17,10 @@ pub enum TickEvent {
         until: Option<chrono::DateTime<chrono::Utc>>,
         reason: PlayerBanReason,
     },
+    /// A match finished and its result should be kept,
+    /// e.g. for tournaments.
+    /// The server writes the given JSON as is to a file.
+    MatchResult { json: String },
 }
 
 /// The tick result contains per tick data
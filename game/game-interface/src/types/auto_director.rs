@@ -0,0 +1,88 @@
+use hiarc::Hiarc;
+use math::math::{length, vector::vec2};
+use serde::{Deserialize, Serialize};
+
+use crate::types::{game::GameTickType, id_types::CharacterId};
+
+/// A character the [`AutoDirector`] could follow.
+#[derive(Debug, Hiarc, Clone, Copy)]
+pub struct AutoDirectorCandidate {
+    pub id: CharacterId,
+    /// The current velocity of the character.
+    /// The unit does not matter, as long as all candidates use the same.
+    pub vel: vec2,
+    /// Whether the character currently carries a flag.
+    pub carries_flag: bool,
+}
+
+impl AutoDirectorCandidate {
+    fn speed(&self) -> f32 {
+        length(&self.vel)
+    }
+
+    /// Whether this candidate is clearly more interesting to watch than `other`.
+    ///
+    /// Flag carriers are always preferred, else the faster character wins,
+    /// but only by a margin, so the camera does not jump around between
+    /// similarly active characters.
+    fn beats(&self, other: &Self) -> bool {
+        const SWITCH_FACTOR: f32 = 1.5;
+        if self.carries_flag != other.carries_flag {
+            self.carries_flag
+        } else {
+            self.speed() > other.speed() * SWITCH_FACTOR
+        }
+    }
+}
+
+/// Automatically picks the most interesting character for a spectator,
+/// e.g. the flag carrier or the most active player.
+///
+/// Once a character is picked, it is followed for at least
+/// `min_follow_ticks` (see [`AutoDirector::update`]).
+#[derive(Debug, Hiarc, Default, Clone, Copy, Serialize, Deserialize)]
+pub struct AutoDirector {
+    /// The character that is currently followed.
+    pub current: Option<CharacterId>,
+    /// Ticks until the director is allowed to switch to another character.
+    switch_in: GameTickType,
+}
+
+impl AutoDirector {
+    /// Advances the director by `ticks_passed` ticks and returns
+    /// the character that should be followed, if any.
+    pub fn update(
+        &mut self,
+        candidates: impl IntoIterator<Item = AutoDirectorCandidate>,
+        ticks_passed: GameTickType,
+        min_follow_ticks: GameTickType,
+    ) -> Option<CharacterId> {
+        self.switch_in = self.switch_in.saturating_sub(ticks_passed);
+
+        let mut current = None;
+        let mut best: Option<AutoDirectorCandidate> = None;
+        for candidate in candidates {
+            if Some(candidate.id) == self.current {
+                current = Some(candidate);
+            }
+            if best.is_none_or(|best| {
+                (candidate.carries_flag, candidate.speed()) > (best.carries_flag, best.speed())
+            }) {
+                best = Some(candidate);
+            }
+        }
+
+        let next = match (current, best) {
+            (Some(current), Some(best)) if self.switch_in == 0 && best.beats(&current) => {
+                Some(best.id)
+            }
+            (Some(current), _) => Some(current.id),
+            (None, best) => best.map(|best| best.id),
+        };
+        if next != self.current {
+            self.current = next;
+            self.switch_in = min_follow_ticks;
+        }
+        self.current
+    }
+}
@@ -16,6 +16,16 @@ pub enum NetworkSkinInfo {
         body_color: ubvec4,
         feet_color: ubvec4,
     },
+    /// Colors for every part of a part based skin (see [`NetworkSkinParts`]).
+    /// `None` keeps the original colors of that part.
+    Parts {
+        body_color: Option<ubvec4>,
+        /// The alpha channel is the opacity of the marking.
+        marking_color: Option<ubvec4>,
+        decoration_color: Option<ubvec4>,
+        feet_color: Option<ubvec4>,
+        eyes_color: Option<ubvec4>,
+    },
 }
 
 #[derive(Debug, Hiarc, Copy, Clone, Serialize, Deserialize)]
@@ -39,6 +49,22 @@ pub const MAX_FLAG_NAME_LEN: usize = 7;
 pub const MAX_LANG_NAME_LEN: usize = 13;
 pub const MAX_ASSET_NAME_LEN: usize = 24;
 
+/// Skins the individual parts of the tee are taken from,
+/// similar to the part based skins of 0.7.
+#[derive(Debug, Hiarc, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NetworkSkinParts {
+    /// `None` uses the body of the main skin.
+    pub body: Option<NetworkResourceKey<MAX_ASSET_NAME_LEN>>,
+    /// `None` renders no marking.
+    pub marking: Option<NetworkResourceKey<MAX_ASSET_NAME_LEN>>,
+    /// `None` renders no decoration.
+    pub decoration: Option<NetworkResourceKey<MAX_ASSET_NAME_LEN>>,
+    /// `None` uses the feet of the main skin.
+    pub feet: Option<NetworkResourceKey<MAX_ASSET_NAME_LEN>>,
+    /// `None` uses the eyes of the main skin.
+    pub eyes: Option<NetworkResourceKey<MAX_ASSET_NAME_LEN>>,
+}
+
 #[derive(Debug, Hiarc, Default, Clone, Serialize, Deserialize)]
 pub struct NetworkCharacterInfo {
     pub name: NetworkString<MAX_CHARACTER_NAME_LEN>,
@@ -67,6 +93,8 @@ pub struct NetworkCharacterInfo {
     pub emoticons: NetworkResourceKey<MAX_ASSET_NAME_LEN>,
     pub particles: NetworkResourceKey<MAX_ASSET_NAME_LEN>,
     pub hook: NetworkResourceKey<MAX_ASSET_NAME_LEN>,
+    /// Overrides parts of [`Self::skin`] by parts of other skins.
+    pub skin_parts: NetworkSkinParts,
 
     /// The default eyes to use, if the server supports settings
     /// custom eyes.
@@ -97,6 +125,7 @@ impl NetworkCharacterInfo {
             emoticons: "default".try_into().unwrap(),
             particles: "default".try_into().unwrap(),
             hook: "default".try_into().unwrap(),
+            skin_parts: NetworkSkinParts::default(),
 
             default_eyes: TeeEye::Normal,
         }
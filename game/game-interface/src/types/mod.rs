@@ -1,3 +1,4 @@
+pub mod auto_director;
 pub mod character_info;
 pub mod emoticons;
 pub mod fixed_zoom_level;
@@ -49,6 +49,14 @@ pub enum MatchRoundTimeType {
         ticks_left: GameTickType,
     },
     SuddenDeath,
+    /// The match did not start yet, players can warm up.
+    Warmup {
+        /// Ticks until the match starts.
+        /// `None` if the match waits for all players to be ready.
+        ticks_left: Option<GameTickType>,
+    },
+    /// The match is paused, e.g. by an admin during a tournament.
+    Paused,
     GameOver {
         winner: MatchRoundGameOverWinner,
         by: MatchRoundGameOverWinBy,
@@ -5,6 +5,7 @@ use hiarc::Hiarc;
 use math::math::vector::ubvec4;
 use pool::datatypes::{PoolFxLinkedHashMap, PoolVec};
 use serde::{Deserialize, Serialize};
+use strum::EnumCount;
 
 use crate::{
     client_commands::MAX_TEAM_NAME_LEN,
@@ -12,6 +13,7 @@ use crate::{
     types::{
         id_types::{CharacterId, StageId},
         network_stats::PlayerNetworkStats,
+        weapons::WeaponType,
     },
 };
 
@@ -30,11 +32,49 @@ pub enum ScoreboardScoreType {
     None,
 }
 
+#[derive(Debug, Hiarc, Default, Clone, Copy, Serialize, Deserialize)]
+pub struct ScoreboardWeaponStats {
+    pub shots: u32,
+    pub hits: u32,
+    pub kills: u32,
+}
+
+/// Statistics of a character in the current match.
+#[derive(Debug, Hiarc, Default, Clone, Copy, Serialize, Deserialize)]
+pub struct ScoreboardCharacterStats {
+    /// Indexed by [`WeaponType`].
+    pub weapons: [ScoreboardWeaponStats; WeaponType::COUNT],
+    pub ninja_kills: u32,
+    pub deaths: u32,
+    pub suicides: u32,
+    pub flag_grabs: u32,
+    pub flag_captures: u32,
+    pub flag_returns: u32,
+    pub damage_dealt: u32,
+    pub damage_taken: u32,
+    pub time_alive: Duration,
+}
+
+impl ScoreboardCharacterStats {
+    pub fn kills(&self) -> u32 {
+        self.weapons.iter().map(|w| w.kills).sum::<u32>() + self.ninja_kills
+    }
+
+    /// Hits per shot over all weapons, `None` if nothing was fired yet.
+    pub fn accuracy(&self) -> Option<f64> {
+        let shots: u32 = self.weapons.iter().map(|w| w.shots).sum();
+        let hits: u32 = self.weapons.iter().map(|w| w.hits).sum();
+        (shots > 0).then(|| (hits as f64 / shots as f64).min(1.0))
+    }
+}
+
 #[derive(Debug, Hiarc, Clone, Serialize, Deserialize)]
 pub struct ScoreboardCharacterInfo {
     pub id: CharacterId,
     pub score: ScoreboardScoreType,
     pub ping: ScoreboardConnectionType,
+    /// Match statistics, if the modification tracks them.
+    pub stats: Option<ScoreboardCharacterStats>,
 }
 
 #[derive(Debug, Hiarc, Clone, Serialize, Deserialize)]
@@ -96,6 +136,9 @@ pub enum ScoreboardGameTypeOptions {
 pub struct ScoreboardGameOptions {
     pub ty: ScoreboardGameTypeOptions,
     pub map_name: PoolNetworkString<MAX_MAP_NAME_LEN>,
+    /// The match is over, the scoreboard shows
+    /// the end-of-match summary.
+    pub match_over: bool,
 }
 
 #[derive(Debug, Hiarc, Serialize, Deserialize)]
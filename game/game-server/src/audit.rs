@@ -0,0 +1,359 @@
+use std::{
+    collections::VecDeque,
+    fmt::Display,
+    io::Write,
+    path::PathBuf,
+    sync::{
+        mpsc::{Receiver, Sender},
+        Arc,
+    },
+    thread::JoinHandle,
+};
+
+use base::hash::fmt_hash;
+use base_io::io::Io;
+use game_config::config::ConfigServerAudit;
+use game_database::{traits::DbInterface, types::UnixUtcTimestamp};
+use game_interface::{
+    rcon_entries::AuthLevel,
+    types::{id_types::PlayerId, player_info::PlayerUniqueId},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::sql::audit::{AuditInsert, StatementArg};
+
+/// How many entries are kept in memory for the `audit` command.
+const MAX_RECENT_ENTRIES: usize = 1000;
+const AUDIT_FILE_NAME: &str = "audit.log";
+
+/// Who executed a privileged action.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum AuditActor {
+    /// The server itself, e.g. startup arguments or config files.
+    Server,
+    Player {
+        player_id: PlayerId,
+        name: String,
+        unique_identifier: Option<PlayerUniqueId>,
+    },
+    /// A connection of the external console.
+    Econ { addr: String },
+}
+
+impl Display for AuditActor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AuditActor::Server => write!(f, "server"),
+            AuditActor::Player {
+                player_id,
+                name,
+                unique_identifier,
+            } => {
+                write!(f, "player {player_id} ({name}")?;
+                match unique_identifier {
+                    Some(PlayerUniqueId::Account(account_id)) => {
+                        write!(f, ", account: {account_id}")?
+                    }
+                    Some(PlayerUniqueId::CertFingerprint(hash)) => {
+                        write!(f, ", cert: {}", fmt_hash(hash))?
+                    }
+                    None => {}
+                }
+                write!(f, ")")
+            }
+            AuditActor::Econ { addr } => write!(f, "econ {addr}"),
+        }
+    }
+}
+
+/// The player a privileged action was executed on.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditTarget {
+    pub player_id: PlayerId,
+    pub name: Option<String>,
+    pub unique_identifier: Option<PlayerUniqueId>,
+}
+
+impl Display for AuditTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "player {}", self.player_id)?;
+        if let Some(name) = &self.name {
+            write!(f, " ({name})")?;
+        }
+        match &self.unique_identifier {
+            Some(PlayerUniqueId::Account(account_id)) => write!(f, " account: {account_id}"),
+            Some(PlayerUniqueId::CertFingerprint(hash)) => {
+                write!(f, " cert: {}", fmt_hash(hash))
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+/// A single privileged action.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+    pub actor: AuditActor,
+    pub auth_level: AuthLevel,
+    pub cmd: String,
+    pub args: String,
+    pub target: Option<AuditTarget>,
+    pub success: bool,
+}
+
+impl Display for AuditEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "[{}] {} ({:?}): {} {}",
+            self.timestamp.format("%Y-%m-%d %H:%M:%S"),
+            self.actor,
+            self.auth_level,
+            self.cmd,
+            self.args
+        )?;
+        if let Some(target) = &self.target {
+            write!(f, " -> {target}")?;
+        }
+        if !self.success {
+            write!(f, " (failed)")?;
+        }
+        Ok(())
+    }
+}
+
+/// Whether a command succeeded. Besides the result of the command itself,
+/// commands (e.g. the commands of the game mod) report failures through
+/// the `responses` they added.
+pub fn command_succeeded(
+    res: &anyhow::Result<String>,
+    responses: &[Result<String, String>],
+) -> bool {
+    res.is_ok() && responses.iter().all(|res| res.is_ok())
+}
+
+/// Writes the audit entries as json lines and rotates
+/// the file once it exceeds the max size.
+struct AuditFileWriter {
+    dir: PathBuf,
+    max_file_size: u64,
+    max_files: u32,
+}
+
+impl AuditFileWriter {
+    fn file_path(&self, index: u32) -> PathBuf {
+        if index == 0 {
+            self.dir.join(AUDIT_FILE_NAME)
+        } else {
+            self.dir.join(format!("{AUDIT_FILE_NAME}.{index}"))
+        }
+    }
+
+    fn rotate(&self) -> std::io::Result<()> {
+        let _ = std::fs::remove_file(self.file_path(self.max_files));
+        for index in (0..self.max_files).rev() {
+            let path = self.file_path(index);
+            if path.exists() {
+                std::fs::rename(path, self.file_path(index + 1))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn write(&self, entry: &AuditEntry) -> anyhow::Result<()> {
+        let mut line = serde_json::to_string(entry)?;
+        line.push('\n');
+
+        std::fs::create_dir_all(&self.dir)?;
+        let path = self.file_path(0);
+        let size = std::fs::metadata(&path).map(|m| m.len()).unwrap_or_default();
+        if size > 0 && size + line.len() as u64 > self.max_file_size {
+            self.rotate()?;
+        }
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)?;
+        file.write_all(line.as_bytes())?;
+        file.flush()?;
+        Ok(())
+    }
+
+    fn run(self, recv: Receiver<AuditEntry>) {
+        while let Ok(entry) = recv.recv() {
+            if let Err(err) = self.write(&entry) {
+                log::error!(target: "audit", "failed to write audit entry: {err}");
+            }
+        }
+    }
+}
+
+/// Records all privileged actions (rcon commands, bans, kicks, config changes)
+/// to a rotating log file and optionally the database.
+pub struct AuditLog {
+    recent: VecDeque<AuditEntry>,
+
+    io: Io,
+    writer: Option<Sender<AuditEntry>>,
+    writer_thread: Option<JoinHandle<()>>,
+    db: Option<AuditInsert>,
+}
+
+impl AuditLog {
+    pub fn new(io: &Io, config: &ConfigServerAudit, db: Option<Arc<dyn DbInterface>>) -> Self {
+        if !config.enabled {
+            return Self {
+                recent: Default::default(),
+                io: io.clone(),
+                writer: None,
+                writer_thread: None,
+                db: None,
+            };
+        }
+
+        let file_writer = AuditFileWriter {
+            dir: io.fs.get_save_path().join(&config.path),
+            max_file_size: config.max_file_size,
+            max_files: config.max_files,
+        };
+
+        // restore the most recent entries of previous runs
+        let mut recent: VecDeque<AuditEntry> = std::fs::read_to_string(file_writer.file_path(0))
+            .map(|file| {
+                file.lines()
+                    .filter_map(|line| serde_json::from_str(line).ok())
+                    .collect()
+            })
+            .unwrap_or_default();
+        while recent.len() > MAX_RECENT_ENTRIES {
+            recent.pop_front();
+        }
+
+        let (writer, recv) = std::sync::mpsc::channel();
+        let writer_thread = std::thread::Builder::new()
+            .name("audit-writer".into())
+            .spawn(move || file_writer.run(recv));
+        let (writer, writer_thread) = match writer_thread {
+            Ok(thread) => (Some(writer), Some(thread)),
+            Err(err) => {
+                log::error!(target: "audit", "failed to start audit log writer: {err}");
+                (None, None)
+            }
+        };
+
+        let db = db.filter(|_| config.db).and_then(|db| {
+            io.rt
+                .spawn(async move { AuditInsert::new(db).await })
+                .get_storage()
+                .map_err(|err| {
+                    log::error!(target: "audit", "failed to prepare the audit database: {err}");
+                })
+                .ok()
+        });
+
+        Self {
+            recent,
+            io: io.clone(),
+            writer,
+            writer_thread,
+            db,
+        }
+    }
+
+    pub fn add(&mut self, entry: AuditEntry) {
+        log::info!(target: "audit", "{entry}");
+
+        if let Some(writer) = &self.writer {
+            let _ = writer.send(entry.clone());
+        }
+        if let Some(db) = &self.db {
+            let db = db.clone();
+            let arg = StatementArg {
+                create_time: UnixUtcTimestamp::from_chrono(entry.timestamp).unwrap_or_default(),
+                actor: entry.actor.to_string(),
+                auth_level: format!("{:?}", entry.auth_level),
+                cmd: entry.cmd.clone(),
+                args: entry.args.clone(),
+                target: entry
+                    .target
+                    .as_ref()
+                    .map(|target| target.to_string())
+                    .unwrap_or_default(),
+                success: entry.success,
+            };
+            self.io
+                .rt
+                .spawn_without_lifetime(async move { db.execute(arg).await });
+        }
+
+        if self.recent.len() >= MAX_RECENT_ENTRIES {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(entry);
+    }
+
+    /// The last `count` entries, the newest entry last.
+    pub fn recent(&self, count: usize) -> impl Iterator<Item = &AuditEntry> {
+        self.recent
+            .iter()
+            .skip(self.recent.len().saturating_sub(count))
+    }
+}
+
+impl Drop for AuditLog {
+    fn drop(&mut self) {
+        // closing the channel ends the writer thread
+        self.writer.take();
+        if let Some(thread) = self.writer_thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use base_io::{io::create_runtime, runtime::IoRuntime};
+    use game_database::dummy::DummyDb;
+    use game_interface::{
+        interface::{GameStateCreate, GameStateCreateOptions, GameStateInterface},
+        rcon_entries::{AuthLevel, ExecRconInput},
+    };
+    use vanilla::state::state::GameState;
+
+    use super::command_succeeded;
+
+    #[test]
+    fn failing_mod_rcon_command() {
+        let file = include_bytes!("../../../data/map/maps/ctf1.twmap");
+        let rt = create_runtime();
+        let (mut game, _) = GameState::new(
+            file.to_vec(),
+            "ctf1".try_into().unwrap(),
+            GameStateCreateOptions::default(),
+            IoRuntime::new(rt),
+            Arc::new(DummyDb),
+        )
+        .unwrap();
+
+        // the mod reports the failure as response, not as result of the command
+        let responses: Vec<Result<String, String>> = game
+            .rcon_command(
+                None,
+                ExecRconInput {
+                    raw: "not_a_command 1".try_into().unwrap(),
+                    auth_level: AuthLevel::Admin,
+                },
+            )
+            .into_iter()
+            .map(|res| res.map(|r| r.into()).map_err(|err| err.into()))
+            .collect();
+        assert!(responses.iter().any(|res| res.is_err()));
+        assert!(!command_succeeded(&Ok(String::new()), &responses));
+
+        assert!(command_succeeded(&Ok(String::new()), &[Ok("done".into())]));
+        assert!(!command_succeeded(&Err(anyhow::anyhow!("failed")), &[]));
+    }
+}
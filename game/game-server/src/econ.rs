@@ -0,0 +1,404 @@
+use std::{
+    collections::HashMap,
+    io::{BufRead, BufReader, Read, Write},
+    net::{SocketAddr, TcpListener, TcpStream},
+    sync::{
+        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+        mpsc::{Receiver, Sender, SyncSender, TrySendError},
+        Arc, Mutex, Weak,
+    },
+    thread::JoinHandle,
+    time::Duration,
+};
+
+use anyhow::anyhow;
+use game_config::config::{ConfigRconAuthLevel, ConfigServerEcon};
+use game_interface::rcon_entries::AuthLevel;
+
+/// The maximum length of a single line the external console reads.
+const MAX_LINE_LEN: u64 = 65536;
+/// How many tries a connection has to enter the correct token.
+const MAX_AUTH_TRIES: usize = 3;
+/// How many lines can be queued for a connection, before
+/// it is considered too slow and closed.
+const MAX_QUEUED_LINES: usize = 1024;
+
+pub type EconConnectionId = u64;
+
+/// A command line received by the external console.
+#[derive(Debug)]
+pub struct EconCmd {
+    pub con_id: EconConnectionId,
+    pub addr: SocketAddr,
+    pub auth_level: AuthLevel,
+    pub line: String,
+}
+
+/// An authenticated connection. The lines are written by
+/// its own writer thread, so sending never blocks the server.
+#[derive(Debug)]
+struct EconClient {
+    lines: SyncSender<String>,
+    stream: TcpStream,
+    addr: SocketAddr,
+}
+
+type EconClients = Arc<Mutex<HashMap<EconConnectionId, EconClient>>>;
+
+/// The connections that receive the log records, see [`EconLogger`].
+static LOG_CLIENTS: Mutex<Option<Weak<Mutex<HashMap<EconConnectionId, EconClient>>>>> =
+    Mutex::new(None);
+
+/// An external admin console that accepts line based rcon commands
+/// over a plain TCP connection (similar to the econ of ddnet).
+///
+/// A connection first has to send one of the configured tokens,
+/// afterwards every line is treated as a rcon command line.
+/// Server events like chat messages or joining/leaving players
+/// are streamed back to all authenticated connections,
+/// together with the server log if [`EconLogger`] is installed.
+#[derive(Debug)]
+pub struct Econ {
+    clients: EconClients,
+    cmds: Receiver<EconCmd>,
+    is_open: Arc<AtomicBool>,
+    local_addr: SocketAddr,
+
+    accept_thread: Option<JoinHandle<()>>,
+}
+
+impl Econ {
+    pub fn new(config: &ConfigServerEcon) -> anyhow::Result<Self> {
+        anyhow::ensure!(
+            !config.tokens.is_empty(),
+            "the external console requires at least one token"
+        );
+        let tokens: Arc<Vec<(String, AuthLevel)>> = Arc::new(
+            config
+                .tokens
+                .iter()
+                .map(|(token, level)| {
+                    (
+                        token.clone(),
+                        match level {
+                            ConfigRconAuthLevel::Moderator => AuthLevel::Moderator,
+                            ConfigRconAuthLevel::Admin => AuthLevel::Admin,
+                        },
+                    )
+                })
+                .collect(),
+        );
+        let bind_addr: std::net::IpAddr = config
+            .bind_addr
+            .parse()
+            .map_err(|err| anyhow!("invalid econ bind address {}: {err}", config.bind_addr))?;
+        let listener = TcpListener::bind((bind_addr, config.port))?;
+        // non blocking, so the thread can check if the server closed
+        listener.set_nonblocking(true)?;
+        let local_addr = listener.local_addr()?;
+
+        let clients: EconClients = Default::default();
+        let (cmds_sender, cmds) = std::sync::mpsc::channel();
+        let is_open = Arc::new(AtomicBool::new(true));
+
+        let auth_timeout = Duration::from_secs(config.auth_timeout);
+        let max_connections = config.max_connections as usize;
+
+        let clients_thread = clients.clone();
+        let is_open_thread = is_open.clone();
+        let accept_thread = std::thread::Builder::new()
+            .name("econ".into())
+            .spawn(move || {
+                let id_gen = AtomicU64::new(0);
+                let connection_count = Arc::new(AtomicUsize::new(0));
+                while is_open_thread.load(Ordering::Relaxed) {
+                    match listener.accept() {
+                        Ok((stream, addr)) => {
+                            if connection_count.load(Ordering::SeqCst) >= max_connections {
+                                let mut stream = stream;
+                                let _ = writeln!(stream, "Too many connections.");
+                                continue;
+                            }
+                            connection_count.fetch_add(1, Ordering::SeqCst);
+                            let con_id = id_gen.fetch_add(1, Ordering::Relaxed);
+                            let tokens = tokens.clone();
+                            let clients = clients_thread.clone();
+                            let cmds_sender = cmds_sender.clone();
+                            let connection_count = connection_count.clone();
+                            let res = std::thread::Builder::new()
+                                .name(format!("econ-con {con_id}"))
+                                .spawn(move || {
+                                    if let Err(err) = Self::handle_connection(
+                                        stream,
+                                        addr,
+                                        con_id,
+                                        &tokens,
+                                        auth_timeout,
+                                        &clients,
+                                        &cmds_sender,
+                                    ) {
+                                        log::info!(target: "econ", "connection {addr} closed: {err}");
+                                    }
+                                    clients.lock().unwrap().remove(&con_id);
+                                    connection_count.fetch_sub(1, Ordering::SeqCst);
+                                });
+                            if let Err(err) = res {
+                                log::error!(target: "econ", "failed to spawn connection thread: {err}");
+                            }
+                        }
+                        Err(err) if err.kind() == std::io::ErrorKind::WouldBlock => {
+                            std::thread::sleep(Duration::from_millis(100));
+                        }
+                        Err(err) => {
+                            log::error!(target: "econ", "failed to accept connection: {err}");
+                            std::thread::sleep(Duration::from_millis(100));
+                        }
+                    }
+                }
+            })?;
+
+        *LOG_CLIENTS.lock().unwrap() = Some(Arc::downgrade(&clients));
+
+        log::info!(target: "econ", "external console listening on {local_addr}");
+
+        Ok(Self {
+            clients,
+            cmds,
+            is_open,
+            local_addr,
+
+            accept_thread: Some(accept_thread),
+        })
+    }
+
+    /// Compares the token in constant time to not leak
+    /// information about the token through timings.
+    fn token_eq(token: &str, input: &str) -> bool {
+        let token = token.as_bytes();
+        let input = input.as_bytes();
+        token.len() == input.len()
+            && token
+                .iter()
+                .zip(input.iter())
+                .fold(0, |acc, (a, b)| acc | (a ^ b))
+                == 0
+    }
+
+    /// Reads a single line, lines longer than [`MAX_LINE_LEN`] are an error,
+    /// so their remainder is never treated as a line of its own.
+    fn read_line(reader: &mut BufReader<TcpStream>, line: &mut String) -> anyhow::Result<()> {
+        line.clear();
+        let read = reader.by_ref().take(MAX_LINE_LEN).read_line(line)?;
+        anyhow::ensure!(read > 0, "connection closed by peer");
+        anyhow::ensure!(
+            line.ends_with('\n') || (read as u64) < MAX_LINE_LEN,
+            "line longer than {MAX_LINE_LEN} bytes"
+        );
+        Ok(())
+    }
+
+    fn handle_connection(
+        stream: TcpStream,
+        addr: SocketAddr,
+        con_id: EconConnectionId,
+        tokens: &[(String, AuthLevel)],
+        auth_timeout: Duration,
+        clients: &EconClients,
+        cmds_sender: &Sender<EconCmd>,
+    ) -> anyhow::Result<()> {
+        stream.set_nonblocking(false)?;
+        let mut writer = stream.try_clone()?;
+        stream.set_read_timeout(Some(auth_timeout))?;
+        let mut reader = BufReader::new(stream);
+
+        writeln!(writer, "Enter token")?;
+        let mut line = String::new();
+        let mut auth_level = None;
+        for _ in 0..MAX_AUTH_TRIES {
+            Self::read_line(&mut reader, &mut line)?;
+            let input = line.trim_end_matches(['\r', '\n']);
+            if let Some((_, level)) = tokens
+                .iter()
+                .find(|(token, _)| Self::token_eq(token, input))
+            {
+                auth_level = Some(*level);
+                break;
+            }
+            writeln!(writer, "Wrong token.")?;
+        }
+        let Some(auth_level) = auth_level else {
+            writeln!(writer, "Too many wrong tokens.")?;
+            return Err(anyhow!("authentication failed"));
+        };
+
+        reader.get_ref().set_read_timeout(None)?;
+        writeln!(
+            writer,
+            "Authentication successful. External console access granted ({auth_level:?})."
+        )?;
+        log::info!(target: "econ", "{addr} authenticated as {auth_level:?}");
+
+        let (lines_sender, lines) = std::sync::mpsc::sync_channel::<String>(MAX_QUEUED_LINES);
+        let stream = writer.try_clone()?;
+        std::thread::Builder::new()
+            .name(format!("econ-write {con_id}"))
+            .spawn(move || {
+                // ends when the connection was removed from the clients
+                for line in lines {
+                    if let Err(err) = writeln!(writer, "{line}") {
+                        log::info!(target: "econ", "failed to send to {addr}: {err}");
+                        // also ends the read loop of the connection
+                        let _ = writer.shutdown(std::net::Shutdown::Both);
+                        break;
+                    }
+                }
+            })?;
+        clients.lock().unwrap().insert(
+            con_id,
+            EconClient {
+                lines: lines_sender,
+                stream,
+                addr,
+            },
+        );
+
+        loop {
+            Self::read_line(&mut reader, &mut line)?;
+            let cmd = line.trim();
+            if !cmd.is_empty() {
+                cmds_sender.send(EconCmd {
+                    con_id,
+                    addr,
+                    auth_level,
+                    line: cmd.to_string(),
+                })?;
+            }
+        }
+    }
+
+    /// The address the external console listens on.
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    /// All command lines that were received since the last call.
+    pub fn take_cmds(&self) -> Vec<EconCmd> {
+        self.cmds.try_iter().collect()
+    }
+
+    /// Queues the line for the client, returns `false` if
+    /// the client is too slow or its connection closed.
+    fn queue_line(client: &EconClient, line: &str) -> bool {
+        match client.lines.try_send(line.to_string()) {
+            Ok(_) => true,
+            Err(TrySendError::Full(_) | TrySendError::Disconnected(_)) => {
+                let _ = client.stream.shutdown(std::net::Shutdown::Both);
+                false
+            }
+        }
+    }
+
+    fn broadcast_to(clients: &EconClients, line: &str) {
+        let mut dropped = Vec::new();
+        clients.lock().unwrap().retain(|_, client| {
+            let keep = Self::queue_line(client, line);
+            if !keep {
+                dropped.push(client.addr);
+            }
+            keep
+        });
+        for addr in dropped {
+            log::info!(target: "econ", "closed the connection of {addr}, it does not keep up");
+        }
+    }
+
+    /// Sends the lines to a single connection.
+    pub fn send_to<'a>(&self, con_id: EconConnectionId, lines: impl IntoIterator<Item = &'a str>) {
+        let mut clients = self.clients.lock().unwrap();
+        let Some(client) = clients.get(&con_id) else {
+            return;
+        };
+        if !lines.into_iter().all(|line| Self::queue_line(client, line)) {
+            let addr = client.addr;
+            clients.remove(&con_id);
+            drop(clients);
+            log::info!(target: "econ", "closed the connection of {addr}, it does not keep up");
+        }
+    }
+
+    /// Sends the line to all authenticated connections.
+    pub fn broadcast(&self, line: &str) {
+        Self::broadcast_to(&self.clients, line);
+    }
+}
+
+impl Drop for Econ {
+    fn drop(&mut self) {
+        self.is_open.store(false, Ordering::Relaxed);
+        // closing the streams also ends the blocking reads of the connection threads
+        for (_, client) in self.clients.lock().unwrap().drain() {
+            let _ = client.stream.shutdown(std::net::Shutdown::Both);
+        }
+        if let Some(thread) = self.accept_thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// A logger that forwards the log records to all authenticated
+/// connections of the external console, in addition to writing
+/// them to the wrapped logger.
+pub struct EconLogger {
+    inner: Box<dyn log::Log>,
+}
+
+impl EconLogger {
+    /// Installs the logger as global logger.
+    pub fn init(
+        inner: Box<dyn log::Log>,
+        max_level: log::LevelFilter,
+    ) -> Result<(), log::SetLoggerError> {
+        log::set_boxed_logger(Box::new(Self { inner }))?;
+        log::set_max_level(max_level);
+        Ok(())
+    }
+}
+
+impl log::Log for EconLogger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        self.inner.enabled(metadata)
+    }
+
+    fn log(&self, record: &log::Record) {
+        if !self.inner.enabled(record.metadata()) {
+            return;
+        }
+        self.inner.log(record);
+
+        // the econ's own records are not forwarded,
+        // they are about the connections themselves.
+        if record.target() == "econ" {
+            return;
+        }
+        let clients = LOG_CLIENTS
+            .lock()
+            .unwrap()
+            .as_ref()
+            .and_then(|clients| clients.upgrade());
+        if let Some(clients) = clients {
+            Econ::broadcast_to(
+                &clients,
+                &format!(
+                    "[{}] {}: {}",
+                    record.level(),
+                    record.target(),
+                    record.args()
+                ),
+            );
+        }
+    }
+
+    fn flush(&self) {
+        self.inner.flush();
+    }
+}
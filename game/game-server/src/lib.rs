@@ -1,11 +1,15 @@
 #![allow(clippy::too_many_arguments)]
 
+pub mod audit;
 pub mod auto_map_votes;
 pub mod client;
+pub mod econ;
 pub mod local_server;
 pub mod map_votes;
+pub mod metrics;
 pub mod network_plugins;
 pub mod rcon;
 pub mod server;
 pub mod server_game;
 pub mod spatial_chat;
+pub mod sql;
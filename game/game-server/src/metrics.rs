@@ -0,0 +1,397 @@
+use std::{
+    collections::HashMap,
+    fmt::Write,
+    net::SocketAddr,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use anyhow::anyhow;
+use base_http::http_server::{HttpDynamicRoute, HttpDynamicServer};
+use game_config::config::ConfigServerMetrics;
+
+/// Upper bounds in seconds of the buckets of tick durations.
+const TICK_BUCKETS: [f64; 10] = [
+    0.0005, 0.001, 0.002, 0.004, 0.008, 0.012, 0.016, 0.02, 0.04, 0.1,
+];
+/// Upper bounds in seconds of the buckets of database queries.
+const DB_BUCKETS: [f64; 10] = [0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 5.0];
+
+const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// A gauge that holds a float.
+#[derive(Debug, Default)]
+pub struct Gauge(AtomicU64);
+
+impl Gauge {
+    pub fn set(&self, val: f64) {
+        self.0.store(val.to_bits(), Ordering::Relaxed);
+    }
+
+    pub fn get(&self) -> f64 {
+        f64::from_bits(self.0.load(Ordering::Relaxed))
+    }
+}
+
+/// A histogram with fixed buckets.
+#[derive(Debug)]
+pub struct Histogram {
+    bounds: &'static [f64],
+    /// Non cumulative counts, the last one is the `+Inf` bucket.
+    buckets: Vec<AtomicU64>,
+    sum_micros: AtomicU64,
+    count: AtomicU64,
+}
+
+impl Histogram {
+    fn new(bounds: &'static [f64]) -> Self {
+        Self {
+            bounds,
+            buckets: (0..=bounds.len()).map(|_| AtomicU64::new(0)).collect(),
+            sum_micros: AtomicU64::new(0),
+            count: AtomicU64::new(0),
+        }
+    }
+
+    pub fn observe(&self, duration: Duration) {
+        let secs = duration.as_secs_f64();
+        let index = self
+            .bounds
+            .iter()
+            .position(|bound| secs <= *bound)
+            .unwrap_or(self.bounds.len());
+        self.buckets[index].fetch_add(1, Ordering::Relaxed);
+        self.sum_micros
+            .fetch_add(duration.as_micros() as u64, Ordering::Relaxed);
+        self.count.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn write(&self, out: &mut String, name: &str, help: &str) {
+        let _ = writeln!(out, "# HELP {name} {help}");
+        let _ = writeln!(out, "# TYPE {name} histogram");
+        let mut cumulative = 0;
+        for (bound, bucket) in self.bounds.iter().zip(self.buckets.iter()) {
+            cumulative += bucket.load(Ordering::Relaxed);
+            let _ = writeln!(out, "{name}_bucket{{le=\"{bound}\"}} {cumulative}");
+        }
+        cumulative += self.buckets[self.bounds.len()].load(Ordering::Relaxed);
+        let _ = writeln!(out, "{name}_bucket{{le=\"+Inf\"}} {cumulative}");
+        let _ = writeln!(
+            out,
+            "{name}_sum {}",
+            self.sum_micros.load(Ordering::Relaxed) as f64 / 1_000_000.0
+        );
+        let _ = writeln!(out, "{name}_count {}", self.count.load(Ordering::Relaxed));
+    }
+}
+
+/// Statistics of the server that are exported by the metrics endpoint.
+///
+/// Everything is atomic, so the server can update them without locking,
+/// while the http server renders them.
+#[derive(Debug)]
+pub struct ServerMetrics {
+    pub players: AtomicU64,
+    /// Clients that finished connecting.
+    pub clients: AtomicU64,
+    /// Connections that are still connecting or are queued.
+    pub connecting: AtomicU64,
+
+    pub ticks: AtomicU64,
+    /// The duration of a server tick, including building
+    /// the snapshots and events for all clients.
+    pub tick_duration: Histogram,
+
+    pub snapshots_sent: AtomicU64,
+    pub snapshot_bytes: AtomicU64,
+
+    /// Average & maximum ping of all connected clients in seconds.
+    pub ping_avg: Gauge,
+    pub ping_max: Gauge,
+    /// Average & maximum packet loss ratio of all connected clients.
+    pub packet_loss_avg: Gauge,
+    pub packet_loss_max: Gauge,
+
+    pub rcon_commands: AtomicU64,
+    pub votes_started: AtomicU64,
+    pub votes_passed: AtomicU64,
+
+    pub db_query_duration: Histogram,
+    pub db_query_errors: AtomicU64,
+}
+
+impl Default for ServerMetrics {
+    fn default() -> Self {
+        Self {
+            players: Default::default(),
+            clients: Default::default(),
+            connecting: Default::default(),
+
+            ticks: Default::default(),
+            tick_duration: Histogram::new(&TICK_BUCKETS),
+
+            snapshots_sent: Default::default(),
+            snapshot_bytes: Default::default(),
+
+            ping_avg: Default::default(),
+            ping_max: Default::default(),
+            packet_loss_avg: Default::default(),
+            packet_loss_max: Default::default(),
+
+            rcon_commands: Default::default(),
+            votes_started: Default::default(),
+            votes_passed: Default::default(),
+
+            db_query_duration: Histogram::new(&DB_BUCKETS),
+            db_query_errors: Default::default(),
+        }
+    }
+}
+
+impl ServerMetrics {
+    pub fn inc(counter: &AtomicU64) {
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn write_value(
+        out: &mut String,
+        name: &str,
+        ty: &str,
+        help: &str,
+        val: impl std::fmt::Display,
+    ) {
+        let _ = writeln!(out, "# HELP {name} {help}");
+        let _ = writeln!(out, "# TYPE {name} {ty}");
+        let _ = writeln!(out, "{name} {val}");
+    }
+
+    /// Renders all metrics in the Prometheus text format.
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        let load = |val: &AtomicU64| val.load(Ordering::Relaxed);
+
+        Self::write_value(
+            &mut out,
+            "server_players",
+            "gauge",
+            "Players (including dummies) on the server.",
+            load(&self.players),
+        );
+        Self::write_value(
+            &mut out,
+            "server_clients",
+            "gauge",
+            "Clients that are connected and ready.",
+            load(&self.clients),
+        );
+        Self::write_value(
+            &mut out,
+            "server_connecting_clients",
+            "gauge",
+            "Connections that are still connecting or queued.",
+            load(&self.connecting),
+        );
+
+        Self::write_value(
+            &mut out,
+            "server_ticks_total",
+            "counter",
+            "Game ticks simulated by the server.",
+            load(&self.ticks),
+        );
+        self.tick_duration.write(
+            &mut out,
+            "server_tick_duration_seconds",
+            "Time to simulate a tick and build the snapshots.",
+        );
+
+        Self::write_value(
+            &mut out,
+            "server_snapshots_sent_total",
+            "counter",
+            "Snapshots sent to clients.",
+            load(&self.snapshots_sent),
+        );
+        Self::write_value(
+            &mut out,
+            "server_snapshot_bytes_total",
+            "counter",
+            "Bytes of (diffed) snapshots sent to clients, before compression.",
+            load(&self.snapshot_bytes),
+        );
+
+        Self::write_value(
+            &mut out,
+            "server_client_ping_avg_seconds",
+            "gauge",
+            "Average ping of the connected clients.",
+            self.ping_avg.get(),
+        );
+        Self::write_value(
+            &mut out,
+            "server_client_ping_max_seconds",
+            "gauge",
+            "Highest ping of the connected clients.",
+            self.ping_max.get(),
+        );
+        Self::write_value(
+            &mut out,
+            "server_client_packet_loss_avg_ratio",
+            "gauge",
+            "Average packet loss of the connected clients.",
+            self.packet_loss_avg.get(),
+        );
+        Self::write_value(
+            &mut out,
+            "server_client_packet_loss_max_ratio",
+            "gauge",
+            "Highest packet loss of the connected clients.",
+            self.packet_loss_max.get(),
+        );
+
+        Self::write_value(
+            &mut out,
+            "server_rcon_commands_total",
+            "counter",
+            "Rcon command lines executed (by players & the external console).",
+            load(&self.rcon_commands),
+        );
+        Self::write_value(
+            &mut out,
+            "server_votes_started_total",
+            "counter",
+            "Votes started by players.",
+            load(&self.votes_started),
+        );
+        Self::write_value(
+            &mut out,
+            "server_votes_passed_total",
+            "counter",
+            "Votes that passed.",
+            load(&self.votes_passed),
+        );
+
+        self.db_query_duration.write(
+            &mut out,
+            "server_db_query_duration_seconds",
+            "Duration of database requests.",
+        );
+        Self::write_value(
+            &mut out,
+            "server_db_query_errors_total",
+            "counter",
+            "Database requests that failed.",
+            load(&self.db_query_errors),
+        );
+
+        out
+    }
+}
+
+/// Serves the [`ServerMetrics`] in the Prometheus text format
+/// under `/metrics`.
+pub struct MetricsServer {
+    server: HttpDynamicServer,
+}
+
+impl MetricsServer {
+    pub fn new(config: &ConfigServerMetrics, metrics: Arc<ServerMetrics>) -> anyhow::Result<Self> {
+        let bind_addr: std::net::IpAddr = config
+            .bind_addr
+            .parse()
+            .map_err(|err| anyhow!("invalid metrics bind address {}: {err}", config.bind_addr))?;
+        let server = HttpDynamicServer::new(
+            HashMap::from([(
+                "metrics".to_string(),
+                (
+                    CONTENT_TYPE,
+                    Arc::new(move || metrics.render()) as HttpDynamicRoute,
+                ),
+            )]),
+            SocketAddr::new(bind_addr, config.port),
+        )?;
+
+        log::info!(target: "metrics", "metrics endpoint listening on http://{}/metrics", server.addr);
+
+        Ok(Self { server })
+    }
+
+    /// The address the metrics endpoint listens on.
+    pub fn local_addr(&self) -> SocketAddr {
+        self.server.addr
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{sync::atomic::Ordering, time::Duration};
+
+    use super::{Histogram, ServerMetrics};
+
+    #[test]
+    fn histogram_buckets() {
+        static BOUNDS: [f64; 3] = [0.001, 0.01, 0.1];
+        let histogram = Histogram::new(&BOUNDS);
+        histogram.observe(Duration::from_micros(500));
+        // the upper bound is inclusive
+        histogram.observe(Duration::from_millis(10));
+        histogram.observe(Duration::from_millis(50));
+        histogram.observe(Duration::from_secs(1));
+
+        let counts: Vec<_> = histogram
+            .buckets
+            .iter()
+            .map(|bucket| bucket.load(Ordering::Relaxed))
+            .collect();
+        assert_eq!(counts, [1, 1, 1, 1]);
+
+        let mut out = String::new();
+        histogram.write(&mut out, "test", "A test.");
+        assert_eq!(
+            out,
+            "# HELP test A test.\n\
+            # TYPE test histogram\n\
+            test_bucket{le=\"0.001\"} 1\n\
+            test_bucket{le=\"0.01\"} 2\n\
+            test_bucket{le=\"0.1\"} 3\n\
+            test_bucket{le=\"+Inf\"} 4\n\
+            test_sum 1.0605\n\
+            test_count 4\n"
+        );
+    }
+
+    #[test]
+    fn render() {
+        let metrics = ServerMetrics::default();
+        metrics.players.store(3, Ordering::Relaxed);
+        ServerMetrics::inc(&metrics.rcon_commands);
+        metrics.ping_avg.set(0.05);
+        metrics.tick_duration.observe(Duration::from_millis(3));
+
+        let out = metrics.render();
+        assert!(out.contains(
+            "# HELP server_players Players (including dummies) on the server.\n\
+            # TYPE server_players gauge\n\
+            server_players 3\n"
+        ));
+        assert!(out.contains("\nserver_rcon_commands_total 1\n"));
+        assert!(out.contains("\nserver_client_ping_avg_seconds 0.05\n"));
+        assert!(out.contains("\nserver_tick_duration_seconds_bucket{le=\"0.002\"} 0\n"));
+        assert!(out.contains("\nserver_tick_duration_seconds_bucket{le=\"0.004\"} 1\n"));
+        assert!(out.contains("\nserver_tick_duration_seconds_count 1\n"));
+        // every sample line belongs to a metric with HELP & TYPE
+        for line in out.lines().filter(|line| !line.starts_with('#')) {
+            let name = line.split(['{', ' ']).next().unwrap();
+            let base = ["_bucket", "_sum", "_count"]
+                .iter()
+                .find_map(|suffix| name.strip_suffix(suffix))
+                .filter(|base| out.contains(&format!("# TYPE {base} histogram")))
+                .unwrap_or(name);
+            assert!(out.contains(&format!("# HELP {base} ")), "{line}");
+            assert!(out.contains(&format!("# TYPE {base} ")), "{line}");
+        }
+    }
+}
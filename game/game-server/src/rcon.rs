@@ -1,14 +1,21 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, net::SocketAddr};
 
+use anyhow::anyhow;
 use base_io::io::Io;
+use game_config::config::ConfigRconAuthLevel;
 use game_interface::{
     rcon_entries::AuthLevel,
-    types::player_info::{AccountId, PlayerUniqueId},
+    types::{
+        id_types::PlayerId,
+        player_info::{AccountId, PlayerUniqueId},
+    },
 };
 use rand::Rng;
 
 use crate::client::ServerClient;
 
+const AUTH_FILE: &str = "auth.json";
+
 /// Everything the server needs for rcon
 #[derive(Debug)]
 pub struct Rcon {
@@ -24,7 +31,7 @@ impl Rcon {
         let auths = io
             .rt
             .spawn(async move {
-                let file = fs.read_file("auth.json".as_ref()).await?;
+                let file = fs.read_file(AUTH_FILE.as_ref()).await?;
                 Ok(serde_json::from_slice::<HashMap<AccountId, AuthLevel>>(
                     &file,
                 )?)
@@ -55,6 +62,109 @@ impl Rcon {
             false
         }
     }
+
+    /// Writes the current auths back to the `auth.json`.
+    ///
+    /// The file is replaced atomically, so a crash during
+    /// writing never leaves a half written file behind.
+    pub fn save(&self, io: &Io) -> anyhow::Result<()> {
+        let fs = io.fs.clone();
+        let file = serde_json::to_vec_pretty(&self.auths)?;
+        io.rt
+            .spawn(async move { Ok(fs.write_file(AUTH_FILE.as_ref(), file).await?) })
+            .get_storage()
+    }
+
+    /// Adds or updates the auth level of an account and persists the change.
+    ///
+    /// Returns the previous auth level of the account, if any.
+    pub fn add_auth(
+        &mut self,
+        io: &Io,
+        account_id: AccountId,
+        level: AuthLevel,
+    ) -> anyhow::Result<Option<AuthLevel>> {
+        anyhow::ensure!(
+            !matches!(level, AuthLevel::None),
+            "An auth level of none is not allowed, use auth_remove instead."
+        );
+        let prev = self.auths.insert(account_id, level);
+        if let Err(err) = self.save(io) {
+            // restore the old state, memory and file should never differ
+            match prev {
+                Some(prev) => self.auths.insert(account_id, prev),
+                None => self.auths.remove(&account_id),
+            };
+            return Err(err);
+        }
+        Ok(prev)
+    }
+
+    /// Removes the auth level of an account and persists the change.
+    ///
+    /// Returns the removed auth level of the account, if any.
+    pub fn remove_auth(
+        &mut self,
+        io: &Io,
+        account_id: AccountId,
+    ) -> anyhow::Result<Option<AuthLevel>> {
+        let Some(prev) = self.auths.remove(&account_id) else {
+            return Ok(None);
+        };
+        if let Err(err) = self.save(io) {
+            self.auths.insert(account_id, prev);
+            return Err(err);
+        }
+        Ok(Some(prev))
+    }
+
+    pub fn parse_auth_level(level: &str) -> anyhow::Result<AuthLevel> {
+        match level.to_lowercase().as_str() {
+            "moderator" | "mod" => Ok(AuthLevel::Moderator),
+            "admin" => Ok(AuthLevel::Admin),
+            "none" => Ok(AuthLevel::None),
+            _ => Err(anyhow!(
+                "{level} is not a valid auth level, expected moderator or admin"
+            )),
+        }
+    }
+
+    /// The minimum auth level required to execute the given command,
+    /// respecting the overrides of the config.
+    pub fn required_auth_level(
+        overrides: &HashMap<String, ConfigRconAuthLevel>,
+        ident: &str,
+        cmd: Option<ServerRconCommand>,
+    ) -> AuthLevel {
+        overrides
+            .get(ident)
+            .map(|level| match level {
+                ConfigRconAuthLevel::Moderator => AuthLevel::Moderator,
+                ConfigRconAuthLevel::Admin => AuthLevel::Admin,
+            })
+            .or_else(|| cmd.map(|cmd| cmd.default_auth_level()))
+            // mod commands handle the auth level themselves
+            .unwrap_or(AuthLevel::None)
+    }
+}
+
+/// Who executes a rcon command line.
+#[derive(Debug, Clone, Copy)]
+pub enum RconActor {
+    /// The server itself, e.g. startup arguments.
+    Server,
+    Player(PlayerId),
+    /// A connection of the external console.
+    Econ { addr: SocketAddr },
+}
+
+impl RconActor {
+    pub fn player_id(&self) -> Option<&PlayerId> {
+        match self {
+            RconActor::Player(player_id) => Some(player_id),
+            RconActor::Server | RconActor::Econ { .. } => None,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -71,4 +181,32 @@ pub enum ServerRconCommand {
     AddMiscVote,
     RemoveMiscVote,
     RecordDemo,
+    /// Gives an account an auth level
+    AuthAdd,
+    /// Removes the auth level of an account
+    AuthRemove,
+    /// Lists all accounts with an auth level
+    AuthList,
+    /// Shows the most recent entries of the audit log
+    Audit,
+}
+
+impl ServerRconCommand {
+    /// The auth level a client needs at least to execute this command,
+    /// if not overwritten by the config.
+    pub fn default_auth_level(&self) -> AuthLevel {
+        match self {
+            Self::BanId | Self::KickId | Self::Status => AuthLevel::Moderator,
+            Self::Audit => AuthLevel::Admin,
+            Self::ConfVariable
+            | Self::Exec
+            | Self::Load
+            | Self::AddMiscVote
+            | Self::RemoveMiscVote
+            | Self::RecordDemo
+            | Self::AuthAdd
+            | Self::AuthRemove
+            | Self::AuthList => AuthLevel::Admin,
+        }
+    }
 }
@@ -1,6 +1,7 @@
 use std::{
     collections::{BTreeMap, HashMap},
     fmt::Debug,
+    future::Future,
     net::IpAddr,
     num::NonZeroUsize,
     path::PathBuf,
@@ -67,14 +68,17 @@ use vanilla::{
 use x509_cert::der::Encode;
 
 use crate::{
+    audit::{command_succeeded, AuditActor, AuditEntry, AuditLog, AuditTarget},
     auto_map_votes::AutoMapVotes,
     client::{
         ClientSnapshotForDiff, ClientSnapshotStorage, Clients, ServerClient, ServerClientPlayer,
         ServerNetworkClient, ServerNetworkQueuedClient,
     },
+    econ::Econ,
     map_votes::{MapVotes, ServerMapVotes},
+    metrics::{MetricsServer, ServerMetrics},
     network_plugins::{accounts_only::AccountsOnly, cert_ban::CertBans},
-    rcon::{Rcon, ServerRconCommand},
+    rcon::{Rcon, RconActor, ServerRconCommand},
     server_game::{
         ClientAuth, ServerExtraVoteInfo, ServerGame, ServerVote, RESERVED_DDNET_NAMES,
         RESERVED_VANILLA_NAMES,
@@ -205,6 +209,10 @@ pub struct Server {
 
     last_network_stats_time: Duration,
 
+    /// Counts the written match results, so that results
+    /// finished in the same second get distinct file names.
+    match_results_written: u64,
+
     shared_info: Weak<LocalServerInfo>,
 
     // for server register
@@ -212,6 +220,12 @@ pub struct Server {
 
     // rcon
     rcon: Rcon,
+    econ: Option<Econ>,
+    audit: AuditLog,
+
+    // metrics
+    metrics: Arc<ServerMetrics>,
+    _metrics_server: Option<MetricsServer>,
 
     // server side demos
     demo_recorder: Option<DemoRecorder>,
@@ -493,6 +507,75 @@ impl Server {
                     cmd: ServerRconCommand::RemoveMiscVote,
                 },
             ),
+            (
+                "auth_add".try_into().unwrap(),
+                Command {
+                    rcon: RconEntry {
+                        args: vec![
+                            CommandArg {
+                                ty: CommandArgType::Number,
+                                user_ty: Some("ACCOUNT_ID".try_into().unwrap()),
+                            },
+                            CommandArg {
+                                ty: CommandArgType::Text,
+                                user_ty: Some("AUTH_LEVEL".try_into().unwrap()),
+                            },
+                        ],
+                        description: "Gives an account the auth level \
+                            moderator or admin and saves it to the auth.json."
+                            .try_into()
+                            .unwrap(),
+                        usage: "auth_add <account_id> <level>".try_into().unwrap(),
+                    },
+                    cmd: ServerRconCommand::AuthAdd,
+                },
+            ),
+            (
+                "auth_remove".try_into().unwrap(),
+                Command {
+                    rcon: RconEntry {
+                        args: vec![CommandArg {
+                            ty: CommandArgType::Number,
+                            user_ty: Some("ACCOUNT_ID".try_into().unwrap()),
+                        }],
+                        description: "Removes the auth level of an account \
+                            and saves it to the auth.json."
+                            .try_into()
+                            .unwrap(),
+                        usage: "auth_remove <account_id>".try_into().unwrap(),
+                    },
+                    cmd: ServerRconCommand::AuthRemove,
+                },
+            ),
+            (
+                "auth_list".try_into().unwrap(),
+                Command {
+                    rcon: RconEntry {
+                        args: Default::default(),
+                        description: "Lists all accounts that have an auth level."
+                            .try_into()
+                            .unwrap(),
+                        usage: "auth_list".try_into().unwrap(),
+                    },
+                    cmd: ServerRconCommand::AuthList,
+                },
+            ),
+            (
+                "audit".try_into().unwrap(),
+                Command {
+                    rcon: RconEntry {
+                        args: vec![CommandArg {
+                            ty: CommandArgType::Number,
+                            user_ty: Some("COUNT".try_into().unwrap()),
+                        }],
+                        description: "Shows the most recent entries of the audit log."
+                            .try_into()
+                            .unwrap(),
+                        usage: "audit <n>".try_into().unwrap(),
+                    },
+                    cmd: ServerRconCommand::Audit,
+                },
+            ),
         ];
 
         let mut rcon_vars: Vec<_> = Default::default();
@@ -858,6 +941,20 @@ impl Server {
         let config_mod = config_mod_task.get_storage().ok();
 
         let rcon = Rcon::new(&io);
+        let econ = (!shared_info.is_internal_server
+            && config_game.sv.econ.port != 0
+            && !config_game.sv.econ.tokens.is_empty())
+        .then(|| Econ::new(&config_game.sv.econ))
+        .transpose()?;
+        let metrics: Arc<ServerMetrics> = Default::default();
+        let metrics_server = (!shared_info.is_internal_server && config_game.sv.metrics.port != 0)
+            .then(|| MetricsServer::new(&config_game.sv.metrics, metrics.clone()))
+            .transpose()?;
+        let audit = AuditLog::new(
+            &io,
+            &config_game.sv.audit,
+            db.is_some().then(|| game_db.clone()),
+        );
 
         // write local server info if required.
         {
@@ -940,6 +1037,8 @@ impl Server {
 
             last_network_stats_time: sys.time_get(),
 
+            match_results_written: 0,
+
             sys,
 
             shared_info: Arc::downgrade(&shared_info),
@@ -949,6 +1048,12 @@ impl Server {
 
             // rcon
             rcon,
+            econ,
+            audit,
+
+            // metrics
+            metrics,
+            _metrics_server: metrics_server,
 
             // server side demo recorder
             demo_recorder: None,
@@ -1235,6 +1340,13 @@ impl Server {
     ) -> Option<PlayerId> {
         if let Some(client) = self.clients.clients.get_mut(con_id) {
             let player_id = self.game_server.player_join(con_id, &player_info);
+            if let Some(econ) = &self.econ {
+                econ.broadcast(&format!(
+                    "[join] player_id: {player_id}, name: {}, ip: {}",
+                    player_info.info.name.as_str(),
+                    client.ip
+                ));
+            }
             client.players.insert(
                 player_id,
                 ServerClientPlayer {
@@ -1299,6 +1411,12 @@ impl Server {
                     }
                     ClientToServerPlayerMessage::RemLocalPlayer => {
                         if player.players.len() > 1 && player.players.remove(player_id).is_some() {
+                            if let Some(econ) = &self.econ {
+                                econ.broadcast(&format!(
+                                    "[leave] player_id: {player_id}, reason: {:?}",
+                                    PlayerDropReason::Disconnect
+                                ));
+                            }
                             self.game_server
                                 .player_drop(player_id, PlayerDropReason::Disconnect);
                         }
@@ -1350,6 +1468,18 @@ impl Server {
                                             demo::DemoEvent::Chat(Box::new(msg.clone())),
                                         );
                                     }
+                                    if let Some(econ) = &self.econ {
+                                        econ.broadcast(&format!(
+                                            "[chat] {} ({player_id}){}: {}",
+                                            msg.sender.name.as_str(),
+                                            if matches!(channel, NetChatMsgPlayerChannel::Global) {
+                                                ""
+                                            } else {
+                                                " [team]"
+                                            },
+                                            msg.msg
+                                        ));
+                                    }
 
                                     let net_channel = NetworkInOrderChannel::Custom(3841); // This number reads as "chat".
                                     let pkt = ServerToClientMessage::Chat(MsgSvChatMsg { msg });
@@ -1606,6 +1736,7 @@ impl Server {
                             };
                             match vote {
                                 Either::Left((vote, extra_vote_info, no_voter)) => {
+                                    ServerMetrics::inc(&self.metrics.votes_started);
                                     self.game_server.cur_vote = Some(ServerVote {
                                         state: VoteState {
                                             vote,
@@ -1703,7 +1834,7 @@ impl Server {
                         let auth_level = player.auth.level;
                         if matches!(auth_level, AuthLevel::Moderator | AuthLevel::Admin) {
                             let res = self.handle_rcon_commands(
-                                Some(player_id),
+                                &RconActor::Player(*player_id),
                                 auth_level,
                                 &format!("{} {}", ident_text.as_str(), args.as_str()),
                                 false,
@@ -1753,29 +1884,47 @@ impl Server {
         }
     }
 
-    fn send_rcon_commands(&self, con_id: &NetworkConnectionId) {
+    /// Sends the rcon commands & variables the auth level allows to use,
+    /// clients without auth level receive an empty list.
+    fn send_rcon_commands(&self, con_id: &NetworkConnectionId, auth: AuthLevel) {
+        let overrides = &self.config_game.sv.rcon_auth_levels;
+        let allowed = |name: &str, cmd: Option<ServerRconCommand>| {
+            !matches!(auth, AuthLevel::None)
+                && auth >= Rcon::required_auth_level(overrides, name, cmd)
+        };
         // Server variables have highest prio
         let mut rcon_entries = RconEntries {
             vars: self
                 .rcon_chain
                 .var_list()
                 .iter()
+                .filter(|(name, cmd)| allowed(name, Some(cmd.cmd)))
                 .map(|(name, cmd)| (name.clone(), cmd.rcon.clone()))
                 .collect(),
             cmds: Default::default(),
         };
         // Mod rcon variables & commands next
-        rcon_entries
-            .vars
-            .extend(self.game_server.game.info.rcon_commands.vars.clone());
-        rcon_entries
-            .cmds
-            .extend(self.game_server.game.info.rcon_commands.cmds.clone());
+        let mod_cmds = &self.game_server.game.info.rcon_commands;
+        rcon_entries.vars.extend(
+            mod_cmds
+                .vars
+                .iter()
+                .filter(|(name, _)| allowed(name, None))
+                .map(|(name, entry)| (name.clone(), entry.clone())),
+        );
+        rcon_entries.cmds.extend(
+            mod_cmds
+                .cmds
+                .iter()
+                .filter(|(name, _)| allowed(name, None))
+                .map(|(name, entry)| (name.clone(), entry.clone())),
+        );
         // Then server commands
         rcon_entries.cmds.extend(
             self.rcon_chain
                 .cmd_list()
                 .iter()
+                .filter(|(name, cmd)| allowed(name, Some(cmd.cmd)))
                 .map(|(name, cmd)| (name.clone(), cmd.rcon.clone())),
         );
 
@@ -1788,10 +1937,120 @@ impl Server {
         );
     }
 
+    /// Creates the audit entry for a command, if the command
+    /// is a privileged action that should be recorded.
+    ///
+    /// Must be called before the command is executed,
+    /// so that the target player still exists.
+    fn audit_entry(
+        &self,
+        cmd: &parser::Command,
+        actor: &RconActor,
+        auth: AuthLevel,
+    ) -> Option<AuditEntry> {
+        let chain_cmd = self.rcon_chain.by_ident(&cmd.ident).map(|c| c.cmd);
+        if matches!(
+            chain_cmd,
+            Some(ServerRconCommand::Status | ServerRconCommand::AuthList | ServerRconCommand::Audit)
+        ) {
+            return None;
+        }
+
+        let account_server_public_keys = self
+            .account_server_certs_downloader
+            .as_ref()
+            .map(|c| c.public_keys())
+            .unwrap_or_default();
+        let player_unique_id = |player_id: &PlayerId| {
+            self.game_server.players.get(player_id).and_then(|player| {
+                self.clients
+                    .clients
+                    .get(&player.network_id)
+                    .map(|client| {
+                        Self::user_id_to_player_unique_id(&Self::user_id(
+                            &account_server_public_keys,
+                            &client.auth,
+                        ))
+                    })
+            })
+        };
+        let player_name = |player_id: &PlayerId| {
+            self.game_server
+                .cached_character_infos
+                .get(player_id)
+                .map(|c| c.info.name.to_string())
+        };
+
+        let actor = match actor {
+            RconActor::Server => AuditActor::Server,
+            RconActor::Player(player_id) => AuditActor::Player {
+                player_id: *player_id,
+                name: player_name(player_id).unwrap_or_default(),
+                unique_identifier: player_unique_id(player_id),
+            },
+            RconActor::Econ { addr } => AuditActor::Econ {
+                addr: addr.to_string(),
+            },
+        };
+        let target = match chain_cmd {
+            Some(ServerRconCommand::BanId | ServerRconCommand::KickId) => {
+                match cmd.args.first().map(|(arg, _)| arg) {
+                    Some(Syn::Number(num)) => num
+                        .parse::<GameEntityId>()
+                        .ok()
+                        .map(PlayerId::from)
+                        .map(|player_id| AuditTarget {
+                            player_id,
+                            name: player_name(&player_id),
+                            unique_identifier: player_unique_id(&player_id),
+                        }),
+                    _ => None,
+                }
+            }
+            _ => None,
+        };
+
+        Some(AuditEntry {
+            timestamp: chrono::Utc::now(),
+            actor,
+            auth_level: auth,
+            cmd: cmd.ident.clone(),
+            args: parser::format_args(&cmd.args),
+            target,
+            success: false,
+        })
+    }
+
     fn handle_cmd_full(
         &mut self,
         cmd: parser::Command,
-        player_id: Option<&PlayerId>,
+        actor: &RconActor,
+        auth: AuthLevel,
+        responses: &mut Vec<Result<String, String>>,
+        skipped_lines: &mut Vec<String>,
+        ignore_mod_cmds: bool,
+    ) -> anyhow::Result<String> {
+        let audit_entry = self.audit_entry(&cmd, actor, auth);
+        let responses_start = responses.len();
+        let res = self.exec_cmd_full(
+            cmd,
+            actor,
+            auth,
+            responses,
+            skipped_lines,
+            ignore_mod_cmds,
+        );
+        if let Some(mut entry) = audit_entry {
+            entry.success = command_succeeded(&res, &responses[responses_start..]);
+            self.audit.add(entry);
+        }
+        res
+    }
+
+    fn exec_cmd_full(
+        &mut self,
+        cmd: parser::Command,
+        actor: &RconActor,
         auth: AuthLevel,
         responses: &mut Vec<Result<String, String>>,
         skipped_lines: &mut Vec<String>,
@@ -1812,6 +2071,12 @@ impl Server {
                 .vars
                 .contains_key(&cmd.ident)
         {
+            Self::check_auth_level(
+                &self.config_game,
+                auth,
+                &cmd.ident,
+                self.rcon_chain.by_ident(&cmd.ident).map(|c| c.cmd),
+            )?;
             // This _if_ is purposely after the ident check,
             // because the server commands with same ident
             // should be ignored too.
@@ -1820,7 +2085,7 @@ impl Server {
                     self.game_server
                         .game
                         .rcon_command(
-                            player_id.copied(),
+                            actor.player_id().copied(),
                             ExecRconInput {
                                 raw: NetworkString::new_lossy(cmd.to_string()),
                                 auth_level: auth,
@@ -1838,6 +2103,7 @@ impl Server {
             let Some(chain_cmd) = self.rcon_chain.by_ident(&cmd.ident) else {
                 return Err(anyhow!("Command {} not found", cmd.ident));
             };
+            Self::check_auth_level(&self.config_game, auth, &cmd.ident, Some(chain_cmd.cmd))?;
 
             fn ban_or_kick(
                 cmd: &parser::Command,
@@ -2017,6 +2283,70 @@ impl Server {
                         .for_each(|c| c.loaded_misc_votes = false);
                     Ok(res)
                 }
+                ServerRconCommand::AuthAdd => {
+                    let Syn::Number(account_id) = &cmd.args[0].0 else {
+                        panic!("Command parser returned a non requested command arg");
+                    };
+                    let Syn::Text(level) = &cmd.args[1].0 else {
+                        panic!("Command parser returned a non requested command arg");
+                    };
+                    let account_id: AccountId = account_id.parse()?;
+                    let level = Rcon::parse_auth_level(level)?;
+                    let prev = self.rcon.add_auth(&self.io, account_id, level)?;
+                    self.update_clients_auth_level(account_id, level);
+                    Ok(match prev {
+                        Some(prev) => format!(
+                            "Changed auth level of account {account_id} from {prev:?} to {level:?}"
+                        ),
+                        None => format!("Added auth level {level:?} for account {account_id}"),
+                    })
+                }
+                ServerRconCommand::AuthRemove => {
+                    let Syn::Number(account_id) = &cmd.args[0].0 else {
+                        panic!("Command parser returned a non requested command arg");
+                    };
+                    let account_id: AccountId = account_id.parse()?;
+                    match self.rcon.remove_auth(&self.io, account_id)? {
+                        Some(prev) => {
+                            self.update_clients_auth_level(account_id, AuthLevel::None);
+                            Ok(format!(
+                                "Removed auth level {prev:?} from account {account_id}"
+                            ))
+                        }
+                        None => Err(anyhow!("Account {account_id} has no auth level")),
+                    }
+                }
+                ServerRconCommand::AuthList => {
+                    let mut auths: Vec<_> = self.rcon.auths.iter().collect();
+                    auths.sort_by_key(|(account_id, _)| **account_id);
+                    let res: Vec<String> = auths
+                        .into_iter()
+                        .map(|(account_id, level)| {
+                            format!("account_id: {account_id}, level: {level:?}")
+                        })
+                        .collect();
+                    if res.is_empty() {
+                        Ok("No account has an auth level.".to_string())
+                    } else {
+                        Ok(res.join("\n"))
+                    }
+                }
+                ServerRconCommand::Audit => {
+                    let Syn::Number(count) = &cmd.args[0].0 else {
+                        panic!("Command parser returned a non requested command arg");
+                    };
+                    let count: usize = count.parse()?;
+                    let res: Vec<String> = self
+                        .audit
+                        .recent(count)
+                        .map(|entry| entry.to_string())
+                        .collect();
+                    if res.is_empty() {
+                        Ok("No audit entries.".to_string())
+                    } else {
+                        Ok(res.join("\n"))
+                    }
+                }
                 ServerRconCommand::RecordDemo => {
                     let had_demo_recorder = self.demo_recorder.is_some();
                     self.demo_recorder = Some(DemoRecorder::new(
@@ -2064,10 +2394,73 @@ impl Server {
         }
     }
 
+    /// Executes all command lines received from the external console
+    /// and sends the results back.
+    fn handle_econ_cmds(&mut self) {
+        let Some(cmds) = self.econ.as_ref().map(|econ| econ.take_cmds()) else {
+            return;
+        };
+        for cmd in cmds {
+            log::info!(target: "econ", "executing: {}", cmd.line);
+            let results = self.handle_rcon_commands(
+                &RconActor::Econ { addr: cmd.addr },
+                cmd.auth_level,
+                &cmd.line,
+                false,
+            );
+            let lines: Vec<String> = results
+                .into_iter()
+                .filter_map(|res| match res {
+                    Ok(msg) => (!msg.is_empty()).then(|| msg.to_string()),
+                    Err(err) => Some(format!("error: {}", err.as_str())),
+                })
+                .collect();
+            if let Some(econ) = &self.econ {
+                econ.send_to(cmd.con_id, lines.iter().flat_map(|l| l.lines()));
+            }
+        }
+    }
+
+    fn check_auth_level(
+        config_game: &ConfigGame,
+        auth: AuthLevel,
+        ident: &str,
+        cmd: Option<ServerRconCommand>,
+    ) -> anyhow::Result<()> {
+        let required = Rcon::required_auth_level(&config_game.sv.rcon_auth_levels, ident, cmd);
+        anyhow::ensure!(
+            auth >= required,
+            "The command {ident} requires at least the auth level {required:?}."
+        );
+        Ok(())
+    }
+
+    /// Updates the auth level of all connected clients
+    /// that are logged in with the given account.
+    fn update_clients_auth_level(&mut self, account_id: AccountId, level: AuthLevel) {
+        let account_server_public_keys = self
+            .account_server_certs_downloader
+            .as_ref()
+            .map(|c| c.public_keys())
+            .unwrap_or_default();
+        let mut changed: Vec<NetworkConnectionId> = Default::default();
+        for (con_id, client) in self.clients.clients.iter_mut() {
+            let user_id = Self::user_id(&account_server_public_keys, &client.auth);
+            if user_id.account_id == Some(account_id) && client.auth.level != level {
+                changed.push(*con_id);
+                client.auth.level = level;
+            }
+        }
+        // also shrinks the command list of demoted clients
+        for con_id in changed {
+            self.send_rcon_commands(&con_id, level);
+        }
+    }
+
     fn handle_cmd(
         &mut self,
         cmd: parser::CommandType,
-        player_id: Option<&PlayerId>,
+        actor: &RconActor,
         auth: AuthLevel,
         responses: &mut Vec<Result<String, String>>,
         skipped_lines: &mut Vec<String>,
@@ -2076,7 +2469,7 @@ impl Server {
         match cmd {
             CommandType::Full(cmd) => self.handle_cmd_full(
                 cmd,
-                player_id,
+                actor,
                 auth,
                 responses,
                 skipped_lines,
@@ -2101,6 +2494,12 @@ impl Server {
                         .vars
                         .contains_key(&cmd.ident)
                 {
+                    Self::check_auth_level(
+                        &self.config_game,
+                        auth,
+                        &cmd.ident,
+                        self.rcon_chain.by_ident(&cmd.ident).map(|c| c.cmd),
+                    )?;
                     // This _if_ is purposely after the ident check,
                     // because the server commands with same ident
                     // should be ignored too.
@@ -2109,7 +2508,7 @@ impl Server {
                             self.game_server
                                 .game
                                 .rcon_command(
-                                    player_id.copied(),
+                                    actor.player_id().copied(),
                                     ExecRconInput {
                                         raw: NetworkString::new_lossy(cmd.to_string()),
                                         auth_level: auth,
@@ -2129,6 +2528,12 @@ impl Server {
                     };
 
                     if let ServerRconCommand::ConfVariable = chain_cmd.cmd {
+                        Self::check_auth_level(
+                            &self.config_game,
+                            auth,
+                            &cmd.ident,
+                            Some(chain_cmd.cmd),
+                        )?;
                         handle_config_variable_cmd(cmd, &mut self.config_game)
                             .map(|msg| format!("Current value for {}: {}", cmd.cmd_text, msg))
                     } else {
@@ -2139,14 +2544,32 @@ impl Server {
         }
     }
 
+    /// Records the duration and failures of a database request in the metrics.
+    fn timed_db_request(
+        metrics: &Arc<ServerMetrics>,
+        req: impl Future<Output = anyhow::Result<GameServerDb>> + Send + 'static,
+    ) -> impl Future<Output = anyhow::Result<GameServerDb>> + Send + 'static {
+        let metrics = metrics.clone();
+        async move {
+            let start = std::time::Instant::now();
+            let res = req.await;
+            metrics.db_query_duration.observe(start.elapsed());
+            if res.is_err() {
+                ServerMetrics::inc(&metrics.db_query_errors);
+            }
+            res
+        }
+    }
+
     /// Returns the responses of the executed commands
     fn handle_rcon_commands(
         &mut self,
-        player_id: Option<&PlayerId>,
+        actor: &RconActor,
         auth: AuthLevel,
         line: &str,
         ignore_mod_cmds: bool,
     ) -> Vec<Result<NetworkString<65536>, NetworkString<65536>>> {
+        ServerMetrics::inc(&self.metrics.rcon_commands);
         let parser_entries = self.game_server.parser.get_or_insert_with(|| {
             self.rcon_chain
                 .cmd_list()
@@ -2189,7 +2612,7 @@ impl Server {
         for cmd in cmds {
             match self.handle_cmd(
                 cmd,
-                player_id,
+                actor,
                 auth,
                 &mut responses,
                 &mut skipped_lines,
@@ -2210,7 +2633,7 @@ impl Server {
         }
         if !skipped_lines.is_empty() {
             for line in skipped_lines {
-                res.append(&mut self.handle_rcon_commands(player_id, auth, &line, ignore_mod_cmds));
+                res.append(&mut self.handle_rcon_commands(actor, auth, &line, ignore_mod_cmds));
             }
         }
         res
@@ -2245,6 +2668,7 @@ impl Server {
                             ready_info.rcon_secret.as_ref(),
                             &unique_identifier,
                         );
+                        let auth_level = client.auth.level;
 
                         let initial_network_stats = client.network_stats;
 
@@ -2294,24 +2718,30 @@ impl Server {
                             }
                         }
                         if send_rcon {
-                            self.send_rcon_commands(con_id);
+                            self.send_rcon_commands(con_id, auth_level);
                         }
 
                         if let Some((accounts, db)) = self.accounts.as_ref().zip(self.db.as_ref()) {
                             if let Some(pool) = db.pools.get(&accounts.kind) {
                                 let shared = accounts.shared.clone();
                                 let pool = pool.clone();
-                                self.db_requests.push(self.io.rt.spawn(async move {
-                                    let new_account_was_created =
-                                        ddnet_account_game_server::auto_login::auto_login(
-                                            shared, &pool, &user_id,
-                                        )
-                                        .await?;
-                                    Ok(GameServerDb::Account(GameServerDbAccount::AutoLogin {
-                                        user_id,
-                                        new_account_was_created,
-                                    }))
-                                }));
+                                self.db_requests
+                                    .push(self.io.rt.spawn(Self::timed_db_request(
+                                        &self.metrics,
+                                        async move {
+                                            let new_account_was_created =
+                                                ddnet_account_game_server::auto_login::auto_login(
+                                                    shared, &pool, &user_id,
+                                                )
+                                                .await?;
+                                            Ok(GameServerDb::Account(
+                                                GameServerDbAccount::AutoLogin {
+                                                    user_id,
+                                                    new_account_was_created,
+                                                },
+                                            ))
+                                        },
+                                    )));
                             }
                         }
 
@@ -2569,25 +2999,30 @@ impl Server {
                                 let shared = accounts.shared.clone();
                                 let pool = pool.clone();
                                 let con_id = *con_id;
-                                self.db_requests.push(self.io.rt.spawn(async move {
-                                    let rename_res = ddnet_account_game_server::rename::rename(
-                                        shared,
-                                        &pool,
-                                        &user_id,
-                                        new_name.as_str(),
-                                    )
-                                    .await
-                                    .map(|_| ());
-                                    Ok(GameServerDb::Account(GameServerDbAccount::Rename {
-                                        con_id,
-                                        rename_result: rename_res
-                                            .map_err(|err| {
-                                                NetworkString::new_lossy(err.to_string())
-                                            })
-                                            .map(|_| new_name),
-                                        account_id: user_id.account_id,
-                                    }))
-                                }));
+                                self.db_requests
+                                    .push(self.io.rt.spawn(Self::timed_db_request(
+                                        &self.metrics,
+                                        async move {
+                                            let rename_res =
+                                                ddnet_account_game_server::rename::rename(
+                                                    shared,
+                                                    &pool,
+                                                    &user_id,
+                                                    new_name.as_str(),
+                                                )
+                                                .await
+                                                .map(|_| ());
+                                            Ok(GameServerDb::Account(GameServerDbAccount::Rename {
+                                                con_id,
+                                                rename_result: rename_res
+                                                    .map_err(|err| {
+                                                        NetworkString::new_lossy(err.to_string())
+                                                    })
+                                                    .map(|_| new_name),
+                                                account_id: user_id.account_id,
+                                            }))
+                                        },
+                                    )));
                             }
                         } else {
                             self.network.send_unordered_to(
@@ -2632,26 +3067,34 @@ impl Server {
                         {
                             let account_info = account_info.clone();
                             let con_id = *con_id;
-                            self.db_requests.push(self.io.rt.spawn(async move {
-                                let details_res = account_info.fetch(account_id).await;
-                                Ok(GameServerDb::Account(GameServerDbAccount::Info {
-                                    con_id,
-                                    account_details: details_res
-                                        .map_err(|err| NetworkString::new_lossy(err.to_string()))
-                                        .and_then(|res| {
-                                            res.name
-                                                .as_str()
-                                                .try_into()
-                                                .map(|name| account_info::AccountInfo {
-                                                    name,
-                                                    creation_date: res.create_time,
-                                                })
+                            self.db_requests
+                                .push(self.io.rt.spawn(Self::timed_db_request(
+                                    &self.metrics,
+                                    async move {
+                                        let details_res = account_info.fetch(account_id).await;
+                                        Ok(GameServerDb::Account(GameServerDbAccount::Info {
+                                            con_id,
+                                            account_details: details_res
                                                 .map_err(|err| {
                                                     NetworkString::new_lossy(err.to_string())
                                                 })
-                                        }),
-                                }))
-                            }));
+                                                .and_then(|res| {
+                                                    res.name
+                                                        .as_str()
+                                                        .try_into()
+                                                        .map(|name| account_info::AccountInfo {
+                                                            name,
+                                                            creation_date: res.create_time,
+                                                        })
+                                                        .map_err(|err| {
+                                                            NetworkString::new_lossy(
+                                                                err.to_string(),
+                                                            )
+                                                        })
+                                                }),
+                                        }))
+                                    },
+                                )));
                         } else {
                             self.network.send_unordered_to(
                                 &ServerToClientMessage::AccountDetails(Err(
@@ -3045,8 +3488,7 @@ impl Server {
                                     self.client_disconnect(&con_id, &reason.to_string())
                                 {
                                     for player_id in players.keys() {
-                                        self.game_server.player_drop(
-                                            player_id,
+                                        let drop_reason =
                                             if let Some(drop_reason) = drop_reason.clone() {
                                                 drop_reason
                                             } else if matches!(
@@ -3057,8 +3499,14 @@ impl Server {
                                                 PlayerDropReason::Disconnect
                                             } else {
                                                 PlayerDropReason::Timeout
-                                            },
-                                        );
+                                            };
+                                        if let Some(econ) = &self.econ {
+                                            econ.broadcast(&format!(
+                                                "[leave] player_id: {player_id}, reason: {:?}",
+                                                drop_reason
+                                            ));
+                                        }
+                                        self.game_server.player_drop(player_id, drop_reason);
                                     }
                                 }
                             }
@@ -3086,11 +3534,27 @@ impl Server {
                                 {
                                     self.last_network_stats_time = cur_time;
                                     let mut player_stats = self.player_network_stats_pool.new();
+                                    let (mut ping_sum, mut ping_max) =
+                                        (Duration::ZERO, Duration::ZERO);
+                                    let (mut loss_sum, mut loss_max) = (0.0, 0.0_f32);
                                     for client in self.clients.clients.values() {
                                         for player_id in client.players.keys() {
                                             player_stats.insert(*player_id, client.network_stats);
                                         }
+                                        ping_sum += client.network_stats.ping;
+                                        ping_max = ping_max.max(client.network_stats.ping);
+                                        loss_sum += client.network_stats.packet_loss;
+                                        loss_max = loss_max.max(client.network_stats.packet_loss);
                                     }
+                                    let client_count = self.clients.clients.len().max(1);
+                                    self.metrics
+                                        .ping_avg
+                                        .set(ping_sum.as_secs_f64() / client_count as f64);
+                                    self.metrics.ping_max.set(ping_max.as_secs_f64());
+                                    self.metrics
+                                        .packet_loss_avg
+                                        .set((loss_sum / client_count as f32) as f64);
+                                    self.metrics.packet_loss_max.set(loss_max as f64);
                                     self.game_server.game.network_stats(player_stats);
                                 }
                             }
@@ -3108,6 +3572,8 @@ impl Server {
                     .store(false, std::sync::atomic::Ordering::Relaxed);
             }
 
+            self.handle_econ_cmds();
+
             let ticks_in_a_second = self.game_server.game.game_tick_speed();
 
             // get time before checking ticks
@@ -3125,6 +3591,7 @@ impl Server {
                     let vote = self.game_server.cur_vote.take().unwrap();
                     // fake democracy
                     if vote.state.yes_votes > vote.state.no_votes {
+                        ServerMetrics::inc(&self.metrics.votes_passed);
                         let vote_result =
                             match vote.state.vote {
                                 VoteType::Map { key, .. } => {
@@ -3204,6 +3671,8 @@ impl Server {
             }
 
             while is_next_tick(cur_time, &mut self.last_tick_time, ticks_in_a_second) {
+                let tick_start = self.sys.time_get();
+
                 // apply all queued inputs
                 if let Some(mut inputs) = self
                     .game_server
@@ -3241,6 +3710,21 @@ impl Server {
                                 );
                             }
                         }
+                        TickEvent::MatchResult { json } => {
+                            let fs = self.io.fs.clone();
+                            let path = format!(
+                                "match_results/{}_{}_{}.json",
+                                chrono::Utc::now().format("%Y-%m-%d_%H-%M-%S"),
+                                self.game_server.map.name.as_str().replace('/', "_"),
+                                self.match_results_written
+                            );
+                            self.match_results_written += 1;
+                            self.io.rt.spawn_without_lifetime(async move {
+                                fs.create_dir("match_results".as_ref()).await?;
+                                fs.write_file(path.as_ref(), json.into_bytes()).await?;
+                                Ok(())
+                            });
+                        }
                         TickEvent::Ban {
                             player_id,
                             until,
@@ -3365,6 +3849,12 @@ impl Server {
                                 (snap, None, None)
                             };
 
+                        ServerMetrics::inc(&self.metrics.snapshots_sent);
+                        self.metrics.snapshot_bytes.fetch_add(
+                            snap_diff.len() as u64,
+                            std::sync::atomic::Ordering::Relaxed,
+                        );
+
                         // quickly rewrite the input ack's logic overhead
                         let cur_time = self.sys.time_get();
                         client.inputs_to_ack.iter_mut().for_each(|inp| {
@@ -3413,8 +3903,27 @@ impl Server {
                 }
 
                 self.game_server.game.clear_events();
+
+                ServerMetrics::inc(&self.metrics.ticks);
+                self.metrics
+                    .tick_duration
+                    .observe(self.sys.time_get().saturating_sub(tick_start));
             }
 
+            self.metrics.players.store(
+                self.game_server.players.len() as u64,
+                std::sync::atomic::Ordering::Relaxed,
+            );
+            self.metrics.clients.store(
+                self.clients.clients.len() as u64,
+                std::sync::atomic::Ordering::Relaxed,
+            );
+            self.metrics.connecting.store(
+                (self.clients.network_clients.len() + self.clients.network_queued_clients.len())
+                    as u64,
+                std::sync::atomic::Ordering::Relaxed,
+            );
+
             self.game_server.cached_character_infos =
                 self.game_server.game.collect_characters_info();
 
@@ -3614,6 +4123,9 @@ impl Server {
     }
 
     fn load_map(&mut self, map: &NetworkReducedAsciiString<MAX_MAP_NAME_LEN>) {
+        if let Some(econ) = &self.econ {
+            econ.broadcast(&format!("[server] changing map to {}", map.as_str()));
+        }
         self.config_game.sv.map = map.to_string();
         if let Err(err) = self.load_impl(None, map) {
             log::error!("Fatal error during map load: {err}");
@@ -3726,7 +4238,7 @@ pub fn ddnet_server_main<const IS_INTERNAL_SERVER: bool>(
 
     // Handle remaining args after the server started.
     for line in skipped_lines {
-        for res in server.handle_rcon_commands(None, AuthLevel::Admin, &line, true) {
+        for res in server.handle_rcon_commands(&RconActor::Server, AuthLevel::Admin, &line, true) {
             match res {
                 Ok(res) => {
                     if !res.is_empty() {
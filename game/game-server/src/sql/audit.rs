@@ -0,0 +1,93 @@
+use std::{collections::HashMap, sync::Arc};
+
+use anyhow::anyhow;
+use game_database::{
+    statement::{Statement, StatementBuilder},
+    traits::{DbInterface, DbKind, DbKindExtra, SqlText},
+    types::UnixUtcTimestamp,
+    StatementArgs,
+};
+
+#[derive(Debug, StatementArgs)]
+pub struct StatementArg {
+    pub create_time: UnixUtcTimestamp,
+    pub actor: String,
+    pub auth_level: String,
+    pub cmd: String,
+    pub args: String,
+    pub target: String,
+    pub success: bool,
+}
+
+type StatementResult = ();
+
+/// Writes audit log entries to the main database.
+#[derive(Clone)]
+pub struct AuditInsert(Arc<Statement<StatementArg, StatementResult>>);
+
+impl AuditInsert {
+    fn main_kind(db: &Arc<dyn DbInterface>) -> anyhow::Result<DbKind> {
+        let kinds = db.kinds();
+        [
+            DbKind::MySql(DbKindExtra::Main),
+            DbKind::Sqlite(DbKindExtra::Main),
+        ]
+        .into_iter()
+        .find(|kind| kinds.contains(kind))
+        .ok_or_else(|| anyhow!("No main database configured"))
+    }
+
+    pub async fn setup(db: Arc<dyn DbInterface>) -> anyhow::Result<()> {
+        let kind = Self::main_kind(&db)?;
+        let stmts: HashMap<DbKind, Vec<SqlText>> = [(
+            kind,
+            vec![if matches!(kind, DbKind::MySql(_)) {
+                include_str!("mysql/audit/audit.sql").into()
+            } else {
+                include_str!("sqlite/audit/audit.sql").into()
+            }],
+        )]
+        .into_iter()
+        .collect();
+
+        db.setup(
+            "game-server-audit",
+            vec![(1, stmts)].into_iter().collect(),
+        )
+        .await
+    }
+
+    pub async fn new(db: Arc<dyn DbInterface>) -> anyhow::Result<Self> {
+        Self::setup(db.clone()).await?;
+
+        let kind = Self::main_kind(&db)?;
+        let builder = StatementBuilder::<_, StatementArg, StatementResult>::new(
+            kind,
+            if matches!(kind, DbKind::MySql(_)) {
+                include_str!("mysql/audit/insert.sql")
+            } else {
+                include_str!("sqlite/audit/insert.sql")
+            },
+            |arg| {
+                vec![
+                    arg.create_time,
+                    arg.actor,
+                    arg.auth_level,
+                    arg.cmd,
+                    arg.args,
+                    arg.target,
+                    arg.success,
+                ]
+            },
+        );
+
+        let stm = Arc::new(Statement::new(db, builder).await?);
+
+        Ok(Self(stm))
+    }
+
+    pub async fn execute(&self, arg: StatementArg) -> anyhow::Result<()> {
+        self.0.execute(arg).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,11 @@
+CREATE TABLE server_audit_log (
+    id BIGINT NOT NULL AUTO_INCREMENT,
+    create_time DATETIME NOT NULL,
+    actor VARCHAR(256) NOT NULL,
+    auth_level VARCHAR(32) NOT NULL,
+    cmd VARCHAR(256) NOT NULL,
+    args TEXT NOT NULL,
+    target VARCHAR(256) NOT NULL,
+    success BOOLEAN NOT NULL,
+    PRIMARY KEY(id)
+);
@@ -0,0 +1,10 @@
+CREATE TABLE server_audit_log (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    create_time DATETIME NOT NULL,
+    actor VARCHAR(256) NOT NULL,
+    auth_level VARCHAR(32) NOT NULL,
+    cmd VARCHAR(256) NOT NULL,
+    args TEXT NOT NULL,
+    target VARCHAR(256) NOT NULL,
+    success BOOLEAN NOT NULL
+);
@@ -0,0 +1,12 @@
+INSERT INTO
+    server_audit_log (
+        create_time,
+        actor,
+        auth_level,
+        cmd,
+        args,
+        target,
+        success
+    )
+VALUES
+    (?, ?, ?, ?, ?, ?, ?);
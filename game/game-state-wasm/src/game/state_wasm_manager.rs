@@ -9,9 +9,9 @@ use base_io::io::Io;
 use base_io::runtime::IoRuntime;
 use base_io_traits::fs_traits::FileSystemInterface;
 use cache::Cache;
+use ddrace::ddrace::ddrace::Ddrace;
 use game_database::traits::DbInterface;
 use game_interface::account_info::MAX_ACCOUNT_NAME_LEN;
-//use ddnet::Ddnet;
 use game_interface::client_commands::ClientCommand;
 use game_interface::events::{EventClientInfo, GameEvents};
 use game_interface::ghosts::GhostResult;
@@ -60,7 +60,7 @@ pub enum GameStateMod {
 
 enum GameStateWrapper {
     Native(GameState),
-    //Ddnet(Ddnet),
+    Ddnet(Ddrace),
     Wasm(StateWasm),
 }
 
@@ -68,7 +68,7 @@ impl GameStateWrapper {
     pub fn as_ref(&self) -> &dyn GameStateInterface {
         match self {
             Self::Native(state) => state,
-            //GameStateWrapper::Ddnet(state) => state,
+            Self::Ddnet(state) => state,
             Self::Wasm(state) => state,
         }
     }
@@ -76,7 +76,7 @@ impl GameStateWrapper {
     pub fn as_mut(&mut self) -> &mut dyn GameStateInterface {
         match self {
             Self::Native(state) => state,
-            //GameStateWrapper::Ddnet(state) => state,
+            Self::Ddnet(state) => state,
             Self::Wasm(state) => state,
         }
     }
@@ -124,11 +124,9 @@ impl GameStateWasmManager {
                 (GameStateWrapper::Native(state), info)
             }
             GameStateMod::Ddnet => {
-                // TODO: let (state, info) = <Ddnet as GameStateCreate>::new(map, options);
-                // (GameStateWrapper::Ddnet(state), info)
-                let (state, info) = GameState::new(map, map_name, options, io.rt.clone(), db)
+                let (state, info) = Ddrace::new(map, map_name, options, io.rt.clone(), db)
                     .map_err(|err| anyhow!(err))?;
-                (GameStateWrapper::Native(state), info)
+                (GameStateWrapper::Ddnet(state), info)
             }
             GameStateMod::Wasm { file: wasm_module } => {
                 let mut info = GameStateStaticInfo {
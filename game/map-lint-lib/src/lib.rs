@@ -0,0 +1,135 @@
+pub mod lint;
+
+#[cfg(test)]
+mod test {
+    use game_base::mapdef_06::{DdraceTileNum, EEntityTiles};
+    use map::{
+        map::groups::{
+            layers::{
+                physics::{MapLayerPhysics, MapLayerTilePhysicsBase, MapLayerTilePhysicsTele},
+                tiles::{TeleTile, TileBase},
+            },
+            MapGroupPhysics, MapGroupPhysicsAttr,
+        },
+        types::NonZeroU16MinusOne,
+    };
+
+    use crate::lint::{lint_physics, LintIssueKind, LintSeverity, LintTilePos};
+
+    const W: u16 = 8;
+    const H: u16 = 3;
+
+    fn tile(index: u8) -> TileBase {
+        TileBase {
+            index,
+            ..Default::default()
+        }
+    }
+
+    /// A small room, surrounded by solid tiles.
+    fn game_layer() -> Vec<TileBase> {
+        let mut tiles = vec![tile(DdraceTileNum::Solid as u8); W as usize * H as usize];
+        for x in 1..W as usize - 1 {
+            tiles[W as usize + x] = tile(DdraceTileNum::Air as u8);
+        }
+        tiles
+    }
+
+    fn physics(layers: Vec<MapLayerPhysics>) -> MapGroupPhysics {
+        MapGroupPhysics {
+            attr: MapGroupPhysicsAttr {
+                width: NonZeroU16MinusOne::new(W).unwrap(),
+                height: NonZeroU16MinusOne::new(H).unwrap(),
+            },
+            layers,
+        }
+    }
+
+    fn kinds(physics: &MapGroupPhysics) -> Vec<LintIssueKind> {
+        lint_physics(physics)
+            .into_iter()
+            .map(|issue| issue.kind)
+            .collect()
+    }
+
+    #[test]
+    fn missing_spawn_and_race_tiles() {
+        let physics = physics(vec![MapLayerPhysics::Game(MapLayerTilePhysicsBase {
+            tiles: game_layer(),
+        })]);
+        let issues = lint_physics(&physics);
+        assert!(issues
+            .iter()
+            .any(|i| i.kind == LintIssueKind::MissingSpawn && i.severity == LintSeverity::Error));
+        assert!(issues
+            .iter()
+            .any(|i| i.kind == LintIssueKind::NoRaceTiles && i.severity == LintSeverity::Info));
+    }
+
+    #[test]
+    fn unreachable_finish() {
+        let mut tiles = game_layer();
+        let w = W as usize;
+        tiles[w + 1] = tile(EEntityTiles::Spawn as u8);
+        tiles[w + 2] = tile(DdraceTileNum::Start as u8);
+        tiles[w + 4] = tile(DdraceTileNum::Solid as u8);
+        tiles[w + 6] = tile(DdraceTileNum::Finish as u8);
+        let physics = physics(vec![MapLayerPhysics::Game(MapLayerTilePhysicsBase {
+            tiles,
+        })]);
+        assert_eq!(
+            kinds(&physics),
+            vec![LintIssueKind::UnreachableFinish {
+                pos: LintTilePos { x: 6, y: 1 }
+            }]
+        );
+    }
+
+    #[test]
+    fn teleporter_makes_finish_reachable() {
+        let mut tiles = game_layer();
+        let w = W as usize;
+        tiles[w + 1] = tile(EEntityTiles::Spawn as u8);
+        tiles[w + 2] = tile(DdraceTileNum::Start as u8);
+        tiles[w + 4] = tile(DdraceTileNum::Solid as u8);
+        tiles[w + 6] = tile(DdraceTileNum::Finish as u8);
+        let mut tele = vec![TeleTile::default(); W as usize * H as usize];
+        tele[w + 3] = TeleTile {
+            base: tile(DdraceTileNum::TeleIn as u8),
+            number: 1,
+        };
+        tele[w + 5] = TeleTile {
+            base: tile(DdraceTileNum::TeleOut as u8),
+            number: 1,
+        };
+        // a second teleporter without exit
+        tele[w + 2] = TeleTile {
+            base: tile(DdraceTileNum::TeleIn as u8),
+            number: 2,
+        };
+        let physics = physics(vec![
+            MapLayerPhysics::Game(MapLayerTilePhysicsBase { tiles }),
+            MapLayerPhysics::Tele(MapLayerTilePhysicsTele {
+                base: MapLayerTilePhysicsBase { tiles: tele },
+                tele_names: Default::default(),
+            }),
+        ]);
+        assert_eq!(
+            kinds(&physics),
+            vec![LintIssueKind::TeleWithoutExit {
+                number: 2,
+                pos: LintTilePos { x: 2, y: 1 }
+            }]
+        );
+    }
+
+    #[test]
+    fn json_output() {
+        let physics = physics(vec![MapLayerPhysics::Game(MapLayerTilePhysicsBase {
+            tiles: game_layer(),
+        })]);
+        let json = serde_json::to_value(lint_physics(&physics)).unwrap();
+        assert_eq!(json[0]["severity"], "error");
+        assert_eq!(json[0]["kind"], "missing_spawn");
+    }
+}
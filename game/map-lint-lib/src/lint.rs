@@ -0,0 +1,675 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, VecDeque},
+    fmt::Display,
+};
+
+use game_base::mapdef_06::{DdraceTileNum, EEntityTiles, TILE_SWITCHTIMEDOPEN};
+use map::map::{
+    animations::Animations,
+    groups::{
+        layers::{
+            design::MapLayer,
+            physics::MapLayerPhysics,
+            tiles::{SwitchTile, TeleTile, Tile, TuneTile},
+        },
+        MapGroup, MapGroupPhysics, MapGroups,
+    },
+    resources::Resources,
+    Map,
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LintSeverity {
+    /// Not necessarily a problem, e.g. a map without race tiles.
+    Info,
+    /// Most likely a problem, but the map still works.
+    Warning,
+    /// The map is broken for players.
+    Error,
+}
+
+impl Display for LintSeverity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LintSeverity::Info => write!(f, "info"),
+            LintSeverity::Warning => write!(f, "warning"),
+            LintSeverity::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// A tile position inside the physics group.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LintTilePos {
+    pub x: u16,
+    pub y: u16,
+}
+
+impl Display for LintTilePos {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LintResourceTy {
+    Image,
+    ImageArray,
+    Sound,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LintAnimTy {
+    Pos,
+    Color,
+    Sound,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum LintIssueKind {
+    UnusedResource {
+        ty: LintResourceTy,
+        index: usize,
+        name: String,
+    },
+    UnusedAnimation {
+        ty: LintAnimTy,
+        index: usize,
+        name: String,
+    },
+    MissingSpawn,
+    MissingStart,
+    MissingFinish,
+    /// No start/finish tiles at all, fine for non race maps.
+    NoRaceTiles,
+    /// The tile can not be reached from any spawn.
+    UnreachableStart {
+        pos: LintTilePos,
+    },
+    /// The tile can not be reached from any spawn.
+    UnreachableFinish {
+        pos: LintTilePos,
+    },
+    /// A teleporter (or tele checkpoint) without any exit of the same number.
+    TeleWithoutExit {
+        number: u8,
+        pos: LintTilePos,
+    },
+    /// Switch tiles toggle a number that no door or other switchable tile uses.
+    SwitchWithoutDoor {
+        number: u8,
+        pos: LintTilePos,
+    },
+    /// A tune tile uses a zone that has no tunings.
+    TuneZoneWithoutTunings {
+        number: u8,
+        pos: LintTilePos,
+    },
+    OversizedLayer {
+        group: String,
+        layer: String,
+        width: u16,
+        height: u16,
+    },
+}
+
+impl Display for LintIssueKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LintIssueKind::UnusedResource { ty, index, name } => {
+                write!(f, "{ty:?} {index} ({name}) is never used")
+            }
+            LintIssueKind::UnusedAnimation { ty, index, name } => {
+                write!(f, "{ty:?} animation {index} ({name}) is never used")
+            }
+            LintIssueKind::MissingSpawn => write!(f, "the game layer has no spawn"),
+            LintIssueKind::MissingStart => write!(f, "the map has a finish, but no start"),
+            LintIssueKind::MissingFinish => write!(f, "the map has a start, but no finish"),
+            LintIssueKind::NoRaceTiles => write!(f, "the map has no start or finish tiles"),
+            LintIssueKind::UnreachableStart { pos } => {
+                write!(f, "no start tile can be reached from a spawn, e.g. {pos}")
+            }
+            LintIssueKind::UnreachableFinish { pos } => {
+                write!(f, "no finish tile can be reached from a spawn, e.g. {pos}")
+            }
+            LintIssueKind::TeleWithoutExit { number, pos } => {
+                write!(f, "teleporter {number} at {pos} has no matching exit")
+            }
+            LintIssueKind::SwitchWithoutDoor { number, pos } => {
+                write!(f, "switch {number} at {pos} has no door")
+            }
+            LintIssueKind::TuneZoneWithoutTunings { number, pos } => {
+                write!(f, "tune zone {number} at {pos} has no tunings")
+            }
+            LintIssueKind::OversizedLayer {
+                group,
+                layer,
+                width,
+                height,
+            } => write!(
+                f,
+                "layer {layer} in group {group} is oversized ({width}x{height})"
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LintIssue {
+    pub severity: LintSeverity,
+    #[serde(flatten)]
+    pub kind: LintIssueKind,
+}
+
+impl LintIssue {
+    fn new(severity: LintSeverity, kind: LintIssueKind) -> Self {
+        Self { severity, kind }
+    }
+}
+
+impl Display for LintIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.severity, self.kind)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct LintOptions {
+    /// Tile layers with more tiles than this are reported as oversized.
+    pub max_layer_tiles: u64,
+}
+
+impl Default for LintOptions {
+    fn default() -> Self {
+        Self {
+            max_layer_tiles: 1000 * 1000,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LintReport {
+    pub issues: Vec<LintIssue>,
+}
+
+impl LintReport {
+    pub fn has_errors(&self) -> bool {
+        self.issues
+            .iter()
+            .any(|issue| issue.severity == LintSeverity::Error)
+    }
+
+    pub fn count(&self, severity: LintSeverity) -> usize {
+        self.issues
+            .iter()
+            .filter(|issue| issue.severity == severity)
+            .count()
+    }
+}
+
+fn is_switch_activator(index: u8) -> bool {
+    index == TILE_SWITCHTIMEDOPEN
+        || index == DdraceTileNum::SwitchTimedClose as u8
+        || index == DdraceTileNum::SwitchOpen as u8
+        || index == DdraceTileNum::SwitchClose as u8
+}
+
+fn is_tele_in(index: u8) -> bool {
+    index == DdraceTileNum::TeleIn as u8
+        || index == DdraceTileNum::TeleInEvil as u8
+        || index == DdraceTileNum::TeleInWeapon as u8
+        || index == DdraceTileNum::TeleInHook as u8
+}
+
+fn is_tele_check_in(index: u8) -> bool {
+    index == DdraceTileNum::TeleCheckIn as u8 || index == DdraceTileNum::TeleCheckInEvil as u8
+}
+
+/// Runs all checks on the map.
+pub fn lint_map(map: &Map, options: &LintOptions) -> LintReport {
+    let mut issues = lint_resources(&map.resources, &map.animations, &map.groups);
+    issues.extend(lint_physics(&map.groups.physics));
+    issues.extend(lint_layer_sizes(&map.groups, options));
+    issues.sort_by(|i1, i2| i2.severity.cmp(&i1.severity));
+    LintReport { issues }
+}
+
+/// Reports images, sounds and animations that no layer uses.
+pub fn lint_resources(
+    resources: &Resources,
+    animations: &Animations,
+    groups: &MapGroups,
+) -> Vec<LintIssue> {
+    let mut used_images = vec![false; resources.images.len()];
+    let mut used_image_arrays = vec![false; resources.image_arrays.len()];
+    let mut used_sounds = vec![false; resources.sounds.len()];
+    let mut used_pos_anims = vec![false; animations.pos.len()];
+    let mut used_color_anims = vec![false; animations.color.len()];
+    let mut used_sound_anims = vec![false; animations.sound.len()];
+
+    fn mark(used: &mut [bool], index: Option<usize>) {
+        if let Some(used) = index.and_then(|index| used.get_mut(index)) {
+            *used = true;
+        }
+    }
+
+    for layer in groups
+        .background
+        .iter()
+        .chain(groups.foreground.iter())
+        .flat_map(|group| group.layers.iter())
+    {
+        match layer {
+            MapLayer::Abritrary(_) => {}
+            MapLayer::Tile(layer) => {
+                mark(&mut used_image_arrays, layer.attr.image_array);
+                mark(&mut used_color_anims, layer.attr.color_anim);
+            }
+            MapLayer::Quad(layer) => {
+                mark(&mut used_images, layer.attr.image);
+                for quad in &layer.quads {
+                    mark(&mut used_pos_anims, quad.pos_anim);
+                    mark(&mut used_color_anims, quad.color_anim);
+                }
+            }
+            MapLayer::Sound(layer) => {
+                mark(&mut used_sounds, layer.attr.sound);
+                for sound in &layer.sounds {
+                    mark(&mut used_pos_anims, sound.pos_anim);
+                    mark(&mut used_sound_anims, sound.sound_anim);
+                }
+            }
+        }
+    }
+
+    let unused_resources = [
+        (LintResourceTy::Image, &resources.images, used_images),
+        (
+            LintResourceTy::ImageArray,
+            &resources.image_arrays,
+            used_image_arrays,
+        ),
+        (LintResourceTy::Sound, &resources.sounds, used_sounds),
+    ]
+    .into_iter()
+    .flat_map(|(ty, res, used)| {
+        res.iter()
+            .zip(used)
+            .enumerate()
+            .filter(|(_, (_, used))| !used)
+            .map(move |(index, (res, _))| {
+                LintIssue::new(
+                    LintSeverity::Warning,
+                    LintIssueKind::UnusedResource {
+                        ty,
+                        index,
+                        name: res.name.as_str().to_string(),
+                    },
+                )
+            })
+            .collect::<Vec<_>>()
+    });
+
+    let anim_names = [
+        (
+            LintAnimTy::Pos,
+            animations
+                .pos
+                .iter()
+                .map(|a| a.name.as_str())
+                .collect::<Vec<_>>(),
+            used_pos_anims,
+        ),
+        (
+            LintAnimTy::Color,
+            animations.color.iter().map(|a| a.name.as_str()).collect(),
+            used_color_anims,
+        ),
+        (
+            LintAnimTy::Sound,
+            animations.sound.iter().map(|a| a.name.as_str()).collect(),
+            used_sound_anims,
+        ),
+    ];
+    let unused_anims = anim_names.into_iter().flat_map(|(ty, names, used)| {
+        names
+            .into_iter()
+            .zip(used)
+            .enumerate()
+            .filter(|(_, (_, used))| !used)
+            .map(move |(index, (name, _))| {
+                LintIssue::new(
+                    LintSeverity::Warning,
+                    LintIssueKind::UnusedAnimation {
+                        ty,
+                        index,
+                        name: name.to_string(),
+                    },
+                )
+            })
+            .collect::<Vec<_>>()
+    });
+
+    unused_resources.chain(unused_anims).collect()
+}
+
+/// Reports tile layers that exceed [`LintOptions::max_layer_tiles`].
+pub fn lint_layer_sizes(groups: &MapGroups, options: &LintOptions) -> Vec<LintIssue> {
+    let mut issues = Vec::new();
+    let is_oversized =
+        |width: u16, height: u16| width as u64 * height as u64 > options.max_layer_tiles;
+
+    let physics = &groups.physics.attr;
+    if is_oversized(physics.width.get(), physics.height.get()) {
+        issues.push(LintIssue::new(
+            LintSeverity::Warning,
+            LintIssueKind::OversizedLayer {
+                group: "physics".to_string(),
+                layer: "physics".to_string(),
+                width: physics.width.get(),
+                height: physics.height.get(),
+            },
+        ));
+    }
+
+    let design_groups = |groups: &[MapGroup], ty: &str| {
+        groups
+            .iter()
+            .enumerate()
+            .flat_map(move |(g, group)| {
+                group.layers.iter().filter_map(move |layer| {
+                    let MapLayer::Tile(layer) = layer else {
+                        return None;
+                    };
+                    let (width, height) = (layer.attr.width.get(), layer.attr.height.get());
+                    is_oversized(width, height).then(|| {
+                        LintIssue::new(
+                            LintSeverity::Warning,
+                            LintIssueKind::OversizedLayer {
+                                group: if group.name.is_empty() {
+                                    format!("{ty} #{g}")
+                                } else {
+                                    group.name.clone()
+                                },
+                                layer: layer.name.clone(),
+                                width,
+                                height,
+                            },
+                        )
+                    })
+                })
+            })
+            .collect::<Vec<_>>()
+    };
+    issues.extend(design_groups(&groups.background, "background"));
+    issues.extend(design_groups(&groups.foreground, "foreground"));
+
+    issues
+}
+
+/// Reports gameplay problems of the physics layers, like missing spawns,
+/// unreachable start/finish tiles or teleporters without exits.
+pub fn lint_physics(physics: &MapGroupPhysics) -> Vec<LintIssue> {
+    let width = physics.attr.width.get() as usize;
+    let pos = |index: usize| LintTilePos {
+        x: (index % width) as u16,
+        y: (index / width) as u16,
+    };
+
+    let mut game: Option<&[Tile]> = None;
+    let mut front: Option<&[Tile]> = None;
+    let mut tele: Option<&[TeleTile]> = None;
+    let mut switch: Option<&[SwitchTile]> = None;
+    let mut tune = None;
+    for layer in &physics.layers {
+        match layer {
+            MapLayerPhysics::Arbitrary(_) | MapLayerPhysics::Speedup(_) => {}
+            MapLayerPhysics::Game(layer) => game = Some(&layer.tiles),
+            MapLayerPhysics::Front(layer) => front = Some(&layer.tiles),
+            MapLayerPhysics::Tele(layer) => tele = Some(&layer.base.tiles),
+            MapLayerPhysics::Switch(layer) => switch = Some(&layer.base.tiles),
+            MapLayerPhysics::Tune(layer) => tune = Some(layer),
+        }
+    }
+    let game = game.unwrap_or_default();
+
+    let mut issues = Vec::new();
+
+    // spawns
+    let spawns: Vec<usize> = game
+        .iter()
+        .enumerate()
+        .filter(|(_, tile)| {
+            tile.index == EEntityTiles::Spawn as u8
+                || tile.index == EEntityTiles::SpawnRed as u8
+                || tile.index == EEntityTiles::SpawnBlue as u8
+        })
+        .map(|(index, _)| index)
+        .collect();
+    if spawns.is_empty() {
+        issues.push(LintIssue::new(
+            LintSeverity::Error,
+            LintIssueKind::MissingSpawn,
+        ));
+    }
+
+    // teleporters
+    if let Some(tele) = tele {
+        let tele_outs: BTreeSet<u8> = tele
+            .iter()
+            .filter(|tile| tile.base.index == DdraceTileNum::TeleOut as u8)
+            .map(|tile| tile.number)
+            .collect();
+        let tele_check_outs: BTreeSet<u8> = tele
+            .iter()
+            .filter(|tile| tile.base.index == DdraceTileNum::TeleCheckOut as u8)
+            .map(|tile| tile.number)
+            .collect();
+
+        // only report the first tile of a number
+        let mut missing: BTreeMap<u8, usize> = Default::default();
+        for (index, tile) in tele.iter().enumerate() {
+            let has_exit = if is_tele_in(tile.base.index) {
+                tele_outs.contains(&tile.number)
+            } else if tile.base.index == DdraceTileNum::TeleCheck as u8 {
+                tele_check_outs.contains(&tile.number)
+            } else if is_tele_check_in(tile.base.index) {
+                // goes to the exit of the last touched tele checkpoint
+                !tele_check_outs.is_empty()
+            } else {
+                true
+            };
+            if !has_exit {
+                missing.entry(tile.number).or_insert(index);
+            }
+        }
+        issues.extend(missing.into_iter().map(|(number, index)| {
+            LintIssue::new(
+                LintSeverity::Error,
+                LintIssueKind::TeleWithoutExit {
+                    number,
+                    pos: pos(index),
+                },
+            )
+        }));
+    }
+
+    // switches
+    if let Some(switch) = switch {
+        // doors and all other switchable tiles
+        let targets: BTreeSet<u8> = switch
+            .iter()
+            .filter(|tile| tile.base.index != 0 && !is_switch_activator(tile.base.index))
+            .map(|tile| tile.number)
+            .collect();
+        let mut missing: BTreeMap<u8, usize> = Default::default();
+        for (index, tile) in switch.iter().enumerate() {
+            if is_switch_activator(tile.base.index)
+                && tile.number != 0
+                && !targets.contains(&tile.number)
+            {
+                missing.entry(tile.number).or_insert(index);
+            }
+        }
+        issues.extend(missing.into_iter().map(|(number, index)| {
+            LintIssue::new(
+                LintSeverity::Warning,
+                LintIssueKind::SwitchWithoutDoor {
+                    number,
+                    pos: pos(index),
+                },
+            )
+        }));
+    }
+
+    // tune zones
+    if let Some(tune) = tune {
+        let tiles: &[TuneTile] = &tune.base.tiles;
+        let mut missing: BTreeMap<u8, usize> = Default::default();
+        for (index, tile) in tiles.iter().enumerate() {
+            // zone 0 is the global tune zone
+            if tile.base.index == DdraceTileNum::Tune as u8
+                && tile.number != 0
+                && tune
+                    .tune_zones
+                    .get(&tile.number)
+                    .is_none_or(|zone| zone.tunes.is_empty())
+            {
+                missing.entry(tile.number).or_insert(index);
+            }
+        }
+        issues.extend(missing.into_iter().map(|(number, index)| {
+            LintIssue::new(
+                LintSeverity::Warning,
+                LintIssueKind::TuneZoneWithoutTunings {
+                    number,
+                    pos: pos(index),
+                },
+            )
+        }));
+    }
+
+    // start & finish
+    let race_tiles = |index: u8| -> Vec<usize> {
+        game.iter()
+            .enumerate()
+            .chain(front.unwrap_or_default().iter().enumerate())
+            .filter(|(_, tile)| tile.index == index)
+            .map(|(index, _)| index)
+            .collect()
+    };
+    let starts = race_tiles(DdraceTileNum::Start as u8);
+    let finishes = race_tiles(DdraceTileNum::Finish as u8);
+    match (starts.is_empty(), finishes.is_empty()) {
+        (true, true) => issues.push(LintIssue::new(
+            LintSeverity::Info,
+            LintIssueKind::NoRaceTiles,
+        )),
+        (true, false) => issues.push(LintIssue::new(
+            LintSeverity::Error,
+            LintIssueKind::MissingStart,
+        )),
+        (false, true) => issues.push(LintIssue::new(
+            LintSeverity::Error,
+            LintIssueKind::MissingFinish,
+        )),
+        (false, false) => {}
+    }
+    if !spawns.is_empty() && (!starts.is_empty() || !finishes.is_empty()) {
+        let reachable = reachable_tiles(width, game, front, tele, &spawns);
+        if !starts.is_empty() && !starts.iter().any(|&index| reachable[index]) {
+            issues.push(LintIssue::new(
+                LintSeverity::Warning,
+                LintIssueKind::UnreachableStart {
+                    pos: pos(starts[0]),
+                },
+            ));
+        }
+        if !finishes.is_empty() && !finishes.iter().any(|&index| reachable[index]) {
+            issues.push(LintIssue::new(
+                LintSeverity::Warning,
+                LintIssueKind::UnreachableFinish {
+                    pos: pos(finishes[0]),
+                },
+            ));
+        }
+    }
+
+    issues
+}
+
+/// A rough approximation of all tiles a player can reach from the spawns.
+///
+/// Walks over all tiles that are not solid or deadly and follows
+/// teleporters to their exits. It ignores one way tiles, stoppers
+/// and doors, so it never reports a reachable tile as unreachable
+/// because of them.
+fn reachable_tiles(
+    width: usize,
+    game: &[Tile],
+    front: Option<&[Tile]>,
+    tele: Option<&[TeleTile]>,
+    spawns: &[usize],
+) -> Vec<bool> {
+    let is_blocked = |index: usize| {
+        let game_index = game[index].index;
+        game_index == DdraceTileNum::Solid as u8
+            || game_index == DdraceTileNum::NoHook as u8
+            || game_index == DdraceTileNum::Death as u8
+            || front.is_some_and(|front| front[index].index == DdraceTileNum::Death as u8)
+    };
+
+    let mut tele_outs: BTreeMap<u8, Vec<usize>> = Default::default();
+    let mut tele_check_outs: Vec<usize> = Default::default();
+    for (index, tile) in tele.unwrap_or_default().iter().enumerate() {
+        if tile.base.index == DdraceTileNum::TeleOut as u8 {
+            tele_outs.entry(tile.number).or_default().push(index);
+        } else if tile.base.index == DdraceTileNum::TeleCheckOut as u8 {
+            tele_check_outs.push(index);
+        }
+    }
+
+    let mut reachable = vec![false; game.len()];
+    let mut queue: VecDeque<usize> = spawns.iter().copied().collect();
+    for &spawn in spawns {
+        reachable[spawn] = true;
+    }
+    let height = game.len() / width;
+    while let Some(index) = queue.pop_front() {
+        let (x, y) = (index % width, index / width);
+        let mut next: Vec<usize> = Vec::with_capacity(4);
+        if x > 0 {
+            next.push(index - 1);
+        }
+        if x + 1 < width {
+            next.push(index + 1);
+        }
+        if y > 0 {
+            next.push(index - width);
+        }
+        if y + 1 < height {
+            next.push(index + width);
+        }
+        if let Some(tile) = tele.map(|tele| &tele[index]) {
+            if tile.base.index == DdraceTileNum::TeleIn as u8
+                || tile.base.index == DdraceTileNum::TeleInEvil as u8
+            {
+                next.extend(tele_outs.get(&tile.number).into_iter().flatten());
+            } else if is_tele_check_in(tile.base.index) {
+                next.extend(tele_check_outs.iter());
+            }
+        }
+        for next in next {
+            if !reachable[next] && !is_blocked(next) {
+                reachable[next] = true;
+                queue.push_back(next);
+            }
+        }
+    }
+    reachable
+}
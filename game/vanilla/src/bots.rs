@@ -0,0 +1,507 @@
+pub mod bots {
+    use std::{fmt::Debug, rc::Rc};
+
+    use base::{hash::Hash, linked_hash_map_view::FxLinkedHashMap};
+    use game_interface::types::{
+        id_types::{CharacterId, PlayerId},
+        input::{cursor::CharacterInputCursor, CharacterInput, CharacterInputConsumableDiff},
+        player_info::PlayerUniqueId,
+        render::game::game_match::MatchSide,
+        weapons::WeaponType,
+    };
+    use hiarc::Hiarc;
+    use math::math::{
+        distance_squared,
+        vector::{dvec2, vec2},
+        Rng,
+    };
+
+    use crate::{
+        collision::collision::{Collision, CollisionTile, CollisionTypes},
+        entities::{
+            character::{
+                character::{Character, CharacterPhasedState},
+                player::player::Players,
+            },
+            flag::flag::Flags,
+        },
+        stage::stage::Stages,
+        world::world::GameWorld,
+    };
+
+    /// Start of the fingerprint of every bot, the rest is the bot index.
+    /// Certificate hashes of real clients are practically never equal.
+    const BOT_FINGERPRINT_PREFIX: &[u8] = b"vanilla-bot";
+
+    /// Weapons a bot prefers, best first.
+    const WEAPON_PREFERENCE: [WeaponType; 5] = [
+        WeaponType::Laser,
+        WeaponType::Grenade,
+        WeaponType::Shotgun,
+        WeaponType::Gun,
+        WeaponType::Hammer,
+    ];
+    /// Distance in which a bot starts to shoot at a visible enemy.
+    const FIRE_RANGE: f32 = 700.0;
+    /// Distance in which a bot uses the hammer.
+    const HAMMER_RANGE: f32 = 64.0;
+
+    /// Decides which target a bot follows.
+    ///
+    /// Aiming, shooting & climbing are the same for all bots,
+    /// a behaviour only picks where the bot walks to.
+    pub trait BotBehaviour: Debug {
+        /// The name used by the `add_bot` rcon command.
+        fn name(&self) -> &'static str;
+
+        /// Where the bot walks to, `None` for a random walk.
+        fn move_to(
+            &self,
+            world: &GameWorld,
+            collision: &Collision,
+            character: &Character,
+        ) -> Option<vec2>;
+    }
+
+    /// Walks around randomly and only shoots enemies in sight.
+    #[derive(Debug)]
+    pub struct Wander;
+
+    impl BotBehaviour for Wander {
+        fn name(&self) -> &'static str {
+            "wander"
+        }
+
+        fn move_to(&self, _: &GameWorld, _: &Collision, _: &Character) -> Option<vec2> {
+            None
+        }
+    }
+
+    /// Follows the nearest enemy.
+    #[derive(Debug)]
+    pub struct Chase;
+
+    impl BotBehaviour for Chase {
+        fn name(&self) -> &'static str {
+            "chase"
+        }
+
+        fn move_to(
+            &self,
+            world: &GameWorld,
+            collision: &Collision,
+            character: &Character,
+        ) -> Option<vec2> {
+            Bots::nearest_enemy(world, collision, character, false)
+        }
+    }
+
+    /// Grabs the enemy flag and brings it home,
+    /// falls back to chasing if there are no flags.
+    #[derive(Debug)]
+    pub struct FlagRunner;
+
+    impl BotBehaviour for FlagRunner {
+        fn name(&self) -> &'static str {
+            "flag_runner"
+        }
+
+        fn move_to(
+            &self,
+            world: &GameWorld,
+            collision: &Collision,
+            character: &Character,
+        ) -> Option<vec2> {
+            character
+                .core
+                .side
+                .and_then(|side| Bots::flag_target(world, &character.base.game_element_id, side))
+                .or_else(|| Bots::nearest_enemy(world, collision, character, false))
+        }
+    }
+
+    /// All behaviours bots can be added with, by name.
+    ///
+    /// Contains the vanilla behaviours by default,
+    /// mods can register their own.
+    #[derive(Debug, Hiarc)]
+    pub struct BotBehaviours {
+        #[hiarc_skip_unsafe]
+        behaviours: Vec<Rc<dyn BotBehaviour>>,
+    }
+
+    impl Default for BotBehaviours {
+        fn default() -> Self {
+            let mut behaviours = Self {
+                behaviours: Default::default(),
+            };
+            behaviours.register(Rc::new(Wander));
+            behaviours.register(Rc::new(Chase));
+            behaviours.register(Rc::new(FlagRunner));
+            behaviours
+        }
+    }
+
+    impl BotBehaviours {
+        /// Adds a behaviour, replacing any behaviour of the same name.
+        pub fn register(&mut self, behaviour: Rc<dyn BotBehaviour>) {
+            self.behaviours.retain(|b| b.name() != behaviour.name());
+            self.behaviours.push(behaviour);
+        }
+
+        pub fn get(&self, name: &str) -> Option<Rc<dyn BotBehaviour>> {
+            self.behaviours.iter().find(|b| b.name() == name).cloned()
+        }
+
+        pub fn names(&self) -> impl Iterator<Item = &'static str> + '_ {
+            self.behaviours.iter().map(|b| b.name())
+        }
+    }
+
+    #[derive(Debug, Hiarc)]
+    pub struct Bot {
+        #[hiarc_skip_unsafe]
+        pub behaviour: Rc<dyn BotBehaviour>,
+        input: CharacterInput,
+
+        /// Direction of the current random walk.
+        wander_dir: i32,
+        /// Ticks until the random walk changes its direction.
+        wander_ticks: u32,
+        /// Ticks the hook is still held.
+        hook_ticks: u32,
+        /// Ticks the bot did not move horizontally even though it wanted to.
+        stuck_ticks: u32,
+    }
+
+    impl Bot {
+        fn new(behaviour: Rc<dyn BotBehaviour>) -> Self {
+            Self {
+                behaviour,
+                input: Default::default(),
+
+                wander_dir: 1,
+                wander_ticks: 0,
+                hook_ticks: 0,
+                stuck_ticks: 0,
+            }
+        }
+    }
+
+    /// What a bot wants to do in the current tick.
+    #[derive(Debug, Default)]
+    struct BotIntent {
+        /// Where the bot walks to, `None` for a random walk.
+        move_to: Option<vec2>,
+        /// The enemy the bot aims at.
+        enemy: Option<vec2>,
+    }
+
+    /// All server-side bots of a game.
+    ///
+    /// Bots are regular players, the only difference is that their
+    /// input is generated here instead of received over the network.
+    #[derive(Debug, Hiarc)]
+    pub struct Bots {
+        bots: FxLinkedHashMap<PlayerId, Bot>,
+        behaviours: BotBehaviours,
+        rng: Rng,
+        /// Used to give every bot an unique name & client id.
+        next_index: u64,
+    }
+
+    impl Default for Bots {
+        fn default() -> Self {
+            Self::new(Default::default())
+        }
+    }
+
+    impl Bots {
+        pub fn new(behaviours: BotBehaviours) -> Self {
+            Self {
+                bots: Default::default(),
+                behaviours,
+                rng: Rng::new(0),
+                next_index: 0,
+            }
+        }
+
+        pub fn behaviours(&self) -> &BotBehaviours {
+            &self.behaviours
+        }
+
+        /// Returns an index that was not used by any bot before.
+        pub fn next_index(&mut self) -> u64 {
+            self.next_index += 1;
+            self.next_index
+        }
+
+        /// A unique identifier for the bot of the given index,
+        /// so bots don't share timeouts or statistics.
+        pub fn unique_id(index: u64) -> PlayerUniqueId {
+            let mut hash = Hash::default();
+            hash[..BOT_FINGERPRINT_PREFIX.len()].copy_from_slice(BOT_FINGERPRINT_PREFIX);
+            let index_start = hash.len() - std::mem::size_of::<u64>();
+            hash[index_start..].copy_from_slice(&index.to_le_bytes());
+            PlayerUniqueId::CertFingerprint(hash)
+        }
+
+        pub fn is_bot_unique_id(id: &PlayerUniqueId) -> bool {
+            matches!(id, PlayerUniqueId::CertFingerprint(hash) if hash.starts_with(BOT_FINGERPRINT_PREFIX))
+        }
+
+        pub fn insert(&mut self, id: PlayerId, behaviour: Rc<dyn BotBehaviour>) {
+            self.bots.insert(id, Bot::new(behaviour));
+        }
+
+        pub fn remove(&mut self, id: &PlayerId) -> bool {
+            self.bots.remove(id).is_some()
+        }
+
+        pub fn contains(&self, id: &PlayerId) -> bool {
+            self.bots.contains_key(id)
+        }
+
+        pub fn len(&self) -> usize {
+            self.bots.len()
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.bots.is_empty()
+        }
+
+        pub fn ids(&self) -> impl DoubleEndedIterator<Item = &PlayerId> {
+            self.bots.keys()
+        }
+
+        pub fn has_line_of_sight(collision: &Collision, from: &vec2, to: &vec2) -> bool {
+            let mut out_collision = vec2::default();
+            let mut out_before_collision = vec2::default();
+            matches!(
+                collision.intersect_line(
+                    from,
+                    to,
+                    &mut out_collision,
+                    &mut out_before_collision,
+                    CollisionTypes::SOLID,
+                ),
+                CollisionTile::None
+            )
+        }
+
+        pub fn is_enemy(character: &Character, other: &Character) -> bool {
+            character.base.game_element_id != other.base.game_element_id
+                && (character.core.side.is_none() || character.core.side != other.core.side)
+                && matches!(other.phased, CharacterPhasedState::Normal(_))
+        }
+
+        pub fn nearest_enemy(
+            world: &GameWorld,
+            collision: &Collision,
+            character: &Character,
+            visible_only: bool,
+        ) -> Option<vec2> {
+            let pos = *character.pos.pos();
+            world
+                .characters
+                .values()
+                .filter(|other| Self::is_enemy(character, other))
+                .map(|other| *other.pos.pos())
+                .filter(|other_pos| {
+                    !visible_only || Self::has_line_of_sight(collision, &pos, other_pos)
+                })
+                .min_by(|a, b| distance_squared(&pos, a).total_cmp(&distance_squared(&pos, b)))
+        }
+
+        /// The target of a flag runner, `None` if there are no flags.
+        pub fn flag_target(world: &GameWorld, id: &CharacterId, side: MatchSide) -> Option<vec2> {
+            let (own_flags, enemy_flags): (&Flags, &Flags) = match side {
+                MatchSide::Red => (world.get_red_flags(), world.get_blue_flags()),
+                MatchSide::Blue => (world.get_blue_flags(), world.get_red_flags()),
+            };
+            let carries_flag = enemy_flags
+                .values()
+                .any(|flag| flag.core.carrier == Some(*id));
+            if carries_flag {
+                // bring the flag home, if the own flag is not at home,
+                // then it's position is also where the carrier is
+                own_flags.values().next().map(|flag| flag.core.pos)
+            } else {
+                enemy_flags
+                    .values()
+                    .find(|flag| {
+                        flag.core.carrier.is_none_or(|carrier| {
+                            world
+                                .characters
+                                .get(&carrier)
+                                .is_some_and(|c| c.core.side != Some(side))
+                        })
+                    })
+                    .map(|flag| flag.core.pos)
+            }
+        }
+
+        fn intent(
+            bot: &Bot,
+            world: &GameWorld,
+            collision: &Collision,
+            character: &Character,
+        ) -> BotIntent {
+            let enemy = Self::nearest_enemy(world, collision, character, true);
+            let move_to = bot.behaviour.move_to(world, collision, character);
+            BotIntent { move_to, enemy }
+        }
+
+        fn best_weapon(character: &Character, enemy_distance: Option<f32>) -> WeaponType {
+            if enemy_distance.is_some_and(|distance| distance < HAMMER_RANGE) {
+                return WeaponType::Hammer;
+            }
+            WEAPON_PREFERENCE
+                .into_iter()
+                .find(|weapon| {
+                    character
+                        .reusable_core
+                        .weapons
+                        .get(weapon)
+                        .is_some_and(|w| w.cur_ammo.is_none_or(|ammo| ammo > 0))
+                })
+                .unwrap_or(WeaponType::Hammer)
+        }
+
+        /// Generates the input of a single bot for this tick.
+        fn next_input(
+            bot: &mut Bot,
+            rng: &mut Rng,
+            world: &GameWorld,
+            collision: &Collision,
+            character: &Character,
+        ) -> CharacterInput {
+            let mut inp = bot.input;
+            if matches!(character.phased, CharacterPhasedState::Dead(_)) {
+                inp.state.dir.set(0);
+                inp.state.fire.set(false);
+                inp.state.hook.set(false);
+                inp.state.jump.set(false);
+                return inp;
+            }
+
+            let pos = *character.pos.pos();
+            let intent = Self::intent(bot, world, collision, character);
+
+            // movement
+            let dir = match intent.move_to {
+                Some(target) if (target.x - pos.x).abs() > 16.0 => {
+                    if target.x < pos.x {
+                        -1
+                    } else {
+                        1
+                    }
+                }
+                Some(_) => 0,
+                None => {
+                    if bot.wander_ticks == 0 {
+                        bot.wander_dir = rng.random_int_in(0..=2) as i32 - 1;
+                        bot.wander_ticks = rng.random_int_in(25..=150) as u32;
+                    }
+                    bot.wander_ticks -= 1;
+                    bot.wander_dir
+                }
+            };
+            if dir != 0 && character.core.core.vel.x.abs() < 0.5 {
+                bot.stuck_ticks += 1;
+            } else {
+                bot.stuck_ticks = 0;
+            }
+            let wall_ahead = dir != 0 && collision.check_pointf(pos.x + dir as f32 * 32.0, pos.y);
+            let target_above = intent.move_to.is_some_and(|target| target.y < pos.y - 64.0);
+            let jump = (wall_ahead || target_above || bot.stuck_ticks > 10) && !*inp.state.jump;
+
+            // hook upwards into the direction of the target if the bot has to climb
+            let hook_dir = vec2::new(dir as f32 * 0.5, -1.0);
+            if bot.hook_ticks > 0 {
+                bot.hook_ticks -= 1;
+            } else if (target_above || bot.stuck_ticks > 25)
+                && !Self::has_line_of_sight(
+                    collision,
+                    &pos,
+                    &(pos + hook_dir * collision.get_tune_at(&pos).hook_length),
+                )
+            {
+                bot.hook_ticks = 30;
+                bot.stuck_ticks = 0;
+            }
+            let hook = bot.hook_ticks > 0;
+
+            // aim & shoot
+            let enemy_distance = intent
+                .enemy
+                .map(|enemy| distance_squared(&pos, &enemy).sqrt());
+            let aim = match intent.enemy {
+                Some(enemy) if !hook => enemy - pos,
+                _ if hook => hook_dir * 100.0,
+                _ => vec2::new(dir as f32 * 100.0, 0.0),
+            };
+            let weapon = Self::best_weapon(character, enemy_distance);
+            if weapon != character.core.active_weapon
+                && character.core.queued_weapon != Some(weapon)
+            {
+                inp.consumable.set_weapon_req(Some(weapon));
+            }
+            let fire = enemy_distance.is_some_and(|distance| {
+                distance
+                    < if weapon == WeaponType::Hammer {
+                        HAMMER_RANGE
+                    } else {
+                        FIRE_RANGE
+                    }
+            }) && !*inp.state.fire;
+
+            inp.cursor.set(CharacterInputCursor::from_vec2(&dvec2::new(
+                aim.x as f64,
+                aim.y as f64,
+            )));
+            if jump {
+                inp.consumable.jump.add(1);
+            }
+            if fire {
+                inp.consumable.fire.add(1, *inp.cursor);
+            }
+            if hook && !*inp.state.hook {
+                inp.consumable.hook.add(1, *inp.cursor);
+            }
+            inp.state.dir.set(dir);
+            inp.state.jump.set(jump);
+            inp.state.fire.set(fire);
+            inp.state.hook.set(hook);
+
+            inp
+        }
+
+        /// Generates the input of all bots for this tick.
+        ///
+        /// Returns the new inputs together with their difference
+        /// to the previous input.
+        pub fn tick(
+            &mut self,
+            stages: &Stages,
+            players: &Players,
+            collision: &Collision,
+        ) -> Vec<(PlayerId, CharacterInput, CharacterInputConsumableDiff)> {
+            let mut res = Vec::with_capacity(self.bots.len());
+            for (id, bot) in self.bots.iter_mut() {
+                let Some((world, character)) = players.player(id).and_then(|player| {
+                    let world = &stages.get(&player.stage_id())?.world;
+                    Some((world, world.characters.get(id)?))
+                }) else {
+                    continue;
+                };
+
+                let inp = Self::next_input(bot, &mut self.rng, world, collision, character);
+                let diff = inp.consumable.diff(&bot.input.consumable);
+                bot.input = inp;
+                res.push((*id, inp, diff));
+            }
+            res
+        }
+    }
+}
@@ -1,4 +1,6 @@
 pub mod collision {
+    use std::collections::BTreeMap;
+
     use anyhow::anyhow;
     use bitflags::bitflags;
     use config::{traits::ConfigInterface, ConfigInterface};
@@ -20,6 +22,7 @@ pub mod collision {
     use math::math::{
         distance, dot, mix, round_to_int,
         vector::{ivec2, vec2},
+        Rng,
     };
 
     use crate::state::state::TICKS_PER_SECOND;
@@ -174,6 +177,11 @@ pub mod collision {
         width: u32,
         height: u32,
 
+        /// Positions of all tele outs by their number.
+        tele_outs: BTreeMap<u8, Vec<vec2>>,
+        /// Positions of all tele checkpoint outs by their number.
+        tele_check_outs: BTreeMap<u8, Vec<vec2>>,
+
         pub(crate) tune_zones: Vec<Tunings>,
     }
 
@@ -251,9 +259,31 @@ pub mod collision {
                     tune_tiles
                 };
 
+            let mut tele_outs: BTreeMap<u8, Vec<vec2>> = Default::default();
+            let mut tele_check_outs: BTreeMap<u8, Vec<vec2>> = Default::default();
+            if let Some(tele_layer) = tele_layer {
+                for (index, tile) in tele_layer.base.tiles.iter().enumerate() {
+                    let outs = if tile.base.index == DdraceTileNum::TeleOut as u8 {
+                        &mut tele_outs
+                    } else if tile.base.index == DdraceTileNum::TeleCheckOut as u8 {
+                        &mut tele_check_outs
+                    } else {
+                        continue;
+                    };
+                    let x = index % width as usize;
+                    let y = index / width as usize;
+                    outs.entry(tile.number).or_default().push(vec2::new(
+                        x as f32 * 32.0 + 16.0,
+                        y as f32 * 32.0 + 16.0,
+                    ));
+                }
+            }
+
             Ok(Self {
                 width,
                 height,
+                tele_outs,
+                tele_check_outs,
                 tiles: {
                     let mut tiles = game_layer.tiles.to_vec();
                     tiles.shrink_to_fit();
@@ -479,6 +509,46 @@ pub mod collision {
             *in_out_vel = vel;
         }
 
+        /// Picks one of the exits in a deterministic way.
+        ///
+        /// The seed only depends on the position that entered the teleporter,
+        /// so the client prediction always picks the same exit as the server.
+        fn random_out(outs: &[vec2], number: u8, seed_pos: &vec2) -> Option<vec2> {
+            match outs {
+                [] => None,
+                [out] => Some(*out),
+                outs => {
+                    let seed = ((round_to_int(seed_pos.x) as u32 as u64) << 32)
+                        | (round_to_int(seed_pos.y) as u32 as u64);
+                    let mut rng = Rng::new(seed ^ number as u64);
+                    Some(outs[rng.random_index(outs)])
+                }
+            }
+        }
+
+        /// A tele out of the teleporter with the given number,
+        /// or `None` if the map has no matching tele out.
+        pub fn tele_out(&self, number: u8, seed_pos: &vec2) -> Option<vec2> {
+            Self::random_out(
+                self.tele_outs.get(&number).map(|o| o.as_slice()).unwrap_or_default(),
+                number,
+                seed_pos,
+            )
+        }
+
+        /// A tele checkpoint out of the checkpoint with the given number,
+        /// or `None` if the map has no matching tele checkpoint out.
+        pub fn tele_check_out(&self, number: u8, seed_pos: &vec2) -> Option<vec2> {
+            Self::random_out(
+                self.tele_check_outs
+                    .get(&number)
+                    .map(|o| o.as_slice())
+                    .unwrap_or_default(),
+                number,
+                seed_pos,
+            )
+        }
+
         fn is_teleport(&self, index: usize) -> Option<u8> {
             let tile = &self.tele_tiles[index];
             (tile.base.index == DdraceTileNum::TeleIn as u8).then_some(tile.number)
@@ -702,4 +772,172 @@ pub mod collision {
             &self.tune_zones[tune_tile.number as usize]
         }
     }
+
+    #[cfg(test)]
+    mod test {
+        use game_base::mapdef_06::DdraceTileNum;
+        use map::{
+            map::groups::{
+                layers::{
+                    physics::{
+                        MapLayerPhysics, MapLayerTilePhysicsBase, MapLayerTilePhysicsTele,
+                    },
+                    tiles::{TeleTile, TileBase},
+                },
+                MapGroupPhysics, MapGroupPhysicsAttr,
+            },
+            types::NonZeroU16MinusOne,
+        };
+        use math::math::vector::vec2;
+
+        use super::{Collision, CollisionTile, CollisionTypes};
+
+        const W: usize = 10;
+        const H: usize = 3;
+
+        fn tile_center(x: usize, y: usize) -> vec2 {
+            vec2::new(x as f32 * 32.0 + 16.0, y as f32 * 32.0 + 16.0)
+        }
+
+        /// A small map with:
+        /// - a player tele in (1) at x = 1, a hook tele in (2) at x = 2,
+        ///   a weapon tele in (3) at x = 3 and a tele in (4) without exit at x = 4
+        /// - tele outs of number 1 at x = 6 and x = 7
+        /// - tele outs of number 2 and 3 at x = 8
+        /// - a tele checkpoint out of number 5 at x = 5, y = 2
+        fn tele_physics_group() -> MapGroupPhysics {
+            let mut tele = vec![TeleTile::default(); W * H];
+            let mut set = |x: usize, index: DdraceTileNum, number: u8| {
+                tele[W + x] = TeleTile {
+                    base: TileBase {
+                        index: index as u8,
+                        ..Default::default()
+                    },
+                    number,
+                };
+            };
+            set(1, DdraceTileNum::TeleIn, 1);
+            set(2, DdraceTileNum::TeleInHook, 2);
+            set(3, DdraceTileNum::TeleInWeapon, 3);
+            set(4, DdraceTileNum::TeleIn, 4);
+            set(6, DdraceTileNum::TeleOut, 1);
+            set(7, DdraceTileNum::TeleOut, 1);
+            set(8, DdraceTileNum::TeleOut, 2);
+            tele[W + 9] = TeleTile {
+                base: TileBase {
+                    index: DdraceTileNum::TeleOut as u8,
+                    ..Default::default()
+                },
+                number: 3,
+            };
+            tele[2 * W + 5] = TeleTile {
+                base: TileBase {
+                    index: DdraceTileNum::TeleCheckOut as u8,
+                    ..Default::default()
+                },
+                number: 5,
+            };
+
+            MapGroupPhysics {
+                attr: MapGroupPhysicsAttr {
+                    width: NonZeroU16MinusOne::new(W as u16).unwrap(),
+                    height: NonZeroU16MinusOne::new(H as u16).unwrap(),
+                },
+                layers: vec![
+                    MapLayerPhysics::Game(MapLayerTilePhysicsBase {
+                        tiles: vec![Default::default(); W * H],
+                    }),
+                    MapLayerPhysics::Tele(MapLayerTilePhysicsTele {
+                        base: MapLayerTilePhysicsBase { tiles: tele },
+                        tele_names: Default::default(),
+                    }),
+                ],
+            }
+        }
+
+        fn tele_map() -> Collision {
+            Collision::new(&tele_physics_group(), true).unwrap()
+        }
+
+        #[test]
+        fn tele_outs() {
+            let collision = tele_map();
+
+            assert_eq!(
+                collision.tele_out(2, &tile_center(2, 1)),
+                Some(tile_center(8, 1))
+            );
+            assert_eq!(
+                collision.tele_out(3, &tile_center(3, 1)),
+                Some(tile_center(9, 1))
+            );
+            assert_eq!(collision.tele_out(4, &tile_center(4, 1)), None);
+            assert_eq!(collision.tele_check_out(1, &tile_center(1, 1)), None);
+            assert_eq!(
+                collision.tele_check_out(5, &tile_center(1, 1)),
+                Some(tile_center(5, 2))
+            );
+
+            // multiple exits are picked deterministically
+            let outs = [tile_center(6, 1), tile_center(7, 1)];
+            for x in 0..64 {
+                let seed_pos = vec2::new(x as f32 * 3.0, 40.0);
+                let out = collision.tele_out(1, &seed_pos).unwrap();
+                assert!(outs.contains(&out));
+                assert_eq!(collision.tele_out(1, &seed_pos), Some(out));
+            }
+        }
+
+        #[test]
+        fn tele_map_is_not_loaded_without_all_layers() {
+            // the group has a tele layer, but no front & tune layer
+            let collision = Collision::new(&tele_physics_group(), false).unwrap();
+            assert_eq!(collision.tele_out(1, &tile_center(1, 1)), None);
+            assert_eq!(collision.tele_check_out(5, &tile_center(1, 1)), None);
+
+            let mut col = vec2::default();
+            let mut before_col = vec2::default();
+            assert_eq!(
+                collision.intersect_line(
+                    &tile_center(0, 1),
+                    &tile_center(9, 1),
+                    &mut col,
+                    &mut before_col,
+                    CollisionTypes::PLAYER_TELE
+                        | CollisionTypes::HOOK_TELE
+                        | CollisionTypes::WEAPON_TELE,
+                ),
+                CollisionTile::None
+            );
+        }
+
+        #[test]
+        fn intersect_teleporters() {
+            let collision = tele_map();
+            let from = tile_center(0, 1);
+            let to = tile_center(9, 1);
+            let mut col = vec2::default();
+            let mut before_col = vec2::default();
+
+            let hit = |types: CollisionTypes, col: &mut vec2, before_col: &mut vec2| {
+                collision.intersect_line(&from, &to, col, before_col, types)
+            };
+            assert_eq!(
+                hit(CollisionTypes::PLAYER_TELE, &mut col, &mut before_col),
+                CollisionTile::PlayerTele(1)
+            );
+            assert_eq!(
+                hit(CollisionTypes::HOOK_TELE, &mut col, &mut before_col),
+                CollisionTile::HookTele(2)
+            );
+            assert_eq!(
+                hit(CollisionTypes::WEAPON_TELE, &mut col, &mut before_col),
+                CollisionTile::WeaponTele(3)
+            );
+            assert_eq!(
+                hit(CollisionTypes::SOLID, &mut col, &mut before_col),
+                CollisionTile::None
+            );
+        }
+    }
 }
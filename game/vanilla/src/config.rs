@@ -47,7 +47,29 @@ pub mod config {
         #[default = 16]
         #[conf_valid(range(min = 1, max = 1000000))]
         pub max_ingame_players: u32,
+        /// In tournament mode a match only starts after all players
+        /// used `/ready`, sides are never balanced automatically
+        /// and the results of finished matches are saved.
         pub tournament_mode: bool,
+        /// How long players can warm up before a match starts.
+        /// In tournament mode the warmup additionally lasts
+        /// until all players are ready.
+        /// A value of `0` means no warmup.
+        ///
+        /// Time unit is seconds.
+        #[default = 0]
+        pub warmup_secs: u64,
+        /// Save the results & statistics of every finished match,
+        /// not only of tournament matches.
+        /// The statistics of a running match can be saved
+        /// using the `export_stats` command.
+        pub export_match_stats: bool,
+        /// Fills the game with server-side bots until this many
+        /// players are ingame, as long as at least one real player
+        /// is connected.
+        /// A value of `0` means no bots are added automatically.
+        #[default = 0]
+        pub bot_fill_players: u32,
         /// This will allow the game to follow the current voted player
         /// even if not in range. Since this potentially allows cheating
         /// this is false for vanilla
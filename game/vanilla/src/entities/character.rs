@@ -3,6 +3,7 @@ pub mod hook;
 pub mod player;
 pub mod pos;
 pub mod score;
+pub mod stats;
 
 pub mod character {
     use std::{
@@ -39,7 +40,7 @@ pub mod character {
         },
     };
     use hiarc::{hiarc_safer_rc_refcell, Hiarc};
-    use map::map::groups::layers::tiles::Tile;
+    use map::map::groups::layers::tiles::{TeleTile, Tile};
     use pool::{datatypes::PoolFxLinkedHashMap, mt_pool::Pool as MtPool};
     use rustc_hash::FxHashSet;
 
@@ -49,6 +50,7 @@ pub mod character {
         player::player::{PlayerInfo, Players, SpectatorPlayer, SpectatorPlayers},
         pos::character_pos::{CharacterPos, CharacterPositionPlayfield},
         score::character_score::{CharacterScore, CharacterScores},
+        stats::character_stats::CharacterStats,
     };
     use crate::{
         collision::collision::{Collision, CollisionTile, CollisionTypes, HitTile},
@@ -121,6 +123,9 @@ pub mod character {
 
         pub side: Option<MatchSide>,
 
+        /// The number of the last tele checkpoint the character passed.
+        pub tele_checkpoint: Option<u8>,
+
         pub eye: TeeEye,
         pub normal_eye_in: GameTickCooldown,
 
@@ -407,6 +412,7 @@ pub mod character {
         pub(crate) pos: CharacterPos,
         pub(crate) phased: CharacterPhasedState,
         pub(crate) score: CharacterScore,
+        pub(crate) stats: CharacterStats,
 
         game_pending_events: GameWorldPendingEvents,
         simulation_events: SimulationWorldEvents,
@@ -466,6 +472,7 @@ pub mod character {
                     false,
                 )),
                 score: scores.get_new_score(*id, 0),
+                stats: Default::default(),
 
                 game_pending_events: game_pending_events.clone(),
                 simulation_events: simulation_events.clone(),
@@ -661,9 +668,26 @@ pub mod character {
             }
         }
 
+        /// Saves passed tele checkpoints & remembers the first teleporter
+        /// on the way in `tele`, which is used after all tiles were handled.
+        fn handle_tele_layer_tile(&mut self, tile: &TeleTile, tele: &mut Option<TeleTile>) {
+            if tele.is_some() {
+                // only the first teleporter on the way counts
+            } else if tile.base.index == DdraceTileNum::TeleCheck as u8 {
+                self.core.tele_checkpoint = Some(tile.number);
+            } else if tile.base.index == DdraceTileNum::TeleIn as u8
+                || tile.base.index == DdraceTileNum::TeleInEvil as u8
+                || tile.base.index == DdraceTileNum::TeleCheckIn as u8
+                || tile.base.index == DdraceTileNum::TeleCheckInEvil as u8
+            {
+                *tele = Some(*tile);
+            }
+        }
+
         #[must_use]
         fn handle_tiles(&mut self, old_pos: vec2, collision: &Collision) -> CharacterDamageResult {
             let mut res = CharacterDamageResult::None;
+            let mut tele = None;
             let cur_pos = *self.pos.pos();
             collision.intersect_line_feedback(&old_pos, &cur_pos, |tile| match tile {
                 HitTile::Game(tile) => {
@@ -675,16 +699,45 @@ pub mod character {
                         res = CharacterDamageResult::Death;
                     }
                 }
-                HitTile::Tele(_) => {}
+                HitTile::Tele(tile) => {
+                    self.handle_tele_layer_tile(tile, &mut tele);
+                }
                 HitTile::Speedup(_) => {}
                 HitTile::Switch(_) => {}
                 HitTile::Tune(_) => {
                     // tune tiles are handled on the fly where needed
                 }
             });
+            if let Some(tile) = tele.filter(|_| !matches!(res, CharacterDamageResult::Death)) {
+                self.handle_tele(&tile, collision);
+            }
             res
         }
 
+        fn handle_tele(&mut self, tile: &TeleTile, collision: &Collision) {
+            let is_checkpoint_tele = tile.base.index == DdraceTileNum::TeleCheckIn as u8
+                || tile.base.index == DdraceTileNum::TeleCheckInEvil as u8;
+            let tele_out = if is_checkpoint_tele {
+                // teleports back to the last passed checkpoint
+                self.core
+                    .tele_checkpoint
+                    .and_then(|number| collision.tele_check_out(number, self.pos.pos()))
+            } else {
+                collision.tele_out(tile.number, self.pos.pos())
+            };
+            let Some(tele_out) = tele_out else {
+                return;
+            };
+            self.pos.move_pos(tele_out);
+            self.phased.hook_mut().set(Hook::None, None);
+            // evil teleporters also stop the character
+            if tile.base.index == DdraceTileNum::TeleInEvil as u8
+                || tile.base.index == DdraceTileNum::TeleCheckInEvil as u8
+            {
+                self.core.core.vel = vec2::default();
+            }
+        }
+
         fn set_weapon(&mut self, new_weapon: WeaponType) {
             if self.core.active_weapon == new_weapon {
                 return;
@@ -909,6 +962,11 @@ pub mod character {
             }
 
             let self_char = characters.char_mut(self_char_id).unwrap();
+            let old_hp = self_char.core.health + self_char.core.armor;
+            let hit_weapon = match &by {
+                DamageBy::Ninja => None,
+                DamageBy::Weapon { weapon, .. } => Some(*weapon),
+            };
             let res = Self::take_damage_from(
                 self_char,
                 self_char_id,
@@ -920,6 +978,17 @@ pub mod character {
                 from,
                 by,
             );
+            let self_char = characters.char_mut(self_char_id).unwrap();
+            let dmg = old_hp - (self_char.core.health + self_char.core.armor);
+            self_char.stats.damage_taken += dmg;
+            if let (FriendlyFireTy::Dmg, true, Some(killer)) =
+                (friendly_fire_ty, dmg > 0, characters.char_mut(&killer_id))
+            {
+                killer.stats.damage_dealt += dmg;
+                if let Some(weapon) = hit_weapon {
+                    killer.stats.weapon_mut(weapon).hits += 1;
+                }
+            }
             if let (CharacterDamageResult::Death, Some(killer)) =
                 (&res, characters.char_mut(&killer_id))
             {
@@ -1015,6 +1084,7 @@ pub mod character {
                             GameCharacterEventSound::HammerFire,
                         )),
                     );
+                    self.stats.weapon_mut(WeaponType::Hammer).shots += 1;
 
                     let mut hits = 0;
                     let core_pos = *self.pos.pos();
@@ -1097,6 +1167,7 @@ pub mod character {
                             GameCharacterEventSound::GunFire,
                         )),
                     );
+                    self.stats.weapon_mut(WeaponType::Gun).shots += 1;
 
                     let fire_delay = tunings.gun_fire_delay;
                     ((fire_delay * TICKS_PER_SECOND as f32 / 1000.0).ceil() as GameTickType).into()
@@ -1117,6 +1188,7 @@ pub mod character {
                             ty: WeaponWithProjectile::Shotgun,
                             lifetime: tunings.shotgun_lifetime,
                         });
+                        self.stats.weapon_mut(WeaponType::Shotgun).shots += 1;
                     }
 
                     self.push_sound(
@@ -1146,6 +1218,7 @@ pub mod character {
                             GameCharacterEventSound::GrenadeFire,
                         )),
                     );
+                    self.stats.weapon_mut(WeaponType::Grenade).shots += 1;
                     let fire_delay = tunings.grenade_fire_delay;
                     ((fire_delay * TICKS_PER_SECOND as f32 / 1000.0).ceil() as GameTickType).into()
                 }
@@ -1162,6 +1235,7 @@ pub mod character {
                             GameCharacterEventSound::LaserFire,
                         )),
                     );
+                    self.stats.weapon_mut(WeaponType::Laser).shots += 1;
 
                     let fire_delay = pipe.collision.get_tune_at(&proj_start_pos).laser_fire_delay;
                     ((fire_delay * TICKS_PER_SECOND as f32 / 1000.0).ceil() as GameTickType).into()
@@ -1501,6 +1575,7 @@ pub mod character {
         fn tick(&mut self, pipe: &mut SimulationPipeCharacter) -> EntityTickResult {
             self.mod_tick();
             self.handle_ticks();
+            self.stats.ticks_alive += 1;
 
             self.handle_weapon_switch(None, None);
 
@@ -73,15 +73,15 @@ pub mod character_core {
 
         pub(crate) jumped: i32,
         // counts the jumps performed in the air
-        jumped_total: i32,
-        jumps: i32,
+        pub(crate) jumped_total: i32,
+        pub(crate) jumps: i32,
         pub(crate) queued_jumps: u64,
 
         direction: i32,
 
         // DDRace
-        colliding: i32,
-        left_wall: bool,
+        pub(crate) colliding: i32,
+        pub(crate) left_wall: bool,
 
         // DDNet Character
         solo: bool,
@@ -436,7 +436,10 @@ pub mod character_core {
 
                     if *hook_state == HookState::HookFlying {
                         match hit {
-                            CollisionTile::None => {
+                            // only solid tiles and hook teleporters are requested
+                            CollisionTile::None
+                            | CollisionTile::PlayerTele(_)
+                            | CollisionTile::WeaponTele(_) => {
                                 *hook_pos = new_pos;
                             }
                             CollisionTile::Solid(tile) => {
@@ -459,20 +462,18 @@ pub mod character_core {
                                 }
                                 *hook_pos = new_pos;
                             }
-                            CollisionTile::PlayerTele(_) => todo!(),
-                            CollisionTile::HookTele(_) => {
-                                if false
-                                /* TODO: && self.m_pTeleOuts && !self.m_pTeleOuts->empty() && !(*self.m_pTeleOuts)[teleNr - 1].empty()*/
-                                {
+                            CollisionTile::HookTele(number) => {
+                                if let Some(tele_out) = collision.tele_out(number, &new_pos) {
                                     hooked_char = None;
                                     self.new_hook = true;
-                                    let _random_out = 0; // TODO: self.m_pWorld->RandomOr0((*self.m_pTeleOuts)[teleNr - 1].size());
-                                    *hook_pos = vec2::default(); // TODO: (*self.m_pTeleOuts)[teleNr - 1][RandomOut] + TargetDirection * PhysicalSize() * 1.5f;
+                                    *hook_pos = tele_out + target_direction * physical_size() * 1.5;
                                     *hook_dir = target_direction;
                                     *hook_tele_base = *hook_pos;
+                                } else {
+                                    // a teleporter without exit is ignored
+                                    *hook_pos = new_pos;
                                 }
                             }
-                            CollisionTile::WeaponTele(_) => todo!(),
                         }
                     }
                 }
@@ -5,6 +5,7 @@ pub mod player {
     use base::network_string::NetworkReducedAsciiString;
     use game_interface::account_info::MAX_ACCOUNT_NAME_LEN;
     use game_interface::client_commands::ClientCameraMode;
+    use game_interface::types::auto_director::{AutoDirector, AutoDirectorCandidate};
     use game_interface::types::character_info::NetworkCharacterInfo;
     use game_interface::types::game::{GameTickCooldown, GameTickType};
     use game_interface::types::id_types::{CharacterId, PlayerId, StageId};
@@ -101,12 +102,26 @@ pub mod player {
         }
     }
 
+    /// Decides how the spectated characters of a [`SpectatorPlayer`] are picked.
+    #[derive(Debug, Hiarc, Default, Clone, Copy, Serialize, Deserialize)]
+    pub enum SpectatorCamera {
+        /// The client picked the spectated characters itself.
+        #[default]
+        Characters,
+        /// All characters of the stage are spectated.
+        Stage(StageId),
+        /// The auto director picks the character to spectate.
+        AutoDirector(AutoDirector),
+    }
+
     #[derive(Debug, Hiarc, Serialize, Deserialize)]
     pub struct SpectatorPlayer {
         pub player_info: PlayerInfo,
         pub player_input: CharacterInput,
         pub id: PlayerId,
         pub spectated_characters: PoolFxHashSet<CharacterId>,
+        /// How the spectated characters are picked.
+        pub camera: SpectatorCamera,
         pub default_eye: TeeEye,
         pub default_eye_reset_in: GameTickCooldown,
 
@@ -128,6 +143,7 @@ pub mod player {
                 player_input,
                 id: *id,
                 spectated_characters,
+                camera: Default::default(),
                 default_eye,
                 default_eye_reset_in,
 
@@ -176,17 +192,68 @@ pub mod player {
             mode: ClientCameraMode,
         ) {
             if let Some(spectator) = self.players.get_mut(id) {
-                spectator.spectated_characters = match mode {
-                    ClientCameraMode::None => pool.new(),
+                (spectator.camera, spectator.spectated_characters) = match mode {
+                    ClientCameraMode::None => (SpectatorCamera::Characters, pool.new()),
                     ClientCameraMode::FreeCam(characters)
                     | ClientCameraMode::PhasedFreeCam(characters) => {
                         let mut item = pool.new();
                         (*item).clone_from(&characters);
-                        item
+                        (SpectatorCamera::Characters, item)
                     }
+                    // filled in the next tick, see `update_cameras`
+                    ClientCameraMode::Stage(stage_id) => {
+                        (SpectatorCamera::Stage(stage_id), pool.new())
+                    }
+                    ClientCameraMode::AutoDirector => (
+                        SpectatorCamera::AutoDirector(Default::default()),
+                        pool.new(),
+                    ),
                 };
             }
         }
+        /// Whether any spectator uses a camera that depends on the
+        /// current characters, see [`SpectatorPlayers::update_cameras`].
+        pub fn has_dynamic_cameras(&self) -> bool {
+            self.players
+                .values()
+                .any(|p| !matches!(p.camera, SpectatorCamera::Characters))
+        }
+        /// Updates the spectated characters of all spectators that
+        /// spectate a stage or use the auto director.
+        ///
+        /// `characters` are all characters of all stages.
+        pub(crate) fn update_cameras(
+            &mut self,
+            characters: &[(StageId, AutoDirectorCandidate)],
+            ticks_passed: GameTickType,
+            min_follow_ticks: GameTickType,
+        ) {
+            for spectator in self.players.values_mut() {
+                let spectated_characters = &mut spectator.spectated_characters;
+                match &mut spectator.camera {
+                    SpectatorCamera::Characters => {
+                        // nothing to do
+                    }
+                    SpectatorCamera::Stage(stage_id) => {
+                        spectated_characters.clear();
+                        spectated_characters.extend(
+                            characters
+                                .iter()
+                                .filter(|(id, _)| *id == *stage_id)
+                                .map(|(_, c)| c.id),
+                        );
+                    }
+                    SpectatorCamera::AutoDirector(director) => {
+                        spectated_characters.clear();
+                        spectated_characters.extend(director.update(
+                            characters.iter().map(|(_, c)| *c),
+                            ticks_passed,
+                            min_follow_ticks,
+                        ));
+                    }
+                }
+            }
+        }
         pub fn set_default_eye(&mut self, id: &PlayerId, eye: TeeEye, normal_in: GameTickType) {
             if let Some(spectator) = self.players.get_mut(id) {
                 spectator.default_eye = eye;
@@ -196,6 +263,12 @@ pub mod player {
         pub fn contains_key(&self, id: &PlayerId) -> bool {
             self.players.get(id).is_some()
         }
+        pub fn len(&self) -> usize {
+            self.players.len()
+        }
+        pub fn is_empty(&self) -> bool {
+            self.players.is_empty()
+        }
         pub fn any_with_name(&self, except_id: Option<PlayerId>, name: &str) -> bool {
             self.players
                 .iter()
@@ -218,7 +291,7 @@ pub mod player {
         ) {
             for (id, player) in self.players.iter() {
                 copy_pool.insert(*id, {
-                    SpectatorPlayer::new(
+                    let mut copy = SpectatorPlayer::new(
                         player.player_info.clone(),
                         player.player_input,
                         id,
@@ -226,7 +299,9 @@ pub mod player {
                         player.default_eye,
                         player.default_eye_reset_in,
                         player.network_stats,
-                    )
+                    );
+                    copy.camera = player.camera;
+                    copy
                 });
             }
         }
@@ -0,0 +1,71 @@
+pub mod character_stats {
+    use std::time::Duration;
+
+    use game_interface::types::{
+        emoticons::EnumCount,
+        game::GameTickType,
+        render::scoreboard::{ScoreboardCharacterStats, ScoreboardWeaponStats},
+        weapons::WeaponType,
+    };
+    use hiarc::Hiarc;
+    use serde::{Deserialize, Serialize};
+
+    use crate::state::state::TICKS_PER_SECOND;
+
+    #[derive(Debug, Hiarc, Default, Clone, Copy, Serialize, Deserialize)]
+    pub struct WeaponStats {
+        pub shots: u32,
+        /// Every character damaged by this weapon counts as hit.
+        pub hits: u32,
+        pub kills: u32,
+    }
+
+    /// Statistics of a character for the current match.
+    ///
+    /// Reset together with the score when a new match starts.
+    #[derive(Debug, Hiarc, Default, Clone, Copy, Serialize, Deserialize)]
+    pub struct CharacterStats {
+        pub weapons: [WeaponStats; WeaponType::COUNT],
+        pub ninja_kills: u32,
+        pub deaths: u32,
+        pub suicides: u32,
+
+        pub flag_grabs: u32,
+        pub flag_captures: u32,
+        pub flag_returns: u32,
+
+        pub damage_dealt: u32,
+        pub damage_taken: u32,
+
+        pub ticks_alive: GameTickType,
+    }
+
+    impl CharacterStats {
+        pub fn weapon_mut(&mut self, weapon: WeaponType) -> &mut WeaponStats {
+            &mut self.weapons[weapon as usize]
+        }
+
+        pub fn kills(&self) -> u32 {
+            self.weapons.iter().map(|w| w.kills).sum::<u32>() + self.ninja_kills
+        }
+
+        pub fn to_scoreboard(&self) -> ScoreboardCharacterStats {
+            ScoreboardCharacterStats {
+                weapons: self.weapons.map(|w| ScoreboardWeaponStats {
+                    shots: w.shots,
+                    hits: w.hits,
+                    kills: w.kills,
+                }),
+                ninja_kills: self.ninja_kills,
+                deaths: self.deaths,
+                suicides: self.suicides,
+                flag_grabs: self.flag_grabs,
+                flag_captures: self.flag_captures,
+                flag_returns: self.flag_returns,
+                damage_dealt: self.damage_dealt,
+                damage_taken: self.damage_taken,
+                time_alive: Duration::from_millis(self.ticks_alive * 1000 / TICKS_PER_SECOND),
+            }
+        }
+    }
+}
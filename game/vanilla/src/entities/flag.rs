@@ -112,6 +112,14 @@ pub mod flag {
             }
         }
 
+        fn push_event(&self, ev: FlagEvent) {
+            self.simulation_events
+                .push_world(SimulationEventWorldEntityType::Flag {
+                    id: self.base.game_element_id,
+                    ev,
+                });
+        }
+
         /// check for capture
         fn check_captured(&mut self, carrier: CharacterId, other_team_flags: &Flags) {
             for other_flag in other_team_flags.values() {
@@ -196,6 +204,9 @@ pub mod flag {
                                 None,
                                 GameWorldEntitySoundEvent::Flag(GameFlagEventSound::Return),
                             );
+                            self.push_event(FlagEvent::Return {
+                                by: Some(intersection.base.game_element_id),
+                            });
                             self.reset(pipe.is_prediction);
                             self.core.non_linear_event += 1;
                         }
@@ -209,6 +220,8 @@ pub mod flag {
                         );
                         self.core.carrier = Some(intersection.base.game_element_id);
                         self.core.drop_ticks = None;
+                        // only counted, a grab itself is not scored
+                        intersection.stats.flag_grabs += 1;
                     }
                 }
 
@@ -220,6 +233,7 @@ pub mod flag {
                                 None,
                                 GameWorldEntitySoundEvent::Flag(GameFlagEventSound::Return),
                             );
+                            self.push_event(FlagEvent::Return { by: None });
                             self.reset(pipe.is_prediction);
                             self.core.non_linear_event += 1;
                         }
@@ -57,6 +57,9 @@ pub mod laser {
 
         pub energy: f32,
         pub bounces: usize,
+        /// The exit of the weapon teleporter the laser hit,
+        /// the next evaluation continues from there.
+        pub tele_pos: Option<vec2>,
         pub next_eval_in: GameTickCooldownAndLastActionCounter,
         // TODO: int m_Owner;
         // TODO: int m_TeamMask;
@@ -105,6 +108,7 @@ pub mod laser {
                 from: *pos,
                 ty: LaserType::Rifle,
                 bounces: 0,
+                tele_pos: None,
                 dir: *dir,
                 energy: start_energy,
                 next_eval_in: Default::default(),
@@ -273,6 +277,10 @@ pub mod laser {
             if self.core.energy < 0.0 {
                 return false;
             }
+            if let Some(tele_pos) = self.core.tele_pos.take() {
+                self.core.from = tele_pos;
+                self.core.pos = tele_pos;
+            }
             //self.core.m_PrevPos = self.core.pos;
             let mut col_tile = vec2::default();
 
@@ -286,7 +294,21 @@ pub mod laser {
                 CollisionTypes::SOLID | CollisionTypes::WEAPON_TELE,
             );
 
-            if !matches!(res, CollisionTile::None) {
+            let tele_out = match res {
+                CollisionTile::WeaponTele(number) => pipe.collision.tele_out(number, &col_tile),
+                _ => None,
+            };
+
+            if let Some(tele_out) = tele_out {
+                let cur_pos = self.core.pos;
+                if !self.hit_character(pipe, &cur_pos, &to) {
+                    let core = &mut self.core;
+                    core.from = core.pos;
+                    core.pos = to;
+                    core.energy -= distance(&core.from, &core.pos);
+                    core.tele_pos = Some(tele_out);
+                }
+            } else if !matches!(res, CollisionTile::None) {
                 let cur_pos = self.core.pos;
                 if !self.hit_character(pipe, &cur_pos, &to) {
                     let core = &mut self.core;
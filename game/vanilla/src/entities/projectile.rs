@@ -240,6 +240,11 @@ pub mod projectile {
                 &mut dummy_pos,
                 CollisionTypes::SOLID | CollisionTypes::WEAPON_TELE,
             );
+            // weapon teleporters without an exit act like solid tiles
+            let tele_out = match collide {
+                CollisionTile::WeaponTele(number) => pipe.collision.tele_out(number, &cur_pos),
+                _ => None,
+            };
 
             self.core.life_span -= 1;
 
@@ -252,7 +257,7 @@ pub mod projectile {
             );
 
             let res = if intersection.is_some()
-                || !matches!(collide, CollisionTile::None)
+                || (!matches!(collide, CollisionTile::None) && tele_out.is_none())
                 || self.core.life_span < 0
                 || Entity::<ProjectileId>::outside_of_playfield(&cur_pos, pipe.collision)
             {
@@ -293,7 +298,9 @@ pub mod projectile {
             } else {
                 EntityTickResult::None
             };
-            self.core.pos = cur_pos;
+            self.core.pos = tele_out
+                .filter(|_| matches!(res, EntityTickResult::None))
+                .unwrap_or(cur_pos);
             res
         }
 
@@ -54,6 +54,11 @@ pub mod events {
             by: CharacterId,
             pos: vec2,
         },
+        /// The flag returned to its spawn, either because a
+        /// character of its side touched it or by timeout.
+        Return {
+            by: Option<CharacterId>,
+        },
     }
 
     #[derive(Debug, Hiarc, Serialize, Deserialize)]
@@ -2,6 +2,7 @@
 #![allow(clippy::module_inception)]
 #![allow(clippy::multiple_bound_locations)]
 
+pub mod bots;
 pub mod collision;
 pub mod command_chain;
 pub mod config;
@@ -11,6 +12,8 @@ pub mod game_objects;
 pub mod match_manager;
 pub mod match_state;
 pub mod reusable;
+#[cfg(test)]
+mod replay;
 pub mod simulation_pipe;
 pub mod snapshot;
 pub mod spawns;
@@ -18,6 +21,8 @@ pub mod spawns;
 pub mod sql;
 pub mod stage;
 pub mod state;
+#[cfg(test)]
+mod teleporters;
 pub mod types;
 pub mod weapons;
 pub mod world;
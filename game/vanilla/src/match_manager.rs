@@ -2,13 +2,19 @@
 pub mod match_manager {
     use std::time::Duration;
 
-    use game_interface::types::{game::GameTickType, render::game::game_match::MatchSide};
+    use game_interface::{
+        events::GameWorldActionKillWeapon,
+        types::{game::GameTickType, id_types::CharacterId, render::game::game_match::MatchSide},
+    };
     use hiarc::{hi_closure, Hiarc};
+    use rustc_hash::FxHashSet;
+    use serde::Serialize;
 
     use crate::{
         config::config::ConfigGameType,
+        entities::character::stats::character_stats::CharacterStats,
         events::events::{CharacterEvent, CharacterEventMod, FlagEvent},
-        match_state::match_state::{Match, MatchState, MatchType},
+        match_state::match_state::{Match, MatchState, MatchType, MatchWinner},
         simulation_pipe::simulation_pipe::{
             SimulationEventWorldEntityType, SimulationStageEvents, SimulationWorldEvent,
         },
@@ -17,16 +23,73 @@ pub mod match_manager {
         world::world::GameWorld,
     };
 
+    /// How a new match starts.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum MatchStart {
+        /// Starts with a warmup, if the game options ask for one.
+        Warmup,
+        /// Starts the match directly.
+        Running,
+    }
+
+    #[derive(Debug, Hiarc, Clone, Serialize)]
+    pub enum MatchResultWinner {
+        Player(String),
+        Side(MatchSide),
+    }
+
+    #[derive(Debug, Hiarc, Clone, Serialize)]
+    pub struct MatchResultPlayer {
+        pub name: String,
+        pub clan: String,
+        pub side: Option<MatchSide>,
+        pub score: i64,
+        pub stats: CharacterStats,
+    }
+
+    /// The result of a match, see [`MatchManager::take_result`].
+    #[derive(Debug, Hiarc, Clone, Serialize)]
+    pub struct MatchResult {
+        pub game_type: ConfigGameType,
+        /// `None` if the match is not over yet.
+        pub winner: Option<MatchResultWinner>,
+        /// The scores of red & blue, if the match was sided.
+        pub side_scores: Option<[i64; 2]>,
+        pub duration_secs: u64,
+        pub by_time_limit: bool,
+        pub players: Vec<MatchResultPlayer>,
+    }
+
     #[derive(Debug, Hiarc)]
     pub struct MatchManager {
         pub(crate) game_options: GameOptions,
         simulation_events: SimulationStageEvents,
 
         pub(crate) game_match: Match,
+
+        /// Players that used `/ready` during the warmup.
+        ready_players: FxHashSet<CharacterId>,
+        /// Whether the game over of this match was already handled.
+        game_over_handled: bool,
+        result: Option<MatchResult>,
     }
 
     impl MatchManager {
-        pub fn new(game_options: GameOptions, simulation_events: &SimulationStageEvents) -> Self {
+        fn ticks_from_duration(time: Option<Duration>) -> GameTickType {
+            time.map(|time| {
+                ((time.as_micros() * TICKS_PER_SECOND as u128) / Duration::from_secs(1).as_micros())
+                    as GameTickType
+            })
+            .unwrap_or_default()
+        }
+
+        pub fn new(
+            game_options: GameOptions,
+            simulation_events: &SimulationStageEvents,
+            start: MatchStart,
+        ) -> Self {
+            let warmup = start == MatchStart::Warmup
+                && (game_options.tournament_mode() || game_options.warmup_time().is_some());
             Self {
                 game_match: Match {
                     ty: match game_options.ty() {
@@ -35,22 +98,121 @@ pub mod match_manager {
                             scores: Default::default(),
                         },
                     },
-                    state: MatchState::Running {
-                        round_ticks_passed: Default::default(),
-                        round_ticks_left: game_options
-                            .time_limit()
-                            .map(|time| {
-                                ((time.as_micros() * TICKS_PER_SECOND as u128)
-                                    / Duration::from_secs(1).as_micros())
-                                    as GameTickType
-                            })
-                            .unwrap_or_default()
-                            .into(),
+                    state: if warmup {
+                        MatchState::Warmup {
+                            ticks_left: Self::ticks_from_duration(game_options.warmup_time())
+                                .into(),
+                        }
+                    } else {
+                        MatchState::Running {
+                            round_ticks_passed: Default::default(),
+                            round_ticks_left: Self::ticks_from_duration(game_options.time_limit())
+                                .into(),
+                        }
                     },
                     balance_tick: Default::default(),
                 },
                 game_options,
                 simulation_events: simulation_events.clone(),
+
+                ready_players: Default::default(),
+                game_over_handled: false,
+                result: None,
+            }
+        }
+
+        pub fn is_ready(&self, id: &CharacterId) -> bool {
+            self.ready_players.contains(id)
+        }
+
+        pub fn set_ready(&mut self, id: &CharacterId, ready: bool) {
+            if ready {
+                self.ready_players.insert(*id);
+            } else {
+                self.ready_players.remove(id);
+            }
+        }
+
+        /// Returns how many of the characters in the world are ready
+        /// and how many characters there are in total.
+        pub fn ready_count(&self, world: &GameWorld) -> (usize, usize) {
+            let ready = world
+                .characters
+                .keys()
+                .filter(|id| self.ready_players.contains(id))
+                .count();
+            (ready, world.characters.len())
+        }
+
+        fn all_ready(&self, world: &GameWorld) -> bool {
+            let (ready, total) = self.ready_count(world);
+            let sides_filled = match self.game_match.ty {
+                MatchType::Solo => true,
+                MatchType::Sided { .. } => {
+                    let (red, blue) = world.count_sides();
+                    red > 0 && blue > 0
+                }
+            };
+            total > 0 && ready == total && sides_filled
+        }
+
+        /// The result of the match, once it is over.
+        /// Only tournament matches or matches with
+        /// exported statistics keep their result.
+        pub fn take_result(&mut self) -> Option<MatchResult> {
+            self.result.take()
+        }
+
+        /// Collects the current result of the match,
+        /// which is then handled like the result of a finished match.
+        pub fn export_result(&mut self, world: &GameWorld) {
+            self.result = Some(self.collect_result(world));
+        }
+
+        /// Collects the result of the match, this can also be used
+        /// for a match that is still running.
+        pub fn collect_result(&self, world: &GameWorld) -> MatchResult {
+            let (winner, by_time_limit) = match self.game_match.state {
+                MatchState::GameOver {
+                    winner,
+                    by_cooldown,
+                    ..
+                } => (Some(winner), by_cooldown),
+                MatchState::Running { .. }
+                | MatchState::Paused { .. }
+                | MatchState::SuddenDeath { .. }
+                | MatchState::PausedSuddenDeath { .. }
+                | MatchState::Warmup { .. } => (None, false),
+            };
+            MatchResult {
+                game_type: self.game_options.game_ty(),
+                winner: winner.map(|winner| match winner {
+                    MatchWinner::Character(id) => MatchResultWinner::Player(
+                        world
+                            .characters
+                            .get(&id)
+                            .map(|char| char.player_info.player_info.name.to_string())
+                            .unwrap_or_default(),
+                    ),
+                    MatchWinner::Side(side) => MatchResultWinner::Side(side),
+                }),
+                side_scores: match self.game_match.ty {
+                    MatchType::Solo => None,
+                    MatchType::Sided { scores } => Some(scores),
+                },
+                duration_secs: self.game_match.state.passed_ticks() / TICKS_PER_SECOND,
+                by_time_limit,
+                players: world
+                    .characters
+                    .values()
+                    .map(|char| MatchResultPlayer {
+                        name: char.player_info.player_info.name.to_string(),
+                        clan: char.player_info.player_info.clan.to_string(),
+                        side: char.core.side,
+                        score: char.score.get(),
+                        stats: char.stats,
+                    })
+                    .collect(),
             }
         }
 
@@ -79,13 +241,30 @@ pub mod match_manager {
                         SimulationWorldEvent::Entity(entity_ev) => match &entity_ev.ev {
                             SimulationEventWorldEntityType::Character { ev, .. } => {
                                 match ev {
-                                    CharacterEvent::Despawn { killer_id, id: victim_id, .. } => {
+                                    CharacterEvent::Despawn { killer_id, id: victim_id, weapon } => {
+                                        if let Some(victim) = world.characters.get_mut(victim_id) {
+                                            victim.stats.deaths += 1;
+                                            if killer_id.is_none_or(|killer_id| killer_id == *victim_id) {
+                                                victim.stats.suicides += 1;
+                                            }
+                                        }
                                         if let Some(char) = killer_id.and_then(|killer_id| world.characters.get_mut(&killer_id)) {
                                             if Some(*victim_id) == *killer_id {
                                                 char.score.set(char.score.get() - 1);
                                             }
                                             else {
                                                 char.score.set(char.score.get() + 1);
+                                                match weapon {
+                                                    GameWorldActionKillWeapon::Weapon { weapon } => {
+                                                        char.stats.weapon_mut(*weapon).kills += 1;
+                                                    }
+                                                    GameWorldActionKillWeapon::Ninja => {
+                                                        char.stats.ninja_kills += 1;
+                                                    }
+                                                    GameWorldActionKillWeapon::World => {
+                                                        // ignore
+                                                    }
+                                                }
                                                 if let (MatchType::Sided { scores }, Some(score)) = (&mut game_match.ty, char.core.side) {
                                                     scores[score as usize] += MatchManager::side_score_player_kill(game_options);
                                                 }
@@ -103,6 +282,7 @@ pub mod match_manager {
                                     FlagEvent::Capture { by, .. } => {
                                         if let Some(char) = world.characters.get_mut(by) {
                                             char.score.set(char.score.get() + 5);
+                                            char.stats.flag_captures += 1;
                                             if let (MatchType::Sided { scores }, Some(score)) = (&mut game_match.ty, char.core.side) {
                                                 scores[score as usize] += 100;
                                             }
@@ -118,6 +298,11 @@ pub mod match_manager {
                                             game_match.win_check(game_options, &world.scores, false);
                                         }
                                     },
+                                    FlagEvent::Return { by } => {
+                                        if let Some(char) = by.and_then(|by| world.characters.get_mut(&by)) {
+                                            char.stats.flag_returns += 1;
+                                        }
+                                    },
                                     FlagEvent:: Despawn {
                                       ..
                                     } => {
@@ -142,16 +327,8 @@ pub mod match_manager {
         fn auto_sided_balance(&mut self, world: &mut GameWorld) {
             if Self::needs_sided_balance(world) {
                 if self.game_match.balance_tick.is_none() {
-                    self.game_match.balance_tick = self
-                        .game_options
-                        .sided_balance_time()
-                        .map(|time| {
-                            ((time.as_micros() * TICKS_PER_SECOND as u128)
-                                / Duration::from_secs(1).as_micros())
-                                as GameTickType
-                        })
-                        .unwrap_or_default()
-                        .into();
+                    self.game_match.balance_tick =
+                        Self::ticks_from_duration(self.game_options.sided_balance_time()).into();
                 } else if self.game_match.balance_tick.tick().unwrap_or_default() {
                     // force auto balance
                     let (red, blue) = world.count_sides();
@@ -180,37 +357,61 @@ pub mod match_manager {
             }
         }
 
-        /// returns true, if match needs a restart
+        fn reset_characters(world: &mut GameWorld) {
+            world.characters.values_mut().for_each(|char| {
+                char.score.set(0);
+                char.stats = Default::default();
+                char.despawn_to_respawn(false);
+            });
+        }
+
+        /// Returns how the next match starts, if the match needs a restart.
         #[must_use]
-        pub fn tick(&mut self, world: &mut GameWorld) -> bool {
+        pub fn tick(&mut self, world: &mut GameWorld) -> Option<MatchStart> {
             self.handle_events(world);
 
-            if let MatchState::GameOver { new_game_in, .. } = &mut self.game_match.state {
-                if new_game_in.tick().unwrap_or_default() {
-                    self.game_match.state = MatchState::Running {
-                        round_ticks_passed: Default::default(),
-                        round_ticks_left: self
-                            .game_options
-                            .time_limit()
-                            .map(|time| {
-                                ((time.as_micros() * TICKS_PER_SECOND as u128)
-                                    / Duration::from_secs(1).as_micros())
-                                    as GameTickType
-                            })
-                            .unwrap_or_default()
-                            .into(),
-                    };
-                    world.characters.values_mut().for_each(|char| {
-                        char.score.set(0);
-                        char.despawn_to_respawn(false);
-                    });
-                    true
-                } else {
-                    false
+            if !self.game_over_handled
+                && matches!(self.game_match.state, MatchState::GameOver { .. })
+            {
+                self.game_over_handled = true;
+                if self.game_options.tournament_mode() || self.game_options.export_match_stats() {
+                    self.result = Some(self.collect_result(world));
+                }
+            }
+
+            match &mut self.game_match.state {
+                MatchState::GameOver { new_game_in, .. } => {
+                    if new_game_in.tick().unwrap_or_default() {
+                        Self::reset_characters(world);
+                        // outside of tournaments only the first match has a warmup
+                        Some(if self.game_options.tournament_mode() {
+                            MatchStart::Warmup
+                        } else {
+                            MatchStart::Running
+                        })
+                    } else {
+                        None
+                    }
+                }
+                MatchState::Warmup { ticks_left } => {
+                    if ticks_left.is_none()
+                        && (!self.game_options.tournament_mode() || self.all_ready(world))
+                    {
+                        Self::reset_characters(world);
+                        Some(MatchStart::Running)
+                    } else {
+                        None
+                    }
+                }
+                MatchState::Running { .. }
+                | MatchState::Paused { .. }
+                | MatchState::SuddenDeath { .. }
+                | MatchState::PausedSuddenDeath { .. } => {
+                    if !self.game_options.tournament_mode() {
+                        self.auto_sided_balance(world);
+                    }
+                    None
                 }
-            } else {
-                self.auto_sided_balance(world);
-                false
             }
         }
     }
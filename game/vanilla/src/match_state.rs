@@ -54,6 +54,12 @@ pub mod match_state {
             round_ticks_passed: GameTickType,
             by_cooldown: bool,
         },
+        /// Players can warm up before the actual match starts.
+        Warmup {
+            /// If no cooldown is active (anymore), the match
+            /// starts as soon as all players are ready.
+            ticks_left: GameTickCooldown,
+        },
     }
 
     impl MatchState {
@@ -74,6 +80,7 @@ pub mod match_state {
                 MatchState::GameOver {
                     round_ticks_passed, ..
                 } => *round_ticks_passed,
+                MatchState::Warmup { .. } => 0,
             }
         }
 
@@ -85,18 +92,19 @@ pub mod match_state {
             match self {
                 MatchState::Running {
                     round_ticks_left, ..
-                }
-                | MatchState::Paused {
-                    round_ticks_left, ..
                 } => round_ticks_left
                     .get()
                     .map(|ticks_left| MatchRoundTimeType::TimeLimit {
                         ticks_left: ticks_left.get(),
                     })
                     .unwrap_or(MatchRoundTimeType::Normal),
-                MatchState::SuddenDeath { .. } | MatchState::PausedSuddenDeath { .. } => {
-                    MatchRoundTimeType::SuddenDeath
+                MatchState::SuddenDeath { .. } => MatchRoundTimeType::SuddenDeath,
+                MatchState::Paused { .. } | MatchState::PausedSuddenDeath { .. } => {
+                    MatchRoundTimeType::Paused
                 }
+                MatchState::Warmup { ticks_left } => MatchRoundTimeType::Warmup {
+                    ticks_left: ticks_left.get().map(|ticks_left| ticks_left.get()),
+                },
                 MatchState::GameOver {
                     winner,
                     by_cooldown,
@@ -185,6 +193,10 @@ pub mod match_state {
             scores: &CharacterScores,
             round_time_limit_reached: bool,
         ) {
+            // nobody can win a warmup
+            if let MatchState::Warmup { .. } = self.state {
+                return;
+            }
             let cur_tick = self.state.passed_ticks();
             let round_time_limit_reached = round_time_limit_reached
                 | matches!(
@@ -264,6 +276,9 @@ pub mod match_state {
                 } => {
                     *round_ticks_passed += 1;
                 }
+                MatchState::Warmup { ticks_left } => {
+                    ticks_left.tick();
+                }
                 MatchState::Paused { .. }
                 | MatchState::PausedSuddenDeath { .. }
                 | MatchState::GameOver { .. } => {
@@ -271,5 +286,70 @@ pub mod match_state {
                 }
             }
         }
+
+        /// Pauses a running match, which freezes the world until
+        /// [`Match::unpause`] is called.
+        ///
+        /// Returns `false` if the match was not running.
+        pub fn pause(&mut self) -> bool {
+            match self.state {
+                MatchState::Running {
+                    round_ticks_passed,
+                    round_ticks_left,
+                } => {
+                    self.state = MatchState::Paused {
+                        round_ticks_passed,
+                        round_ticks_left,
+                    };
+                    true
+                }
+                MatchState::SuddenDeath {
+                    round_ticks_passed,
+                    by_cooldown,
+                } => {
+                    self.state = MatchState::PausedSuddenDeath {
+                        round_ticks_passed,
+                        by_cooldown,
+                    };
+                    true
+                }
+                MatchState::Paused { .. }
+                | MatchState::PausedSuddenDeath { .. }
+                | MatchState::GameOver { .. }
+                | MatchState::Warmup { .. } => false,
+            }
+        }
+
+        /// Continues a match paused by [`Match::pause`].
+        ///
+        /// Returns `false` if the match was not paused.
+        pub fn unpause(&mut self) -> bool {
+            match self.state {
+                MatchState::Paused {
+                    round_ticks_passed,
+                    round_ticks_left,
+                } => {
+                    self.state = MatchState::Running {
+                        round_ticks_passed,
+                        round_ticks_left,
+                    };
+                    true
+                }
+                MatchState::PausedSuddenDeath {
+                    round_ticks_passed,
+                    by_cooldown,
+                } => {
+                    self.state = MatchState::SuddenDeath {
+                        round_ticks_passed,
+                        by_cooldown,
+                    };
+                    true
+                }
+                MatchState::Running { .. }
+                | MatchState::SuddenDeath { .. }
+                | MatchState::GameOver { .. }
+                | MatchState::Warmup { .. } => false,
+            }
+        }
     }
 }
@@ -0,0 +1,307 @@
+//! Deterministic input replays to catch accidental physics changes
+//! and prediction desyncs.
+//!
+//! A replay loads a map, lets a fixed number of players join and
+//! feeds an input script (one [`CharacterInput`] per player per tick)
+//! into the game. After every tick the snapshot of the whole world is
+//! hashed and compared against a golden file.
+//!
+//! Missing or differing files fail the test. To record missing input
+//! scripts from seeded random inputs and to (re)write the golden files
+//! from the current physics, run the tests with `VANILLA_BLESS_REPLAY=1`
+//! and commit the written files.
+
+use std::{path::PathBuf, sync::Arc};
+
+use base::{
+    hash::{fmt_hash, generate_hash_for},
+    linked_hash_map_view::FxLinkedHashMap,
+};
+use base_io::{io::create_runtime, runtime::IoRuntime};
+use game_database::dummy::DummyDb;
+use game_interface::{
+    interface::{GameStateCreate, GameStateCreateOptions, GameStateInterface},
+    types::{
+        character_info::NetworkCharacterInfo,
+        id_types::PlayerId,
+        input::{cursor::CharacterInputCursor, CharacterInput, CharacterInputInfo},
+        network_stats::PlayerNetworkStats,
+        player_info::{PlayerClientInfo, PlayerUniqueId},
+        snapshot::SnapshotClientInfo,
+        ticks::TickOptions,
+    },
+};
+use math::math::{vector::dvec2, Rng};
+use pool::pool::Pool;
+use serde::{Deserialize, Serialize};
+
+use crate::{config::config::ConfigVanilla, state::state::GameState};
+
+/// The inputs of all players for every tick.
+#[derive(Debug, Serialize, Deserialize)]
+struct InputScript {
+    players: usize,
+    ticks: Vec<Vec<CharacterInput>>,
+}
+
+impl InputScript {
+    /// Seeded random inputs, including consumable inputs,
+    /// so that weapons, hooks and jumps are part of the replay.
+    fn record(players: usize, ticks: usize, seed: u64) -> Self {
+        let mut rng = Rng::new(seed);
+        let mut cur = vec![CharacterInput::default(); players];
+        let ticks = (0..ticks)
+            .map(|_| {
+                cur.iter_mut()
+                    .map(|inp| {
+                        if rng.random_int_in(0..=10) == 0 {
+                            inp.state.dir.set(rng.random_int_in(0..=2) as i32 - 1);
+                        }
+                        inp.cursor.set(CharacterInputCursor::from_vec2(&dvec2::new(
+                            rng.random_float_in(-300.0..=300.0) as f64,
+                            rng.random_float_in(-300.0..=300.0) as f64,
+                        )));
+                        if rng.random_int_in(0..=8) == 0 {
+                            let jump = !*inp.state.jump;
+                            if jump {
+                                inp.consumable.jump.add(1);
+                            }
+                            inp.state.jump.set(jump);
+                        }
+                        if rng.random_int_in(0..=8) == 0 {
+                            let hook = !*inp.state.hook;
+                            if hook {
+                                inp.consumable.hook.add(1, *inp.cursor);
+                            }
+                            inp.state.hook.set(hook);
+                        }
+                        if rng.random_int_in(0..=8) == 0 {
+                            let fire = !*inp.state.fire;
+                            if fire {
+                                inp.consumable.fire.add(1, *inp.cursor);
+                            }
+                            inp.state.fire.set(fire);
+                        }
+                        *inp
+                    })
+                    .collect()
+            })
+            .collect();
+        Self { players, ticks }
+    }
+}
+
+/// Snapshot hashes of every tick of a replay.
+#[derive(Debug, Serialize, Deserialize)]
+struct Golden {
+    hashes: Vec<String>,
+}
+
+fn test_data_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("test-data")
+        .join("replay")
+        .join(name)
+}
+
+fn bless() -> bool {
+    std::env::var("VANILLA_BLESS_REPLAY").is_ok_and(|val| val != "0")
+}
+
+fn write_json<T: Serialize>(path: &PathBuf, val: &T) {
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(path, serde_json::to_vec_pretty(val).unwrap()).unwrap();
+}
+
+fn load_or_record_script(name: &str, players: usize, ticks: usize) -> InputScript {
+    let path = test_data_path(&format!("{name}.inputs.json"));
+    match std::fs::read(&path) {
+        Ok(file) => serde_json::from_slice(&file).unwrap(),
+        Err(err) => {
+            assert!(
+                bless(),
+                "the input script {path:?} could not be read: {err}. \
+                Run with VANILLA_BLESS_REPLAY=1 to record it."
+            );
+            let script = InputScript::record(players, ticks, 0);
+            write_json(&path, &script);
+            script
+        }
+    }
+}
+
+fn create_game(players: usize) -> GameState {
+    let file = include_bytes!("../../../data/map/maps/ctf1.twmap");
+
+    let rt = create_runtime();
+    let io_rt = IoRuntime::new(rt);
+    let (game, _) = GameState::new(
+        file.to_vec(),
+        "ctf1".try_into().unwrap(),
+        GameStateCreateOptions {
+            hint_max_characters: Some(players),
+            config: Some(
+                serde_json::to_vec(&ConfigVanilla {
+                    max_ingame_players: players as u32,
+                    ..Default::default()
+                })
+                .unwrap(),
+            ),
+            ..Default::default()
+        },
+        io_rt,
+        Arc::new(DummyDb),
+    )
+    .unwrap();
+    game
+}
+
+fn join_players(game: &mut GameState, players: usize) -> Vec<PlayerId> {
+    (0..players)
+        .map(|index| {
+            game.player_join(&PlayerClientInfo {
+                info: NetworkCharacterInfo::explicit_default(),
+                id: index as u64,
+                unique_identifier: PlayerUniqueId::Account(index as u64),
+                initial_network_stats: PlayerNetworkStats::default(),
+            })
+        })
+        .collect()
+}
+
+/// Keeps track of the previous inputs to build the consumable diffs.
+struct ScriptPlayer {
+    pool: Pool<FxLinkedHashMap<PlayerId, CharacterInputInfo>>,
+    prev: Vec<CharacterInput>,
+}
+
+impl ScriptPlayer {
+    fn new(players: usize) -> Self {
+        Self {
+            pool: Pool::with_capacity(2),
+            prev: vec![CharacterInput::default(); players],
+        }
+    }
+
+    /// Sets the inputs of a tick for all given games.
+    fn set_inputs(
+        &mut self,
+        games: &mut [&mut GameState],
+        ids: &[PlayerId],
+        tick: &[CharacterInput],
+    ) {
+        let inps: Vec<_> = ids
+            .iter()
+            .zip(tick.iter())
+            .zip(self.prev.iter_mut())
+            .map(|((id, inp), prev)| {
+                let diff = inp.consumable.diff(&prev.consumable);
+                *prev = *inp;
+                (*id, CharacterInputInfo { inp: *inp, diff })
+            })
+            .collect();
+        for game in games.iter_mut() {
+            let mut game_inps = self.pool.new();
+            game_inps.extend(inps.iter().map(|(id, inp)| (*id, inp.clone())));
+            game.set_player_inputs(game_inps);
+        }
+    }
+}
+
+fn snapshot_hash(game: &GameState) -> String {
+    let snapshot = game.snapshot_for(SnapshotClientInfo::Everything);
+    fmt_hash(&generate_hash_for(&snapshot))
+}
+
+/// Runs the script and returns the snapshot hash of every tick.
+fn replay(script: &InputScript) -> Vec<String> {
+    let mut game = create_game(script.players);
+    let ids = join_players(&mut game, script.players);
+    let mut player = ScriptPlayer::new(script.players);
+
+    script
+        .ticks
+        .iter()
+        .map(|tick| {
+            player.set_inputs(&mut [&mut game], &ids, tick);
+            game.tick(Default::default());
+            game.clear_events();
+            snapshot_hash(&game)
+        })
+        .collect()
+}
+
+fn first_mismatch(a: &[String], b: &[String]) -> Option<usize> {
+    a.iter()
+        .zip(b.iter())
+        .position(|(a, b)| a != b)
+        .or((a.len() != b.len()).then_some(a.len().min(b.len())))
+}
+
+#[test]
+fn replay_matches_golden() {
+    let script = load_or_record_script("ctf1", 8, 50 * 20);
+    let hashes = replay(&script);
+
+    let path = test_data_path("ctf1.golden.json");
+    if bless() {
+        write_json(&path, &Golden { hashes });
+        return;
+    }
+    let golden = std::fs::read(&path).unwrap_or_else(|err| {
+        panic!(
+            "the golden file {path:?} could not be read: {err}. \
+            Run with VANILLA_BLESS_REPLAY=1 to write it."
+        )
+    });
+    let golden: Golden = serde_json::from_slice(&golden).unwrap();
+    let mismatch = first_mismatch(&golden.hashes, &hashes);
+    assert!(
+        mismatch.is_none(),
+        "the world differs from the golden file starting at tick {mismatch:?}. \
+        If the physics change is intended, run with VANILLA_BLESS_REPLAY=1."
+    );
+}
+
+#[test]
+fn replay_is_deterministic() {
+    let script = InputScript::record(8, 50 * 5, 1);
+    let first = replay(&script);
+    let second = replay(&script);
+    assert_eq!(first_mismatch(&first, &second), None);
+}
+
+/// A client that builds the world from the server's snapshot and
+/// predicts the next tick with the same inputs must end up with
+/// exactly the same world as the server.
+#[test]
+fn snapshot_prediction_matches_server() {
+    let script = InputScript::record(8, 50 * 5, 2);
+    let mut server = create_game(script.players);
+    let mut client = create_game(script.players);
+    let ids = join_players(&mut server, script.players);
+    let mut player = ScriptPlayer::new(script.players);
+
+    for (tick, inps) in script.ticks.iter().enumerate() {
+        let snapshot = server.snapshot_for(SnapshotClientInfo::Everything);
+        // panics if the snapshot can not be decoded
+        client.build_from_snapshot(&snapshot);
+
+        player.set_inputs(&mut [&mut server, &mut client], &ids, inps);
+        server.tick(Default::default());
+        client.tick(TickOptions {
+            is_future_tick_prediction: true,
+        });
+        server.clear_events();
+        client.clear_events();
+
+        let server_snapshot = server.snapshot_for(SnapshotClientInfo::Everything);
+        let client_snapshot = client.snapshot_for(SnapshotClientInfo::Everything);
+        let (server_snapshot, client_snapshot): (&[u8], &[u8]) =
+            (&server_snapshot, &client_snapshot);
+        assert!(
+            server_snapshot == client_snapshot,
+            "the predicted world differs from the server's world at tick {tick}"
+        );
+    }
+}
@@ -38,6 +38,7 @@ pub mod snapshot {
                 player::player::{
                     PlayerCharacterInfo, PlayerInfo, Players, SpectatorPlayer, SpectatorPlayers,
                 },
+                stats::character_stats::CharacterStats,
             },
             entity::entity::{DropMode, EntityInterface},
             flag::flag::{Flag, FlagCore, FlagReusableCore, Flags, PoolFlagReusableCore},
@@ -94,6 +95,7 @@ pub mod snapshot {
         pub pos: vec2,
         pub phased: SnapshotCharacterPhasedState,
         pub score: i64,
+        pub stats: CharacterStats,
 
         pub game_el_id: CharacterId,
     }
@@ -301,6 +303,9 @@ pub mod snapshot {
         pub voted_player: Option<PlayerId>,
 
         pub global_tune_zone: Tunings,
+
+        /// See [`GameState::teams_locked`].
+        pub teams_locked: bool,
     }
 
     impl Snapshot {
@@ -309,6 +314,7 @@ pub mod snapshot {
             id_generator_id: GameEntityId,
             voted_player: Option<PlayerId>,
             global_tune_zone: Tunings,
+            teams_locked: bool,
         ) -> Self {
             Self {
                 stages: pool.stages_pool.new(),
@@ -317,6 +323,7 @@ pub mod snapshot {
                 id_generator_id,
                 voted_player,
                 global_tune_zone,
+                teams_locked,
             }
         }
     }
@@ -370,6 +377,7 @@ pub mod snapshot {
                             }
                         },
                         score: char.score.get(),
+                        stats: char.stats,
                         game_el_id: char.base.game_element_id,
                         ty: if let Some(network_stats) = char.is_player_character() {
                             SnapshotCharacterPlayerTy::Player(network_stats)
@@ -516,6 +524,7 @@ pub mod snapshot {
                 game.id_generator.peek_next_id(),
                 game.game.voted_player,
                 game.collision.tune_zones[0],
+                game.teams_locked,
             );
             if let SnapshotFor::Client(client) = snap_for {
                 match client {
@@ -797,6 +806,7 @@ pub mod snapshot {
                         },
                     }
                     stage_char.score.set(char.score);
+                    stage_char.stats = char.stats;
                 });
 
                 // go through all projectiles of the stage, add missing ones
@@ -1016,6 +1026,8 @@ pub mod snapshot {
 
             write_game_state.collision.tune_zones[0] = snapshot.global_tune_zone;
 
+            write_game_state.teams_locked = snapshot.teams_locked;
+
             snapshot.local_players
         }
     }
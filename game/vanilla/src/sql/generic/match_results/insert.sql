@@ -0,0 +1,7 @@
+INSERT INTO vanilla_match_results (
+    map_name,
+    game_type,
+    result
+)
+VALUES
+    (?, ?, ?);
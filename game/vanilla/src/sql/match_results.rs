@@ -0,0 +1,87 @@
+use std::{collections::HashMap, sync::Arc};
+
+use anyhow::anyhow;
+use game_database::{
+    statement::{Statement, StatementBuilder},
+    traits::{DbInterface, DbKind, DbKindExtra, SqlText},
+    StatementArgs,
+};
+
+#[derive(Debug, StatementArgs)]
+struct InsertArg {
+    map_name: String,
+    game_type: String,
+    result: String,
+}
+
+/// Keeps the results of finished tournament matches.
+#[derive(Clone)]
+pub struct MatchResults {
+    insert: Arc<Statement<InsertArg, ()>>,
+}
+
+impl MatchResults {
+    pub async fn new(db: Arc<dyn DbInterface>) -> anyhow::Result<Self> {
+        let kinds = db.kinds();
+        let kind = [
+            DbKind::MySql(DbKindExtra::Main),
+            DbKind::Sqlite(DbKindExtra::Main),
+        ]
+        .into_iter()
+        .find(|kind| kinds.contains(kind))
+        .ok_or_else(|| anyhow!("No main db specified"))?;
+
+        let insert = StatementBuilder::<_, InsertArg, ()>::new(
+            kind,
+            include_str!("generic/match_results/insert.sql"),
+            |arg| vec![arg.map_name, arg.game_type, arg.result],
+        );
+
+        Ok(Self {
+            insert: Arc::new(Statement::new(db, insert).await?),
+        })
+    }
+
+    /// Inserts a match result, `result` is the result serialized as JSON.
+    pub async fn insert(
+        &self,
+        map_name: &str,
+        game_type: &str,
+        result: &str,
+    ) -> anyhow::Result<u64> {
+        self.insert
+            .execute(InsertArg {
+                map_name: map_name.to_string(),
+                game_type: game_type.to_string(),
+                result: result.to_string(),
+            })
+            .await
+    }
+}
+
+#[derive(Clone)]
+pub struct SetupMatchResults {
+    pub(crate) stmts: HashMap<DbKind, Vec<SqlText>>,
+}
+
+impl SetupMatchResults {
+    pub fn new(db: &Arc<dyn DbInterface>) -> Self {
+        let mut stmts: HashMap<_, Vec<_>> = Default::default();
+        let kinds = db.kinds();
+
+        if kinds.contains(&DbKind::MySql(DbKindExtra::Main)) {
+            stmts
+                .entry(DbKind::MySql(DbKindExtra::Main))
+                .or_default()
+                .push(include_str!("mysql/match_results/match_results.sql").into());
+        }
+        if kinds.contains(&DbKind::Sqlite(DbKindExtra::Main)) {
+            stmts
+                .entry(DbKind::Sqlite(DbKindExtra::Main))
+                .or_default()
+                .push(include_str!("sqlite/match_results/match_results.sql").into());
+        }
+
+        Self { stmts }
+    }
+}
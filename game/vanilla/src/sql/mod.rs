@@ -1,3 +1,4 @@
 pub mod account_created;
 pub mod account_info;
+pub mod match_results;
 pub mod save;
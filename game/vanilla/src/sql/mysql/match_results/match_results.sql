@@ -0,0 +1,9 @@
+CREATE TABLE vanilla_match_results (
+    id BIGINT NOT NULL AUTO_INCREMENT,
+    map_name VARCHAR(64) NOT NULL,
+    game_type VARCHAR(32) NOT NULL,
+    result TEXT NOT NULL,
+    create_time DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY(id),
+    KEY(map_name)
+);
@@ -2,6 +2,8 @@ use std::{collections::HashMap, sync::Arc};
 
 use game_database::traits::{DbInterface, DbKind, DbKindExtra, SqlText};
 
+use super::match_results::SetupMatchResults;
+
 #[derive(Clone)]
 pub struct SetupSaves {
     stmts: HashMap<DbKind, Vec<SqlText>>,
@@ -31,10 +33,13 @@ impl SetupSaves {
 
 pub async fn setup(db: Arc<dyn DbInterface>) -> anyhow::Result<()> {
     let setup_saves = SetupSaves::new(db.clone()).await?;
+    let setup_match_results = SetupMatchResults::new(&db);
 
     db.setup(
         "game-server-vanilla",
-        vec![(1, setup_saves.stmts)].into_iter().collect(),
+        vec![(1, setup_saves.stmts), (2, setup_match_results.stmts)]
+            .into_iter()
+            .collect(),
     )
     .await
 }
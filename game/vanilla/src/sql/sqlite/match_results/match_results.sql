@@ -0,0 +1,8 @@
+CREATE TABLE vanilla_match_results (
+    id INTEGER AUTO_INCREMENT,
+    map_name TEXT NOT NULL,
+    game_type TEXT NOT NULL,
+    result TEXT NOT NULL,
+    create_time DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY(id)
+);
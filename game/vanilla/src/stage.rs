@@ -12,7 +12,7 @@ pub mod stage {
     use crate::{
         entities::character::pos::character_pos::CharacterPositionPlayfield,
         game_objects::game_objects::GameObjectDefinitions,
-        match_manager::match_manager::MatchManager,
+        match_manager::match_manager::{MatchManager, MatchStart},
         match_state::match_state::MatchState,
         simulation_pipe::simulation_pipe::{GameStagePendingEventsRaii, SimulationStageEvents},
         spawns::GameSpawns,
@@ -73,7 +73,11 @@ pub mod stage {
                     Default::default(),
                     spawn_default_entities,
                 ),
-                match_manager: MatchManager::new(game_options, &simulation_events),
+                match_manager: MatchManager::new(
+                    game_options,
+                    &simulation_events,
+                    MatchStart::Warmup,
+                ),
                 stage_name,
                 stage_color,
                 game_pending_events,
@@ -90,12 +94,18 @@ pub mod stage {
                 .game_match
                 .tick(&self.match_manager.game_options, &self.world.scores);
 
-            if let MatchState::Running { .. } | MatchState::SuddenDeath { .. } =
-                self.match_manager.game_match.state
+            if let MatchState::Running { .. }
+            | MatchState::SuddenDeath { .. }
+            | MatchState::Warmup { .. } = self.match_manager.game_match.state
             {
                 self.world.tick(pipe);
             }
-            if !pipe.is_prediction && self.match_manager.tick(&mut self.world) {
+            let start = if !pipe.is_prediction {
+                self.match_manager.tick(&mut self.world)
+            } else {
+                None
+            };
+            if let Some(start) = start {
                 let characters = std::mem::replace(
                     &mut self.world.characters,
                     self.world.world_pool.character_pool.character_pool.new(),
@@ -116,7 +126,8 @@ pub mod stage {
                 );
                 self.world.characters = characters;
                 let game_options = self.match_manager.game_options.clone();
-                self.match_manager = MatchManager::new(game_options, &self.simulation_events);
+                self.match_manager =
+                    MatchManager::new(game_options, &self.simulation_events, start);
             }
 
             self.simulation_events.clear();
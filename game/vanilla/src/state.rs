@@ -28,7 +28,8 @@ pub mod state {
     use game_interface::pooling::GamePooling;
     use game_interface::rcon_entries::{AuthLevel, ExecRconInput, RconEntries, RconEntry};
     use game_interface::settings::GameStateSettings;
-    use game_interface::tick_result::TickResult;
+    use game_interface::tick_result::{TickEvent, TickResult};
+    use game_interface::types::auto_director::AutoDirectorCandidate;
     use game_interface::types::character_info::{
         NetworkCharacterInfo, NetworkLaserInfo, NetworkSkinInfo, MAX_ASSET_NAME_LEN,
         MAX_CHARACTER_NAME_LEN,
@@ -87,6 +88,7 @@ pub mod state {
     use pool::rc::PoolRc;
     use rustc_hash::FxHashMap;
 
+    use crate::bots::bots::{BotBehaviour, Bots, Chase, FlagRunner};
     use crate::collision::collision::Tunings;
     use crate::command_chain::{Command, CommandChain};
     use crate::config::config::{ConfigGameType, ConfigVanilla, ConfigVanillaWrapper};
@@ -100,12 +102,13 @@ pub mod state {
     use crate::entities::pickup::pickup::Pickup;
     use crate::entities::projectile::projectile::{self};
     use crate::game_objects::game_objects::GameObjectDefinitions;
-    use crate::match_manager::match_manager::MatchManager;
+    use crate::match_manager::match_manager::{MatchManager, MatchResult};
     use crate::match_state::match_state::{MatchState, MatchType};
     use crate::simulation_pipe::simulation_pipe::{GamePendingEvents, GameStagePendingEvents};
     use crate::snapshot::snapshot::{Snapshot, SnapshotFor, SnapshotManager, SnapshotStage};
     use crate::sql::account_created::{self, AccountCreated};
     use crate::sql::account_info::{AccountInfo, StatementResult};
+    use crate::sql::match_results::MatchResults;
     use crate::sql::save;
     use crate::stage::stage::Stages;
     use crate::types::types::{GameOptions, GameType};
@@ -117,6 +120,7 @@ pub mod state {
         stage::stage::GameStage, world::world::WorldPool,
     };
 
+    use serde::Serialize;
     use thiserror::Error;
 
     #[derive(Error, Debug)]
@@ -126,6 +130,8 @@ pub mod state {
     }
 
     pub(crate) const TICKS_PER_SECOND: u64 = 50;
+    /// How long the auto director follows a character at least.
+    const AUTO_DIRECTOR_MIN_FOLLOW_SECS: u64 = 3;
 
     #[derive(Debug, Clone, Copy)]
     pub enum VanillaRconCommandCheat {
@@ -133,13 +139,38 @@ pub mod state {
         Tune,
     }
 
+    #[derive(Debug, Clone, Copy)]
+    pub enum VanillaRconCommandMatch {
+        Pause,
+        Unpause,
+        LockTeams,
+        UnlockTeams,
+        ExportStats,
+    }
+
+    #[derive(Debug, Clone, Copy)]
+    pub enum VanillaRconCommandBot {
+        Add,
+        RemoveAll,
+    }
+
     #[derive(Debug, Clone, Copy)]
     pub enum VanillaRconCommand {
         Info,
         Cheats(VanillaRconCommandCheat),
+        Match(VanillaRconCommandMatch),
+        Bots(VanillaRconCommandBot),
         ConfVariable,
     }
 
+    /// A [`MatchResult`] as it is saved to a file.
+    #[derive(Debug, Serialize)]
+    struct MatchResultRecord<'a> {
+        map_name: &'a str,
+        #[serde(flatten)]
+        result: &'a MatchResult,
+    }
+
     pub struct Game {
         pub(crate) stages: Stages,
 
@@ -175,6 +206,7 @@ pub mod state {
         pub(crate) io_rt: IoRuntime,
         pub(crate) account_info: Option<AccountInfo>,
         pub(crate) statements: Option<GameStatements>,
+        pub(crate) match_results: Option<MatchResults>,
 
         pub(crate) cur_queries: Vec<IoRuntimeTask<GameDbQueries>>,
         pub(crate) cur_queries_helper: Vec<IoRuntimeTask<GameDbQueries>>,
@@ -210,6 +242,10 @@ pub mod state {
         pub(crate) rcon_chain: CommandChain<VanillaRconCommand>,
         cache: ParserCache,
         map_name: NetworkReducedAsciiString<MAX_MAP_NAME_LEN>,
+        /// Players can't switch sides, stages or join the game while
+        /// the teams are locked.
+        pub(crate) teams_locked: bool,
+        bots: Bots,
 
         // db
         game_db: GameDb,
@@ -314,6 +350,7 @@ pub mod state {
         where
             Self: Sized,
         {
+            let bots = Bots::new(Default::default());
             let rcon_cmds = vec![
                 (
                     "info".try_into().unwrap(),
@@ -376,6 +413,99 @@ pub mod state {
                         cmd: VanillaRconCommand::Cheats(VanillaRconCommandCheat::Tune),
                     },
                 ),
+                (
+                    "pause".try_into().unwrap(),
+                    Command {
+                        rcon: RconEntry {
+                            args: Default::default(),
+                            description: "Pauses all running matches".try_into().unwrap(),
+                            usage: "".try_into().unwrap(),
+                        },
+                        cmd: VanillaRconCommand::Match(VanillaRconCommandMatch::Pause),
+                    },
+                ),
+                (
+                    "unpause".try_into().unwrap(),
+                    Command {
+                        rcon: RconEntry {
+                            args: Default::default(),
+                            description: "Continues all paused matches".try_into().unwrap(),
+                            usage: "".try_into().unwrap(),
+                        },
+                        cmd: VanillaRconCommand::Match(VanillaRconCommandMatch::Unpause),
+                    },
+                ),
+                (
+                    "lock_teams".try_into().unwrap(),
+                    Command {
+                        rcon: RconEntry {
+                            args: Default::default(),
+                            description: "Prevents players from switching sides or stages"
+                                .try_into()
+                                .unwrap(),
+                            usage: "".try_into().unwrap(),
+                        },
+                        cmd: VanillaRconCommand::Match(VanillaRconCommandMatch::LockTeams),
+                    },
+                ),
+                (
+                    "unlock_teams".try_into().unwrap(),
+                    Command {
+                        rcon: RconEntry {
+                            args: Default::default(),
+                            description: "Allows players to switch sides or stages again"
+                                .try_into()
+                                .unwrap(),
+                            usage: "".try_into().unwrap(),
+                        },
+                        cmd: VanillaRconCommand::Match(VanillaRconCommandMatch::UnlockTeams),
+                    },
+                ),
+                (
+                    "export_stats".try_into().unwrap(),
+                    Command {
+                        rcon: RconEntry {
+                            args: Default::default(),
+                            description: "Saves the statistics of all running matches"
+                                .try_into()
+                                .unwrap(),
+                            usage: "".try_into().unwrap(),
+                        },
+                        cmd: VanillaRconCommand::Match(VanillaRconCommandMatch::ExportStats),
+                    },
+                ),
+                (
+                    "add_bot".try_into().unwrap(),
+                    Command {
+                        rcon: RconEntry {
+                            description: "Adds a server-side bot with the given behaviour"
+                                .try_into()
+                                .unwrap(),
+                            usage: "<behaviour>".try_into().unwrap(),
+                            args: vec![CommandArg {
+                                ty: CommandArgType::TextFrom(
+                                    bots.behaviours()
+                                        .names()
+                                        .map(|name| name.try_into().unwrap())
+                                        .collect(),
+                                ),
+                                user_ty: None,
+                            }],
+                        },
+                        cmd: VanillaRconCommand::Bots(VanillaRconCommandBot::Add),
+                    },
+                ),
+                (
+                    "remove_bots".try_into().unwrap(),
+                    Command {
+                        rcon: RconEntry {
+                            args: Default::default(),
+                            description: "Removes all server-side bots".try_into().unwrap(),
+                            usage: "".try_into().unwrap(),
+                        },
+                        cmd: VanillaRconCommand::Bots(VanillaRconCommandBot::RemoveAll),
+                    },
+                ),
             ];
 
             let mut rcon_vars: Vec<_> = Default::default();
@@ -453,6 +583,16 @@ pub mod state {
                         "failed to prepare account info sql: {}", err);
                     }
 
+                    let match_results = match MatchResults::new(db.clone()).await {
+                        Ok(match_results) => Some(match_results),
+                        Err(err) => {
+                            log::warn!(
+                            target: "sql",
+                            "failed to prepare match results sql: {}", err);
+                            None
+                        }
+                    };
+
                     let account_created = match AccountCreated::new(db, options.account_db).await {
                         Ok(account_created) => Some(account_created),
                         Err(err) => {
@@ -466,7 +606,7 @@ pub mod state {
                     let statements =
                         account_created.map(|account_created| GameStatements { account_created });
 
-                    Ok(statements.zip(acc_info.ok()))
+                    Ok((statements.zip(acc_info.ok()), match_results))
                 } else {
                     Err(anyhow!("Databases not active."))
                 }
@@ -501,14 +641,19 @@ pub mod state {
 
             let game_type = Self::get_game_type_from_conf(config.game_type);
 
-            let (statements, account_info) = db_task.get_storage().ok().flatten().unzip();
+            let (statements, match_results) = db_task.get_storage().ok().unzip();
+            let (statements, account_info) = statements.flatten().unzip();
+            let match_results = match_results.flatten();
 
             let has_accounts = account_info.is_some();
 
             let chat_commands = ChatCommands {
-                cmds: vec![("account_info".try_into().unwrap(), vec![])]
-                    .into_iter()
-                    .collect(),
+                cmds: vec![
+                    ("account_info".try_into().unwrap(), vec![]),
+                    ("ready".try_into().unwrap(), vec![]),
+                ]
+                .into_iter()
+                .collect(),
                 prefixes: vec!['/'],
             };
 
@@ -553,12 +698,15 @@ pub mod state {
                 rcon_chain,
                 cache,
                 map_name,
+                teams_locked: false,
+                bots,
 
                 // db
                 game_db: GameDb {
                     io_rt,
                     account_info,
                     statements,
+                    match_results,
 
                     cur_queries: Default::default(),
                     cur_queries_helper: Default::default(),
@@ -789,7 +937,42 @@ pub mod state {
             }
         }
 
+        /// Updates the cameras of spectators that follow a stage
+        /// or the auto director.
+        fn spectator_camera_tick(&mut self) {
+            if !self.game.spectator_players.has_dynamic_cameras() {
+                return;
+            }
+            let mut characters = Vec::new();
+            for (stage_id, stage) in self.game.stages.iter() {
+                let flag_carriers = stage
+                    .world
+                    .get_red_flags()
+                    .values()
+                    .chain(stage.world.get_blue_flags().values())
+                    .filter_map(|flag| flag.core.carrier)
+                    .collect::<Vec<_>>();
+                characters.extend(stage.world.characters.iter().map(|(id, character)| {
+                    (
+                        *stage_id,
+                        AutoDirectorCandidate {
+                            id: *id,
+                            vel: character.core.core.vel,
+                            carries_flag: flag_carriers.contains(id),
+                        },
+                    )
+                }));
+            }
+            self.game.spectator_players.update_cameras(
+                &characters,
+                1,
+                TICKS_PER_SECOND * AUTO_DIRECTOR_MIN_FOLLOW_SECS,
+            );
+        }
+
         pub fn player_tick(&mut self) {
+            self.spectator_camera_tick();
+
             let mut kick_players = Vec::new();
             self.game.timeout_players.retain(|_, player| {
                 if player.1.tick().unwrap_or_default() {
@@ -804,6 +987,38 @@ pub mod state {
             }
         }
 
+        /// Saves the collected match results & statistics
+        /// to the database and lets the server write them to a file.
+        fn match_result_tick(&mut self, events: &mut Vec<TickEvent>) {
+            for stage in self.game.stages.values_mut() {
+                let Some(result) = stage.match_manager.take_result() else {
+                    continue;
+                };
+                let json = match serde_json::to_string_pretty(&MatchResultRecord {
+                    map_name: self.map_name.as_str(),
+                    result: &result,
+                }) {
+                    Ok(json) => json,
+                    Err(err) => {
+                        log::error!("failed to serialize match result: {err}");
+                        continue;
+                    }
+                };
+                if let Some(match_results) = self.game_db.match_results.clone() {
+                    let map_name = self.map_name.to_string();
+                    let game_type = Self::get_mod_name_from_conf(result.game_type)
+                        .as_str()
+                        .to_string();
+                    let result = json.clone();
+                    self.game_db.io_rt.spawn_without_lifetime(async move {
+                        match_results.insert(&map_name, &game_type, &result).await?;
+                        Ok(())
+                    });
+                }
+                events.push(TickEvent::MatchResult { json });
+            }
+        }
+
         fn query_tick(&mut self) {
             self.game_db.cur_queries_helper.clear();
             for query in self.game_db.cur_queries.drain(..) {
@@ -926,6 +1141,7 @@ pub mod state {
                         | MatchState::Paused { .. }
                         | MatchState::SuddenDeath { .. }
                         | MatchState::PausedSuddenDeath { .. }
+                        | MatchState::Warmup { .. }
                 ) {
                     stage
                         .world
@@ -983,24 +1199,80 @@ pub mod state {
             )
         }
 
-        fn handle_chat_commands(&mut self, player_id: &PlayerId, cmds: Vec<CommandType>) {
-            let Some(server_player) = self.game.players.player(player_id) else {
+        fn push_player_msg(&mut self, player_id: &PlayerId, msg: &str) {
+            let mut s = self.game_pools.mt_network_string_common_pool.new();
+            s.try_set(msg).unwrap();
+            self.player_events
+                .entry(*player_id)
+                .or_default()
+                .push(GameWorldEvent::Notification(
+                    GameWorldNotificationEvent::System(GameWorldSystemMessage::Custom(s)),
+                ));
+        }
+
+        fn push_stage_msg(stage: &GameStage, game_pools: &GamePooling, msg: &str) {
+            let mut s = game_pools.mt_network_string_common_pool.new();
+            s.try_set(msg).unwrap();
+            stage.game_pending_events.push(GameWorldEvent::Notification(
+                GameWorldNotificationEvent::System(GameWorldSystemMessage::Custom(s)),
+            ));
+        }
+
+        /// Toggles whether a player is ready for the next tournament match.
+        fn cmd_ready(&mut self, player_id: &PlayerId, stage_id: &StageId) {
+            if !self.game_options.tournament_mode() {
+                self.push_player_msg(player_id, "/ready is only used in tournament mode.");
+                return;
+            }
+            let Some(stage) = self.game.stages.get_mut(stage_id) else {
                 return;
             };
-            let Some(character) = self
-                .game
-                .stages
-                .get(&server_player.stage_id())
-                .and_then(|stage| stage.world.characters.get(player_id))
-            else {
+            let Some(character) = stage.world.characters.get(player_id) else {
+                return;
+            };
+            if !matches!(
+                stage.match_manager.game_match.state,
+                MatchState::Warmup { .. }
+            ) {
+                self.push_player_msg(player_id, "The match already started.");
+                return;
+            }
+            let ready = !stage.match_manager.is_ready(player_id);
+            let msg = format!(
+                "{} is {}ready",
+                character.player_info.player_info.name.as_str(),
+                if ready { "" } else { "not " }
+            );
+            stage.match_manager.set_ready(player_id, ready);
+            let (ready_count, total) = stage.match_manager.ready_count(&stage.world);
+            Self::push_stage_msg(
+                stage,
+                &self.game_pools,
+                &format!("{msg} ({ready_count}/{total})"),
+            );
+        }
+
+        fn handle_chat_commands(&mut self, player_id: &PlayerId, cmds: Vec<CommandType>) {
+            let Some(server_player) = self.game.players.player(player_id) else {
                 return;
             };
+            let stage_id = server_player.stage_id();
             for cmd in cmds {
                 match cmd {
                     CommandType::Full(cmd) => {
                         match cmd.ident.as_str() {
                             "account_info" => {
-                                Self::cmd_account_info(&mut self.game_db, player_id, character);
+                                if let Some(character) = self
+                                    .game
+                                    .stages
+                                    .get(&stage_id)
+                                    .and_then(|stage| stage.world.characters.get(player_id))
+                                {
+                                    Self::cmd_account_info(&mut self.game_db, player_id, character);
+                                }
+                            }
+                            "ready" => {
+                                self.cmd_ready(player_id, &stage_id);
                             }
                             _ => {
                                 // TODO: send command not found text
@@ -1094,6 +1366,97 @@ pub mod state {
                         }
                     }
                 },
+                VanillaRconCommand::Match(cmd) => match cmd {
+                    VanillaRconCommandMatch::Pause | VanillaRconCommandMatch::Unpause => {
+                        let pause = matches!(cmd, VanillaRconCommandMatch::Pause);
+                        let mut changed = 0;
+                        for stage in self.game.stages.values_mut() {
+                            let game_match = &mut stage.match_manager.game_match;
+                            if (pause && game_match.pause()) || (!pause && game_match.unpause()) {
+                                Self::push_stage_msg(
+                                    stage,
+                                    &self.game_pools,
+                                    if pause {
+                                        "The match was paused."
+                                    } else {
+                                        "The match continues."
+                                    },
+                                );
+                                changed += 1;
+                            }
+                        }
+                        if changed > 0 {
+                            Ok(format!(
+                                "{} {changed} match(es)",
+                                if pause { "Paused" } else { "Unpaused" }
+                            ))
+                        } else if pause {
+                            Err(anyhow!("No match is running"))
+                        } else {
+                            Err(anyhow!("No match is paused"))
+                        }
+                    }
+                    VanillaRconCommandMatch::LockTeams | VanillaRconCommandMatch::UnlockTeams => {
+                        self.teams_locked = matches!(cmd, VanillaRconCommandMatch::LockTeams);
+                        let msg = if self.teams_locked {
+                            "The teams were locked."
+                        } else {
+                            "The teams were unlocked."
+                        };
+                        for stage in self.game.stages.values() {
+                            Self::push_stage_msg(stage, &self.game_pools, msg);
+                        }
+                        Ok(msg.to_string())
+                    }
+                    VanillaRconCommandMatch::ExportStats => {
+                        let mut exported = 0;
+                        for stage in self.game.stages.values_mut() {
+                            if stage.world.characters.is_empty() {
+                                continue;
+                            }
+                            stage.match_manager.export_result(&stage.world);
+                            exported += 1;
+                        }
+                        if exported > 0 {
+                            Ok(format!("Exported the statistics of {exported} match(es)"))
+                        } else {
+                            Err(anyhow!("No match has any players"))
+                        }
+                    }
+                },
+                VanillaRconCommand::Bots(bot_cmd) => match bot_cmd {
+                    VanillaRconCommandBot::Add => {
+                        let Some(Syn::Text(behaviour)) = cmd.args.pop().map(|(name, _)| name)
+                        else {
+                            panic!("Expected a text, this is an implementation bug");
+                        };
+                        let Some(behaviour) = self.bots.behaviours().get(&behaviour) else {
+                            return Err(anyhow!(
+                                "Unknown bot behaviour {behaviour}, expected one of: {}",
+                                self.bots
+                                    .behaviours()
+                                    .names()
+                                    .collect::<Vec<_>>()
+                                    .join(", ")
+                            ));
+                        };
+                        self.add_bot(behaviour.clone())?;
+                        Ok(format!(
+                            "Added a bot with the {} behaviour",
+                            behaviour.name()
+                        ))
+                    }
+                    VanillaRconCommandBot::RemoveAll => {
+                        let bot_ids: Vec<_> = self.bots.ids().copied().collect();
+                        if bot_ids.is_empty() {
+                            return Err(anyhow!("There are no bots"));
+                        }
+                        for bot_id in &bot_ids {
+                            self.remove_bot(bot_id);
+                        }
+                        Ok(format!("Removed {} bot(s)", bot_ids.len()))
+                    }
+                },
                 VanillaRconCommand::ConfVariable => {
                     let mut config = ConfigVanillaWrapper {
                         vanilla: self.game_options.config_clone(),
@@ -1103,6 +1466,7 @@ pub mod state {
                     {
                         Ok(res) => {
                             self.game_options.replace_conf(config.vanilla);
+                            self.fill_bots();
                             Ok(res)
                         }
                         Err(err) => Err(err),
@@ -1613,83 +1977,372 @@ pub mod state {
             info.name = name;
             info
         }
-    }
 
-    impl GameStateInterface for GameState {
-        fn collect_characters_info(&self) -> PoolFxLinkedHashMap<CharacterId, CharacterInfo> {
-            let mut character_infos = self.game_pools.character_info_pool.new();
+        fn player_join_impl(&mut self, client_player_info: &PlayerClientInfo) -> PlayerId {
+            if let Some((timeout_player_id, character_info)) = self
+                .game
+                .timeout_players
+                .remove(&(client_player_info.unique_identifier, client_player_info.id))
+                .and_then(|(id, _)| self.game.players.player(&id).map(|char| (id, char)))
+            {
+                let char = self
+                    .game
+                    .stages
+                    .get_mut(&character_info.stage_id())
+                    .unwrap()
+                    .world
+                    .characters
+                    .get_mut(&timeout_player_id)
+                    .unwrap();
+                char.core.is_timeout = false;
+                return timeout_player_id;
+            }
+
+            let player_id = self.id_generator.next_id();
+            let stage_0_id = self.stage_0_id;
+
+            let character_info = self.check_player_info(client_player_info.info.clone(), None);
 
-            let mut players = self.spectator_player_clone_pool.new();
-            self.game.spectator_players.pooled_clone_into(&mut players);
-            let spectator_players = players.iter().map(|(_, player)| {
-                let (player_info, stage_id) = (
-                    CharacterPlayerInfo {
-                        cam_mode: if player.spectated_characters.len() == 0 {
-                            PlayerCameraMode::Free
-                        } else {
-                            PlayerCameraMode::LockedOn {
-                                character_ids: player.spectated_characters.clone(),
-                                locked_ingame: false,
-                            }
-                        },
-                        force_scoreboard_visible: player.spectated_characters.len() > 0
-                            && player
-                                .spectated_characters
-                                .iter()
-                                .all(|spectated_player_id| {
-                                    self.game
-                                        .players
-                                        .player(spectated_player_id)
-                                        .and_then(|info| self.game.stages.get(&info.stage_id()))
-                                        .map(|stage| {
-                                            matches!(
-                                                &stage.match_manager.game_match.state,
-                                                MatchState::GameOver { .. }
-                                            )
-                                        })
-                                        .unwrap_or_default()
-                                }),
-                        ingame_mode: PlayerIngameMode::Spectator,
-                    },
-                    None,
-                );
-                (
-                    stage_id,
-                    (&player.id, None, &player.player_info),
-                    Some(player_info),
-                    self.game_pools.network_string_score_pool.new(),
-                )
-            });
-            // of all chars (even server-side ones)
-            // + all spectator players
             self.game
                 .stages
-                .iter()
-                .flat_map(|(stage_id, stage)| {
-                    stage.world.characters.iter().map(|(id, character)| {
-                        (
-                            Some(*stage_id),
-                            (id, Some(character.core.side), &character.player_info),
-                            self.game
-                                .players
-                                .player(id)
-                                .is_some()
-                                .then_some(CharacterPlayerInfo {
-                                    cam_mode: if matches!(
-                                        character.phased,
-                                        character::CharacterPhasedState::Dead { .. }
-                                    ) {
-                                        PlayerCameraMode::LockedTo {
-                                            pos: *character.pos.pos() / 32.0,
-                                            locked_ingame: true,
-                                        }
-                                    } else {
-                                        PlayerCameraMode::Default
-                                    },
-                                    force_scoreboard_visible: matches!(
-                                        stage.match_manager.game_match.state,
-                                        MatchState::GameOver { .. }
-                                    ),
+                .get(&stage_0_id)
+                .unwrap()
+                .game_pending_events
+                .push(GameWorldEvent::Notification(
+                    GameWorldNotificationEvent::System(GameWorldSystemMessage::PlayerJoined {
+                        id: player_id,
+                        name: {
+                            let mut s = self.game_pools.mt_network_string_name_pool.new();
+                            s.try_set(character_info.name.as_str()).unwrap();
+                            s
+                        },
+                        skin: {
+                            let mut skin = self.game_pools.mt_resource_key_pool.new();
+                            (*skin).clone_from(&character_info.skin);
+                            skin
+                        },
+                        skin_info: character_info.skin_info,
+                    }),
+                ));
+
+            if client_player_info.id == 0 {
+                let events = self.player_events.entry(player_id).or_default();
+
+                let mut msg = self.game_pools.mt_network_string_common_pool.new();
+                msg.try_set("alpha version vanilla.").unwrap();
+
+                events.push(GameWorldEvent::Notification(
+                    GameWorldNotificationEvent::Motd { msg },
+                ));
+            }
+
+            let player_info = PlayerInfo {
+                player_info: PoolRc::from_item_without_pool(character_info),
+                version: 0,
+                unique_identifier: client_player_info.unique_identifier,
+                account_name: None,
+                id: client_player_info.id,
+            };
+            if !self.teams_locked
+                && self
+                    .game
+                    .stages
+                    .get(&self.stage_0_id)
+                    .unwrap()
+                    .world
+                    .characters
+                    .len()
+                    < self.game_options.max_ingame_players() as usize
+            {
+                // spawn and send character info
+                let default_eyes = player_info.player_info.default_eyes;
+                Self::add_char_to_stage(
+                    &mut self.game.stages,
+                    &stage_0_id,
+                    &player_id,
+                    player_info,
+                    Default::default(),
+                    self.game.players.clone(),
+                    self.game.spectator_players.clone(),
+                    client_player_info.initial_network_stats,
+                    None,
+                    0,
+                    default_eyes,
+                    Default::default(),
+                    &self.game_pools,
+                );
+            } else {
+                self.game.spectator_players.insert(
+                    player_id,
+                    SpectatorPlayer::new(
+                        player_info,
+                        Default::default(),
+                        &player_id,
+                        self.game_pools.character_id_hashset_pool.new(),
+                        client_player_info.info.default_eyes,
+                        Default::default(),
+                        client_player_info.initial_network_stats,
+                    ),
+                );
+            }
+
+            // bots have no account
+            if !Bots::is_bot_unique_id(&client_player_info.unique_identifier) {
+                Self::push_account_info_task(
+                    &mut self.game_db,
+                    &player_id,
+                    &client_player_info.unique_identifier,
+                );
+            }
+
+            player_id
+        }
+
+        fn player_drop_impl(&mut self, player_id: &PlayerId, reason: PlayerDropReason) {
+            self.bots.remove(player_id);
+
+            let name = if let Some(server_player) = self.game.players.player(player_id) {
+                let stage = self.game.stages.get_mut(&server_player.stage_id()).unwrap();
+
+                let character = stage.world.characters.get_mut(player_id).unwrap();
+
+                let mut name = self.game_pools.mt_network_string_name_pool.new();
+                (*name).clone_from(&character.player_info.player_info.name);
+
+                let skin = {
+                    let mut skin = self.game_pools.mt_resource_key_pool.new();
+                    (*skin).clone_from(&character.player_info.player_info.skin);
+                    skin
+                };
+                let skin_info = character.player_info.player_info.skin_info;
+
+                character.despawn_completely_silent();
+                stage.world.characters.remove(player_id);
+
+                Some((name, skin, skin_info, server_player.stage_id()))
+            } else if let Some(spectator_player) = self.game.spectator_players.remove(player_id) {
+                let mut name = self.game_pools.mt_network_string_name_pool.new();
+                (*name).clone_from(&spectator_player.player_info.player_info.name);
+                let skin = {
+                    let mut skin = self.game_pools.mt_resource_key_pool.new();
+                    (*skin).clone_from(&spectator_player.player_info.player_info.skin);
+                    skin
+                };
+                let skin_info = spectator_player.player_info.player_info.skin_info;
+                Some((name, skin, skin_info, self.stage_0_id))
+            } else {
+                None
+            };
+
+            if let Some((name, skin, skin_info, stage_id)) = name {
+                let stage = self.game.stages.get(&stage_id).unwrap();
+                stage.game_pending_events.push(GameWorldEvent::Notification(
+                    GameWorldNotificationEvent::System(GameWorldSystemMessage::PlayerLeft {
+                        id: *player_id,
+                        name: {
+                            let mut s = self.game_pools.mt_network_string_name_pool.new();
+                            s.try_set(name.as_str()).unwrap();
+                            s
+                        },
+                        skin,
+                        skin_info,
+                        reason,
+                    }),
+                ));
+
+                self.check_stage_remove(stage_id);
+            }
+        }
+
+        /// Whether a joining player would be ingame instead of a spectator.
+        fn has_ingame_slot(&self) -> bool {
+            !self.teams_locked
+                && self
+                    .game
+                    .stages
+                    .get(&self.stage_0_id)
+                    .unwrap()
+                    .world
+                    .characters
+                    .len()
+                    < self.game_options.max_ingame_players() as usize
+        }
+
+        /// Adds a server-side bot that joins like a regular player.
+        fn add_bot(&mut self, behaviour: Rc<dyn BotBehaviour>) -> anyhow::Result<PlayerId> {
+            if !self.has_ingame_slot() {
+                return Err(anyhow!("There is no free slot for another player"));
+            }
+            let index = self.bots.next_index();
+            let mut info = NetworkCharacterInfo::explicit_default();
+            info.name = NetworkString::new_lossy(format!("bot {index}"));
+            let player_id = self.player_join_impl(&PlayerClientInfo {
+                info,
+                id: index,
+                unique_identifier: Bots::unique_id(index),
+                initial_network_stats: Default::default(),
+            });
+            self.bots.insert(player_id, behaviour);
+            Ok(player_id)
+        }
+
+        fn remove_bot(&mut self, player_id: &PlayerId) {
+            self.player_drop_impl(player_id, PlayerDropReason::Disconnect);
+        }
+
+        /// Removes a bot if the game is full, so that a joining player
+        /// does not have to spectate.
+        fn make_room_for_player(&mut self) {
+            if !self.teams_locked && !self.has_ingame_slot() {
+                if let Some(bot_id) = self.bots.ids().next_back().copied() {
+                    self.remove_bot(&bot_id);
+                }
+            }
+        }
+
+        /// Adds or removes bots, so that [`ConfigVanilla::bot_fill_players`]
+        /// players are ingame.
+        /// Bots are only added while real players are connected.
+        fn fill_bots(&mut self) {
+            let fill = self.game_options.bot_fill_players() as usize;
+            if fill == 0 {
+                return;
+            }
+            let ingame_players = self
+                .game
+                .stages
+                .values()
+                .flat_map(|stage| stage.world.characters.iter())
+                .filter(|(id, character)| {
+                    character.is_player_character().is_some() && !self.bots.contains(id)
+                })
+                .count();
+            let wanted_bots = if ingame_players + self.game.spectator_players.len() > 0 {
+                fill.saturating_sub(ingame_players)
+            } else {
+                0
+            };
+
+            while self.bots.len() > wanted_bots {
+                let bot_id = *self.bots.ids().next_back().unwrap();
+                self.remove_bot(&bot_id);
+            }
+            while self.bots.len() < wanted_bots {
+                let behaviour: Rc<dyn BotBehaviour> = match self.game_options.game_ty() {
+                    ConfigGameType::Dm => Rc::new(Chase),
+                    ConfigGameType::Ctf => Rc::new(FlagRunner),
+                };
+                if self.add_bot(behaviour).is_err() {
+                    break;
+                }
+            }
+        }
+
+        /// Generates the input of all bots for the next tick.
+        fn bot_tick(&mut self) {
+            if self.bots.is_empty() {
+                return;
+            }
+            let inputs = self
+                .bots
+                .tick(&self.game.stages, &self.game.players, &self.collision);
+            for (player_id, inp, diff) in inputs {
+                self.set_player_inp_impl(&player_id, &inp, diff);
+            }
+
+            // bots are always ready for a tournament match
+            if self.game_options.tournament_mode() {
+                for bot_id in self.bots.ids() {
+                    if let Some(stage) = self
+                        .game
+                        .players
+                        .player(bot_id)
+                        .and_then(|player| self.game.stages.get_mut(&player.stage_id()))
+                    {
+                        if !stage.match_manager.is_ready(bot_id) {
+                            stage.match_manager.set_ready(bot_id, true);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    impl GameStateInterface for GameState {
+        fn collect_characters_info(&self) -> PoolFxLinkedHashMap<CharacterId, CharacterInfo> {
+            let mut character_infos = self.game_pools.character_info_pool.new();
+
+            let mut players = self.spectator_player_clone_pool.new();
+            self.game.spectator_players.pooled_clone_into(&mut players);
+            let spectator_players = players.iter().map(|(_, player)| {
+                let (player_info, stage_id) = (
+                    CharacterPlayerInfo {
+                        cam_mode: if player.spectated_characters.len() == 0 {
+                            PlayerCameraMode::Free
+                        } else {
+                            PlayerCameraMode::LockedOn {
+                                character_ids: player.spectated_characters.clone(),
+                                locked_ingame: false,
+                            }
+                        },
+                        force_scoreboard_visible: player.spectated_characters.len() > 0
+                            && player
+                                .spectated_characters
+                                .iter()
+                                .all(|spectated_player_id| {
+                                    self.game
+                                        .players
+                                        .player(spectated_player_id)
+                                        .and_then(|info| self.game.stages.get(&info.stage_id()))
+                                        .map(|stage| {
+                                            matches!(
+                                                &stage.match_manager.game_match.state,
+                                                MatchState::GameOver { .. }
+                                            )
+                                        })
+                                        .unwrap_or_default()
+                                }),
+                        ingame_mode: PlayerIngameMode::Spectator,
+                    },
+                    None,
+                );
+                (
+                    stage_id,
+                    (&player.id, None, &player.player_info),
+                    Some(player_info),
+                    self.game_pools.network_string_score_pool.new(),
+                )
+            });
+            // of all chars (even server-side ones)
+            // + all spectator players
+            self.game
+                .stages
+                .iter()
+                .flat_map(|(stage_id, stage)| {
+                    stage.world.characters.iter().map(|(id, character)| {
+                        (
+                            Some(*stage_id),
+                            (id, Some(character.core.side), &character.player_info),
+                            self.game
+                                .players
+                                .player(id)
+                                .is_some()
+                                .then_some(CharacterPlayerInfo {
+                                    cam_mode: if matches!(
+                                        character.phased,
+                                        character::CharacterPhasedState::Dead { .. }
+                                    ) {
+                                        PlayerCameraMode::LockedTo {
+                                            pos: *character.pos.pos() / 32.0,
+                                            locked_ingame: true,
+                                        }
+                                    } else {
+                                        PlayerCameraMode::Default
+                                    },
+                                    force_scoreboard_visible: matches!(
+                                        stage.match_manager.game_match.state,
+                                        MatchState::GameOver { .. }
+                                    ),
                                     ingame_mode: PlayerIngameMode::InGame {
                                         in_custom_stage: *stage_id != self.stage_0_id,
                                     },
@@ -1798,11 +2451,13 @@ pub mod state {
                         id: *id,
 
                         score: ScoreboardScoreType::Points(character.score.get()),
-                        ping: if let Some(stats) = character.is_player_character() {
-                            ScoreboardConnectionType::Network(stats)
-                        } else {
-                            ScoreboardConnectionType::Bot
+                        ping: match character.is_player_character() {
+                            Some(stats) if !self.bots.contains(id) => {
+                                ScoreboardConnectionType::Network(stats)
+                            }
+                            _ => ScoreboardConnectionType::Bot,
                         },
+                        stats: Some(character.stats.to_scoreboard()),
                     };
 
                     match character.core.side {
@@ -1874,6 +2529,7 @@ pub mod state {
 
                     score: ScoreboardScoreType::None,
                     ping: ScoreboardConnectionType::Network(p.network_stats),
+                    stats: None,
                 });
             }
 
@@ -1920,6 +2576,12 @@ pub mod state {
                         score_limit: self.game_options.score_limit(),
                         time_limit: self.game_options.time_limit(),
                     },
+                    match_over: self.game.stages.get(&self.stage_0_id).is_some_and(|stage| {
+                        matches!(
+                            stage.match_manager.game_match.state,
+                            MatchState::GameOver { .. }
+                        )
+                    }),
                 },
             }
         }
@@ -2048,173 +2710,15 @@ pub mod state {
         }
 
         fn player_join(&mut self, client_player_info: &PlayerClientInfo) -> PlayerId {
-            if let Some((timeout_player_id, character_info)) = self
-                .game
-                .timeout_players
-                .remove(&(client_player_info.unique_identifier, client_player_info.id))
-                .and_then(|(id, _)| self.game.players.player(&id).map(|char| (id, char)))
-            {
-                let char = self
-                    .game
-                    .stages
-                    .get_mut(&character_info.stage_id())
-                    .unwrap()
-                    .world
-                    .characters
-                    .get_mut(&timeout_player_id)
-                    .unwrap();
-                char.core.is_timeout = false;
-                return timeout_player_id;
-            }
-
-            let player_id = self.id_generator.next_id();
-            let stage_0_id = self.stage_0_id;
-
-            let character_info = self.check_player_info(client_player_info.info.clone(), None);
-
-            self.game
-                .stages
-                .get(&stage_0_id)
-                .unwrap()
-                .game_pending_events
-                .push(GameWorldEvent::Notification(
-                    GameWorldNotificationEvent::System(GameWorldSystemMessage::PlayerJoined {
-                        id: player_id,
-                        name: {
-                            let mut s = self.game_pools.mt_network_string_name_pool.new();
-                            s.try_set(character_info.name.as_str()).unwrap();
-                            s
-                        },
-                        skin: {
-                            let mut skin = self.game_pools.mt_resource_key_pool.new();
-                            (*skin).clone_from(&character_info.skin);
-                            skin
-                        },
-                        skin_info: character_info.skin_info,
-                    }),
-                ));
-
-            if client_player_info.id == 0 {
-                let events = self.player_events.entry(player_id).or_default();
-
-                let mut msg = self.game_pools.mt_network_string_common_pool.new();
-                msg.try_set("alpha version vanilla.").unwrap();
-
-                events.push(GameWorldEvent::Notification(
-                    GameWorldNotificationEvent::Motd { msg },
-                ));
-            }
-
-            let player_info = PlayerInfo {
-                player_info: PoolRc::from_item_without_pool(character_info),
-                version: 0,
-                unique_identifier: client_player_info.unique_identifier,
-                account_name: None,
-                id: client_player_info.id,
-            };
-            if self
-                .game
-                .stages
-                .get(&self.stage_0_id)
-                .unwrap()
-                .world
-                .characters
-                .len()
-                < self.game_options.max_ingame_players() as usize
-            {
-                // spawn and send character info
-                let default_eyes = player_info.player_info.default_eyes;
-                Self::add_char_to_stage(
-                    &mut self.game.stages,
-                    &stage_0_id,
-                    &player_id,
-                    player_info,
-                    Default::default(),
-                    self.game.players.clone(),
-                    self.game.spectator_players.clone(),
-                    client_player_info.initial_network_stats,
-                    None,
-                    0,
-                    default_eyes,
-                    Default::default(),
-                    &self.game_pools,
-                );
-            } else {
-                self.game.spectator_players.insert(
-                    player_id,
-                    SpectatorPlayer::new(
-                        player_info,
-                        Default::default(),
-                        &player_id,
-                        self.game_pools.character_id_hashset_pool.new(),
-                        client_player_info.info.default_eyes,
-                        Default::default(),
-                        client_player_info.initial_network_stats,
-                    ),
-                );
-            }
-
-            Self::push_account_info_task(
-                &mut self.game_db,
-                &player_id,
-                &client_player_info.unique_identifier,
-            );
-
+            self.make_room_for_player();
+            let player_id = self.player_join_impl(client_player_info);
+            self.fill_bots();
             player_id
         }
 
         fn player_drop(&mut self, player_id: &PlayerId, reason: PlayerDropReason) {
-            let name = if let Some(server_player) = self.game.players.player(player_id) {
-                let stage = self.game.stages.get_mut(&server_player.stage_id()).unwrap();
-
-                let character = stage.world.characters.get_mut(player_id).unwrap();
-
-                let mut name = self.game_pools.mt_network_string_name_pool.new();
-                (*name).clone_from(&character.player_info.player_info.name);
-
-                let skin = {
-                    let mut skin = self.game_pools.mt_resource_key_pool.new();
-                    (*skin).clone_from(&character.player_info.player_info.skin);
-                    skin
-                };
-                let skin_info = character.player_info.player_info.skin_info;
-
-                character.despawn_completely_silent();
-                stage.world.characters.remove(player_id);
-
-                Some((name, skin, skin_info, server_player.stage_id()))
-            } else if let Some(spectator_player) = self.game.spectator_players.remove(player_id) {
-                let mut name = self.game_pools.mt_network_string_name_pool.new();
-                (*name).clone_from(&spectator_player.player_info.player_info.name);
-                let skin = {
-                    let mut skin = self.game_pools.mt_resource_key_pool.new();
-                    (*skin).clone_from(&spectator_player.player_info.player_info.skin);
-                    skin
-                };
-                let skin_info = spectator_player.player_info.player_info.skin_info;
-                Some((name, skin, skin_info, self.stage_0_id))
-            } else {
-                None
-            };
-
-            if let Some((name, skin, skin_info, stage_id)) = name {
-                let stage = self.game.stages.get(&stage_id).unwrap();
-                stage.game_pending_events.push(GameWorldEvent::Notification(
-                    GameWorldNotificationEvent::System(GameWorldSystemMessage::PlayerLeft {
-                        id: *player_id,
-                        name: {
-                            let mut s = self.game_pools.mt_network_string_name_pool.new();
-                            s.try_set(name.as_str()).unwrap();
-                            s
-                        },
-                        skin,
-                        skin_info,
-                        reason,
-                    }),
-                ));
-
-                self.check_stage_remove(stage_id);
-            }
+            self.player_drop_impl(player_id, reason);
+            self.fill_bots();
         }
 
         fn try_overwrite_player_character_info(
@@ -2464,6 +2968,13 @@ pub mod state {
                     );
                     self.handle_chat_commands(player_id, cmds);
                 }
+                ClientCommand::JoinStage(_)
+                | ClientCommand::JoinSide(_)
+                | ClientCommand::JoinSpectator
+                    if self.teams_locked =>
+                {
+                    self.push_player_msg(player_id, "The teams are locked.");
+                }
                 ClientCommand::JoinStage(join_stage) => {
                     if self.game_options.allow_stages()
                         || (!Self::is_sided_from_conf(self.game_options.game_ty())
@@ -2582,7 +3093,7 @@ pub mod state {
                 }
                 ClientCommand::SetCameraMode(mut mode) => {
                     match &mut mode {
-                        ClientCameraMode::None => {
+                        ClientCameraMode::None | ClientCameraMode::AutoDirector => {
                             // nothing to do
                         }
                         ClientCameraMode::FreeCam(spectated_players)
@@ -2590,6 +3101,11 @@ pub mod state {
                             // validate that these players exist
                             spectated_players.retain(|id| self.game.players.player(id).is_some());
                         }
+                        ClientCameraMode::Stage(stage_id) => {
+                            if !self.game.stages.contains_key(stage_id) {
+                                return;
+                            }
+                        }
                     }
                     self.game.spectator_players.set_camera_mode(
                         player_id,
@@ -2706,16 +3222,19 @@ pub mod state {
         }
 
         fn tick(&mut self, options: TickOptions) -> TickResult {
+            if !options.is_future_tick_prediction {
+                self.bot_tick();
+            }
             self.tick_impl(options.is_future_tick_prediction);
 
+            let mut events = PoolVec::new_without_pool();
             if !options.is_future_tick_prediction {
                 self.player_tick();
                 self.query_tick();
+                self.match_result_tick(&mut events);
             }
 
-            TickResult {
-                events: PoolVec::new_without_pool(),
-            }
+            TickResult { events }
         }
 
         fn snapshot_for(&self, client: SnapshotClientInfo) -> MtPoolCow<'static, [u8]> {
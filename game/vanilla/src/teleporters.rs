@@ -0,0 +1,259 @@
+//! Ticks characters, hooks and projectiles through the teleporters
+//! of a small map that replaces the physics of ctf1.
+
+use std::sync::Arc;
+
+use base::linked_hash_map_view::FxLinkedHashMap;
+use base_io::{io::create_runtime, runtime::IoRuntime};
+use game_base::mapdef_06::DdraceTileNum;
+use game_database::dummy::DummyDb;
+use game_interface::{
+    interface::{GameStateCreate, GameStateCreateOptions, GameStateInterface},
+    types::{
+        character_info::NetworkCharacterInfo,
+        id_types::PlayerId,
+        input::{cursor::CharacterInputCursor, CharacterInput, CharacterInputInfo},
+        network_stats::PlayerNetworkStats,
+        player_info::{PlayerClientInfo, PlayerUniqueId},
+        render::projectiles::WeaponWithProjectile,
+    },
+};
+use map::{
+    map::groups::{
+        layers::{
+            physics::{MapLayerPhysics, MapLayerTilePhysicsBase, MapLayerTilePhysicsTele},
+            tiles::{TeleTile, TileBase},
+        },
+        MapGroupPhysics, MapGroupPhysicsAttr,
+    },
+    types::NonZeroU16MinusOne,
+};
+use math::math::{
+    distance,
+    vector::{dvec2, vec2},
+};
+use pool::pool::Pool;
+
+use crate::{
+    collision::collision::Collision,
+    entities::character::{character::Character, hook::character_hook::Hook},
+    state::state::GameState,
+};
+
+const W: usize = 30;
+const H: usize = 10;
+/// The row the characters stand in, the row below is solid.
+const ROW: usize = 7;
+
+fn tile_center(x: usize, y: usize) -> vec2 {
+    vec2::new(x as f32 * 32.0 + 16.0, y as f32 * 32.0 + 16.0)
+}
+
+/// Creates a game whose collision is a `W` x `H` map with the given
+/// tele tiles and lets a single player join.
+fn tele_game(tele_tiles: &[(usize, usize, DdraceTileNum, u8)]) -> (GameState, PlayerId) {
+    let mut tiles = vec![TileBase::default(); W * H];
+    for tile in tiles[(ROW + 1) * W..(ROW + 2) * W].iter_mut() {
+        tile.index = DdraceTileNum::Solid as u8;
+    }
+    let mut tele = vec![TeleTile::default(); W * H];
+    for &(x, y, index, number) in tele_tiles {
+        tele[y * W + x] = TeleTile {
+            base: TileBase {
+                index: index as u8,
+                ..Default::default()
+            },
+            number,
+        };
+    }
+    let collision = Collision::new(
+        &MapGroupPhysics {
+            attr: MapGroupPhysicsAttr {
+                width: NonZeroU16MinusOne::new(W as u16).unwrap(),
+                height: NonZeroU16MinusOne::new(H as u16).unwrap(),
+            },
+            layers: vec![
+                MapLayerPhysics::Game(MapLayerTilePhysicsBase { tiles }),
+                MapLayerPhysics::Tele(MapLayerTilePhysicsTele {
+                    base: MapLayerTilePhysicsBase { tiles: tele },
+                    tele_names: Default::default(),
+                }),
+            ],
+        },
+        true,
+    )
+    .unwrap();
+
+    let file = include_bytes!("../../../data/map/maps/ctf1.twmap");
+    let rt = create_runtime();
+    let io_rt = IoRuntime::new(rt);
+    let (mut game, _) = GameState::new(
+        file.to_vec(),
+        "ctf1".try_into().unwrap(),
+        GameStateCreateOptions::default(),
+        io_rt,
+        Arc::new(DummyDb),
+    )
+    .unwrap();
+    // ctf1 is bigger than the test map, so the playfield still fits
+    game.collision = collision;
+
+    let id = game.player_join(&PlayerClientInfo {
+        info: NetworkCharacterInfo::explicit_default(),
+        id: 0,
+        unique_identifier: PlayerUniqueId::Account(0),
+        initial_network_stats: PlayerNetworkStats::default(),
+    });
+    (game, id)
+}
+
+fn character<'a>(game: &'a mut GameState, id: &PlayerId) -> &'a mut Character {
+    let stage_id = game.stage_0_id;
+    game.game
+        .stages
+        .get_mut(&stage_id)
+        .unwrap()
+        .world
+        .characters
+        .get_mut(id)
+        .unwrap()
+}
+
+/// Places the character on the tile without any velocity.
+fn place(game: &mut GameState, id: &PlayerId, pos: vec2) {
+    let character = character(game, id);
+    character.pos.move_pos(pos);
+    character.core.core.vel = vec2::default();
+}
+
+fn tick(game: &mut GameState) {
+    game.tick(Default::default());
+    game.clear_events();
+}
+
+#[test]
+fn character_tele() {
+    let out = tile_center(20, ROW);
+    let evil_out = tile_center(24, ROW);
+    let (mut game, id) = tele_game(&[
+        (2, ROW, DdraceTileNum::TeleIn, 1),
+        (4, ROW, DdraceTileNum::TeleInEvil, 2),
+        (20, ROW, DdraceTileNum::TeleOut, 1),
+        (24, ROW, DdraceTileNum::TeleOut, 2),
+    ]);
+
+    place(&mut game, &id, tile_center(2, ROW));
+    character(&mut game, &id).core.core.vel = vec2::new(5.0, 0.0);
+    tick(&mut game);
+    let character = character(&mut game, &id);
+    assert!(distance(character.pos.pos(), &out) < 32.0);
+    // normal teleporters keep the velocity
+    assert!(character.core.core.vel.x > 0.0);
+
+    place(&mut game, &id, tile_center(4, ROW));
+    character(&mut game, &id).core.core.vel = vec2::new(5.0, 0.0);
+    tick(&mut game);
+    let character = character(&mut game, &id);
+    assert!(distance(character.pos.pos(), &evil_out) < 1.0);
+    assert_eq!(character.core.core.vel.x, 0.0);
+}
+
+#[test]
+fn character_tele_checkpoint() {
+    let check_out = tile_center(24, ROW);
+    let (mut game, id) = tele_game(&[
+        (2, ROW, DdraceTileNum::TeleCheck, 3),
+        (6, ROW, DdraceTileNum::TeleCheckIn, 0),
+        (24, ROW, DdraceTileNum::TeleCheckOut, 3),
+    ]);
+
+    // without a passed checkpoint there is nothing to teleport to
+    place(&mut game, &id, tile_center(6, ROW));
+    tick(&mut game);
+    assert!(distance(character(&mut game, &id).pos.pos(), &tile_center(6, ROW)) < 32.0);
+
+    place(&mut game, &id, tile_center(2, ROW));
+    tick(&mut game);
+    assert_eq!(character(&mut game, &id).core.tele_checkpoint, Some(3));
+
+    place(&mut game, &id, tile_center(6, ROW));
+    tick(&mut game);
+    assert!(distance(character(&mut game, &id).pos.pos(), &check_out) < 32.0);
+}
+
+#[test]
+fn hook_tele() {
+    let out = tile_center(20, ROW);
+    let (mut game, id) = tele_game(&[
+        (5, ROW, DdraceTileNum::TeleInHook, 4),
+        (20, ROW, DdraceTileNum::TeleOut, 4),
+    ]);
+    place(&mut game, &id, tile_center(2, ROW));
+
+    // fire the hook to the right
+    let mut inp = CharacterInput::default();
+    inp.cursor
+        .set(CharacterInputCursor::from_vec2(&dvec2::new(100.0, 0.0)));
+    inp.state.hook.set(true);
+    inp.consumable.hook.add(1, *inp.cursor);
+    let diff = inp.consumable.diff(&CharacterInput::default().consumable);
+    let inps_pool: Pool<FxLinkedHashMap<PlayerId, CharacterInputInfo>> = Pool::with_capacity(1);
+    let mut inps = inps_pool.new();
+    inps.insert(id, CharacterInputInfo { inp, diff });
+    game.set_player_inputs(inps);
+
+    let teleported = (0..10).any(|_| {
+        tick(&mut game);
+        matches!(
+            character(&mut game, &id).phased.hook().hook(),
+            Hook::Active { hook_pos, .. } if hook_pos.x > out.x - 32.0
+        )
+    });
+    assert!(teleported, "the hook never passed the hook teleporter");
+}
+
+#[test]
+fn projectile_tele() {
+    let out = tile_center(20, ROW);
+    let (mut game, id) = tele_game(&[
+        (6, ROW, DdraceTileNum::TeleInWeapon, 5),
+        (6, ROW - 1, DdraceTileNum::TeleInWeapon, 5),
+        (20, ROW, DdraceTileNum::TeleOut, 5),
+    ]);
+
+    let projectile_id = game.id_generator.next_id();
+    let stage_id = game.stage_0_id;
+    game.game
+        .stages
+        .get_mut(&stage_id)
+        .unwrap()
+        .world
+        .insert_new_projectile(
+            projectile_id,
+            id,
+            &tile_center(3, ROW),
+            &vec2::new(1.0, 0.0),
+            50,
+            1,
+            0.0,
+            false,
+            WeaponWithProjectile::Gun,
+            None,
+        );
+
+    let teleported = (0..10).any(|_| {
+        tick(&mut game);
+        game.game
+            .stages
+            .get(&stage_id)
+            .unwrap()
+            .world
+            .projectiles
+            .get(&projectile_id)
+            .is_some_and(|proj| proj.projectile.core.pos.x > out.x - 32.0)
+    });
+    assert!(
+        teleported,
+        "the projectile never passed the weapon teleporter"
+    );
+}
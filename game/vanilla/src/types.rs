@@ -52,6 +52,13 @@ pub mod types {
                 None
             }
         }
+        pub fn warmup_time(&self) -> Option<Duration> {
+            if self.config.warmup_secs > 0 {
+                Some(Duration::from_secs(self.config.warmup_secs))
+            } else {
+                None
+            }
+        }
         pub fn friendly_fire(&self) -> bool {
             self.config.friendly_fire
         }
@@ -64,6 +71,12 @@ pub mod types {
         pub fn tournament_mode(&self) -> bool {
             self.config.tournament_mode
         }
+        pub fn export_match_stats(&self) -> bool {
+            self.config.export_match_stats
+        }
+        pub fn bot_fill_players(&self) -> u32 {
+            self.config.bot_fill_players
+        }
 
         pub fn config_clone(&self) -> ConfigVanilla {
             self.config.clone()
@@ -1040,7 +1040,8 @@ pub mod world {
                                     }
                                 }
                                 FlagEvent::Collect { .. } |
-                                FlagEvent::Capture { .. } => {
+                                FlagEvent::Capture { .. } |
+                                FlagEvent::Return { .. } => {
                                     // ignore
                                 }
                             },
@@ -0,0 +1,416 @@
+//! A pure Rust encoder that works on every platform.
+//!
+//! Every video frame is written as lossless PNG, the audio as 32-bit float WAV:
+//! `<name>/000000.png`, `<name>/000001.png`, ... and `<name>/audio.wav`,
+//! where `<name>` is the file path without extension.
+//! The result can be muxed by any video editor or e.g.
+//! `ffmpeg -framerate <fps> -i %06d.png -i audio.wav out.mp4`.
+
+use std::{
+    fs::File,
+    io::{BufWriter, Seek, SeekFrom, Write},
+    marker::PhantomData,
+    path::{Path, PathBuf},
+    rc::Rc,
+    sync::{atomic::AtomicU64, mpsc, Arc},
+};
+
+use base::join_thread::JoinThread;
+use graphics_backend::backend::GraphicsBackend;
+use graphics_backend_traits::{
+    frame_fetcher_plugin::{
+        BackendFrameFetcher, BackendPresentedImageDataRgba, FetchCanvasError, FetchCanvasIndex,
+        OffscreenCanvasId,
+    },
+    traits::GraphicsBackendInterface,
+};
+use hiarc::{hiarc_safer_arc_mutex, Hiarc};
+use pool::mt_datatypes::PoolVec;
+use rayon::iter::{ParallelBridge, ParallelIterator};
+use sound::backend_types::SoundBackendInterface;
+use sound::frame_fetcher_plugin::{
+    self, BackendAudioFrame, FetchSoundManagerError, FetchSoundManagerIndex, OffairSoundManagerId,
+};
+use sound_backend::sound_backend::SoundBackend;
+
+use crate::{traits::AudioVideoEncoder, types::EncoderSettings};
+
+/// Size of the header written by [`WavWriter`].
+const WAV_HEADER_SIZE: u32 = 58;
+/// Size of a stereo frame of 32-bit float samples.
+const WAV_BYTES_PER_FRAME: u32 = 2 * std::mem::size_of::<f32>() as u32;
+/// The sizes in the header are 32-bit, which limits a WAV file to 4 GiB
+/// (about 3.1 hours at 48 kHz).
+const WAV_MAX_FRAMES: u32 = (u32::MAX - (WAV_HEADER_SIZE - 8)) / WAV_BYTES_PER_FRAME;
+
+/// Writes stereo 32-bit float samples to a WAV file.
+///
+/// The sizes in the header are only known at the end,
+/// so they are written by [`WavWriter::finish`].
+struct WavWriter {
+    file: BufWriter<File>,
+    frames: u32,
+}
+
+impl WavWriter {
+    fn new(path: &Path, sample_rate: u32) -> std::io::Result<Self> {
+        let mut file = BufWriter::new(File::create(path)?);
+        let channels: u16 = 2;
+        let bytes_per_sample: u16 = std::mem::size_of::<f32>() as u16;
+        let block_align = channels * bytes_per_sample;
+
+        file.write_all(b"RIFF")?;
+        file.write_all(&(WAV_HEADER_SIZE - 8).to_le_bytes())?;
+        file.write_all(b"WAVE")?;
+
+        file.write_all(b"fmt ")?;
+        file.write_all(&18u32.to_le_bytes())?;
+        // IEEE float
+        file.write_all(&3u16.to_le_bytes())?;
+        file.write_all(&channels.to_le_bytes())?;
+        file.write_all(&sample_rate.to_le_bytes())?;
+        file.write_all(&(sample_rate * block_align as u32).to_le_bytes())?;
+        file.write_all(&block_align.to_le_bytes())?;
+        file.write_all(&(bytes_per_sample * 8).to_le_bytes())?;
+        // no extension
+        file.write_all(&0u16.to_le_bytes())?;
+
+        // non PCM formats require the number of frames
+        file.write_all(b"fact")?;
+        file.write_all(&4u32.to_le_bytes())?;
+        file.write_all(&0u32.to_le_bytes())?;
+
+        file.write_all(b"data")?;
+        file.write_all(&0u32.to_le_bytes())?;
+
+        Ok(Self { file, frames: 0 })
+    }
+
+    /// Fails once the file reached the size limit of WAV files.
+    fn write(&mut self, frame: &BackendAudioFrame) -> std::io::Result<()> {
+        if self.frames >= WAV_MAX_FRAMES {
+            return Err(std::io::Error::other(
+                "the WAV file reached its size limit of 4 GiB",
+            ));
+        }
+        self.file.write_all(&frame.left.to_le_bytes())?;
+        self.file.write_all(&frame.right.to_le_bytes())?;
+        self.frames += 1;
+        Ok(())
+    }
+
+    fn finish(mut self) -> std::io::Result<()> {
+        let too_big = || std::io::Error::other("the WAV file is bigger than 4 GiB");
+        let data_size = self
+            .frames
+            .checked_mul(WAV_BYTES_PER_FRAME)
+            .ok_or_else(too_big)?;
+        let riff_size = data_size
+            .checked_add(WAV_HEADER_SIZE - 8)
+            .ok_or_else(too_big)?;
+        self.file.seek(SeekFrom::Start(4))?;
+        self.file.write_all(&riff_size.to_le_bytes())?;
+        self.file.seek(SeekFrom::Start(46))?;
+        self.file.write_all(&self.frames.to_le_bytes())?;
+        self.file.seek(SeekFrom::Start(54))?;
+        self.file.write_all(&data_size.to_le_bytes())?;
+        self.file.flush()
+    }
+}
+
+/// Writes a RGBA frame as RGB PNG, the alpha channel of
+/// the rendered frame is meaningless for a video.
+fn write_png(path: &Path, width: u32, height: u32, rgba: &[u8]) -> anyhow::Result<()> {
+    let rgb: Vec<u8> = rgba
+        .chunks_exact(4)
+        .flat_map(|pixel| [pixel[0], pixel[1], pixel[2]])
+        .collect();
+    let mut encoder = png::Encoder::new(BufWriter::new(File::create(path)?), width, height);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.set_compression(png::Compression::Fast);
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(&rgb)?;
+    writer.finish()?;
+    Ok(())
+}
+
+#[hiarc_safer_arc_mutex]
+#[derive(Debug, Hiarc)]
+pub struct ImageSequenceEncoderImpl {
+    video_sender: std::sync::mpsc::Sender<(PoolVec<u8>, u64)>,
+    cur_video_frame: u64,
+    video_frame_buffer_id: OffscreenCanvasId,
+
+    audio_sender: std::sync::mpsc::Sender<BackendAudioFrame>,
+    audio_frame_buffer_id: OffairSoundManagerId,
+
+    video_frames_in_queue: Arc<AtomicU64>,
+    max_video_frames_in_queue: u64,
+
+    _backend_data: PhantomData<BackendPresentedImageDataRgba>,
+    _video_thread: JoinThread<()>,
+    _audio_thread: JoinThread<()>,
+}
+
+#[hiarc_safer_arc_mutex]
+impl ImageSequenceEncoderImpl {
+    pub fn new(
+        video_frame_buffer_id: OffscreenCanvasId,
+        audio_frame_buffer_id: OffairSoundManagerId,
+        file_path: &Path,
+        encoder_settings: EncoderSettings,
+    ) -> anyhow::Result<Self> {
+        let dir: PathBuf = file_path.with_extension("");
+        std::fs::create_dir_all(&dir)?;
+
+        let (video_sender, video_receiver) = mpsc::channel::<(PoolVec<u8>, u64)>();
+        let (audio_sender, audio_receiver) = mpsc::channel::<BackendAudioFrame>();
+
+        let video_frames_in_queue: Arc<AtomicU64> = Default::default();
+        let max_video_frames_in_queue: u64 = encoder_settings.max_threads;
+
+        let thread_pool = Arc::new(
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(encoder_settings.max_threads as usize)
+                .build()?,
+        );
+
+        let mut wav = WavWriter::new(&dir.join("audio.wav"), encoder_settings.sample_rate)?;
+
+        let video_dir = dir.clone();
+        let video_frames_in_queue_thread = video_frames_in_queue.clone();
+        let video_thread = std::thread::Builder::new()
+            .name("png-encoder".to_string())
+            .spawn(move || {
+                thread_pool.install(|| {
+                    video_receiver
+                        .into_iter()
+                        .par_bridge()
+                        .for_each(|(rendered, index)| {
+                            let path = video_dir.join(format!("{index:06}.png"));
+                            if let Err(err) = write_png(
+                                &path,
+                                encoder_settings.width,
+                                encoder_settings.height,
+                                &rendered,
+                            ) {
+                                log::error!("writing {} failed: {err}", path.display());
+                            }
+                            video_frames_in_queue_thread
+                                .fetch_sub(1, std::sync::atomic::Ordering::Relaxed);
+                        });
+                })
+            })?;
+        let audio_thread = std::thread::Builder::new()
+            .name("wav-encoder".to_string())
+            .spawn(move || {
+                let res = audio_receiver
+                    .into_iter()
+                    .try_for_each(|frame| wav.write(&frame));
+                // the audio written so far is still usable
+                let res = res.and(wav.finish());
+                if let Err(err) = res {
+                    log::error!("writing the audio to {} failed: {err}", dir.display());
+                }
+            })?;
+
+        Ok(Self {
+            video_sender,
+            cur_video_frame: 0,
+            video_frame_buffer_id,
+
+            audio_sender,
+            audio_frame_buffer_id,
+
+            video_frames_in_queue,
+            max_video_frames_in_queue,
+
+            _backend_data: Default::default(),
+
+            _video_thread: JoinThread::new(video_thread),
+            _audio_thread: JoinThread::new(audio_thread),
+        })
+    }
+
+    pub fn overloaded(&self) -> bool {
+        self.video_frames_in_queue
+            .load(std::sync::atomic::Ordering::Relaxed)
+            >= self.max_video_frames_in_queue
+    }
+}
+
+#[hiarc_safer_arc_mutex]
+impl BackendFrameFetcher for ImageSequenceEncoderImpl {
+    #[hiarc_trait_is_immutable_self]
+    fn next_frame(&mut self, frame_data: BackendPresentedImageDataRgba) {
+        self.video_sender
+            .send((frame_data.dest_data_buffer, self.cur_video_frame))
+            .unwrap();
+        self.cur_video_frame += 1;
+        self.video_frames_in_queue
+            .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+    }
+
+    fn current_fetch_index(&self) -> FetchCanvasIndex {
+        FetchCanvasIndex::Offscreen(self.video_frame_buffer_id)
+    }
+
+    fn fetch_err(&self, err: FetchCanvasError) {
+        match err {
+            FetchCanvasError::CanvasNotFound => {
+                // ignore for now
+            }
+            FetchCanvasError::DriverErr(err) => {
+                panic!("err in video encoding: {err}");
+            }
+        }
+    }
+}
+
+#[hiarc_safer_arc_mutex]
+impl frame_fetcher_plugin::BackendFrameFetcher for ImageSequenceEncoderImpl {
+    #[hiarc_trait_is_immutable_self]
+    fn next_frame(&mut self, frame_data: BackendAudioFrame) {
+        // the audio thread stops early if writing failed
+        let _ = self.audio_sender.send(frame_data);
+    }
+
+    fn current_fetch_index(&self) -> FetchSoundManagerIndex {
+        FetchSoundManagerIndex::Offair(self.audio_frame_buffer_id)
+    }
+
+    fn fetch_err(&self, err: FetchSoundManagerError) {
+        match err {
+            FetchSoundManagerError::SoundManagerNotFound => {
+                // ignore for now
+            }
+            FetchSoundManagerError::DriverErr(err) => {
+                panic!("err in audio encoding: {err}");
+            }
+        }
+    }
+}
+
+pub struct ImageSequenceEncoder {
+    backend: Rc<GraphicsBackend>,
+    sound_backend: Rc<SoundBackend>,
+    encoder: Arc<ImageSequenceEncoderImpl>,
+}
+
+impl AudioVideoEncoder for ImageSequenceEncoder {
+    fn new(
+        video_frame_buffer_id: OffscreenCanvasId,
+        audio_frame_buffer_id: OffairSoundManagerId,
+        file_path: &Path,
+        backend: &Rc<GraphicsBackend>,
+        sound_backend: &Rc<SoundBackend>,
+        encoder_settings: EncoderSettings,
+    ) -> anyhow::Result<Self> {
+        let encoder = Arc::new(ImageSequenceEncoderImpl::new(
+            video_frame_buffer_id,
+            audio_frame_buffer_id,
+            file_path,
+            encoder_settings,
+        )?);
+
+        backend.attach_frame_fetcher("av-encoder".into(), encoder.clone())?;
+        sound_backend.attach_frame_fetcher("av-encoder".into(), encoder.clone())?;
+
+        Ok(Self {
+            backend: backend.clone(),
+            sound_backend: sound_backend.clone(),
+            encoder,
+        })
+    }
+
+    fn overloaded(&self) -> bool {
+        self.encoder.overloaded()
+    }
+}
+
+impl Drop for ImageSequenceEncoder {
+    fn drop(&mut self) {
+        let _ = self.backend.detach_frame_fetcher("av-encoder".into());
+        let _ = self.sound_backend.detach_frame_fetcher("av-encoder".into());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use sound::frame_fetcher_plugin::BackendAudioFrame;
+
+    use super::{WavWriter, WAV_BYTES_PER_FRAME, WAV_HEADER_SIZE, WAV_MAX_FRAMES};
+
+    #[test]
+    fn wav_header() {
+        let path = std::env::temp_dir().join(format!("av-encoder-test-{}.wav", std::process::id()));
+        let mut wav = WavWriter::new(&path, 48000).unwrap();
+        for i in 0..3 {
+            wav.write(&BackendAudioFrame {
+                left: i as f32,
+                right: -(i as f32),
+            })
+            .unwrap();
+        }
+        wav.finish().unwrap();
+        let file = std::fs::read(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let u16_at = |off: usize| u16::from_le_bytes(file[off..off + 2].try_into().unwrap());
+        let u32_at = |off: usize| u32::from_le_bytes(file[off..off + 4].try_into().unwrap());
+
+        let data_size = 3 * 2 * 4;
+        assert_eq!(file.len(), WAV_HEADER_SIZE as usize + data_size);
+
+        assert_eq!(&file[0..4], b"RIFF");
+        assert_eq!(u32_at(4) as usize, file.len() - 8);
+        assert_eq!(&file[8..12], b"WAVE");
+
+        assert_eq!(&file[12..16], b"fmt ");
+        assert_eq!(u32_at(16), 18);
+        assert_eq!(u16_at(20), 3);
+        assert_eq!(u16_at(22), 2);
+        assert_eq!(u32_at(24), 48000);
+        assert_eq!(u32_at(28), 48000 * 8);
+        assert_eq!(u16_at(32), 8);
+        assert_eq!(u16_at(34), 32);
+
+        assert_eq!(&file[38..42], b"fact");
+        assert_eq!(u32_at(42), 4);
+        assert_eq!(u32_at(46), 3);
+
+        assert_eq!(&file[50..54], b"data");
+        assert_eq!(u32_at(54) as usize, data_size);
+        assert_eq!(
+            f32::from_le_bytes(file[70..74].try_into().unwrap()),
+            -1.0,
+            "samples are interleaved"
+        );
+    }
+
+    #[test]
+    fn wav_size_limit() {
+        let path =
+            std::env::temp_dir().join(format!("av-encoder-limit-test-{}.wav", std::process::id()));
+        let mut wav = WavWriter::new(&path, 48000).unwrap();
+        let frame = BackendAudioFrame {
+            left: 0.0,
+            right: 0.0,
+        };
+        wav.frames = WAV_MAX_FRAMES - 1;
+        wav.write(&frame).unwrap();
+        assert!(wav.write(&frame).is_err());
+        wav.finish().unwrap();
+        let file = std::fs::read(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let u32_at = |off: usize| u32::from_le_bytes(file[off..off + 4].try_into().unwrap());
+        assert_eq!(u32_at(46), WAV_MAX_FRAMES);
+        assert_eq!(u32_at(54), WAV_MAX_FRAMES * WAV_BYTES_PER_FRAME);
+        assert_eq!(
+            u32_at(4),
+            WAV_HEADER_SIZE - 8 + WAV_MAX_FRAMES * WAV_BYTES_PER_FRAME
+        );
+    }
+}
@@ -3,11 +3,81 @@ pub mod encoder;
 #[cfg(feature = "ffmpeg")]
 mod ffmpeg;
 
+pub mod image_sequence;
 pub mod stub;
 pub mod traits;
 pub mod types;
 
-#[cfg(feature = "ffmpeg")]
-pub type AvEncoder = encoder::FfmpegEncoder;
-#[cfg(not(feature = "ffmpeg"))]
-pub type AvEncoder = stub::StubEncoder;
+use std::{path::Path, rc::Rc};
+
+use graphics_backend::backend::GraphicsBackend;
+use graphics_backend_traits::frame_fetcher_plugin::OffscreenCanvasId;
+use sound::frame_fetcher_plugin::OffairSoundManagerId;
+use sound_backend::sound_backend::SoundBackend;
+
+use crate::{
+    image_sequence::ImageSequenceEncoder,
+    traits::AudioVideoEncoder,
+    types::{EncoderKind, EncoderSettings},
+};
+
+/// Picks the encoder implementation from [`EncoderSettings::kind`].
+pub enum AvEncoder {
+    #[cfg(feature = "ffmpeg")]
+    Ffmpeg(encoder::FfmpegEncoder),
+    ImageSequence(ImageSequenceEncoder),
+}
+
+impl AudioVideoEncoder for AvEncoder {
+    fn new(
+        video_frame_buffer_id: OffscreenCanvasId,
+        audio_frame_buffer_id: OffairSoundManagerId,
+        file_path: &Path,
+        backend: &Rc<GraphicsBackend>,
+        sound_backend: &Rc<SoundBackend>,
+        encoder_settings: EncoderSettings,
+    ) -> anyhow::Result<Self> {
+        match encoder_settings.kind {
+            #[cfg(feature = "ffmpeg")]
+            EncoderKind::Auto | EncoderKind::Ffmpeg => {
+                Ok(Self::Ffmpeg(encoder::FfmpegEncoder::new(
+                    video_frame_buffer_id,
+                    audio_frame_buffer_id,
+                    file_path,
+                    backend,
+                    sound_backend,
+                    encoder_settings,
+                )?))
+            }
+            #[cfg(not(feature = "ffmpeg"))]
+            EncoderKind::Ffmpeg => Err(anyhow::anyhow!(
+                "This build does not support ffmpeg, use the image sequence encoder instead."
+            )),
+            #[cfg(not(feature = "ffmpeg"))]
+            EncoderKind::Auto => Ok(Self::ImageSequence(ImageSequenceEncoder::new(
+                video_frame_buffer_id,
+                audio_frame_buffer_id,
+                file_path,
+                backend,
+                sound_backend,
+                encoder_settings,
+            )?)),
+            EncoderKind::ImageSequence => Ok(Self::ImageSequence(ImageSequenceEncoder::new(
+                video_frame_buffer_id,
+                audio_frame_buffer_id,
+                file_path,
+                backend,
+                sound_backend,
+                encoder_settings,
+            )?)),
+        }
+    }
+
+    fn overloaded(&self) -> bool {
+        match self {
+            #[cfg(feature = "ffmpeg")]
+            Self::Ffmpeg(encoder) => encoder.overloaded(),
+            Self::ImageSequence(encoder) => encoder.overloaded(),
+        }
+    }
+}
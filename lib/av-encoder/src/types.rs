@@ -1,3 +1,16 @@
+/// The implementation used to encode the video.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum EncoderKind {
+    /// ffmpeg if this build supports it, otherwise the image sequence.
+    #[default]
+    Auto,
+    /// A H.264 encoded mp4 file, requires the `ffmpeg` feature.
+    Ffmpeg,
+    /// Lossless PNG images for every frame and a WAV file for the audio.
+    /// Works on every platform.
+    ImageSequence,
+}
+
 /// Settings that are given to the encoder
 #[derive(Debug, Clone)]
 pub struct EncoderSettings {
@@ -16,6 +29,8 @@ pub struct EncoderSettings {
     pub hw_accel: String,
     /// Max number of CPU threads the encoders should use.
     pub max_threads: u64,
+    /// The implementation used to encode the video.
+    pub kind: EncoderKind,
     /// Sample rate for audio.
     /// It's __strongly__ recommended that this is a multiple of
     /// [`Self::fps`].
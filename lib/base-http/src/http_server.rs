@@ -1,6 +1,8 @@
 use std::{
     collections::HashMap,
+    net::SocketAddr,
     path::{Path, PathBuf},
+    sync::Arc,
     time::Duration,
 };
 
@@ -105,3 +107,76 @@ impl Drop for HttpDownloadServer {
         }
     }
 }
+
+/// Generates the response body of a route on every request.
+pub type HttpDynamicRoute = Arc<dyn Fn() -> String + Send + Sync>;
+
+/// A small server that answers GET requests with text
+/// that is generated on request, e.g. a metrics endpoint.
+pub struct HttpDynamicServer {
+    rt: Option<tokio::runtime::Runtime>,
+    join: Option<tokio::task::JoinHandle<anyhow::Result<()>>>,
+
+    pub addr: SocketAddr,
+}
+
+impl HttpDynamicServer {
+    /// `routes` maps the path to the content type and the
+    /// generator of the response.
+    pub fn new(
+        routes: HashMap<String, (&'static str, HttpDynamicRoute)>,
+        addr: SocketAddr,
+    ) -> anyhow::Result<Self> {
+        let rt = tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .worker_threads(1)
+            .build()?;
+        let _g = rt.enter();
+
+        let tcp_socket = if addr.is_ipv4() {
+            TcpSocket::new_v4()?
+        } else {
+            TcpSocket::new_v6()?
+        };
+        tcp_socket.set_reuseaddr(true)?;
+        tcp_socket.bind(addr)?;
+        let addr = tcp_socket.local_addr()?;
+        let listener = tcp_socket.listen(1024)?;
+
+        let mut app = axum::Router::new();
+        for (path, (content_type, route)) in routes {
+            app = app.route(
+                &format!("/{}", path.trim_start_matches('/')),
+                axum::routing::get(move || async move {
+                    ([(http::header::CONTENT_TYPE, content_type)], route())
+                }),
+            );
+        }
+
+        let join = tokio::task::spawn(async move {
+            axum::serve(listener, app).await?;
+            Ok(())
+        });
+
+        Ok(Self {
+            rt: Some(rt),
+            join: Some(join),
+
+            addr,
+        })
+    }
+}
+
+impl Drop for HttpDynamicServer {
+    fn drop(&mut self) {
+        if let Some(rt) = self.rt.take() {
+            if let Some(join) = self.join.take() {
+                join.abort();
+                if let Ok(Err(err)) = rt.block_on(join) {
+                    log::error!("http server exited with an error: {err}");
+                }
+            }
+            rt.shutdown_timeout(Duration::from_secs(1));
+        }
+    }
+}
@@ -1,5 +1,10 @@
+use std::path::Path;
+
 use base_io::io::{Io, IoFileSys};
-use config::config::ConfigEngine;
+use config::{
+    config::{ConfigEngine, ENGINE_MIGRATIONS},
+    migration::ConfigMigrations,
+};
 
 pub fn save(config: &ConfigEngine, io: &Io) {
     let save_str = config.to_json_string();
@@ -16,11 +21,55 @@ pub fn save(config: &ConfigEngine, io: &Io) {
     }
 }
 
-pub fn load(io: &IoFileSys) -> anyhow::Result<ConfigEngine> {
+/// Reads a stored config and applies the migrations it is missing.
+///
+/// Before migrating, the old file is copied to e.g. `cfg_game.v0.json.bak`.
+/// The migrated config is written back right away, so the migrations
+/// only run once.
+pub fn load_migrated(
+    io: &IoFileSys,
+    path: &Path,
+    migrations: &'static ConfigMigrations,
+) -> anyhow::Result<serde_json::Value> {
     let fs = io.fs.clone();
-    let config_file = io
-        .rt
-        .spawn(async move { Ok(fs.read_file("cfg_engine.json".as_ref()).await?) });
-    let res = config_file.get_storage()?;
-    ConfigEngine::from_json_slice(&res)
+    let path = path.to_path_buf();
+    let config_file = io.rt.spawn(async move {
+        let file = fs.read_file(path.as_ref()).await?;
+        let mut json = ConfigMigrations::parse(&file)?;
+        let file_version = ConfigMigrations::file_version(&json);
+        if migrations.needs_migration(&json) {
+            let backup_path = path.with_extension(format!("v{file_version}.json.bak"));
+            fs.write_file(&backup_path, file).await?;
+            for migration in migrations.migrate(&mut json)? {
+                log::info!(
+                    "migrated {} from version {}: {}",
+                    path.display(),
+                    migration.from_version,
+                    migration.description
+                );
+            }
+            fs.write_file(&path, serde_json::to_vec_pretty(&json)?)
+                .await?;
+            log::info!(
+                "the config before the migration was saved to {}",
+                backup_path.display()
+            );
+        } else if file_version > migrations.version() {
+            log::warn!(
+                "{} was written by a newer version (config version {file_version}), \
+                values this version doesn't know are ignored",
+                path.display()
+            );
+        }
+        Ok(json)
+    });
+    config_file.get_storage()
+}
+
+pub fn load(io: &IoFileSys) -> anyhow::Result<ConfigEngine> {
+    ConfigEngine::from_json_value(load_migrated(
+        io,
+        "cfg_engine.json".as_ref(),
+        &ENGINE_MIGRATIONS,
+    )?)
 }
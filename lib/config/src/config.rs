@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use crate::{self as config, types::ConfRgb};
+use crate::{self as config, migration::ConfigMigrations, types::ConfRgb};
 
 use anyhow::anyhow;
 use atomic_enum::atomic_enum;
@@ -199,6 +199,10 @@ pub struct ConfigBackend {
     pub full_pipeline_creation: bool,
 }
 
+/// Migrations of the stored engine config, see [`ConfigMigrations`].
+/// New migrations are appended, existing ones must never change.
+pub static ENGINE_MIGRATIONS: ConfigMigrations = ConfigMigrations::new(&[]);
+
 #[config_default]
 #[derive(Debug, Clone, Serialize, Deserialize, ConfigInterface)]
 pub struct ConfigEngine {
@@ -234,8 +238,9 @@ impl ConfigEngine {
         }
     }
 
+    /// Serializes the config for storage, including its version.
     pub fn to_json_string(&self) -> anyhow::Result<String> {
-        Ok(serde_json::to_string_pretty(self)?)
+        ENGINE_MIGRATIONS.to_json_string(self)
     }
 
     pub fn from_json_string(json_str: &str) -> anyhow::Result<Self> {
@@ -245,6 +250,10 @@ impl ConfigEngine {
     pub fn from_json_slice(json: &[u8]) -> anyhow::Result<Self> {
         Ok(serde_json::from_slice(json)?)
     }
+
+    pub fn from_json_value(json: serde_json::Value) -> anyhow::Result<Self> {
+        Ok(serde_json::from_value(json)?)
+    }
 }
 
 #[cfg(test)]
@@ -1,5 +1,7 @@
 pub mod config;
+pub mod migration;
 pub mod parsing;
+pub mod schema;
 pub mod traits;
 pub mod types;
 
@@ -0,0 +1,164 @@
+//! Versioning of stored configs.
+//!
+//! Every stored config contains its version under [`VERSION_KEY`].
+//! When a config structure changes incompatibly (a field is renamed,
+//! moved or its type changed), a [`ConfigMigration`] is appended to the
+//! registry of that config. It transforms the json of the previous version
+//! into the json of the new version, so old configs keep their values
+//! instead of silently falling back to the defaults.
+
+use serde::Serialize;
+use serde_json::Value;
+
+/// The key of the version in the stored config json.
+pub const VERSION_KEY: &str = "config_version";
+
+/// A single version step of a config.
+#[derive(Debug)]
+pub struct ConfigMigration {
+    /// Short description of what changed, written to the log when applied.
+    pub description: &'static str,
+    pub migrate: fn(&mut Value) -> anyhow::Result<()>,
+}
+
+/// A migration that was applied to a config.
+#[derive(Debug, Clone, Copy)]
+pub struct AppliedConfigMigration {
+    /// The version the migration started from.
+    pub from_version: u64,
+    pub description: &'static str,
+}
+
+/// All migrations of a config, in order.
+///
+/// The migration at index `i` transforms version `i` to version `i + 1`,
+/// configs without version are version `0`.
+#[derive(Debug)]
+pub struct ConfigMigrations {
+    migrations: &'static [ConfigMigration],
+}
+
+#[derive(Serialize)]
+struct VersionedConfig<'a, T> {
+    config_version: u64,
+    #[serde(flatten)]
+    config: &'a T,
+}
+
+impl ConfigMigrations {
+    pub const fn new(migrations: &'static [ConfigMigration]) -> Self {
+        Self { migrations }
+    }
+
+    /// The version of configs written by this build.
+    pub fn version(&self) -> u64 {
+        self.migrations.len() as u64
+    }
+
+    /// Parses a stored config file.
+    pub fn parse(file: &[u8]) -> anyhow::Result<Value> {
+        Ok(serde_json::from_slice(file)?)
+    }
+
+    /// The version of a stored config.
+    pub fn file_version(json: &Value) -> u64 {
+        json.get(VERSION_KEY)
+            .and_then(|version| version.as_u64())
+            .unwrap_or_default()
+    }
+
+    /// Whether the stored config needs migrations to be loaded correctly.
+    pub fn needs_migration(&self, json: &Value) -> bool {
+        Self::file_version(json) < self.version()
+    }
+
+    /// Applies all migrations that the stored config is missing and
+    /// updates its version.
+    ///
+    /// Configs of a newer version are left untouched.
+    pub fn migrate(&self, json: &mut Value) -> anyhow::Result<Vec<AppliedConfigMigration>> {
+        let file_version = Self::file_version(json);
+        let mut applied = Vec::new();
+        for (from_version, migration) in self
+            .migrations
+            .iter()
+            .enumerate()
+            .skip(file_version as usize)
+        {
+            (migration.migrate)(json).map_err(|err| {
+                anyhow::anyhow!(
+                    "config migration from version {from_version} ({}) failed: {err}",
+                    migration.description
+                )
+            })?;
+            applied.push(AppliedConfigMigration {
+                from_version: from_version as u64,
+                description: migration.description,
+            });
+        }
+        if !applied.is_empty() {
+            if let Some(json) = json.as_object_mut() {
+                json.insert(VERSION_KEY.to_string(), self.version().into());
+            }
+        }
+        Ok(applied)
+    }
+
+    /// Serializes a config for storage, including the current version.
+    pub fn to_json_string<T: Serialize>(&self, config: &T) -> anyhow::Result<String> {
+        Ok(serde_json::to_string_pretty(&VersionedConfig {
+            config_version: self.version(),
+            config,
+        })?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::{ConfigMigration, ConfigMigrations, VERSION_KEY};
+
+    static MIGRATIONS: ConfigMigrations = ConfigMigrations::new(&[
+        ConfigMigration {
+            description: "rename name to nick",
+            migrate: |json| {
+                if let Some(name) = json.as_object_mut().and_then(|json| json.remove("name")) {
+                    json["nick"] = name;
+                }
+                Ok(())
+            },
+        },
+        ConfigMigration {
+            description: "nick is a list",
+            migrate: |json| {
+                json["nick"] = json!([json["nick"].take()]);
+                Ok(())
+            },
+        },
+    ]);
+
+    #[test]
+    fn migrate() {
+        let mut json = json!({ "name": "tee" });
+        assert!(MIGRATIONS.needs_migration(&json));
+        let applied = MIGRATIONS.migrate(&mut json).unwrap();
+        assert_eq!(applied.len(), 2);
+        assert_eq!(json, json!({ "nick": ["tee"], VERSION_KEY: 2 }));
+
+        // only the missing steps are applied
+        let mut json = json!({ "nick": "tee", VERSION_KEY: 1 });
+        let applied = MIGRATIONS.migrate(&mut json).unwrap();
+        assert_eq!(applied.len(), 1);
+        assert_eq!(applied[0].from_version, 1);
+        assert_eq!(json, json!({ "nick": ["tee"], VERSION_KEY: 2 }));
+
+        assert!(!MIGRATIONS.needs_migration(&json));
+        assert!(MIGRATIONS.migrate(&mut json).unwrap().is_empty());
+
+        let stored: serde_json::Value =
+            serde_json::from_str(&MIGRATIONS.to_json_string(&json!({ "nick": [] })).unwrap())
+                .unwrap();
+        assert_eq!(stored[VERSION_KEY], 2);
+    }
+}
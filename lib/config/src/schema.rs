@@ -0,0 +1,554 @@
+//! Generates a JSON Schema and a reference of all config variables
+//! from the [`ConfigValue`] tree and validates config files against it.
+//!
+//! The default config (serialized as json) is passed alongside the tree.
+//! It provides the default values and reveals values that are serialized
+//! differently than their config value suggests (e.g. a [`std::time::Duration`]
+//! is set in milliseconds, but serialized as object). Such values are
+//! documented, but not validated.
+
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+
+use crate::{migration::VERSION_KEY, traits::ConfigValue};
+
+/// A single variable of the reference.
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfigReferenceEntry {
+    /// The full path, e.g. `sv.econ.port` or `players[].name`.
+    pub path: String,
+    /// Human readable type including its restrictions.
+    pub ty: String,
+    pub default: Option<String>,
+    pub description: String,
+}
+
+/// A problem found while validating a config file.
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfigValidationIssue {
+    /// The full path, e.g. `sv.econ.port` or `players[0].name`.
+    pub path: String,
+    pub msg: String,
+}
+
+fn join_path(prefix: &str, name: &str) -> String {
+    if prefix.is_empty() {
+        name.to_string()
+    } else {
+        format!("{prefix}.{name}")
+    }
+}
+
+/// Whether the json value is serialized like the config value,
+/// only then it can be described & validated by the config value.
+fn json_matches_kind(val: &ConfigValue, json: &Value) -> bool {
+    match val {
+        ConfigValue::Boolean => json.is_boolean(),
+        ConfigValue::Int { .. } | ConfigValue::Float { .. } => json.is_number(),
+        ConfigValue::String { .. } | ConfigValue::StringOfList { .. } => json.is_string(),
+        ConfigValue::Color | ConfigValue::JsonLikeRecord { .. } | ConfigValue::Struct { .. } => {
+            json.is_object()
+        }
+        ConfigValue::Array { .. } => json.is_array(),
+    }
+}
+
+fn length_restriction(min: usize, max: usize) -> String {
+    match (min, max) {
+        (0, usize::MAX) => String::new(),
+        (min, usize::MAX) => format!(", length >= {min}"),
+        (0, max) => format!(", length <= {max}"),
+        (min, max) => format!(", length {min}..={max}"),
+    }
+}
+
+/// Human readable type of a config value.
+pub fn type_name(val: &ConfigValue) -> String {
+    match val {
+        ConfigValue::Boolean => "bool".to_string(),
+        ConfigValue::Int { min, max } => format!("int [{min}, {max}]"),
+        ConfigValue::Float { min, max } => format!("float [{min}, {max}]"),
+        ConfigValue::String {
+            min_length,
+            max_length,
+        } => format!("string{}", length_restriction(*min_length, *max_length)),
+        ConfigValue::Color => "color".to_string(),
+        ConfigValue::StringOfList { allowed_values } => {
+            format!("one of: {}", allowed_values.join(", "))
+        }
+        ConfigValue::Array {
+            val_ty,
+            min_length,
+            max_length,
+        } => format!(
+            "array of {}{}",
+            type_name(val_ty),
+            length_restriction(*min_length, *max_length)
+        ),
+        ConfigValue::JsonLikeRecord { val_ty } => format!("map of {}", type_name(val_ty)),
+        ConfigValue::Struct { name, .. } => name.clone(),
+    }
+}
+
+fn schema_for(val: &ConfigValue, description: &str, default: Option<&Value>) -> Value {
+    let mut schema = match default {
+        Some(default) if !default.is_null() && !json_matches_kind(val, default) => {
+            // serialized differently, nothing to describe
+            Map::new()
+        }
+        _ => {
+            let schema = match val {
+                ConfigValue::Boolean => json!({ "type": "boolean" }),
+                ConfigValue::Int { min, max } => {
+                    json!({ "type": "integer", "minimum": min, "maximum": max })
+                }
+                ConfigValue::Float { min, max } => {
+                    json!({ "type": "number", "minimum": min, "maximum": max })
+                }
+                ConfigValue::String {
+                    min_length,
+                    max_length,
+                } => {
+                    let mut schema = json!({ "type": "string", "minLength": min_length });
+                    if *max_length != usize::MAX {
+                        schema["maxLength"] = json!(max_length);
+                    }
+                    schema
+                }
+                ConfigValue::Color => {
+                    let channel = json!({ "type": "integer", "minimum": 0, "maximum": 255 });
+                    json!({
+                        "type": "object",
+                        "properties": { "r": channel, "g": channel, "b": channel },
+                        "additionalProperties": false,
+                    })
+                }
+                ConfigValue::StringOfList { allowed_values } => {
+                    json!({ "type": "string", "enum": allowed_values })
+                }
+                ConfigValue::Array {
+                    val_ty,
+                    min_length,
+                    max_length,
+                } => {
+                    let item_default = default.and_then(|d| d.as_array()).and_then(|d| d.first());
+                    let mut schema = json!({
+                        "type": "array",
+                        "items": schema_for(val_ty, "", item_default),
+                        "minItems": min_length,
+                    });
+                    if *max_length != usize::MAX {
+                        schema["maxItems"] = json!(max_length);
+                    }
+                    schema
+                }
+                ConfigValue::JsonLikeRecord { val_ty } => {
+                    let item_default = default
+                        .and_then(|d| d.as_object())
+                        .and_then(|d| d.values().next());
+                    json!({
+                        "type": "object",
+                        "additionalProperties": schema_for(val_ty, "", item_default),
+                    })
+                }
+                ConfigValue::Struct {
+                    attributes, name, ..
+                } => {
+                    let properties: Map<String, Value> = attributes
+                        .iter()
+                        .map(|attr| {
+                            (
+                                attr.name.clone(),
+                                schema_for(
+                                    &attr.val,
+                                    &attr.description,
+                                    default.and_then(|d| d.get(&attr.name)),
+                                ),
+                            )
+                        })
+                        .collect();
+                    json!({
+                        "title": name,
+                        "type": "object",
+                        "properties": properties,
+                        "additionalProperties": false,
+                    })
+                }
+            };
+            let Value::Object(mut schema) = schema else {
+                unreachable!()
+            };
+            // options are serialized as null
+            if default.is_some_and(|d| d.is_null()) {
+                if let Some(ty) = schema.remove("type") {
+                    schema.insert("type".to_string(), json!([ty, "null"]));
+                }
+            }
+            schema
+        }
+    };
+    if !description.is_empty() {
+        schema.insert("description".to_string(), json!(description));
+    }
+    if let Some(default) = default {
+        schema.insert("default".to_string(), default.clone());
+    }
+    Value::Object(schema)
+}
+
+/// Generates a JSON Schema (draft 2020-12) of a config tree.
+pub fn json_schema(title: &str, val: &ConfigValue, defaults: &Value) -> Value {
+    let mut schema = schema_for(val, "", Some(defaults));
+    schema["$schema"] = json!("https://json-schema.org/draft/2020-12/schema");
+    schema["title"] = json!(title);
+    schema
+}
+
+const VERSION_DESCRIPTION: &str =
+    "The version of the stored config, used to migrate older config files. Set automatically.";
+
+/// Adds the [`VERSION_KEY`] of stored configs to the root of a schema
+/// generated by [`json_schema`], `version` is the current config version.
+pub fn add_version_to_schema(schema: &mut Value, version: u64) {
+    schema["properties"][VERSION_KEY] = json!({
+        "type": "integer",
+        "minimum": 0,
+        "description": VERSION_DESCRIPTION,
+        "default": version,
+    });
+}
+
+/// The reference entry of the [`VERSION_KEY`] of stored configs,
+/// `version` is the current config version.
+pub fn version_reference_entry(version: u64) -> ConfigReferenceEntry {
+    ConfigReferenceEntry {
+        path: VERSION_KEY.to_string(),
+        ty: type_name(&ConfigValue::Int {
+            min: 0,
+            max: u64::MAX,
+        }),
+        default: Some(version.to_string()),
+        description: VERSION_DESCRIPTION.to_string(),
+    }
+}
+
+fn collect_reference(
+    path: &str,
+    val: &ConfigValue,
+    description: &str,
+    default: Option<&Value>,
+    entries: &mut Vec<ConfigReferenceEntry>,
+) {
+    let matches_default = default.is_none_or(|d| d.is_null() || json_matches_kind(val, d));
+    match val {
+        ConfigValue::Struct { attributes, .. } if matches_default => {
+            for attr in attributes {
+                collect_reference(
+                    &join_path(path, &attr.name),
+                    &attr.val,
+                    &attr.description,
+                    default.and_then(|d| d.get(&attr.name)),
+                    entries,
+                );
+            }
+        }
+        ConfigValue::Array { val_ty, .. }
+            if matches_default && matches!(**val_ty, ConfigValue::Struct { .. }) =>
+        {
+            entries.push(ConfigReferenceEntry {
+                path: path.to_string(),
+                ty: type_name(val),
+                default: None,
+                description: description.to_string(),
+            });
+            collect_reference(
+                &format!("{path}[]"),
+                val_ty,
+                "",
+                default.and_then(|d| d.as_array()).and_then(|d| d.first()),
+                entries,
+            );
+        }
+        ConfigValue::JsonLikeRecord { val_ty }
+            if matches_default && matches!(**val_ty, ConfigValue::Struct { .. }) =>
+        {
+            entries.push(ConfigReferenceEntry {
+                path: path.to_string(),
+                ty: type_name(val),
+                default: None,
+                description: description.to_string(),
+            });
+            collect_reference(
+                &format!("{path}.<key>"),
+                val_ty,
+                "",
+                default
+                    .and_then(|d| d.as_object())
+                    .and_then(|d| d.values().next()),
+                entries,
+            );
+        }
+        _ => {
+            entries.push(ConfigReferenceEntry {
+                path: path.to_string(),
+                ty: if matches_default {
+                    type_name(val)
+                } else {
+                    "object".to_string()
+                },
+                default: default.map(|d| d.to_string()),
+                description: description.to_string(),
+            });
+        }
+    }
+}
+
+/// All variables of a config tree, structs are flattened.
+/// `prefix` is prepended to all paths, e.g. `vanilla` for mod configs.
+pub fn reference(prefix: &str, val: &ConfigValue, defaults: &Value) -> Vec<ConfigReferenceEntry> {
+    let mut entries = Vec::new();
+    collect_reference(prefix, val, "", Some(defaults), &mut entries);
+    entries
+}
+
+fn markdown_escape(s: &str) -> String {
+    s.replace('|', "\\|").replace('\n', " ")
+}
+
+/// A markdown table of the reference.
+pub fn markdown_reference(title: &str, entries: &[ConfigReferenceEntry]) -> String {
+    let mut res = format!("## {title}\n\n| Variable | Type | Default | Description |\n");
+    res.push_str("| --- | --- | --- | --- |\n");
+    for entry in entries {
+        res.push_str(&format!(
+            "| `{}` | {} | {} | {} |\n",
+            entry.path,
+            markdown_escape(&entry.ty),
+            entry
+                .default
+                .as_ref()
+                .map(|d| format!("`{}`", markdown_escape(d)))
+                .unwrap_or_default(),
+            markdown_escape(&entry.description),
+        ));
+    }
+    res
+}
+
+fn html_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// A html table of the reference (without the surrounding document).
+pub fn html_reference(title: &str, entries: &[ConfigReferenceEntry]) -> String {
+    let mut res = format!(
+        "<h2>{}</h2>\n<table>\n<tr><th>Variable</th><th>Type</th>\
+        <th>Default</th><th>Description</th></tr>\n",
+        html_escape(title)
+    );
+    for entry in entries {
+        res.push_str(&format!(
+            "<tr id=\"{0}\"><td><code>{0}</code></td><td>{1}</td><td>{2}</td><td>{3}</td></tr>\n",
+            html_escape(&entry.path),
+            html_escape(&entry.ty),
+            entry
+                .default
+                .as_ref()
+                .map(|d| format!("<code>{}</code>", html_escape(d)))
+                .unwrap_or_default(),
+            html_escape(&entry.description).replace('\n', "<br>"),
+        ));
+    }
+    res.push_str("</table>\n");
+    res
+}
+
+fn validate_impl(
+    path: &str,
+    val: &ConfigValue,
+    json: &Value,
+    default: Option<&Value>,
+    issues: &mut Vec<ConfigValidationIssue>,
+) {
+    // options & values that are serialized differently are not validated
+    if json.is_null() || default.is_some_and(|d| !d.is_null() && !json_matches_kind(val, d)) {
+        return;
+    }
+    let mut issue = |msg: String| {
+        issues.push(ConfigValidationIssue {
+            path: path.to_string(),
+            msg,
+        })
+    };
+    if !json_matches_kind(val, json) {
+        issue(format!("expected {}, found {json}", type_name(val)));
+        return;
+    }
+    match val {
+        ConfigValue::Boolean => {}
+        ConfigValue::Int { min, max } => {
+            let in_range = if let Some(v) = json.as_i64() {
+                v >= *min && (v < 0 || (v as u64) <= *max)
+            } else if let Some(v) = json.as_u64() {
+                v <= *max
+            } else {
+                issue(format!("expected an integer, found {json}"));
+                return;
+            };
+            if !in_range {
+                issue(format!("{json} is not in the allowed range [{min}, {max}]"));
+            }
+        }
+        ConfigValue::Float { min, max } => {
+            let v = json.as_f64().unwrap_or_default();
+            if v < *min || v > *max {
+                issue(format!("{json} is not in the allowed range [{min}, {max}]"));
+            }
+        }
+        ConfigValue::String {
+            min_length,
+            max_length,
+        } => {
+            let len = json.as_str().unwrap_or_default().chars().count();
+            if len < *min_length || len > *max_length {
+                issue(format!(
+                    "the length {len} is not in the allowed range [{min_length}, {max_length}]"
+                ));
+            }
+        }
+        ConfigValue::Color => {
+            for (key, channel) in json.as_object().into_iter().flatten() {
+                if !matches!(key.as_str(), "r" | "g" | "b") {
+                    issues.push(ConfigValidationIssue {
+                        path: join_path(path, key),
+                        msg: "unknown key".to_string(),
+                    });
+                } else if channel.as_u64().is_none_or(|c| c > 255) {
+                    issues.push(ConfigValidationIssue {
+                        path: join_path(path, key),
+                        msg: format!("expected an integer in [0, 255], found {channel}"),
+                    });
+                }
+            }
+        }
+        ConfigValue::StringOfList { allowed_values } => {
+            let v = json.as_str().unwrap_or_default();
+            if !allowed_values.iter().any(|allowed| allowed == v) {
+                issue(format!(
+                    "\"{v}\" is not one of the allowed values: {}",
+                    allowed_values.join(", ")
+                ));
+            }
+        }
+        ConfigValue::Array {
+            val_ty,
+            min_length,
+            max_length,
+        } => {
+            let items = json.as_array().map(|a| a.as_slice()).unwrap_or_default();
+            if items.len() < *min_length || items.len() > *max_length {
+                issue(format!(
+                    "the length {} is not in the allowed range [{min_length}, {max_length}]",
+                    items.len()
+                ));
+            }
+            let item_default = default.and_then(|d| d.as_array()).and_then(|d| d.first());
+            for (i, item) in items.iter().enumerate() {
+                validate_impl(&format!("{path}[{i}]"), val_ty, item, item_default, issues);
+            }
+        }
+        ConfigValue::JsonLikeRecord { val_ty } => {
+            let item_default = default
+                .and_then(|d| d.as_object())
+                .and_then(|d| d.values().next());
+            for (key, item) in json.as_object().into_iter().flatten() {
+                validate_impl(&join_path(path, key), val_ty, item, item_default, issues);
+            }
+        }
+        ConfigValue::Struct { attributes, .. } => {
+            for (key, item) in json.as_object().into_iter().flatten() {
+                match attributes.iter().find(|attr| attr.name == *key) {
+                    Some(attr) => validate_impl(
+                        &join_path(path, key),
+                        &attr.val,
+                        item,
+                        default.and_then(|d| d.get(key)),
+                        issues,
+                    ),
+                    None => issues.push(ConfigValidationIssue {
+                        path: join_path(path, key),
+                        msg: "unknown key".to_string(),
+                    }),
+                }
+            }
+        }
+    }
+}
+
+/// Validates a config file (parsed as json) against the config tree.
+/// Reports unknown keys, values of the wrong type and values that
+/// violate the restrictions of the variable.
+pub fn validate(val: &ConfigValue, json: &Value, defaults: &Value) -> Vec<ConfigValidationIssue> {
+    let mut issues = Vec::new();
+    validate_impl("", val, json, Some(defaults), &mut issues);
+    issues
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{self as config, migration::VERSION_KEY, traits::ConfigInterface};
+    use config_macro::{config_default, ConfigInterface};
+    use serde::{Deserialize, Serialize};
+
+    #[config_default]
+    #[derive(Debug, Serialize, Deserialize, ConfigInterface)]
+    pub struct ConfigInner {
+        /// Some port.
+        #[conf_valid(range(min = 1, max = 100))]
+        #[default = 10]
+        pub port: u16,
+    }
+
+    #[config_default]
+    #[derive(Debug, Serialize, Deserialize, ConfigInterface)]
+    pub struct ConfigTest {
+        #[conf_valid(length(max = 4))]
+        #[default = "hi"]
+        pub name: String,
+        pub inner: ConfigInner,
+    }
+
+    #[test]
+    fn validate_and_reference() {
+        let defaults = serde_json::to_value(ConfigTest::default()).unwrap();
+        let val = ConfigTest::conf_value();
+
+        let entries = super::reference("", &val, &defaults);
+        let paths: Vec<_> = entries.iter().map(|e| e.path.as_str()).collect();
+        assert_eq!(paths, ["name", "inner.port"]);
+        assert_eq!(entries[1].default.as_deref(), Some("10"));
+        assert_eq!(entries[1].description, "Some port.");
+
+        assert!(super::validate(&val, &defaults, &defaults).is_empty());
+
+        let file = serde_json::json!({
+            "name": "too long",
+            "inner": { "port": 1000, "unknown": true },
+        });
+        let issues = super::validate(&val, &file, &defaults);
+        let paths: Vec<_> = issues.iter().map(|i| i.path.as_str()).collect();
+        assert_eq!(paths, ["name", "inner.port", "inner.unknown"]);
+
+        let mut schema = super::json_schema("test", &val, &defaults);
+        assert_eq!(
+            schema["properties"]["inner"]["properties"]["port"]["maximum"],
+            100
+        );
+        super::add_version_to_schema(&mut schema, 2);
+        assert_eq!(schema["properties"][VERSION_KEY]["default"], 2);
+        assert_eq!(super::version_reference_entry(2).path, VERSION_KEY);
+    }
+}
@@ -18,14 +18,16 @@ use binds::binds::{BindActionsHotkey, BindActionsLocalPlayer};
 use client_accounts::accounts::{Accounts, AccountsLoading};
 use client_console::console::{
     console::{ConsoleEvents, ConsoleRenderPipe},
-    local_console::{LocalConsole, LocalConsoleBuilder, LocalConsoleEvent},
+    local_console::{LocalConsole, LocalConsoleBuilder, LocalConsoleEvent, TasAction},
     remote_console::RemoteConsoleEvent,
 };
 use client_containers::{
     entities::{EntitiesContainer, ENTITIES_CONTAINER_PATH},
     skins::{SkinContainer, SKIN_CONTAINER_PATH},
 };
-use client_demo::{DemoVideoEncodeProperties, DemoViewer, DemoViewerSettings, EncoderSettings};
+use client_demo::{
+    DemoVideoEncodeProperties, DemoViewer, DemoViewerSettings, EncoderKind, EncoderSettings,
+};
 use client_map::client_map::{ClientMapFile, ClientMapLoading, GameMap};
 use client_notifications::overlay::ClientNotifications;
 use client_render_base::{
@@ -71,10 +73,10 @@ use client_ui::{
 };
 use command_parser::parser::ParserCache;
 use config::config::{ConfigEngine, ConfigMonitor};
-use demo::recorder::DemoRecorder;
+use demo::{recorder::DemoRecorder, DemoEvent};
 use editor::editor::{EditorInterface, EditorResult};
 use egui::{CursorIcon, FontDefinitions};
-use game_config::config::{Config, ConfigGame, ConfigMap};
+use game_config::config::{Config, ConfigGame, ConfigMap, ConfigVideoEncoder};
 use graphics::graphics::graphics::Graphics;
 use graphics_backend::{
     backend::{
@@ -158,7 +160,9 @@ use game_network::messages::{ClientToServerMessage, ClientToServerPlayerMessage}
 
 use super::{
     game::{
+        active::ActiveGame,
         data::{ClientConnectedPlayer, GameData},
+        tas::Tas,
         types::{DisconnectAutoCleanup, GameBase, GameConnect, GameMsgPipeline, ServerCertMode},
     },
     game_events::{GameEventPipeline, GameEventsClient},
@@ -385,6 +389,8 @@ struct ClientNativeImpl {
     sound_backend: Rc<SoundBackend>,
     game: Game,
     connect_info: ConnectMode,
+    /// The last server the client tried to connect to
+    last_connect: Option<(SocketAddr, ServerCertMode, Option<[u8; 32]>)>,
     demo_player: Option<DemoViewer>,
     client_stats: ClientStats,
     notifications: ClientNotifications,
@@ -395,6 +401,9 @@ struct ClientNativeImpl {
     last_refresh_rate_time: Duration,
 
     editor: EditorState,
+    /// The current game is a test play started from the editor,
+    /// the editor is reopened when leaving it.
+    editor_test_play: bool,
 
     entities_container: EntitiesContainer,
     skin_container: SkinContainer,
@@ -488,7 +497,8 @@ impl ClientNativeImpl {
                     can_start_local_server || matches!(*state, LocalServerState::Starting { .. });
                 drop(state);
                 if can_start_local_server {
-                    // try to start the local server
+                    // try to start the local server, which replaces a test play server
+                    self.editor_test_play = false;
                     start_local_server(
                         &self.sys,
                         self.shared_info.clone(),
@@ -625,7 +635,7 @@ impl ClientNativeImpl {
                 mut local_predicted_game,
                 main_intra_tick_ratio,
                 predicted_intra_tick_ratio,
-            ) = if self.config.game.cl.anti_ping && !main_local_char_spec {
+            ) = if (self.config.game.cl.anti_ping || game.tas.is_some()) && !main_local_char_spec {
                 (game_state, None, intra_tick_ratio, intra_tick_ratio)
             } else {
                 let ticks_per_second = game_state.game_tick_speed();
@@ -1236,40 +1246,32 @@ impl ClientNativeImpl {
                         PlayerFeedbackEvent::EmoteWheel(ev) => {
                             local_player.last_emote_wheel_selection = Some(ev);
                         }
-                        PlayerFeedbackEvent::SpectatorSelection(ev) => match ev {
-                            SpectatorSelectionEvent::FreeView => {
-                                game.map.game.client_command(
-                                    &player_id,
-                                    ClientCommand::SetCameraMode(ClientCameraMode::None),
-                                );
-                                game.network.send_unordered_to_server(
-                                    &ClientToServerMessage::PlayerMsg((
-                                        player_id,
-                                        ClientToServerPlayerMessage::SwitchToCamera(
-                                            ClientCameraMode::None,
-                                        ),
-                                    )),
-                                );
-                            }
-                            SpectatorSelectionEvent::Selected(spectated_characters) => {
-                                game.map.game.client_command(
-                                    &player_id,
-                                    ClientCommand::SetCameraMode(ClientCameraMode::FreeCam(
+                        PlayerFeedbackEvent::SpectatorSelection(ev) => {
+                            let mode = match ev {
+                                SpectatorSelectionEvent::FreeView => ClientCameraMode::None,
+                                SpectatorSelectionEvent::Selected(spectated_characters) => {
+                                    ClientCameraMode::FreeCam(
                                         spectated_characters.iter().copied().collect(),
-                                    )),
-                                );
-                                game.network.send_unordered_to_server(
-                                    &ClientToServerMessage::PlayerMsg((
-                                        player_id,
-                                        ClientToServerPlayerMessage::SwitchToCamera(
-                                            ClientCameraMode::FreeCam(
-                                                spectated_characters.iter().copied().collect(),
-                                            ),
-                                        ),
-                                    )),
-                                );
-                            }
-                        },
+                                    )
+                                }
+                                SpectatorSelectionEvent::Stage(stage_id) => {
+                                    ClientCameraMode::Stage(stage_id)
+                                }
+                                SpectatorSelectionEvent::AutoDirector => {
+                                    ClientCameraMode::AutoDirector
+                                }
+                            };
+                            game.map.game.client_command(
+                                &player_id,
+                                ClientCommand::SetCameraMode(mode.clone()),
+                            );
+                            game.network.send_unordered_to_server(
+                                &ClientToServerMessage::PlayerMsg((
+                                    player_id,
+                                    ClientToServerPlayerMessage::SwitchToCamera(mode),
+                                )),
+                            );
+                        }
                     }
                 }
             }
@@ -1327,6 +1329,34 @@ impl ClientNativeImpl {
                 EditorResult::Close => {
                     self.editor = EditorState::None;
                 }
+                EditorResult::TestPlay(test_play) => {
+                    self.editor = match std::mem::take(&mut self.editor) {
+                        EditorState::Open(editor) | EditorState::Minimized(editor) => {
+                            EditorState::Minimized(editor)
+                        }
+                        EditorState::None => EditorState::None,
+                    };
+                    self.game = Game::None;
+
+                    // restart the local server with the test play map & mod
+                    *self.shared_info.state.lock().unwrap() = LocalServerState::None;
+                    let mut config_game = self.config.game.clone();
+                    config_game.sv.map = test_play.map_name;
+                    config_game.sv.game_mod = test_play.game_mod;
+                    start_local_server(
+                        &self.sys,
+                        self.shared_info.clone(),
+                        self.config.engine.clone(),
+                        config_game,
+                    );
+                    self.editor_test_play = true;
+                    self.ui_events.push(UiEvent::Connect {
+                        addr: "127.0.0.1:0".parse().unwrap(),
+                        cert_hash: Default::default(),
+                        rcon_secret: None,
+                        can_start_local_server: false,
+                    });
+                }
             }
         } else {
             self.render_game(native);
@@ -1417,6 +1447,8 @@ impl ClientNativeImpl {
                 for ui_event in ui_events {
                     match ui_event {
                         UiEvent::StartLocalServer => {
+                            // the new server replaces a test play server
+                            self.editor_test_play = false;
                             start_local_server(
                                 &self.sys,
                                 self.shared_info.clone(),
@@ -1460,6 +1492,13 @@ impl ClientNativeImpl {
                                             as u64,
                                         sample_rate: self.config.game.cl.recorder.sample_rate,
                                         crf: self.config.game.cl.recorder.crf,
+                                        kind: match self.config.game.cl.recorder.encoder {
+                                            ConfigVideoEncoder::Auto => EncoderKind::Auto,
+                                            ConfigVideoEncoder::Ffmpeg => EncoderKind::Ffmpeg,
+                                            ConfigVideoEncoder::ImageSequence => {
+                                                EncoderKind::ImageSequence
+                                            }
+                                        },
                                     },
                                     settings: DemoViewerSettings {
                                         global_sound_volume: self
@@ -1552,6 +1591,16 @@ impl ClientNativeImpl {
                         }
                         UiEvent::Disconnect => {
                             self.game = Game::None;
+                            if std::mem::take(&mut self.editor_test_play) {
+                                // the test play server is not needed anymore
+                                *self.shared_info.state.lock().unwrap() = LocalServerState::None;
+                                self.editor = match std::mem::take(&mut self.editor) {
+                                    EditorState::Open(editor) | EditorState::Minimized(editor) => {
+                                        EditorState::Open(editor)
+                                    }
+                                    EditorState::None => EditorState::None,
+                                };
+                            }
                         }
                         UiEvent::ConnectLocalPlayer { as_dummy } => {
                             if let Game::Active(game) = &mut self.game {
@@ -2042,6 +2091,7 @@ impl ClientNativeImpl {
         server_cert: ServerCertMode,
         rcon_secret: Option<[u8; 32]>,
     ) {
+        self.last_connect = Some((addr, server_cert.clone(), rcon_secret));
         self.client_info.set_local_player_count(1);
         self.account_info.fill_account_info(None);
         self.config.engine.ui.path.route("connect");
@@ -2178,6 +2228,19 @@ impl ClientNativeImpl {
                         }
                     }
                 }
+                LocalConsoleEvent::Disconnect => {
+                    self.ui_events.push(UiEvent::Disconnect);
+                }
+                LocalConsoleEvent::Reconnect => {
+                    if let Some((addr, server_cert, rcon_secret)) = self.last_connect.clone() {
+                        self.connect_game(addr, server_cert, rcon_secret);
+                    } else {
+                        self.notifications.add_err(
+                            "There is no server to reconnect to.",
+                            Duration::from_secs(5),
+                        );
+                    }
+                }
                 LocalConsoleEvent::Bind { was_player_profile }
                 | LocalConsoleEvent::Unbind { was_player_profile } => {
                     if let Game::Active(game) = &mut self.game {
@@ -2284,6 +2347,156 @@ impl ClientNativeImpl {
                         }
                     }
                 }
+                LocalConsoleEvent::RecordDemo => {
+                    if matches!(self.game, Game::Active(_)) {
+                        self.ui_events.push(UiEvent::RecordDemo);
+                    } else {
+                        self.notifications.add_err(
+                            "Demos can only be recorded while being ingame.",
+                            Duration::from_secs(5),
+                        );
+                    }
+                }
+                LocalConsoleEvent::StopRecordDemo => {
+                    self.ui_events.push(UiEvent::StopRecordDemo);
+                }
+                LocalConsoleEvent::DemoMarker => {
+                    if let Game::Active(game) = &mut self.game {
+                        let monotonic_tick = game.map.game.predicted_game_monotonic_tick;
+                        for demo_recorder in [
+                            &mut game.auto_demo_recorder,
+                            &mut game.manual_demo_recorder,
+                            &mut game.race_demo_recorder,
+                        ]
+                        .into_iter()
+                        .flatten()
+                        {
+                            demo_recorder.add_event(monotonic_tick, DemoEvent::Marker);
+                        }
+                        game.replay.add_event(monotonic_tick, DemoEvent::Marker);
+                    }
+                }
+                LocalConsoleEvent::PlayDemo { path } => {
+                    self.ui_events.push(UiEvent::PlayDemo { name: path });
+                }
+                LocalConsoleEvent::Screenshot => {
+                    InputHandling::take_screenshot(&self.graphics, &self.io);
+                }
+                LocalConsoleEvent::Tas(action) => {
+                    let Game::Active(game) = &mut self.game else {
+                        self.notifications.add_err(
+                            "Tool-assisted runs are only possible while being ingame.",
+                            Duration::from_secs(5),
+                        );
+                        continue;
+                    };
+                    match (action, &mut game.tas) {
+                        (TasAction::Toggle, tas @ Some(_)) => {
+                            *tas = None;
+                            // follow the server again
+                            if let Some((snapshot, tick)) = &game.game_data.last_snap {
+                                let local_players = game.map.game.build_from_snapshot(snapshot);
+                                GameData::handle_local_players_from_snapshot(
+                                    &mut game.game_data.local.local_players,
+                                    &game.game_data.local.expected_local_players,
+                                    &mut self.config.game,
+                                    &self.local_console.entries,
+                                    local_players,
+                                    &game.parser_cache,
+                                    &game.map.game.info.options,
+                                );
+                                game.map.game.build_from_snapshot_for_prev(snapshot);
+                                game.map.game.predicted_game_monotonic_tick = *tick;
+                            }
+                            self.notifications
+                                .add_info("Stopped the tool-assisted run.", Duration::from_secs(3));
+                        }
+                        (TasAction::Toggle, tas @ None) => {
+                            if game.connect.addr.ip().is_loopback() {
+                                *tas = Some(Tas::new(&mut game.map.game));
+                                self.notifications.add_info(
+                                    "Started a tool-assisted run. The game is paused, \
+                                    use tas_step, tas_rewind, tas_input & tas_export.",
+                                    Duration::from_secs(5),
+                                );
+                            } else {
+                                self.notifications.add_err(
+                                    "Tool-assisted runs are only possible on the local server.",
+                                    Duration::from_secs(5),
+                                );
+                            }
+                        }
+                        (_, None) => {
+                            self.notifications.add_err(
+                                "No tool-assisted run is active, start one with `tas`.",
+                                Duration::from_secs(5),
+                            );
+                        }
+                        (TasAction::TogglePause, Some(tas)) => {
+                            tas.paused = !tas.paused;
+                            if tas.paused {
+                                tas.freeze(&mut game.map.game);
+                            }
+                        }
+                        (TasAction::Step { ticks }, Some(tas)) => {
+                            tas.step(ticks);
+                        }
+                        (TasAction::Rewind { ticks }, Some(tas)) => {
+                            if let Some(local_players) = tas.rewind(&mut game.map.game, ticks) {
+                                GameData::handle_local_players_from_snapshot(
+                                    &mut game.game_data.local.local_players,
+                                    &game.game_data.local.expected_local_players,
+                                    &mut self.config.game,
+                                    &self.local_console.entries,
+                                    local_players,
+                                    &game.parser_cache,
+                                    &game.map.game.info.options,
+                                );
+                            }
+                        }
+                        (TasAction::Cut, Some(tas)) => {
+                            tas.cut(&game.map.game);
+                        }
+                        (TasAction::Input(edit), Some(tas)) => {
+                            if let Some((player_id, _)) = game.game_data.local.active_local_player()
+                            {
+                                tas.edit_input(
+                                    &game.map.game,
+                                    *player_id,
+                                    game.game_data
+                                        .local
+                                        .local_players
+                                        .iter()
+                                        .map(|(id, player)| (*id, player.input.inp)),
+                                    edit,
+                                );
+                            }
+                        }
+                        (TasAction::Export { name }, Some(tas)) => {
+                            match tas.export(
+                                &game.map.game,
+                                &name,
+                                &self.io,
+                                game.demo_recorder_props.clone(),
+                            ) {
+                                Ok(()) => self.notifications.add_info(
+                                    format!(
+                                        "Exported {} ticks of the tool-assisted run as {name}.",
+                                        game.map
+                                            .game
+                                            .predicted_game_monotonic_tick
+                                            .saturating_sub(tas.start_tick())
+                                    ),
+                                    Duration::from_secs(5),
+                                ),
+                                Err(err) => self.notifications.add_err(
+                                    format!("Exporting the tool-assisted run failed: {err}"),
+                                    Duration::from_secs(10),
+                                ),
+                            }
+                        }
+                    }
+                }
                 LocalConsoleEvent::Quit => native.quit(),
                 LocalConsoleEvent::ConfigVariable { name } => {
                     // some special cases
@@ -2382,6 +2595,267 @@ impl ClientNativeImpl {
         let events = self.local_console.get_events();
         self.handle_console_events_impl(native, events, 0);
     }
+
+    /// Simulates the tool-assisted run, the server's world is ignored.
+    fn tick_tas(game: &mut ActiveGame, cur_time: Duration) {
+        let Some(tas) = &mut game.tas else {
+            return;
+        };
+        let game_state = &mut game.map.game;
+        let ticks_per_second = game_state.game_tick_speed();
+
+        let mut ticks = tas.take_pending_steps();
+        if tas.paused {
+            game.game_data.last_game_tick = cur_time;
+        } else {
+            while is_next_tick(
+                cur_time,
+                &mut game.game_data.last_game_tick,
+                ticks_per_second,
+            ) {
+                ticks += 1;
+            }
+        }
+        for _ in 0..ticks {
+            tas.tick(
+                game_state,
+                game.game_data
+                    .local
+                    .local_players
+                    .iter()
+                    .map(|(id, player)| (*id, player.input.inp)),
+            );
+
+            let mut player_ids = game.game_data.player_ids_pool.new();
+            player_ids.extend(game.game_data.local.local_players.keys());
+            let events = game_state.events_for(EventClientInfo {
+                client_player_ids: player_ids,
+                everything: true,
+                other_stages: true,
+            });
+            if !events.is_empty() {
+                game.events
+                    .entry((game_state.predicted_game_monotonic_tick, true))
+                    .or_insert_with(|| events);
+            }
+            game_state.clear_events();
+        }
+
+        if tas.paused {
+            if ticks > 0 {
+                tas.freeze(game_state);
+            }
+            game.game_data.intra_tick_time = Duration::ZERO;
+        } else {
+            game.game_data.intra_tick_time =
+                intra_tick_time(cur_time, game.game_data.last_game_tick, ticks_per_second);
+        }
+    }
+
+    /// Sends the inputs of the local players to the server
+    /// and predicts the world up to the current time.
+    fn predict_game(
+        game: &mut ActiveGame,
+        cur_time: Duration,
+        sys: &dyn SystemTimeInterface,
+        config_game: &ConfigGame,
+        shared_info: &Arc<LocalServerInfo>,
+    ) {
+        game.game_data.prediction_timer.add_frametime(
+            cur_time.saturating_sub(game.game_data.last_frame_time),
+            cur_time,
+        );
+        game.game_data.last_frame_time = cur_time;
+        let game_state = &mut game.map.game;
+
+        let tick_of_inp = game_state.predicted_game_monotonic_tick + 1;
+        let ticks_per_second = game_state.game_tick_speed();
+
+        let mut player_inputs = game.player_inputs_pool.new();
+
+        let time_per_tick = Duration::from_nanos(
+            (Duration::from_secs(1).as_nanos() / ticks_per_second.get() as u128) as u64,
+        );
+        let ticks_to_send = game
+            .game_data
+            .prediction_timer
+            .time_units_to_respect(time_per_tick, 7.try_into().unwrap())
+            as GameTickType;
+        game.game_data.get_and_update_latest_input(
+            cur_time,
+            time_per_tick,
+            ticks_to_send,
+            tick_of_inp,
+            &mut player_inputs,
+            &game.player_inputs_chainable_pool,
+        );
+
+        game.send_input(&player_inputs, sys);
+        let game_state = &mut game.map.game;
+        // save the current input of all users for possible recalculations later
+        let tick_inps = &mut game.game_data.input_per_tick;
+
+        let add_input = |tick_of_inp: GameTickType,
+                         input_per_tick: &mut ClientPlayerInputPerTick| {
+            if !input_per_tick.contains_key(&tick_of_inp) {
+                input_per_tick.insert(tick_of_inp, game.game_data.player_inp_pool.new());
+            }
+
+            // apply input of local player to player
+            game.game_data.local.local_players.iter().for_each(
+                |(local_player_id, local_player)| {
+                    let player_inp = input_per_tick.get_mut(&tick_of_inp).unwrap();
+                    player_inp.insert(*local_player_id, local_player.sent_input);
+                },
+            );
+        };
+        add_input(tick_of_inp, tick_inps);
+
+        let time_for_prediction = cur_time;
+
+        let instant_input = config_game.cl.instant_input;
+        // Reset the game state if needed
+        if instant_input {
+            if let Some(cur_state_snap) = game.game_data.cur_state_snap.take() {
+                let _ = game_state.build_from_snapshot(&cur_state_snap);
+            }
+        }
+
+        fn apply_input(
+            predicted_game_monotonic_tick: GameTickType,
+            tick_inps: &mut FxLinkedHashMap<u64, PoolFxLinkedHashMap<PlayerId, PlayerInput>>,
+            fallback_to_prev_input: bool,
+            mut on_apply: impl FnMut(&PlayerId, &PlayerInput, CharacterInputConsumableDiff),
+        ) {
+            let tick_of_inp = predicted_game_monotonic_tick + 1;
+            let (next_input, prev_input) = (
+                tick_inps.get(&tick_of_inp).or_else(|| {
+                    tick_inps
+                        .iter()
+                        .rev()
+                        .find_map(|(&tick, inp)| (tick <= tick_of_inp).then_some(inp))
+                }),
+                tick_inps.get(&predicted_game_monotonic_tick),
+            );
+            let check_input = if fallback_to_prev_input {
+                next_input.or(prev_input)
+            } else {
+                next_input
+            };
+            if let Some(inputs) = check_input {
+                for (id, tick_inp) in inputs.iter() {
+                    let mut inp = PlayerInput::default();
+                    if let Some(prev_inp) = prev_input.or(next_input).and_then(|inp| inp.get(id)) {
+                        inp.inp = prev_inp.inp;
+                    }
+                    if let Some(diff) = inp.try_overwrite(&tick_inp.inp, tick_inp.version(), true) {
+                        on_apply(id, tick_inp, diff);
+                    }
+                }
+            }
+        }
+
+        // do the ticks if necessary
+        while is_next_tick(
+            time_for_prediction,
+            &mut game.game_data.last_game_tick,
+            ticks_per_second,
+        ) {
+            // apply input of players
+            let mut inps = game.game_data.player_inputs_state_pool.new();
+            apply_input(
+                game_state.predicted_game_monotonic_tick,
+                tick_inps,
+                false,
+                |id, tick_inp, diff| {
+                    inps.insert(
+                        *id,
+                        CharacterInputInfo {
+                            inp: tick_inp.inp,
+                            diff,
+                        },
+                    );
+                },
+            );
+            game_state.set_player_inputs(inps);
+
+            let cur_snap = game_state.snapshot_for(SnapshotClientInfo::Everything);
+            game_state.build_from_snapshot_for_prev(&cur_snap);
+
+            game_state.predicted_game_monotonic_tick += 1;
+            game_state.tick(Default::default());
+
+            Server::dbg_game(
+                &config_game.dbg,
+                &game.game_data.last_game_tick,
+                game_state,
+                tick_inps
+                    .get(&game_state.predicted_game_monotonic_tick)
+                    .map(|inps| inps.values().map(|inp| &inp.inp)),
+                game_state.predicted_game_monotonic_tick,
+                ticks_per_second.get(),
+                shared_info,
+                "client",
+            );
+
+            let mut player_ids = game.game_data.player_ids_pool.new();
+            player_ids.extend(game.game_data.local.local_players.keys());
+            let events = game_state.events_for(EventClientInfo {
+                client_player_ids: player_ids,
+                everything: true,
+                other_stages: true,
+            });
+            if !events.is_empty() {
+                game.events
+                    .entry((game_state.predicted_game_monotonic_tick, true))
+                    .or_insert_with(|| events);
+            }
+            game_state.clear_events();
+
+            // add a "dummy" input for the next tick already, since in a bad
+            // case this while-loop might run again
+            add_input(game_state.predicted_game_monotonic_tick + 1, tick_inps);
+        }
+
+        // next intra tick time
+        game.game_data.intra_tick_time =
+            intra_tick_time(cur_time, game.game_data.last_game_tick, ticks_per_second);
+
+        if instant_input {
+            let cur_state_snap = game_state.snapshot_for(SnapshotClientInfo::Everything);
+            game_state.build_from_snapshot_for_prev(&cur_state_snap);
+            game.game_data.cur_state_snap = Some(cur_state_snap);
+
+            // there is always a prediction tick
+            // apply input of players for it as if it's the next tick
+            let mut pred_inps = game.game_data.player_inputs_state_pool.new();
+            apply_input(
+                game_state.predicted_game_monotonic_tick,
+                tick_inps,
+                true,
+                |id, tick_inp, diff| {
+                    pred_inps.insert(
+                        *id,
+                        CharacterInputInfo {
+                            inp: tick_inp.inp,
+                            diff,
+                        },
+                    );
+                },
+            );
+            game_state.set_player_inputs(pred_inps);
+            game_state.tick(TickOptions {
+                is_future_tick_prediction: true,
+            });
+            game_state.clear_events();
+        }
+
+        game.game_data.last_game_tick = Duration::from_secs_f64(
+            (game.game_data.last_game_tick.as_secs_f64()
+                + game.game_data.prediction_timer.smooth_adjustment_time())
+            .clamp(0.0, f64::MAX),
+        );
+    }
 }
 
 impl FromNativeLoadingImpl<ClientNativeLoadingImpl> for ClientNativeImpl {
@@ -2656,11 +3130,11 @@ impl FromNativeLoadingImpl<ClientNativeLoadingImpl> for ClientNativeImpl {
         let browser_data = ServerBrowserData::default();
 
         #[cfg(feature = "ffmpeg")]
-        fn demo_to_video() -> bool {
+        fn ffmpeg() -> bool {
             true
         }
         #[cfg(not(feature = "ffmpeg"))]
-        fn demo_to_video() -> bool {
+        fn ffmpeg() -> bool {
             false
         }
 
@@ -2673,7 +3147,9 @@ impl FromNativeLoadingImpl<ClientNativeLoadingImpl> for ClientNativeImpl {
             false
         }
         let enabled_features = EnabledFeatures {
-            demo_to_video: demo_to_video(),
+            // the image sequence encoder works without ffmpeg
+            demo_to_video: true,
+            ffmpeg_encoder: ffmpeg(),
             spatial_chat: microphone(),
         };
 
@@ -2789,6 +3265,7 @@ impl FromNativeLoadingImpl<ClientNativeLoadingImpl> for ClientNativeImpl {
             sound_backend,
             game: Game::None,
             connect_info,
+            last_connect: None,
             demo_player: None,
 
             client_stats,
@@ -2799,6 +3276,7 @@ impl FromNativeLoadingImpl<ClientNativeLoadingImpl> for ClientNativeImpl {
             config: Config::new(loading.config_game, loading.config_engine),
             last_refresh_rate_time,
             editor: Default::default(),
+            editor_test_play: false,
 
             local_console,
             console_logs: Default::default(),
@@ -3117,207 +3595,17 @@ impl FromNativeImpl for ClientNativeImpl {
                 );
             }
 
-            game.game_data.prediction_timer.add_frametime(
-                self.cur_time.saturating_sub(game.game_data.last_frame_time),
-                self.cur_time,
-            );
-            game.game_data.last_frame_time = self.cur_time;
-            let game_state = &mut game.map.game;
-
-            let tick_of_inp = game_state.predicted_game_monotonic_tick + 1;
-            let ticks_per_second = game_state.game_tick_speed();
-
-            let mut player_inputs = game.player_inputs_pool.new();
-
-            let time_per_tick = Duration::from_nanos(
-                (Duration::from_secs(1).as_nanos() / ticks_per_second.get() as u128) as u64,
-            );
-            let ticks_to_send = game
-                .game_data
-                .prediction_timer
-                .time_units_to_respect(time_per_tick, 7.try_into().unwrap())
-                as GameTickType;
-            game.game_data.get_and_update_latest_input(
-                self.cur_time,
-                time_per_tick,
-                ticks_to_send,
-                tick_of_inp,
-                &mut player_inputs,
-                &game.player_inputs_chainable_pool,
-            );
-
-            game.send_input(&player_inputs, sys);
-            let game_state = &mut game.map.game;
-            // save the current input of all users for possible recalculations later
-            let tick_inps = &mut game.game_data.input_per_tick;
-
-            let add_input =
-                |tick_of_inp: GameTickType, input_per_tick: &mut ClientPlayerInputPerTick| {
-                    if !input_per_tick.contains_key(&tick_of_inp) {
-                        input_per_tick.insert(tick_of_inp, game.game_data.player_inp_pool.new());
-                    }
-
-                    // apply input of local player to player
-                    game.game_data.local.local_players.iter().for_each(
-                        |(local_player_id, local_player)| {
-                            let player_inp = input_per_tick.get_mut(&tick_of_inp).unwrap();
-                            player_inp.insert(*local_player_id, local_player.sent_input);
-                        },
-                    );
-                };
-            add_input(tick_of_inp, tick_inps);
-
-            let time_for_prediction = self.cur_time;
-
-            let instant_input = self.config.game.cl.instant_input;
-            // Reset the game state if needed
-            if instant_input {
-                if let Some(cur_state_snap) = game.game_data.cur_state_snap.take() {
-                    let _ = game_state.build_from_snapshot(&cur_state_snap);
-                }
-            }
-
-            fn apply_input(
-                predicted_game_monotonic_tick: GameTickType,
-                tick_inps: &mut FxLinkedHashMap<u64, PoolFxLinkedHashMap<PlayerId, PlayerInput>>,
-                fallback_to_prev_input: bool,
-                mut on_apply: impl FnMut(&PlayerId, &PlayerInput, CharacterInputConsumableDiff),
-            ) {
-                let tick_of_inp = predicted_game_monotonic_tick + 1;
-                let (next_input, prev_input) = (
-                    tick_inps.get(&tick_of_inp).or_else(|| {
-                        tick_inps
-                            .iter()
-                            .rev()
-                            .find_map(|(&tick, inp)| (tick <= tick_of_inp).then_some(inp))
-                    }),
-                    tick_inps.get(&predicted_game_monotonic_tick),
-                );
-                let check_input = if fallback_to_prev_input {
-                    next_input.or(prev_input)
-                } else {
-                    next_input
-                };
-                if let Some(inputs) = check_input {
-                    for (id, tick_inp) in inputs.iter() {
-                        let mut inp = PlayerInput::default();
-                        if let Some(prev_inp) =
-                            prev_input.or(next_input).and_then(|inp| inp.get(id))
-                        {
-                            inp.inp = prev_inp.inp;
-                        }
-                        if let Some(diff) =
-                            inp.try_overwrite(&tick_inp.inp, tick_inp.version(), true)
-                        {
-                            on_apply(id, tick_inp, diff);
-                        }
-                    }
-                }
-            }
-
-            // do the ticks if necessary
-            while is_next_tick(
-                time_for_prediction,
-                &mut game.game_data.last_game_tick,
-                ticks_per_second,
-            ) {
-                // apply input of players
-                let mut inps = game.game_data.player_inputs_state_pool.new();
-                apply_input(
-                    game_state.predicted_game_monotonic_tick,
-                    tick_inps,
-                    false,
-                    |id, tick_inp, diff| {
-                        inps.insert(
-                            *id,
-                            CharacterInputInfo {
-                                inp: tick_inp.inp,
-                                diff,
-                            },
-                        );
-                    },
-                );
-                game_state.set_player_inputs(inps);
-
-                let cur_snap = game_state.snapshot_for(SnapshotClientInfo::Everything);
-                game_state.build_from_snapshot_for_prev(&cur_snap);
-
-                game_state.predicted_game_monotonic_tick += 1;
-                game_state.tick(Default::default());
-
-                Server::dbg_game(
-                    &self.config.game.dbg,
-                    &game.game_data.last_game_tick,
-                    game_state,
-                    tick_inps
-                        .get(&game_state.predicted_game_monotonic_tick)
-                        .map(|inps| inps.values().map(|inp| &inp.inp)),
-                    game_state.predicted_game_monotonic_tick,
-                    ticks_per_second.get(),
+            if game.tas.is_some() {
+                Self::tick_tas(game, self.cur_time);
+            } else {
+                Self::predict_game(
+                    game,
+                    self.cur_time,
+                    sys,
+                    &self.config.game,
                     &self.shared_info,
-                    "client",
                 );
-
-                let mut player_ids = game.game_data.player_ids_pool.new();
-                player_ids.extend(game.game_data.local.local_players.keys());
-                let events = game_state.events_for(EventClientInfo {
-                    client_player_ids: player_ids,
-                    everything: true,
-                    other_stages: true,
-                });
-                if !events.is_empty() {
-                    game.events
-                        .entry((game_state.predicted_game_monotonic_tick, true))
-                        .or_insert_with(|| events);
-                }
-                game_state.clear_events();
-
-                // add a "dummy" input for the next tick already, since in a bad
-                // case this while-loop might run again
-                add_input(game_state.predicted_game_monotonic_tick + 1, tick_inps);
-            }
-
-            // next intra tick time
-            game.game_data.intra_tick_time = intra_tick_time(
-                self.cur_time,
-                game.game_data.last_game_tick,
-                ticks_per_second,
-            );
-
-            if instant_input {
-                let cur_state_snap = game_state.snapshot_for(SnapshotClientInfo::Everything);
-                game_state.build_from_snapshot_for_prev(&cur_state_snap);
-                game.game_data.cur_state_snap = Some(cur_state_snap);
-
-                // there is always a prediction tick
-                // apply input of players for it as if it's the next tick
-                let mut pred_inps = game.game_data.player_inputs_state_pool.new();
-                apply_input(
-                    game_state.predicted_game_monotonic_tick,
-                    tick_inps,
-                    true,
-                    |id, tick_inp, diff| {
-                        pred_inps.insert(
-                            *id,
-                            CharacterInputInfo {
-                                inp: tick_inp.inp,
-                                diff,
-                            },
-                        );
-                    },
-                );
-                game_state.set_player_inputs(pred_inps);
-                game_state.tick(TickOptions {
-                    is_future_tick_prediction: true,
-                });
-                game_state.clear_events();
             }
-
-            game.game_data.last_game_tick = Duration::from_secs_f64(
-                (game.game_data.last_game_tick.as_secs_f64()
-                    + game.game_data.prediction_timer.smooth_adjustment_time())
-                .clamp(0.0, f64::MAX),
-            );
         }
 
         // rendering
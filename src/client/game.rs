@@ -1,5 +1,6 @@
 pub mod active;
 pub mod data;
+pub mod tas;
 pub mod types;
 
 use std::{
@@ -449,6 +450,7 @@ impl Game {
             emoticons: NetworkResourceKey::from_str_lossy(&assets_player.emoticons),
             particles: NetworkResourceKey::from_str_lossy(&assets_player.particles),
             hook: NetworkResourceKey::from_str_lossy(&assets_player.hook),
+            skin_parts: (&player.skin.parts).into(),
 
             default_eyes: match player.eyes {
                 ConfigTeeEye::Normal => TeeEye::Normal,
@@ -705,6 +707,8 @@ impl Game {
                         spatial_world,
                         auto_cleanup,
 
+                        tas: None,
+
                         base,
 
                         resource_download_server,
@@ -54,6 +54,7 @@ use crate::{
 
 use super::{
     data::GameData,
+    tas::Tas,
     types::{GameBase, GameConnect, GameMsgPipeline, GameNetwork},
     DisconnectAutoCleanup,
 };
@@ -103,6 +104,11 @@ pub struct ActiveGame {
 
     pub spatial_world: SpatialChatGameWorldTy,
     pub auto_cleanup: DisconnectAutoCleanup,
+
+    /// Tool-assisted run mode, the client simulates the game
+    /// itself and ignores the server's world.
+    pub tas: Option<Tas>,
+
     pub connect: GameConnect,
 
     pub base: GameBase,
@@ -365,6 +371,13 @@ impl ActiveGame {
                     }
                     self.game_data.snap_acks.push(MsgClSnapshotAck { snap_id });
 
+                    if self.tas.is_some() {
+                        // the tool-assisted run simulates the world itself,
+                        // the server's snapshot is only kept for when it ends.
+                        self.game_data.last_snap = Some((snapshot, monotonic_tick));
+                        return;
+                    }
+
                     let predicted_game_monotonic_tick = monotonic_tick.max(prev_tick);
 
                     // if the incoming snapshot is older than the prediction tick, then we can use it directly
@@ -588,6 +601,11 @@ impl ActiveGame {
                 self.replay
                     .add_event(game_monotonic_tick, DemoEvent::Game(events.clone()));
 
+                if self.tas.is_some() {
+                    // the events of the tool-assisted run come from its own simulation
+                    return;
+                }
+
                 let event_id = events.event_id;
                 self.events.insert((game_monotonic_tick, false), events);
                 self.map.game.sync_event_id(event_id);
@@ -0,0 +1,376 @@
+use std::collections::BTreeMap;
+
+use base::linked_hash_map_view::FxLinkedHashMap;
+use base_io::io::Io;
+use client_console::console::local_console::TasInputEdit;
+use demo::recorder::{DemoRecorder, DemoRecorderCreateProps};
+use game_interface::{
+    interface::GameStateInterface,
+    types::{
+        game::GameTickType,
+        id_types::PlayerId,
+        input::{cursor::CharacterInputCursor, CharacterInput, CharacterInputInfo},
+        snapshot::{SnapshotClientInfo, SnapshotLocalPlayers},
+    },
+};
+use game_state_wasm::game::state_wasm_manager::GameStateWasmManager;
+use math::math::vector::dvec2;
+use pool::{mt_datatypes::PoolCow as MtPoolCow, pool::Pool};
+use serde::{Deserialize, Serialize};
+
+/// The exported inputs of a tool-assisted run.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TasInputs {
+    pub ticks_per_second: GameTickType,
+    /// The monotonic tick the run started in.
+    pub start_tick: GameTickType,
+    /// The inputs of all local players for every tick after the start tick.
+    pub ticks: Vec<Vec<(PlayerId, CharacterInput)>>,
+}
+
+/// Tool-assisted run mode.
+///
+/// Instead of following the server, the client simulates the game itself,
+/// one tick at a time if wanted. Every simulated tick is saved as snapshot,
+/// so the run can be rewound and the inputs can be edited afterwards.
+pub struct Tas {
+    start_tick: GameTickType,
+    /// The world at the end of the given tick.
+    snapshots: BTreeMap<GameTickType, MtPoolCow<'static, [u8]>>,
+    /// The inputs that were used to simulate the given tick.
+    inputs: BTreeMap<GameTickType, FxLinkedHashMap<PlayerId, CharacterInput>>,
+
+    pub paused: bool,
+    /// Ticks that should be simulated, even if paused.
+    pending_steps: u64,
+
+    inputs_pool: Pool<FxLinkedHashMap<PlayerId, CharacterInputInfo>>,
+}
+
+impl Tas {
+    pub fn new(game: &mut GameStateWasmManager) -> Self {
+        let start_tick = game.predicted_game_monotonic_tick;
+        let mut snapshots: BTreeMap<_, _> = Default::default();
+        snapshots.insert(
+            start_tick,
+            game.snapshot_for(SnapshotClientInfo::Everything),
+        );
+        let res = Self {
+            start_tick,
+            snapshots,
+            inputs: Default::default(),
+
+            paused: true,
+            pending_steps: 0,
+
+            inputs_pool: Pool::with_capacity(2),
+        };
+        res.freeze(game);
+        res
+    }
+
+    pub fn start_tick(&self) -> GameTickType {
+        self.start_tick
+    }
+
+    /// The amount of ticks for which inputs are known.
+    pub fn recorded_ticks(&self) -> u64 {
+        self.inputs.len() as u64
+    }
+
+    pub fn step(&mut self, ticks: u64) {
+        self.pending_steps += ticks;
+    }
+
+    pub fn take_pending_steps(&mut self) -> u64 {
+        std::mem::take(&mut self.pending_steps)
+    }
+
+    /// Renders the current tick without interpolating
+    /// from the previous one.
+    pub fn freeze(&self, game: &mut GameStateWasmManager) {
+        if let Some(snapshot) = self.snapshots.get(&game.predicted_game_monotonic_tick) {
+            game.build_from_snapshot_for_prev(snapshot);
+        }
+    }
+
+    /// Simulates the next tick.
+    /// If the tick was simulated before, the saved inputs are used,
+    /// else the live inputs of the local players.
+    pub fn tick(
+        &mut self,
+        game: &mut GameStateWasmManager,
+        live_inputs: impl Iterator<Item = (PlayerId, CharacterInput)>,
+    ) {
+        let tick = game.predicted_game_monotonic_tick;
+        let next_tick = tick + 1;
+
+        let inps = self
+            .inputs
+            .entry(next_tick)
+            .or_insert_with(|| live_inputs.collect())
+            .clone();
+        let prev_inps = self.inputs.get(&tick);
+
+        let mut game_inps = self.inputs_pool.new();
+        for (id, inp) in inps.iter() {
+            let prev_inp = prev_inps.and_then(|inps| inps.get(id)).unwrap_or(inp);
+            game_inps.insert(
+                *id,
+                CharacterInputInfo {
+                    inp: *inp,
+                    diff: inp.consumable.diff(&prev_inp.consumable),
+                },
+            );
+        }
+        game.set_player_inputs(game_inps);
+
+        if let Some(snapshot) = self.snapshots.get(&tick) {
+            game.build_from_snapshot_for_prev(snapshot);
+        }
+        game.predicted_game_monotonic_tick = next_tick;
+        game.tick(Default::default());
+
+        self.snapshots
+            .insert(next_tick, game.snapshot_for(SnapshotClientInfo::Everything));
+    }
+
+    /// Goes back the given amount of ticks, but at most to the start of the run.
+    /// The inputs are kept, so stepping forward replays them.
+    ///
+    /// Returns the local players of the rewound world.
+    #[must_use]
+    pub fn rewind(
+        &mut self,
+        game: &mut GameStateWasmManager,
+        ticks: u64,
+    ) -> Option<SnapshotLocalPlayers> {
+        let tick = game
+            .predicted_game_monotonic_tick
+            .saturating_sub(ticks)
+            .max(self.start_tick);
+        // later snapshots are simulated again with the (maybe edited) inputs
+        self.snapshots.split_off(&(tick + 1));
+        let snapshot = self.snapshots.get(&tick)?;
+        let local_players = game.build_from_snapshot(snapshot);
+        game.build_from_snapshot_for_prev(snapshot);
+        game.predicted_game_monotonic_tick = tick;
+        Some(local_players)
+    }
+
+    /// Throws away all inputs after the current tick.
+    pub fn cut(&mut self, game: &GameStateWasmManager) {
+        let tick = game.predicted_game_monotonic_tick;
+        self.inputs.split_off(&(tick + 1));
+        self.snapshots.split_off(&(tick + 1));
+    }
+
+    /// Edits the input of the given player for the next tick.
+    pub fn edit_input(
+        &mut self,
+        game: &GameStateWasmManager,
+        player_id: PlayerId,
+        live_inputs: impl Iterator<Item = (PlayerId, CharacterInput)>,
+        edit: TasInputEdit,
+    ) {
+        let tick = game.predicted_game_monotonic_tick;
+        let prev_inp = self
+            .inputs
+            .get(&tick)
+            .and_then(|inps| inps.get(&player_id))
+            .copied();
+        let inps = self
+            .inputs
+            .entry(tick + 1)
+            .or_insert_with(|| live_inputs.collect());
+        let Some(inp) = inps.get_mut(&player_id) else {
+            return;
+        };
+        let prev_inp = prev_inp.unwrap_or(*inp);
+
+        match edit {
+            TasInputEdit::Dir(dir) => inp.state.dir.set(dir),
+            TasInputEdit::Jump(jump) => {
+                // pressing again must trigger the action,
+                // so count the press relative to the previous tick
+                inp.consumable.jump = prev_inp.consumable.jump;
+                if jump {
+                    inp.consumable.jump.add(1);
+                }
+                inp.state.jump.set(jump);
+            }
+            TasInputEdit::Hook(hook) => {
+                inp.consumable.hook = prev_inp.consumable.hook;
+                if hook {
+                    inp.consumable.hook.add(1, *inp.cursor);
+                }
+                inp.state.hook.set(hook);
+            }
+            TasInputEdit::Fire(fire) => {
+                inp.consumable.fire = prev_inp.consumable.fire;
+                if fire {
+                    inp.consumable.fire.add(1, *inp.cursor);
+                }
+                inp.state.fire.set(fire);
+            }
+            TasInputEdit::CursorX(x) => {
+                let cursor = inp.cursor.to_vec2();
+                inp.cursor
+                    .set(CharacterInputCursor::from_vec2(&dvec2::new(x, cursor.y)));
+            }
+            TasInputEdit::CursorY(y) => {
+                let cursor = inp.cursor.to_vec2();
+                inp.cursor
+                    .set(CharacterInputCursor::from_vec2(&dvec2::new(cursor.x, y)));
+            }
+            TasInputEdit::Weapon(weapon) => inp.consumable.set_weapon_req(Some(weapon)),
+        }
+    }
+
+    /// Writes the inputs up to the current tick to `tas/<name>.json`
+    /// and records the simulated ticks as demo in `demos/tas`.
+    pub fn export(
+        &self,
+        game: &GameStateWasmManager,
+        name: &str,
+        io: &Io,
+        demo_props: DemoRecorderCreateProps,
+    ) -> anyhow::Result<()> {
+        let cur_tick = game.predicted_game_monotonic_tick;
+        let ticks_per_second = game.game_tick_speed();
+
+        let inputs = TasInputs {
+            ticks_per_second: ticks_per_second.get(),
+            start_tick: self.start_tick,
+            ticks: self
+                .inputs
+                .range(self.start_tick + 1..=cur_tick)
+                .map(|(_, inps)| inps.iter().map(|(id, inp)| (*id, *inp)).collect())
+                .collect(),
+        };
+        let inputs = serde_json::to_vec_pretty(&inputs)?;
+
+        let mut recorder = DemoRecorder::new(
+            demo_props,
+            ticks_per_second,
+            Some("tas".as_ref()),
+            Some(name.to_string()),
+        );
+        for (tick, snapshot) in self.snapshots.range(self.start_tick..=cur_tick) {
+            recorder.add_snapshot(*tick, snapshot.to_vec());
+        }
+        // dropping the recorder finishes the demo
+        drop(recorder);
+
+        let fs = io.fs.clone();
+        let path = format!("tas/{name}.json");
+        io.rt.spawn_without_lifetime(async move {
+            fs.create_dir("tas".as_ref()).await?;
+            fs.write_file(path.as_ref(), inputs).await?;
+            Ok(())
+        });
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use base_fs::filesys::FileSystem;
+    use base_http::http::HttpClient;
+    use base_io::io::Io;
+    use game_database::dummy::DummyDb;
+    use game_interface::{
+        interface::GameStateInterface,
+        types::{
+            character_info::NetworkCharacterInfo,
+            id_types::PlayerId,
+            input::CharacterInput,
+            network_stats::PlayerNetworkStats,
+            player_info::{PlayerClientInfo, PlayerUniqueId},
+            snapshot::SnapshotClientInfo,
+        },
+    };
+    use game_state_wasm::game::state_wasm_manager::{GameStateMod, GameStateWasmManager};
+
+    use super::Tas;
+
+    fn game() -> (GameStateWasmManager, PlayerId) {
+        let io = Io::new(
+            |rt| {
+                Arc::new(
+                    FileSystem::new(rt, "ddnet-test", "ddnet-test", "ddnet-test", "ddnet-test")
+                        .unwrap(),
+                )
+            },
+            Arc::new(HttpClient::new()),
+        );
+        let map = include_bytes!("../../../data/map/maps/ctf1.twmap");
+        let mut game = GameStateWasmManager::new(
+            GameStateMod::Native,
+            map.to_vec(),
+            "ctf1".try_into().unwrap(),
+            Default::default(),
+            &io,
+            Arc::new(DummyDb),
+        )
+        .unwrap();
+        let id = game.player_join(&PlayerClientInfo {
+            info: NetworkCharacterInfo::explicit_default(),
+            id: 0,
+            unique_identifier: PlayerUniqueId::Account(0),
+            initial_network_stats: PlayerNetworkStats::default(),
+        });
+        (game, id)
+    }
+
+    fn world(game: &GameStateWasmManager) -> Vec<u8> {
+        game.snapshot_for(SnapshotClientInfo::Everything).to_vec()
+    }
+
+    #[test]
+    fn step() {
+        let (mut game, _) = game();
+        let mut tas = Tas::new(&mut game);
+        assert!(tas.paused);
+        tas.step(3);
+        tas.step(2);
+        assert_eq!(tas.take_pending_steps(), 5);
+        assert_eq!(tas.take_pending_steps(), 0);
+    }
+
+    #[test]
+    fn rewind_replays_the_recorded_inputs() {
+        let (mut game, id) = game();
+        let mut tas = Tas::new(&mut game);
+        let start_tick = tas.start_tick();
+        let start_world = world(&game);
+
+        let mut inp = CharacterInput::default();
+        inp.state.dir.set(1);
+        for _ in 0..10 {
+            tas.tick(&mut game, [(id, inp)].into_iter());
+        }
+        assert_eq!(game.predicted_game_monotonic_tick, start_tick + 10);
+        assert_eq!(tas.recorded_ticks(), 10);
+        let end_world = world(&game);
+        assert!(start_world != end_world, "the character never moved");
+
+        let local_players = tas.rewind(&mut game, 4);
+        assert!(local_players.is_some());
+        assert_eq!(game.predicted_game_monotonic_tick, start_tick + 6);
+
+        // never rewinds before the start of the run
+        let _ = tas.rewind(&mut game, 100);
+        assert_eq!(game.predicted_game_monotonic_tick, start_tick);
+        assert!(world(&game) == start_world);
+        assert_eq!(tas.recorded_ticks(), 10);
+
+        // the live inputs are ignored for already recorded ticks
+        for _ in 0..10 {
+            tas.tick(&mut game, std::iter::empty());
+        }
+        assert!(world(&game) == end_world);
+    }
+}
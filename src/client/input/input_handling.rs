@@ -538,6 +538,45 @@ impl InputHandling {
         }
     }
 
+    /// Takes a screenshot of the next frame and writes it
+    /// to the screenshots directory.
+    pub fn take_screenshot(graphics: &Graphics, io: &Io) {
+        let io = io.clone();
+        #[derive(Debug)]
+        struct Screenshot {
+            io: Io,
+        }
+        impl ScreenshotCb for Screenshot {
+            fn on_screenshot(&self, png: anyhow::Result<Vec<u8>>) {
+                match png {
+                    Ok(png) => {
+                        let fs = self.io.fs.clone();
+
+                        self.io.rt.spawn_without_lifetime(async move {
+                            fs.create_dir("screenshots".as_ref()).await?;
+                            fs.write_file(
+                                format!(
+                                    "screenshots/{}.png",
+                                    chrono::Local::now().format("%Y_%m_%d_%H_%M_%S")
+                                )
+                                .as_ref(),
+                                png,
+                            )
+                            .await?;
+                            Ok(())
+                        });
+                    }
+                    Err(err) => {
+                        log::error!(target: "screenshot", "{err}");
+                    }
+                }
+            }
+        }
+        if let Err(err) = graphics.do_screenshot(Screenshot { io }) {
+            log::error!(target: "screenshot", "{err}");
+        }
+    }
+
     fn handle_global_binds_impl(
         global_binds: &mut Binds<BindActionsHotkey>,
         graphics: &Graphics,
@@ -552,38 +591,7 @@ impl InputHandling {
         for action in actions.click_actions.iter() {
             match action {
                 BindActionsHotkey::Screenshot => {
-                    let io = io.clone();
-                    #[derive(Debug)]
-                    struct Screenshot {
-                        io: Io,
-                    }
-                    impl ScreenshotCb for Screenshot {
-                        fn on_screenshot(&self, png: anyhow::Result<Vec<u8>>) {
-                            match png {
-                                Ok(png) => {
-                                    let fs = self.io.fs.clone();
-
-                                    self.io.rt.spawn_without_lifetime(async move {
-                                        fs.create_dir("screenshots".as_ref()).await?;
-                                        fs.write_file(
-                                            format!(
-                                                "screenshots/{}.png",
-                                                chrono::Local::now().format("%Y_%m_%d_%H_%M_%S")
-                                            )
-                                            .as_ref(),
-                                            png,
-                                        )
-                                        .await?;
-                                        Ok(())
-                                    });
-                                }
-                                Err(err) => {
-                                    log::error!(target: "screenshot", "{err}");
-                                }
-                            }
-                        }
-                    }
-                    graphics.do_screenshot(Screenshot { io }).unwrap();
+                    Self::take_screenshot(graphics, io);
                 }
                 BindActionsHotkey::LocalConsole => {
                     local_console_state.is_ui_open = !local_console_state.is_ui_open;
@@ -0,0 +1,213 @@
+use anyhow::anyhow;
+use clap::{Parser, Subcommand, ValueEnum};
+use config::{
+    config::{ConfigEngine, ENGINE_MIGRATIONS},
+    migration::VERSION_KEY,
+    schema::{
+        add_version_to_schema, html_reference, json_schema, markdown_reference, reference,
+        validate, version_reference_entry,
+    },
+    traits::{ConfigInterface, ConfigValue},
+};
+use game_config::config::{ConfigGame, GAME_MIGRATIONS};
+use vanilla::config::config::ConfigVanilla;
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+enum ConfigKind {
+    /// engine config (`cfg_engine.json`): `inp.*`, `gfx.*`, `snd.*`...
+    Engine,
+    /// game config (`cfg_game.json`): `cl.*`, `sv.*`, `inp.*`...
+    Game,
+    /// config of the vanilla mod
+    Vanilla,
+}
+
+impl ConfigKind {
+    fn all() -> [ConfigKind; 3] {
+        [ConfigKind::Engine, ConfigKind::Game, ConfigKind::Vanilla]
+    }
+
+    fn title(self) -> &'static str {
+        match self {
+            ConfigKind::Engine => "Engine config",
+            ConfigKind::Game => "Game config",
+            ConfigKind::Vanilla => "Vanilla mod config",
+        }
+    }
+
+    /// The prefix of the variables in the reference,
+    /// mod configs are accessed through their mod name.
+    fn prefix(self) -> &'static str {
+        match self {
+            ConfigKind::Engine | ConfigKind::Game => "",
+            ConfigKind::Vanilla => "vanilla",
+        }
+    }
+
+    /// The current version of configs that are stored with a version,
+    /// see [`config::migration::ConfigMigrations`].
+    fn version(self) -> Option<u64> {
+        match self {
+            ConfigKind::Engine => Some(ENGINE_MIGRATIONS.version()),
+            ConfigKind::Game => Some(GAME_MIGRATIONS.version()),
+            ConfigKind::Vanilla => None,
+        }
+    }
+
+    /// The config value tree & the default config serialized as json.
+    fn tree(self) -> (ConfigValue, serde_json::Value) {
+        match self {
+            ConfigKind::Engine => (
+                ConfigEngine::conf_value(),
+                serde_json::to_value(ConfigEngine::default()).unwrap(),
+            ),
+            ConfigKind::Game => (
+                ConfigGame::conf_value(),
+                serde_json::to_value(ConfigGame::default()).unwrap(),
+            ),
+            ConfigKind::Vanilla => (
+                ConfigVanilla::conf_value(),
+                serde_json::to_value(ConfigVanilla::default()).unwrap(),
+            ),
+        }
+    }
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy)]
+enum DocsFormat {
+    Markdown,
+    Html,
+}
+
+#[derive(Subcommand, Debug)]
+enum Cmd {
+    /// print the JSON Schema of a config file
+    Schema {
+        #[arg(short, long, value_enum)]
+        config: ConfigKind,
+    },
+    /// print a reference of all config variables
+    Docs {
+        /// only document this config, documents all configs by default
+        #[arg(short, long, value_enum)]
+        config: Option<ConfigKind>,
+        #[arg(short, long, value_enum, default_value_t = DocsFormat::Markdown)]
+        format: DocsFormat,
+    },
+    /// check a config file for unknown keys & invalid values
+    Validate {
+        #[arg(short, long, value_enum)]
+        config: ConfigKind,
+        /// path to the config file (json)
+        file: String,
+        /// print the issues as json
+        #[arg(short, long, default_value_t = false)]
+        json: bool,
+    },
+}
+
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    #[command(subcommand)]
+    cmd: Cmd,
+    /// write the output to this file instead of stdout
+    #[arg(short, long, global = true)]
+    out: Option<String>,
+}
+
+fn docs(configs: &[ConfigKind], format: DocsFormat) -> String {
+    let sections = configs.iter().map(|&kind| {
+        let (val, defaults) = kind.tree();
+        let mut entries = reference(kind.prefix(), &val, &defaults);
+        if let Some(version) = kind.version() {
+            entries.insert(0, version_reference_entry(version));
+        }
+        match format {
+            DocsFormat::Markdown => markdown_reference(kind.title(), &entries),
+            DocsFormat::Html => html_reference(kind.title(), &entries),
+        }
+    });
+    match format {
+        DocsFormat::Markdown => format!(
+            "# Config reference\n\n{}",
+            sections.collect::<Vec<_>>().join("\n")
+        ),
+        DocsFormat::Html => format!(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+            <title>Config reference</title>\n</head>\n<body>\n\
+            <h1>Config reference</h1>\n{}</body>\n</html>\n",
+            sections.collect::<String>()
+        ),
+    }
+}
+
+fn run(args: Args) -> anyhow::Result<bool> {
+    let (output, valid) = match args.cmd {
+        Cmd::Schema { config } => {
+            let (val, defaults) = config.tree();
+            let mut schema = json_schema(config.title(), &val, &defaults);
+            if let Some(version) = config.version() {
+                add_version_to_schema(&mut schema, version);
+            }
+            (serde_json::to_string_pretty(&schema)?, true)
+        }
+        Cmd::Docs { config, format } => {
+            let configs = config
+                .map(|c| vec![c])
+                .unwrap_or_else(|| ConfigKind::all().to_vec());
+            (docs(&configs, format), true)
+        }
+        Cmd::Validate { config, file, json } => {
+            let content = std::fs::read(&file)
+                .map_err(|err| anyhow!("reading config file {file} failed: {err}"))?;
+            let mut content: serde_json::Value = serde_json::from_slice(&content)
+                .map_err(|err| anyhow!("config file {file} is not valid json: {err}"))?;
+            if let Some(content) = content.as_object_mut() {
+                content.remove(VERSION_KEY);
+            }
+            let (val, defaults) = config.tree();
+            let issues = validate(&val, &content, &defaults);
+            let output = if json {
+                serde_json::to_string_pretty(&issues)?
+            } else {
+                issues
+                    .iter()
+                    .map(|issue| format!("{}: {}", issue.path, issue.msg))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            };
+            if !json {
+                log::info!("{} issue(s) found in {file}", issues.len());
+            }
+            (output, issues.is_empty())
+        }
+    };
+
+    match args.out {
+        Some(out) => {
+            std::fs::write(&out, output).map_err(|err| anyhow!("writing {out} failed: {err}"))?
+        }
+        None if !output.is_empty() => println!("{output}"),
+        None => {}
+    }
+    Ok(valid)
+}
+
+fn main() {
+    let args = Args::parse();
+
+    if std::env::var("RUST_LOG").is_err() {
+        unsafe { std::env::set_var("RUST_LOG", "info") };
+    }
+    env_logger::init();
+
+    match run(args) {
+        Ok(true) => {}
+        Ok(false) => std::process::exit(1),
+        Err(err) => {
+            log::error!("{err}");
+            std::process::exit(2);
+        }
+    }
+}
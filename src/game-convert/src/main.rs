@@ -4,7 +4,7 @@ use std::{
 };
 
 use clap::Parser;
-use client_extra::game_split::Game06Part;
+use client_extra::game_split::{Game06ConvertResult, Game06Part};
 use tar::Header;
 
 #[derive(Parser, Debug)]
@@ -17,6 +17,11 @@ struct Args {
     /// Put the resulting assets into a tar archieve.
     #[arg(short, long, default_value_t = false, action = clap::ArgAction::Set)]
     tar: bool,
+    /// Pack the split directories inside `file` (`weapons/default`, `hooks/default` etc.)
+    /// back into a 0.6 game.png, written to the file `output`.
+    /// The particles of the game.png are taken from `particles/default`.
+    #[arg(short, long, default_value_t = false, action = clap::ArgAction::Set)]
+    reverse: bool,
 }
 
 struct TarFile {
@@ -67,9 +72,134 @@ fn write_part(
     }
 }
 
+fn read_part(input: &Path, base_path: &str, name: &str) -> Game06Part {
+    let path = input.join(base_path).join(format!("{name}.png"));
+    let file = std::fs::read(&path)
+        .unwrap_or_else(|err| panic!("failed to read {name} in {input:?}: {err}"));
+    let mut mem: Vec<u8> = Default::default();
+    let img = image_utils::png::load_png_image_as_rgba(&file, |width, height, bytes_per_pixel| {
+        mem.resize(width * height * bytes_per_pixel, Default::default());
+        &mut mem
+    })
+    .unwrap()
+    .to_persistent();
+    Game06Part {
+        data: img.data,
+        width: img.width,
+        height: img.height,
+    }
+}
+
+/// Like [`read_part`], but the part is allowed to not exist.
+fn read_optional_part(input: &Path, base_path: &str, name: &str) -> Option<Game06Part> {
+    input
+        .join(base_path)
+        .join(format!("{name}.png"))
+        .is_file()
+        .then(|| read_part(input, base_path, name))
+}
+
+/// The particles of the game.png are not split into own files,
+/// they are the same images as the particles of `particles/default`:
+/// slice, ball, the three splats & smoke (one segment each), followed by
+/// shell, airjump & hit (2x2 segments each).
+fn read_particles(input: &Path) -> [Game06Part; 9] {
+    let particles = "particles/default";
+    [
+        "slice_001",
+        "ball_001",
+        "splat_001",
+        "splat_002",
+        "splat_003",
+        "smoke_001",
+        "shell_001",
+        "airjump_001",
+        "hit_001",
+    ]
+    .map(|name| read_part(input, particles, name))
+}
+
+fn pack(input: &Path, output: &Path) {
+    let weapons = "weapons/default";
+    let ninjas = "ninjas/default";
+    let games = "games/default";
+    let huds = "huds/default";
+    // the hook chain is exactly one segment of the atlas
+    let hook_chain = read_part(input, "hooks/default", "hook_chain");
+    let converted = Game06ConvertResult {
+        cursor_hammer: read_part(input, weapons, "hammer/cursor"),
+        cursor_gun: read_part(input, weapons, "gun/cursor"),
+        cursor_shotgun: read_part(input, weapons, "shotgun/cursor"),
+        cursor_grenade: read_part(input, weapons, "grenade/cursor"),
+        cursor_ninja: read_part(input, ninjas, "cursor"),
+        cursor_laser: read_part(input, weapons, "laser/cursor"),
+
+        weapon_hammer: read_part(input, weapons, "hammer/weapon"),
+        weapon_gun: read_part(input, weapons, "gun/weapon"),
+        weapon_shotgun: read_part(input, weapons, "shotgun/weapon"),
+        weapon_grenade: read_part(input, weapons, "grenade/weapon"),
+        weapon_ninja: read_part(input, ninjas, "weapon"),
+        weapon_laser: read_part(input, weapons, "laser/weapon"),
+
+        projectile_gun: read_part(input, weapons, "gun/projectile"),
+        projectile_shotgun: read_part(input, weapons, "shotgun/projectile"),
+        projectile_grenade: read_part(input, weapons, "grenade/projectile"),
+        projectile_laser: read_part(input, weapons, "laser/projectile"),
+
+        muzzle_gun: std::array::from_fn(|index| {
+            read_part(input, weapons, &format!("gun/muzzle_{:03}", index + 1))
+        }),
+        muzzle_shotgun: std::array::from_fn(|index| {
+            read_part(input, weapons, &format!("shotgun/muzzle_{:03}", index + 1))
+        }),
+        muzzle_ninja: std::array::from_fn(|index| {
+            read_part(input, ninjas, &format!("muzzle_{:03}", index + 1))
+        }),
+
+        flag_blue: read_part(input, "ctfs/default", "flag_blue"),
+        flag_red: read_part(input, "ctfs/default", "flag_red"),
+
+        hook_chain,
+        hook_head: read_part(input, "hooks/default", "hook_head"),
+
+        star1: read_part(input, games, "star1"),
+        star2: read_part(input, games, "star2"),
+        star3: read_part(input, games, "star3"),
+
+        health_full: read_part(input, huds, "vanilla/heart"),
+        health_empty: read_part(input, huds, "vanilla/heart_empty"),
+
+        armor_full: read_part(input, huds, "vanilla/shield"),
+        armor_empty: read_part(input, huds, "vanilla/shield_empty"),
+
+        lose_shotgun: read_optional_part(input, games, "lose_shotgun"),
+        lose_grenade: read_optional_part(input, games, "lose_grenade"),
+        lose_laser: read_optional_part(input, games, "lose_laser"),
+        lose_ninja: read_optional_part(input, games, "lose_ninja"),
+
+        particles: read_particles(input),
+
+        pickup_health: read_part(input, games, "heart"),
+        pickup_armor: read_part(input, games, "shield"),
+
+        ninja_bar_full_left: read_optional_part(input, ninjas, "ninja_bar_full_left"),
+        ninja_bar_full: read_optional_part(input, ninjas, "ninja_bar_full"),
+        ninja_bar_empty: read_optional_part(input, ninjas, "ninja_bar_empty"),
+        ninja_bar_empty_right: read_optional_part(input, ninjas, "ninja_bar_empty_right"),
+    };
+    let packed = client_extra::game_split::pack_06_game(&converted).unwrap();
+    let png = image_utils::png::save_png_image(&packed.data, packed.width, packed.height).unwrap();
+    std::fs::write(output, png).unwrap();
+}
+
 fn main() {
     let args = Args::parse();
 
+    if args.reverse {
+        pack(&args.file, &args.output);
+        return;
+    }
+
     let file = std::fs::read(args.file).unwrap();
     let mut mem: Vec<u8> = Default::default();
     let img: image_utils::png::PngResult<'_> =
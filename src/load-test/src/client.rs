@@ -0,0 +1,478 @@
+use std::{
+    borrow::Cow,
+    collections::BTreeMap,
+    sync::{atomic::AtomicBool, Arc},
+    time::Duration,
+};
+
+use base::{
+    hash::Hash, linked_hash_map_view::FxLinkedHashMap, network_string::NetworkString,
+    system::System,
+};
+use game_base::{
+    network::messages::{
+        MsgClAddLocalPlayer, MsgClInputPlayerChain, MsgClReady, MsgClReadyResponse,
+        MsgClSnapshotAck, PlayerInputChainable,
+    },
+    player_input::PlayerInput,
+};
+use game_interface::types::{
+    character_info::NetworkCharacterInfo,
+    game::GameTickType,
+    id_types::PlayerId,
+    input::{cursor::CharacterInputCursor, CharacterInput},
+    weapons::WeaponType,
+};
+use game_network::{
+    game_event_generator::{GameEventGenerator, GameEvents},
+    messages::{ClientToServerMessage, ServerToClientMessage},
+};
+use math::math::{vector::dvec2, Rng, RngSlice};
+use network::network::{
+    event::{NetworkEvent, NetworkStats},
+    packet_compressor::DefaultNetworkPacketCompressor,
+    plugins::{NetworkPluginPacket, NetworkPlugins},
+    quinn_network::QuinnNetwork,
+    types::{NetworkClientCertCheckMode, NetworkClientCertMode, NetworkClientInitOptions},
+    utils::create_certifified_keys,
+};
+use pool::mt_pool::Pool as MtPool;
+
+use crate::stats::ClientStats;
+
+/// Where the inputs of a simulated player come from.
+pub enum InputSource {
+    /// Changes the input randomly every few ticks.
+    Random { rng: Rng },
+    /// Loops over a list of recorded inputs.
+    Recorded {
+        inputs: Arc<Vec<CharacterInput>>,
+        index: usize,
+    },
+}
+
+impl InputSource {
+    fn next(&mut self, cur: &CharacterInput) -> CharacterInput {
+        match self {
+            InputSource::Random { rng } => {
+                let mut inp = *cur;
+                // roughly 4 changes per second at 50 ticks
+                if rng.random_int_in(0..=12) == 0 {
+                    inp.state.dir.set(rng.random_int_in(0..=2) as i32 - 1);
+                }
+                if rng.random_int_in(0..=12) == 0 {
+                    let jump = !*inp.state.jump;
+                    if jump {
+                        inp.consumable.jump.add(1);
+                    }
+                    inp.state.jump.set(jump);
+                }
+                if rng.random_int_in(0..=6) == 0 {
+                    inp.cursor.set(CharacterInputCursor::from_vec2(&dvec2::new(
+                        rng.random_float_in(-400.0..=400.0) as f64,
+                        rng.random_float_in(-400.0..=400.0) as f64,
+                    )));
+                }
+                if rng.random_int_in(0..=12) == 0 {
+                    let hook = !*inp.state.hook;
+                    if hook {
+                        inp.consumable.hook.add(1, *inp.cursor);
+                    }
+                    inp.state.hook.set(hook);
+                }
+                if rng.random_int_in(0..=12) == 0 {
+                    let fire = !*inp.state.fire;
+                    if fire {
+                        inp.consumable.fire.add(1, *inp.cursor);
+                    }
+                    inp.state.fire.set(fire);
+                }
+                if rng.random_int_in(0..=200) == 0 {
+                    const WEAPONS: [WeaponType; 5] = [
+                        WeaponType::Hammer,
+                        WeaponType::Gun,
+                        WeaponType::Shotgun,
+                        WeaponType::Grenade,
+                        WeaponType::Laser,
+                    ];
+                    inp.consumable
+                        .set_weapon_req(Some(*WEAPONS.random_entry(rng)));
+                }
+                inp
+            }
+            InputSource::Recorded { inputs, index } => {
+                let inp = inputs[*index % inputs.len()];
+                *index += 1;
+                inp
+            }
+        }
+    }
+}
+
+pub enum LoadClientState {
+    Connecting,
+    /// Connected, waiting for the server info.
+    Connected,
+    /// Ready was sent, waiting for the response.
+    Ready,
+    Ingame {
+        player_id: PlayerId,
+    },
+    Disconnected(String),
+}
+
+/// A single simulated player, that does not load any map or
+/// simulate the game, but otherwise behaves like a normal client.
+pub struct LoadClient {
+    network: QuinnNetwork,
+    events: Arc<GameEventGenerator<ServerToClientMessage<'static>>>,
+    has_events: Arc<AtomicBool>,
+
+    pub name: String,
+    pub state: LoadClientState,
+
+    input_source: InputSource,
+    input: PlayerInput,
+    input_id: u64,
+    /// Monotonic tick the next input is for.
+    input_tick: Option<GameTickType>,
+    sent_input_ids: BTreeMap<u64, Duration>,
+
+    /// Highest snap id and its monotonic tick.
+    handled_snap: Option<(u64, GameTickType)>,
+    /// Monotonic ticks of snapshots the server might diff against.
+    snap_ticks: BTreeMap<u64, GameTickType>,
+    snap_acks: Vec<MsgClSnapshotAck>,
+
+    chain_pool: MtPool<Vec<u8>>,
+    inputs_pool: MtPool<FxLinkedHashMap<PlayerId, MsgClInputPlayerChain>>,
+
+    pub stats: ClientStats,
+    network_stats: Option<NetworkStats>,
+    reported_network_stats: Option<NetworkStats>,
+}
+
+impl LoadClient {
+    pub fn new(
+        sys: &System,
+        addr: &str,
+        server_cert_hash: Option<Hash>,
+        dicts: Option<(Vec<u8>, Vec<u8>)>,
+        timeout: Duration,
+        name: String,
+        input_source: InputSource,
+    ) -> anyhow::Result<Self> {
+        let has_events = Arc::new(AtomicBool::new(false));
+        let events = Arc::new(GameEventGenerator::new(has_events.clone()));
+
+        let packet_plugin: Arc<dyn NetworkPluginPacket> = match dicts {
+            Some((client_send, server_send)) => Arc::new(
+                DefaultNetworkPacketCompressor::new_with_dict(client_send, server_send),
+            ),
+            None => Arc::new(DefaultNetworkPacketCompressor::new()),
+        };
+
+        let (cert, private_key) = create_certifified_keys();
+        let (network, _notifier) = QuinnNetwork::init_client(
+            None,
+            events.clone(),
+            sys,
+            NetworkClientInitOptions::new(
+                match &server_cert_hash {
+                    Some(hash) => NetworkClientCertCheckMode::CheckByPubKeyHash {
+                        hash: Cow::Borrowed(hash),
+                    },
+                    None => NetworkClientCertCheckMode::DisableCheck,
+                },
+                NetworkClientCertMode::FromCertAndPrivateKey { cert, private_key },
+            )
+            .with_timeout(timeout),
+            NetworkPlugins {
+                packet_plugins: Arc::new(vec![packet_plugin]),
+                connection_plugins: Default::default(),
+            },
+            addr,
+        )?;
+
+        Ok(Self {
+            network,
+            events,
+            has_events,
+
+            name,
+            state: LoadClientState::Connecting,
+
+            input_source,
+            input: Default::default(),
+            input_id: 0,
+            input_tick: None,
+            sent_input_ids: Default::default(),
+
+            handled_snap: None,
+            snap_ticks: Default::default(),
+            snap_acks: Default::default(),
+
+            chain_pool: MtPool::with_capacity(4),
+            inputs_pool: MtPool::with_capacity(4),
+
+            stats: Default::default(),
+            network_stats: None,
+            reported_network_stats: None,
+        })
+    }
+
+    fn send_ready(&mut self) {
+        self.state = LoadClientState::Ready;
+        self.input_tick = None;
+        self.handled_snap = None;
+        self.snap_ticks.clear();
+        self.snap_acks.clear();
+
+        let mut player_info = NetworkCharacterInfo::explicit_default();
+        player_info.name = NetworkString::new_lossy(self.name.as_str());
+        player_info.clan = NetworkString::new_lossy("load-test");
+        self.network
+            .send_unordered_to_server(&ClientToServerMessage::Ready(MsgClReady {
+                players: vec![MsgClAddLocalPlayer { player_info, id: 0 }],
+                rcon_secret: None,
+            }));
+    }
+
+    fn on_snapshot(
+        &mut self,
+        overhead_time: Duration,
+        size: usize,
+        diff_id: Option<u64>,
+        snap_id_diffed: u64,
+        game_monotonic_tick_diff: GameTickType,
+        as_diff: bool,
+    ) {
+        self.stats.add_snapshot(size, overhead_time);
+
+        // the snapshot content is never used, only the ids and ticks matter
+        let snap = match diff_id {
+            Some(diff_id) => match self.snap_ticks.get(&diff_id) {
+                Some(tick) => (diff_id + snap_id_diffed, tick + game_monotonic_tick_diff),
+                None => {
+                    log::debug!(
+                        "{}: dropped a snapshot, because the diffed snapshot was missing",
+                        self.name
+                    );
+                    return;
+                }
+            },
+            None => (snap_id_diffed, game_monotonic_tick_diff),
+        };
+        let (snap_id, monotonic_tick) = snap;
+
+        if self.handled_snap.is_none_or(|(id, _)| id < snap_id) {
+            if let Some((_, prev_tick)) = self.handled_snap {
+                self.stats.server_ticks += monotonic_tick.saturating_sub(prev_tick);
+            }
+            self.handled_snap = Some((snap_id, monotonic_tick));
+            if as_diff {
+                // this should be higher than the number of snapshots saved on the server
+                while self.snap_ticks.len() >= 50 {
+                    self.snap_ticks.pop_first();
+                }
+                self.snap_ticks.insert(snap_id, monotonic_tick);
+            }
+            self.snap_acks.push(MsgClSnapshotAck { snap_id });
+
+            // the input is at least for the next tick of the server
+            self.input_tick = Some(self.input_tick.unwrap_or_default().max(monotonic_tick + 1));
+        }
+    }
+
+    fn on_msg(&mut self, timestamp: Duration, msg: ServerToClientMessage<'static>) {
+        match msg {
+            ServerToClientMessage::ServerInfo { .. } | ServerToClientMessage::Load(_) => {
+                // no map is loaded, the client is ready right away
+                self.send_ready();
+            }
+            ServerToClientMessage::ReadyResponse(res) => match res {
+                MsgClReadyResponse::Success { joined_ids }
+                | MsgClReadyResponse::PartialSuccess { joined_ids, .. } => {
+                    match joined_ids.first() {
+                        Some((_, player_id)) => {
+                            self.state = LoadClientState::Ingame {
+                                player_id: *player_id,
+                            };
+                        }
+                        None => {
+                            self.state =
+                                LoadClientState::Disconnected("no player joined".to_string());
+                        }
+                    }
+                }
+                MsgClReadyResponse::Error { err, .. } => {
+                    self.state = LoadClientState::Disconnected(err.to_string());
+                }
+            },
+            ServerToClientMessage::Snapshot {
+                overhead_time,
+                snapshot,
+                diff_id,
+                snap_id_diffed,
+                game_monotonic_tick_diff,
+                as_diff,
+                input_ack,
+            } => {
+                for input in input_ack.iter() {
+                    if let Some(sent_at) = self.sent_input_ids.remove(&input.id) {
+                        self.stats.rtts.push(
+                            timestamp
+                                .saturating_sub(sent_at)
+                                .saturating_sub(input.logic_overhead),
+                        );
+                    }
+                }
+                self.on_snapshot(
+                    overhead_time,
+                    snapshot.len(),
+                    diff_id,
+                    snap_id_diffed,
+                    game_monotonic_tick_diff,
+                    as_diff,
+                );
+            }
+            _ => {
+                // ignore, nothing is rendered
+            }
+        }
+    }
+
+    /// Handles all events that arrived since the last call.
+    pub fn update(&mut self) {
+        if !self.has_events.load(std::sync::atomic::Ordering::Relaxed) {
+            return;
+        }
+        let mut events_guard = self.events.events.blocking_lock();
+        self.has_events
+            .store(false, std::sync::atomic::Ordering::Relaxed);
+        let events = std::mem::take(&mut *events_guard);
+        drop(events_guard);
+
+        for (_, timestamp, event) in events {
+            match event {
+                GameEvents::NetworkEvent(event) => match event {
+                    NetworkEvent::Connected {
+                        initial_network_stats,
+                        ..
+                    } => {
+                        self.state = LoadClientState::Connected;
+                        self.network_stats = Some(initial_network_stats);
+                    }
+                    NetworkEvent::Disconnected(reason) => {
+                        self.state = LoadClientState::Disconnected(reason.to_string());
+                    }
+                    NetworkEvent::ConnectingFailed(reason) => {
+                        self.state = LoadClientState::Disconnected(reason.to_string());
+                    }
+                    NetworkEvent::NetworkStats(stats) => {
+                        self.network_stats = Some(stats);
+                    }
+                },
+                GameEvents::NetworkMsg(msg) => self.on_msg(timestamp, msg),
+            }
+        }
+    }
+
+    /// Sends the input for the next tick together with
+    /// all snapshot acks.
+    pub fn send_input(&mut self, cur_time: Duration) -> anyhow::Result<()> {
+        let LoadClientState::Ingame { player_id } = self.state else {
+            return Ok(());
+        };
+        let Some(input_tick) = self.input_tick else {
+            // wait for the first snapshot to know the server's tick
+            return Ok(());
+        };
+        self.input_tick = Some(input_tick + 1);
+
+        let inp = self.input_source.next(&self.input.inp);
+        if inp != self.input.inp {
+            self.input.inp = inp;
+            self.input.inc_version();
+        }
+
+        // diff against the default input, so the server never
+        // needs to know an older input of this client.
+        let def = bincode::serde::encode_to_vec(
+            PlayerInputChainable::default(),
+            bincode::config::standard().with_fixed_int_encoding(),
+        )?;
+        let cur = bincode::serde::encode_to_vec(
+            PlayerInputChainable {
+                inp: self.input,
+                for_monotonic_tick: input_tick,
+            },
+            bincode::config::standard().with_fixed_int_encoding(),
+        )?;
+        let mut data = self.chain_pool.new();
+        bin_patch::diff_exact_size(&def, &cur, &mut data)?;
+
+        let mut inputs = self.inputs_pool.new();
+        inputs.insert(
+            player_id,
+            MsgClInputPlayerChain {
+                data,
+                diff_id: None,
+                as_diff: false,
+            },
+        );
+
+        // remove some old sent input timings
+        while self
+            .sent_input_ids
+            .first_key_value()
+            .is_some_and(|(_, sent_at)| cur_time.saturating_sub(*sent_at) > Duration::from_secs(3))
+        {
+            self.sent_input_ids.pop_first();
+        }
+        self.sent_input_ids.insert(self.input_id, cur_time);
+        self.network
+            .send_unordered_auto_to_server(&ClientToServerMessage::Inputs {
+                id: self.input_id,
+                inputs,
+                snap_ack: self.snap_acks.as_slice().into(),
+            });
+        self.snap_acks.clear();
+        self.input_id += 1;
+        self.stats.inputs_sent += 1;
+        Ok(())
+    }
+
+    /// Takes the stats since the last report.
+    pub fn take_stats(&mut self) -> (ClientStats, Option<NetworkStats>) {
+        let stats = std::mem::take(&mut self.stats);
+        let network_stats = self.network_stats.map(|cur| {
+            let prev = self.reported_network_stats.unwrap_or(NetworkStats {
+                ping: Duration::ZERO,
+                packets_lost: 0,
+                packets_sent: 0,
+                bytes_sent: 0,
+                bytes_recv: 0,
+                last_keep_alive_id: 0,
+            });
+            NetworkStats {
+                ping: cur.ping,
+                packets_lost: cur.packets_lost.saturating_sub(prev.packets_lost),
+                packets_sent: cur.packets_sent.saturating_sub(prev.packets_sent),
+                bytes_sent: cur.bytes_sent.saturating_sub(prev.bytes_sent),
+                bytes_recv: cur.bytes_recv.saturating_sub(prev.bytes_recv),
+                last_keep_alive_id: cur.last_keep_alive_id,
+            }
+        });
+        self.reported_network_stats = self.network_stats;
+        (stats, network_stats)
+    }
+
+    pub fn is_ingame(&self) -> bool {
+        matches!(self.state, LoadClientState::Ingame { .. })
+    }
+
+    pub fn is_disconnected(&self) -> bool {
+        matches!(self.state, LoadClientState::Disconnected(_))
+    }
+}
@@ -0,0 +1,243 @@
+mod client;
+mod stats;
+
+use std::{
+    path::PathBuf,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use base::{
+    hash::decode_hash,
+    system::{System, SystemTimeInterface},
+};
+use clap::Parser;
+use client::{InputSource, LoadClient, LoadClientState};
+use game_interface::types::input::CharacterInput;
+use math::math::Rng;
+use stats::Report;
+
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    /// address of the server, e.g. 127.0.0.1:8303
+    addr: String,
+    /// number of simulated clients
+    #[arg(short, long, default_value_t = 16)]
+    clients: usize,
+    /// ticks per second, the rate in which inputs are sent
+    #[arg(long, default_value_t = 50)]
+    tick_rate: u32,
+    /// how long to run the test in seconds, runs until killed if not set
+    #[arg(short, long)]
+    duration: Option<u64>,
+    /// delay in milliseconds between connecting two clients
+    #[arg(long, default_value_t = 50)]
+    connect_delay: u64,
+    /// json file containing a list of character inputs that are replayed
+    /// in a loop. Every client starts at a different offset.
+    /// Without this file the inputs are random.
+    #[arg(short, long)]
+    inputs: Option<PathBuf>,
+    /// hex encoded hash of the server's public key.
+    /// If not set, the certificate is not checked.
+    #[arg(long)]
+    server_cert_hash: Option<String>,
+    /// directory that contains the `client_send` & `server_send` zstd
+    /// dictionaries, must match the ones the server uses
+    #[arg(long)]
+    dict_dir: Option<PathBuf>,
+    /// network timeout in seconds
+    #[arg(long, default_value_t = 10)]
+    timeout: u64,
+    /// interval in seconds of the periodic report
+    #[arg(long, default_value_t = 5)]
+    report_interval: u64,
+    /// print the final report as json
+    #[arg(short, long, default_value_t = false)]
+    json: bool,
+    /// seed for the random inputs
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
+}
+
+fn collect_report(clients: &mut [LoadClient], total_clients: usize) -> Report {
+    let mut report = Report {
+        clients: total_clients,
+        ..Default::default()
+    };
+    for client in clients.iter_mut() {
+        if client.is_ingame() {
+            report.ingame += 1;
+        }
+        if client.is_disconnected() {
+            report.disconnected += 1;
+        }
+        let (stats, network_stats) = client.take_stats();
+        if let Some(network_stats) = network_stats {
+            report.add_client(&stats, network_stats);
+        }
+    }
+    report
+}
+
+fn main() {
+    let args = Args::parse();
+
+    if std::env::var("RUST_LOG").is_err() {
+        unsafe { std::env::set_var("RUST_LOG", "info") };
+    }
+    env_logger::init();
+
+    let sys = System::new();
+
+    let server_cert_hash = match args.server_cert_hash.as_deref().map(decode_hash) {
+        Some(Some(hash)) => Some(hash),
+        Some(None) => {
+            log::error!("the server certificate hash is not a valid hash");
+            std::process::exit(2);
+        }
+        None => None,
+    };
+
+    let dicts = match &args.dict_dir {
+        Some(dir) => {
+            match (
+                std::fs::read(dir.join("client_send")),
+                std::fs::read(dir.join("server_send")),
+            ) {
+                (Ok(client_send), Ok(server_send)) => Some((client_send, server_send)),
+                (Err(err), _) | (_, Err(err)) => {
+                    log::error!("loading the zstd dictionaries failed: {err}");
+                    std::process::exit(2);
+                }
+            }
+        }
+        None => None,
+    };
+
+    let recorded_inputs = match &args.inputs {
+        Some(path) => {
+            let inputs = std::fs::read(path)
+                .map_err(anyhow::Error::from)
+                .and_then(|file| {
+                    serde_json::from_slice::<Vec<CharacterInput>>(&file).map_err(Into::into)
+                });
+            match inputs {
+                Ok(inputs) if !inputs.is_empty() => Some(Arc::new(inputs)),
+                Ok(_) => {
+                    log::error!("the input file contains no inputs");
+                    std::process::exit(2);
+                }
+                Err(err) => {
+                    log::error!("loading the input file failed: {err}");
+                    std::process::exit(2);
+                }
+            }
+        }
+        None => None,
+    };
+
+    let tick_time = Duration::from_secs(1) / args.tick_rate.max(1);
+    let connect_delay = Duration::from_millis(args.connect_delay);
+    let report_interval = Duration::from_secs(args.report_interval.max(1));
+    let duration = args.duration.map(Duration::from_secs);
+
+    let mut clients: Vec<LoadClient> = Vec::with_capacity(args.clients);
+    let start = Instant::now();
+    let mut next_tick = start;
+    let mut last_report = start;
+    let mut total = Report::default();
+
+    log::info!(
+        "starting {} clients against {} at {} ticks per second",
+        args.clients,
+        args.addr,
+        args.tick_rate
+    );
+
+    loop {
+        let now = Instant::now();
+
+        // connect the clients one by one to not flood the server
+        while clients.len() < args.clients
+            && now.duration_since(start) >= connect_delay * clients.len() as u32
+        {
+            let index = clients.len();
+            let input_source = match &recorded_inputs {
+                Some(inputs) => InputSource::Recorded {
+                    index: (index * inputs.len()) / args.clients,
+                    inputs: inputs.clone(),
+                },
+                None => InputSource::Random {
+                    rng: Rng::new(args.seed.wrapping_add(index as u64)),
+                },
+            };
+            match LoadClient::new(
+                &sys,
+                &args.addr,
+                server_cert_hash,
+                dicts.clone(),
+                Duration::from_secs(args.timeout),
+                format!("load {index}"),
+                input_source,
+            ) {
+                Ok(client) => clients.push(client),
+                Err(err) => {
+                    log::error!("creating client {index} failed: {err}");
+                    std::process::exit(1);
+                }
+            }
+        }
+
+        let cur_time = sys.time_get();
+        for client in clients.iter_mut() {
+            let was_disconnected = client.is_disconnected();
+            client.update();
+            if let Err(err) = client.send_input(cur_time) {
+                log::error!("{}: sending input failed: {err}", client.name);
+            }
+            if !was_disconnected {
+                if let LoadClientState::Disconnected(reason) = &client.state {
+                    log::warn!("{} disconnected: {reason}", client.name);
+                }
+            }
+        }
+
+        if now.duration_since(last_report) >= report_interval {
+            let report = collect_report(&mut clients, args.clients);
+            report.print(now.duration_since(last_report));
+            total.merge(report);
+            last_report = now;
+        }
+
+        if duration.is_some_and(|duration| now.duration_since(start) >= duration)
+            || (clients.len() == args.clients && clients.iter().all(|c| c.is_disconnected()))
+        {
+            break;
+        }
+
+        next_tick += tick_time;
+        let now = Instant::now();
+        if next_tick > now {
+            std::thread::sleep(next_tick - now);
+        } else {
+            // the load test itself can't keep up, don't try to catch up
+            next_tick = now;
+        }
+    }
+
+    let now = Instant::now();
+    let report = collect_report(&mut clients, args.clients);
+    total.merge(report);
+
+    log::info!("final report:");
+    let elapsed = now.duration_since(start);
+    total.print(elapsed);
+    if args.json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&total.to_json(elapsed)).unwrap()
+        );
+    }
+}
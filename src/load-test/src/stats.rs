@@ -0,0 +1,185 @@
+use std::time::Duration;
+
+use network::network::event::NetworkStats;
+
+/// Statistics of a single simulated client since the last report.
+#[derive(Debug, Default, Clone)]
+pub struct ClientStats {
+    /// Round trip times of acknowledged inputs, without the
+    /// logic overhead of the server.
+    pub rtts: Vec<Duration>,
+
+    pub snapshots: u64,
+    pub snapshot_bytes: u64,
+    pub max_snapshot_bytes: u64,
+
+    /// Sum of the overhead the server reported for its snapshots,
+    /// which is how late the server calculated the tick
+    /// plus the simulation time itself.
+    pub server_overhead: Duration,
+    pub max_server_overhead: Duration,
+    /// Monotonic ticks the server advanced, seen by snapshots.
+    pub server_ticks: u64,
+
+    pub inputs_sent: u64,
+}
+
+impl ClientStats {
+    pub fn add_snapshot(&mut self, size: usize, overhead: Duration) {
+        self.snapshots += 1;
+        self.snapshot_bytes += size as u64;
+        self.max_snapshot_bytes = self.max_snapshot_bytes.max(size as u64);
+        self.server_overhead += overhead;
+        self.max_server_overhead = self.max_server_overhead.max(overhead);
+    }
+}
+
+/// Stats collected from all clients in a report interval.
+#[derive(Debug, Default)]
+pub struct Report {
+    pub clients: usize,
+    pub ingame: usize,
+    pub disconnected: usize,
+
+    pub rtts: Vec<Duration>,
+    pub snapshots: u64,
+    pub snapshot_bytes: u64,
+    pub max_snapshot_bytes: u64,
+    pub server_overhead: Duration,
+    pub max_server_overhead: Duration,
+    pub server_ticks: u64,
+    pub inputs_sent: u64,
+
+    pub bytes_sent: u64,
+    pub bytes_recv: u64,
+    pub packets_sent: u64,
+    pub packets_lost: u64,
+}
+
+impl Report {
+    pub fn add_client(&mut self, stats: &ClientStats, network: NetworkStats) {
+        self.rtts.extend(stats.rtts.iter().copied());
+        self.snapshots += stats.snapshots;
+        self.snapshot_bytes += stats.snapshot_bytes;
+        self.max_snapshot_bytes = self.max_snapshot_bytes.max(stats.max_snapshot_bytes);
+        self.server_overhead += stats.server_overhead;
+        self.max_server_overhead = self.max_server_overhead.max(stats.max_server_overhead);
+        self.server_ticks += stats.server_ticks;
+        self.inputs_sent += stats.inputs_sent;
+
+        self.bytes_sent += network.bytes_sent;
+        self.bytes_recv += network.bytes_recv;
+        self.packets_sent += network.packets_sent;
+        self.packets_lost += network.packets_lost;
+    }
+
+    /// Adds the stats of a later report.
+    /// The client counts are taken from the later report.
+    pub fn merge(&mut self, other: Report) {
+        self.clients = other.clients;
+        self.ingame = other.ingame;
+        self.disconnected = other.disconnected;
+
+        self.rtts.extend(other.rtts);
+        self.snapshots += other.snapshots;
+        self.snapshot_bytes += other.snapshot_bytes;
+        self.max_snapshot_bytes = self.max_snapshot_bytes.max(other.max_snapshot_bytes);
+        self.server_overhead += other.server_overhead;
+        self.max_server_overhead = self.max_server_overhead.max(other.max_server_overhead);
+        self.server_ticks += other.server_ticks;
+        self.inputs_sent += other.inputs_sent;
+
+        self.bytes_sent += other.bytes_sent;
+        self.bytes_recv += other.bytes_recv;
+        self.packets_sent += other.packets_sent;
+        self.packets_lost += other.packets_lost;
+    }
+
+    fn rtt_percentile(rtts: &[Duration], percentile: f64) -> Duration {
+        if rtts.is_empty() {
+            return Duration::ZERO;
+        }
+        let index = ((rtts.len() - 1) as f64 * percentile).round() as usize;
+        rtts[index]
+    }
+
+    pub fn to_json(&self, interval: Duration) -> serde_json::Value {
+        let mut rtts = self.rtts.clone();
+        rtts.sort();
+        let secs = interval.as_secs_f64().max(f64::EPSILON);
+        let ingame = self.ingame.max(1) as f64;
+        let snapshots = self.snapshots.max(1);
+
+        serde_json::json!({
+            "interval_secs": interval.as_secs_f64(),
+            "clients": self.clients,
+            "ingame": self.ingame,
+            "disconnected": self.disconnected,
+            "rtt_ms": {
+                "min": rtts.first().copied().unwrap_or_default().as_secs_f64() * 1000.0,
+                "avg": if rtts.is_empty() {
+                    0.0
+                } else {
+                    rtts.iter().sum::<Duration>().as_secs_f64() * 1000.0 / rtts.len() as f64
+                },
+                "p50": Self::rtt_percentile(&rtts, 0.5).as_secs_f64() * 1000.0,
+                "p99": Self::rtt_percentile(&rtts, 0.99).as_secs_f64() * 1000.0,
+                "max": rtts.last().copied().unwrap_or_default().as_secs_f64() * 1000.0,
+            },
+            "snapshots": {
+                "per_client_per_sec": self.snapshots as f64 / ingame / secs,
+                "avg_bytes": self.snapshot_bytes / snapshots,
+                "max_bytes": self.max_snapshot_bytes,
+            },
+            "server": {
+                "ticks_per_sec": self.server_ticks as f64 / ingame / secs,
+                "avg_overhead_ms": self.server_overhead.as_secs_f64() * 1000.0 / snapshots as f64,
+                "max_overhead_ms": self.max_server_overhead.as_secs_f64() * 1000.0,
+            },
+            "bandwidth": {
+                "sent_bytes_per_sec": self.bytes_sent as f64 / secs,
+                "recv_bytes_per_sec": self.bytes_recv as f64 / secs,
+                "recv_bytes_per_sec_per_client": self.bytes_recv as f64 / ingame / secs,
+                "packets_sent": self.packets_sent,
+                "packets_lost": self.packets_lost,
+            },
+            "inputs_sent": self.inputs_sent,
+        })
+    }
+
+    pub fn print(&self, interval: Duration) {
+        let json = self.to_json(interval);
+        log::info!(
+            "clients: {}/{} ingame ({} disconnected) | rtt ms avg {:.1} p99 {:.1} max {:.1} | \
+            snaps/s {:.1} avg {} B max {} B | server ticks/s {:.1} overhead avg {:.2} ms \
+            max {:.2} ms | recv {:.1} KiB/s sent {:.1} KiB/s lost {}",
+            self.ingame,
+            self.clients,
+            self.disconnected,
+            json["rtt_ms"]["avg"].as_f64().unwrap_or_default(),
+            json["rtt_ms"]["p99"].as_f64().unwrap_or_default(),
+            json["rtt_ms"]["max"].as_f64().unwrap_or_default(),
+            json["snapshots"]["per_client_per_sec"]
+                .as_f64()
+                .unwrap_or_default(),
+            json["snapshots"]["avg_bytes"].as_u64().unwrap_or_default(),
+            self.max_snapshot_bytes,
+            json["server"]["ticks_per_sec"].as_f64().unwrap_or_default(),
+            json["server"]["avg_overhead_ms"]
+                .as_f64()
+                .unwrap_or_default(),
+            json["server"]["max_overhead_ms"]
+                .as_f64()
+                .unwrap_or_default(),
+            json["bandwidth"]["recv_bytes_per_sec"]
+                .as_f64()
+                .unwrap_or_default()
+                / 1024.0,
+            json["bandwidth"]["sent_bytes_per_sec"]
+                .as_f64()
+                .unwrap_or_default()
+                / 1024.0,
+            self.packets_lost,
+        );
+    }
+}
@@ -0,0 +1,96 @@
+use std::{num::NonZeroUsize, sync::Arc};
+
+use anyhow::anyhow;
+use base_fs::filesys::FileSystem;
+use base_io::io::IoFileSys;
+use clap::Parser;
+use map::map::Map;
+use map_lint_lib::lint::{lint_map, LintOptions, LintSeverity};
+
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    /// file name of the map to check (.twmap)
+    file: String,
+    /// print the issues as json
+    #[arg(short, long, default_value_t = false)]
+    json: bool,
+    /// tile layers with more tiles are reported as oversized
+    #[arg(long, default_value_t = LintOptions::default().max_layer_tiles)]
+    max_layer_tiles: u64,
+    /// also fail on warnings
+    #[arg(long, default_value_t = false)]
+    deny_warnings: bool,
+}
+
+fn main() {
+    let args = Args::parse();
+
+    if std::env::var("RUST_LOG").is_err() {
+        unsafe { std::env::set_var("RUST_LOG", "info") };
+    }
+    env_logger::init();
+
+    let io = IoFileSys::new(|rt| {
+        Arc::new(
+            FileSystem::new(rt, "org", "", "DDNet-Rs-Alpha", "DDNet-Accounts")
+                .expect("map-lint needs the data directory to find the map."),
+        )
+    });
+
+    let thread_pool = Arc::new(
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(
+                std::thread::available_parallelism()
+                    .unwrap_or(NonZeroUsize::new(2).unwrap())
+                    .get(),
+            )
+            .build()
+            .unwrap(),
+    );
+
+    let fs = io.fs.clone();
+    let file = args.file.clone();
+    let map = io.rt.spawn(async move {
+        let map = fs
+            .read_file(file.as_ref())
+            .await
+            .map_err(|err| anyhow!("loading map file failed: {err}"))?;
+        let map = Map::read(&map, &thread_pool)
+            .map_err(|err| anyhow!("loading map from file failed: {err}"))?;
+
+        Ok(map)
+    });
+    let map = match map.get_storage() {
+        Ok(map) => map,
+        Err(err) => {
+            log::error!("{err}");
+            std::process::exit(2);
+        }
+    };
+
+    let report = lint_map(
+        &map,
+        &LintOptions {
+            max_layer_tiles: args.max_layer_tiles,
+        },
+    );
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&report).unwrap());
+    } else {
+        for issue in &report.issues {
+            println!("{issue}");
+        }
+        println!(
+            "{} error(s), {} warning(s), {} info(s)",
+            report.count(LintSeverity::Error),
+            report.count(LintSeverity::Warning),
+            report.count(LintSeverity::Info),
+        );
+    }
+
+    if report.has_errors() || (args.deny_warnings && report.count(LintSeverity::Warning) > 0) {
+        std::process::exit(1);
+    }
+}
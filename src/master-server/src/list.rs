@@ -0,0 +1,374 @@
+//! Serves the aggregated server list over HTTP, so no external web server
+//! is needed.
+//!
+//! The complete list is encoded once whenever it changes, filtered lists
+//! and single server lookups are built on request.
+
+use std::io::Write as _;
+use std::net::SocketAddr;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use master_server_types::addr::Addr;
+use master_server_types::locations::Location;
+use master_server_types::servers::SerializedServer;
+use serde::de::IgnoredAny;
+use serde::Deserialize;
+use serde_json as json;
+use sha2::Digest;
+use sha2::Sha512_256 as SecureHash;
+use warp::http::header;
+use warp::http::Response;
+use warp::http::StatusCode;
+
+/// Quality of the complete list, which is only compressed when it changes.
+const BROTLI_QUALITY_FULL: u32 = 9;
+/// Quality of filtered lists, which are compressed on every request.
+const BROTLI_QUALITY_FILTERED: u32 = 4;
+
+/// The parts of the server info that can be filtered.
+#[derive(Default, Deserialize)]
+#[serde(default)]
+struct FilterInfo {
+    game_type: String,
+    version: String,
+    #[serde(alias = "clients")]
+    players: Vec<IgnoredAny>,
+}
+
+struct Entry {
+    addresses: Vec<Addr>,
+    location: Option<Location>,
+    game_type: String,
+    version: String,
+    player_count: usize,
+    /// The serialized [`SerializedServer`].
+    json: String,
+}
+
+/// Query parameters of the server list route. All filters are optional.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct ListQuery {
+    /// Case insensitive game type, e.g. `ctf`.
+    game_type: Option<String>,
+    /// Location prefix, e.g. `eu` or `as:cn`.
+    location: Option<String>,
+    has_players: Option<bool>,
+    /// Version prefix, e.g. `0.1`.
+    version: Option<String>,
+}
+
+impl ListQuery {
+    fn is_empty(&self) -> bool {
+        self.game_type.is_none()
+            && self.location.is_none()
+            && self.has_players.is_none()
+            && self.version.is_none()
+    }
+
+    fn matches(&self, entry: &Entry) -> bool {
+        self.game_type
+            .as_ref()
+            .is_none_or(|game_type| entry.game_type.eq_ignore_ascii_case(game_type))
+            && self.location.as_ref().is_none_or(|location| {
+                entry
+                    .location
+                    .is_some_and(|l| l.starts_with(location.as_str()))
+            })
+            && self
+                .has_players
+                .is_none_or(|has_players| has_players == (entry.player_count > 0))
+            && self
+                .version
+                .as_ref()
+                .is_none_or(|version| entry.version.starts_with(version.as_str()))
+    }
+}
+
+/// Query parameters of the single server lookup.
+#[derive(Debug, Deserialize)]
+pub struct LookupQuery {
+    /// Either a full address like `ddrs-0.1+quic://127.0.0.1:8303`
+    /// or only `ip:port`, which matches any protocol.
+    address: String,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Encoding {
+    Identity,
+    Gzip,
+    Brotli,
+}
+
+impl Encoding {
+    /// Picks the best supported encoding of an `Accept-Encoding` header.
+    fn negotiate(accept_encoding: Option<&str>) -> Encoding {
+        let mut gzip = false;
+        for coding in accept_encoding.unwrap_or_default().split(',') {
+            let mut parts = coding.split(';');
+            let name = parts.next().unwrap_or_default().trim();
+            let disabled = parts.any(|param| {
+                param
+                    .trim()
+                    .strip_prefix("q=")
+                    .and_then(|q| q.parse::<f32>().ok())
+                    .is_some_and(|q| q <= 0.0)
+            });
+            if disabled {
+                continue;
+            }
+            match name {
+                "br" => return Encoding::Brotli,
+                "gzip" => gzip = true,
+                _ => {}
+            }
+        }
+        if gzip {
+            Encoding::Gzip
+        } else {
+            Encoding::Identity
+        }
+    }
+    fn header_value(self) -> Option<&'static str> {
+        match self {
+            Encoding::Identity => None,
+            Encoding::Gzip => Some("gzip"),
+            Encoding::Brotli => Some("br"),
+        }
+    }
+}
+
+fn gzip(data: &[u8]) -> Vec<u8> {
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(data).unwrap();
+    encoder.finish().unwrap()
+}
+
+fn brotli(data: &[u8], quality: u32) -> Vec<u8> {
+    let mut encoder = brotli::CompressorWriter::new(Vec::new(), 4096, quality, 22);
+    encoder.write_all(data).unwrap();
+    encoder.into_inner()
+}
+
+fn etag_for(data: &[u8]) -> String {
+    let mut hash = SecureHash::new();
+    hash.update(data);
+    hex::encode(&hash.finalize()[..16])
+}
+
+/// Whether one of the entity tags of an `If-None-Match` header matches.
+///
+/// The encoding suffix is ignored, all encodings represent the same list.
+fn etag_matches(if_none_match: Option<&str>, etag: &str) -> bool {
+    if_none_match.unwrap_or_default().split(',').any(|tag| {
+        let tag = tag.trim();
+        let tag = tag.strip_prefix("W/").unwrap_or(tag).trim_matches('"');
+        tag == "*" || tag.split('-').next() == Some(etag)
+    })
+}
+
+/// A response body in all supported encodings.
+struct Encoded {
+    etag: String,
+    identity: Vec<u8>,
+    gzip: Vec<u8>,
+    brotli: Vec<u8>,
+}
+
+impl Encoded {
+    fn new(body: Vec<u8>, brotli_quality: u32) -> Encoded {
+        Encoded {
+            etag: etag_for(&body),
+            gzip: gzip(&body),
+            brotli: brotli(&body, brotli_quality),
+            identity: body,
+        }
+    }
+}
+
+fn respond(
+    etag: &str,
+    body: impl FnOnce(Encoding) -> Vec<u8>,
+    if_none_match: Option<&str>,
+    accept_encoding: Option<&str>,
+) -> Response<Vec<u8>> {
+    let encoding = Encoding::negotiate(accept_encoding);
+    let etag_value = match encoding.header_value() {
+        Some(suffix) => format!("\"{}-{}\"", etag, suffix),
+        None => format!("\"{}\"", etag),
+    };
+    let mut builder = Response::builder()
+        .header(header::ETAG, etag_value)
+        .header(header::VARY, "Accept-Encoding")
+        .header(header::CACHE_CONTROL, "public, max-age=1");
+    if etag_matches(if_none_match, etag) {
+        return builder
+            .status(StatusCode::NOT_MODIFIED)
+            .body(Vec::new())
+            .unwrap();
+    }
+    if let Some(encoding) = encoding.header_value() {
+        builder = builder.header(header::CONTENT_ENCODING, encoding);
+    }
+    builder
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(body(encoding))
+        .unwrap()
+}
+
+fn error_response(status: StatusCode, msg: &str) -> Response<Vec<u8>> {
+    Response::builder()
+        .status(status)
+        .header(header::CONTENT_TYPE, "application/json")
+        .body((json::json!({ "error": msg }).to_string() + "\n").into_bytes())
+        .unwrap()
+}
+
+/// A snapshot of the aggregated server list.
+pub struct ServerList {
+    entries: Vec<Entry>,
+    full: Encoded,
+}
+
+impl ServerList {
+    pub fn empty() -> ServerList {
+        ServerList::new(&[], br#"{"servers":[]}"#.to_vec())
+    }
+
+    /// `servers` must be the servers of the already serialized `full` list.
+    pub fn new(servers: &[SerializedServer], full: Vec<u8>) -> ServerList {
+        let entries = servers
+            .iter()
+            .map(|server| {
+                let info: FilterInfo = json::from_str(server.info.get()).unwrap_or_default();
+                Entry {
+                    addresses: server.addresses.to_vec(),
+                    location: server.location,
+                    game_type: info.game_type,
+                    version: info.version,
+                    player_count: info.players.len(),
+                    json: json::to_string(server).unwrap(),
+                }
+            })
+            .collect();
+        ServerList {
+            entries,
+            full: Encoded::new(full, BROTLI_QUALITY_FULL),
+        }
+    }
+
+    /// Whether the list would be served with the same content.
+    pub fn is_same(&self, full: &[u8]) -> bool {
+        self.full.identity == full
+    }
+
+    pub fn respond_list(
+        &self,
+        query: &ListQuery,
+        if_none_match: Option<&str>,
+        accept_encoding: Option<&str>,
+    ) -> Response<Vec<u8>> {
+        if query.is_empty() {
+            let full = &self.full;
+            return respond(
+                &full.etag,
+                |encoding| match encoding {
+                    Encoding::Identity => full.identity.clone(),
+                    Encoding::Gzip => full.gzip.clone(),
+                    Encoding::Brotli => full.brotli.clone(),
+                },
+                if_none_match,
+                accept_encoding,
+            );
+        }
+        let mut body = String::from(r#"{"servers":["#);
+        for (i, entry) in self
+            .entries
+            .iter()
+            .filter(|entry| query.matches(entry))
+            .enumerate()
+        {
+            if i != 0 {
+                body.push(',');
+            }
+            body.push_str(&entry.json);
+        }
+        body.push_str("]}");
+        let body = body.into_bytes();
+        respond(
+            &etag_for(&body),
+            |encoding| match encoding {
+                Encoding::Identity => body,
+                Encoding::Gzip => gzip(&body),
+                Encoding::Brotli => brotli(&body, BROTLI_QUALITY_FILTERED),
+            },
+            if_none_match,
+            accept_encoding,
+        )
+    }
+
+    pub fn respond_lookup(
+        &self,
+        query: &LookupQuery,
+        if_none_match: Option<&str>,
+        accept_encoding: Option<&str>,
+    ) -> Response<Vec<u8>> {
+        let matches: Box<dyn Fn(&Addr) -> bool> = if let Ok(addr) = Addr::from_str(&query.address) {
+            Box::new(move |a| *a == addr)
+        } else if let Ok(addr) = SocketAddr::from_str(&query.address) {
+            Box::new(move |a| a.to_socket_addr() == addr)
+        } else {
+            return error_response(
+                StatusCode::BAD_REQUEST,
+                "invalid address, expected e.g. ddrs-0.1+quic://127.0.0.1:8303 or 127.0.0.1:8303",
+            );
+        };
+        let Some(entry) = self
+            .entries
+            .iter()
+            .find(|entry| entry.addresses.iter().any(&matches))
+        else {
+            return error_response(StatusCode::NOT_FOUND, "server not found");
+        };
+        let body = entry.json.as_bytes();
+        respond(
+            &etag_for(body),
+            |encoding| match encoding {
+                Encoding::Identity => body.to_vec(),
+                Encoding::Gzip => gzip(body),
+                Encoding::Brotli => brotli(body, BROTLI_QUALITY_FILTERED),
+            },
+            if_none_match,
+            accept_encoding,
+        )
+    }
+}
+
+pub type SharedServerList = Arc<Mutex<Arc<ServerList>>>;
+
+#[cfg(test)]
+mod test {
+    use super::etag_matches;
+    use super::Encoding;
+
+    #[test]
+    fn negotiate_encoding() {
+        assert!(Encoding::negotiate(None) == Encoding::Identity);
+        assert!(Encoding::negotiate(Some("gzip, deflate")) == Encoding::Gzip);
+        assert!(Encoding::negotiate(Some("gzip, deflate, br")) == Encoding::Brotli);
+        assert!(Encoding::negotiate(Some("br;q=0, gzip;q=0.5")) == Encoding::Gzip);
+    }
+
+    #[test]
+    fn etag_match() {
+        assert!(etag_matches(Some("\"abc\""), "abc"));
+        assert!(etag_matches(Some("W/\"abc-br\""), "abc"));
+        assert!(etag_matches(Some("\"def\", \"abc-gzip\""), "abc"));
+        assert!(etag_matches(Some("*"), "abc"));
+        assert!(!etag_matches(Some("\"abcd\""), "abc"));
+        assert!(!etag_matches(None, "abc"));
+    }
+}
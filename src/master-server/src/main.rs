@@ -9,6 +9,7 @@ use clap::value_t_or_exit;
 use clap::App;
 use clap::Arg;
 use headers::HeaderMapExt as _;
+use list::{ListQuery, LookupQuery, ServerList, SharedServerList};
 use master_server_types::{
     addr::{Addr, Protocol, RegisterAddr},
     locations::{Location, Locations},
@@ -16,6 +17,7 @@ use master_server_types::{
     servers::{SerializedServer, SerializedServers, Server},
 };
 use rand::random;
+use replication::Replication;
 use serde::Deserialize;
 use serde::Serialize;
 use serde_json as json;
@@ -49,6 +51,9 @@ use warp::Filter;
 #[macro_use]
 extern crate log;
 
+mod list;
+mod replication;
+
 const SERVER_TIMEOUT_SECONDS: u64 = 30;
 
 type ShortString = ArrayString<[u8; 64]>;
@@ -109,7 +114,7 @@ impl Timekeeper {
     }
 }
 
-#[derive(Deserialize, Serialize)]
+#[derive(Clone, Deserialize, Serialize)]
 struct DumpServer<'a> {
     pub info_serial: i64,
     pub info: Cow<'a, json::value::RawValue>,
@@ -124,7 +129,7 @@ impl<'a> From<&'a Server> for DumpServer<'a> {
     }
 }
 
-#[derive(Deserialize, Serialize)]
+#[derive(Clone, Deserialize, Serialize)]
 struct Dump<'a> {
     pub now: Timestamp,
     // Use `BTreeMap`s so the serialization is stable.
@@ -475,7 +480,9 @@ async fn handle_periodic_writeout(
     dumps_dir: Option<String>,
     dump_filename: Option<String>,
     addresses_filename: Option<String>,
-    servers_filename: String,
+    servers_filename: Option<String>,
+    server_list: SharedServerList,
+    replication: Option<Arc<Replication>>,
     timekeeper: Timekeeper,
 ) {
     let dump_filename = dump_filename.map(|f| {
@@ -486,7 +493,10 @@ async fn handle_periodic_writeout(
         let tmp = format!("{}.tmp.{}", f, process::id());
         (f, tmp)
     });
-    let servers_filename_temp = &format!("{}.tmp.{}", servers_filename, process::id());
+    let servers_filename = servers_filename.map(|f| {
+        let tmp = format!("{}.tmp.{}", f, process::id());
+        (f, tmp)
+    });
 
     let start = Instant::now();
     let mut iteration = 0;
@@ -505,10 +515,13 @@ async fn handle_periodic_writeout(
                 .unwrap();
         }
         {
-            let other_dumps = match &dumps_dir {
+            let mut other_dumps = match &dumps_dir {
                 Some(dir) => read_dump_dir(Path::new(dir), timekeeper).await,
                 None => Vec::new(),
             };
+            if let Some(replication) = &replication {
+                other_dumps.extend(replication.current_peer_dumps(now));
+            }
             if let Some((filename, filename_temp)) = &addresses_filename {
                 let mut non_backcompat_addrs: Vec<Addr> = Vec::new();
                 non_backcompat_addrs.extend(servers.addresses.keys());
@@ -549,11 +562,28 @@ async fn handle_periodic_writeout(
                     SerializedServer::new(s, location)
                 }));
                 serialized.servers.sort_by_key(|s| s.addresses);
-                json::to_string(&serialized).unwrap()
+                let json = json::to_string(&serialized).unwrap();
+                let is_same = server_list
+                    .lock()
+                    .unwrap_or_else(|poison| poison.into_inner())
+                    .is_same(json.as_bytes());
+                if !is_same {
+                    // Encode outside of the lock, compressing takes a while.
+                    let list = Arc::new(ServerList::new(
+                        &serialized.servers,
+                        json.clone().into_bytes(),
+                    ));
+                    *server_list
+                        .lock()
+                        .unwrap_or_else(|poison| poison.into_inner()) = list;
+                }
+                json
             };
-            overwrite_atomically(&servers_filename, servers_filename_temp, json.as_bytes())
-                .await
-                .unwrap();
+            if let Some((filename, filename_temp)) = &servers_filename {
+                overwrite_atomically(filename, filename_temp, json.as_bytes())
+                    .await
+                    .unwrap();
+            }
         }
         let elapsed = start.elapsed();
         if elapsed.as_secs() <= iteration {
@@ -841,11 +871,41 @@ async fn main() {
             .value_name("DUMP_DIR")
             .help("Read dumps from other mastersrv instances from the specified directory (looking only at *.json files).")
         )
+        .arg(Arg::with_name("replication-secret")
+            .long("replication-secret")
+            .value_name("SECRET")
+            .help("Shared secret of all replicating mastersrv instances. Enables the replication endpoints.")
+        )
+        .arg(Arg::with_name("instance-name")
+            .long("instance-name")
+            .value_name("NAME")
+            .help("Unique name of this instance, used to identify pushed dumps. Random by default.")
+        )
+        .arg(Arg::with_name("push-peer")
+            .long("push-peer")
+            .value_name("URL")
+            .multiple(true)
+            .number_of_values(1)
+            .requires("replication-secret")
+            .help("Push the servers registered at this instance to the mastersrv instance at URL each second.")
+        )
+        .arg(Arg::with_name("pull-peer")
+            .long("pull-peer")
+            .value_name("URL")
+            .multiple(true)
+            .number_of_values(1)
+            .requires("replication-secret")
+            .help("Pull the servers registered at the mastersrv instance at URL each second.")
+        )
         .arg(Arg::with_name("out")
             .long("out")
             .value_name("OUT")
             .default_value("servers.json")
             .help("Output file for the aggregated server list in a DDNet 15.5+ compatible format.")
+        )
+        .arg(Arg::with_name("no-out")
+            .long("no-out")
+            .help("Don't write the aggregated server list to a file, only serve it on GET /ddnet/15/servers.json.")
         );
 
     if cfg!(unix) {
@@ -896,10 +956,39 @@ async fn main() {
         }
     }
     let servers = Arc::new(Mutex::new(servers));
+    let server_list: SharedServerList = Arc::new(Mutex::new(Arc::new(ServerList::empty())));
     let socket = Arc::new(tokio::net::UdpSocket::bind("[::]:0").await.unwrap());
     let socket = AssertUnwindSafe(socket);
 
+    let replication = matches.value_of("replication-secret").map(|secret| {
+        let peers = |name| {
+            matches
+                .values_of(name)
+                .map(|peers| peers.map(|s| s.to_owned()).collect())
+                .unwrap_or_default()
+        };
+        Arc::new(Replication {
+            secret: secret.to_owned(),
+            instance: matches
+                .value_of("instance-name")
+                .map(|s| s.to_owned())
+                .unwrap_or_else(|| format!("{:016x}", random::<u64>())),
+            push_peers: peers("push-peer"),
+            pull_peers: peers("pull-peer"),
+            peer_dumps: Default::default(),
+        })
+    });
+
     let task_reseed = tokio::spawn(handle_periodic_reseed(challenger.clone()));
+    let task_replication = tokio::spawn({
+        let replication = replication.clone();
+        let servers = servers.clone();
+        async move {
+            if let Some(replication) = replication {
+                replication::handle_periodic_replication(replication, servers, timekeeper).await;
+            }
+        }
+    });
     let task_writeout = tokio::spawn(handle_periodic_writeout(
         servers.clone(),
         matches.value_of("read-dump-dir").map(|s| s.to_owned()),
@@ -908,7 +997,9 @@ async fn main() {
             .map(|s| s.to_owned())
             .or(read_write_dump),
         matches.value_of("write-addresses").map(|s| s.to_owned()),
-        matches.value_of("out").unwrap().to_owned(),
+        (!matches.is_present("no-out")).then(|| matches.value_of("out").unwrap().to_owned()),
+        server_list.clone(),
+        replication.clone(),
         timekeeper,
     ));
 
@@ -955,6 +1046,44 @@ async fn main() {
             .body(json::to_string(&body).unwrap() + "\n")
     }
 
+    let replicate_route = {
+        let replication = replication.clone();
+        warp::path!("ddnet" / "15" / "replicate")
+            .and(warp::post())
+            .and(warp::header::optional::<String>("authorization"))
+            .and(warp::header::optional::<String>(
+                replication::INSTANCE_HEADER,
+            ))
+            .and(warp::body::content_length_limit(replication::MAX_DUMP_SIZE))
+            .and(warp::body::bytes())
+            .map(
+                move |authorization: Option<String>,
+                      instance: Option<String>,
+                      body: bytes::Bytes| match &replication {
+                    Some(replication) => replication.handle_push(
+                        authorization.as_deref(),
+                        instance.as_deref(),
+                        &body,
+                        timekeeper,
+                    ),
+                    None => replication::disabled_response(),
+                },
+            )
+    };
+    let dump_route = {
+        let replication = replication.clone();
+        let servers = servers.clone();
+        warp::path!("ddnet" / "15" / "dump")
+            .and(warp::get())
+            .and(warp::header::optional::<String>("authorization"))
+            .map(move |authorization: Option<String>| match &replication {
+                Some(replication) => {
+                    replication.handle_pull(authorization.as_deref(), &servers, timekeeper)
+                }
+                None => replication::disabled_response(),
+            })
+    };
+
     let register = warp::path!("ddnet" / "15" / "register")
         .and(warp::post())
         .and(warp::header::headers_cloned())
@@ -987,9 +1116,56 @@ async fn main() {
                     }
                 })
             },
-        )
+        );
+
+    let current_list = move || {
+        server_list
+            .lock()
+            .unwrap_or_else(|poison| poison.into_inner())
+            .clone()
+    };
+    let list_route = {
+        let current_list = current_list.clone();
+        warp::path!("ddnet" / "15" / "servers.json")
+            .and(warp::get())
+            .and(warp::query::<ListQuery>())
+            .and(warp::header::optional::<String>("if-none-match"))
+            .and(warp::header::optional::<String>("accept-encoding"))
+            .map(
+                move |query: ListQuery,
+                      if_none_match: Option<String>,
+                      accept_encoding: Option<String>| {
+                    current_list().respond_list(
+                        &query,
+                        if_none_match.as_deref(),
+                        accept_encoding.as_deref(),
+                    )
+                },
+            )
+    };
+    let lookup_route = warp::path!("ddnet" / "15" / "server")
+        .and(warp::get())
+        .and(warp::query::<LookupQuery>())
+        .and(warp::header::optional::<String>("if-none-match"))
+        .and(warp::header::optional::<String>("accept-encoding"))
+        .map(
+            move |query: LookupQuery,
+                  if_none_match: Option<String>,
+                  accept_encoding: Option<String>| {
+                current_list().respond_lookup(
+                    &query,
+                    if_none_match.as_deref(),
+                    accept_encoding.as_deref(),
+                )
+            },
+        );
+    let routes = register
+        .or(list_route)
+        .or(lookup_route)
+        .or(replicate_route)
+        .or(dump_route)
         .recover(recover);
-    let server = warp::serve(register);
+    let server = warp::serve(routes);
 
     let task_server = if let Some(path) = listen_unix {
         #[cfg(unix)]
@@ -1009,8 +1185,8 @@ async fn main() {
         tokio::spawn(server.run(listen_address))
     };
 
-    match tokio::try_join!(task_reseed, task_writeout, task_server) {
-        Ok(((), (), ())) => unreachable!(),
+    match tokio::try_join!(task_reseed, task_writeout, task_replication, task_server) {
+        Ok(((), (), (), ())) => unreachable!(),
         Err(e) => panic::resume_unwind(e.into_panic()),
     }
 }
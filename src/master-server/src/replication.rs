@@ -0,0 +1,310 @@
+//! Replication of the registered servers between master server instances.
+//!
+//! Every instance only replicates the servers that registered at itself,
+//! so the instances have to be fully meshed: each instance either pushes
+//! its dump to every other instance or pulls theirs.
+//! Received dumps are merged like the dumps of `--read-dump-dir`, so the
+//! newest registration of an address wins.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use serde_json as json;
+use tokio::time;
+use warp::http::header;
+use warp::http::Response;
+use warp::http::StatusCode;
+
+use crate::Dump;
+use crate::Servers;
+use crate::Timekeeper;
+use crate::SERVER_TIMEOUT_SECONDS;
+
+/// Header that names the instance that pushed a dump.
+pub const INSTANCE_HEADER: &str = "Instance";
+/// The maximum size of a pushed dump.
+pub const MAX_DUMP_SIZE: u64 = 64 * 1024 * 1024;
+
+/// The latest dump of every peer, by instance name or peer URL.
+pub type PeerDumps = Arc<Mutex<HashMap<String, Dump<'static>>>>;
+
+pub struct Replication {
+    pub secret: String,
+    pub instance: String,
+    /// Base URLs of instances this instance pushes its dump to.
+    pub push_peers: Vec<String>,
+    /// Base URLs of instances this instance pulls the dump from.
+    pub pull_peers: Vec<String>,
+    pub peer_dumps: PeerDumps,
+}
+
+/// Compares the secret in constant time to not leak
+/// information about the secret through timings.
+fn secret_eq(secret: &str, input: &str) -> bool {
+    let secret = secret.as_bytes();
+    let input = input.as_bytes();
+    secret.len() == input.len()
+        && secret
+            .iter()
+            .zip(input.iter())
+            .fold(0, |acc, (a, b)| acc | (a ^ b))
+            == 0
+}
+
+fn is_authorized(secret: &str, authorization: Option<&str>) -> bool {
+    authorization
+        .and_then(|auth| auth.strip_prefix("Bearer "))
+        .is_some_and(|token| secret_eq(secret, token))
+}
+
+fn json_response(status: StatusCode, body: String) -> Response<String> {
+    Response::builder()
+        .status(status)
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(body + "\n")
+        .unwrap()
+}
+
+fn error_response(status: StatusCode, msg: &str) -> Response<String> {
+    json_response(status, json::json!({ "error": msg }).to_string())
+}
+
+/// Response of the replication endpoints if no secret is configured.
+pub fn disabled_response() -> Response<String> {
+    error_response(StatusCode::NOT_FOUND, "replication is not enabled")
+}
+
+fn read_dump(body: &[u8], timekeeper: Timekeeper) -> Result<Dump<'static>, json::Error> {
+    let mut dump: Dump = json::from_slice(body)?;
+    // The timestamps are relative to the peer's clock,
+    // treat the dump as if it was written just now.
+    dump.fixup_timestamps(timekeeper.now());
+    Ok(dump)
+}
+
+fn own_dump(servers: &Mutex<Servers>, timekeeper: Timekeeper) -> String {
+    let now = timekeeper.now();
+    let servers = servers.lock().unwrap_or_else(|poison| poison.into_inner());
+    json::to_string(&Dump::new(now, &servers)).unwrap()
+}
+
+impl Replication {
+    /// Handles a dump pushed by a peer.
+    pub fn handle_push(
+        &self,
+        authorization: Option<&str>,
+        instance: Option<&str>,
+        body: &[u8],
+        timekeeper: Timekeeper,
+    ) -> Response<String> {
+        if !is_authorized(&self.secret, authorization) {
+            return error_response(StatusCode::UNAUTHORIZED, "invalid replication secret");
+        }
+        let Some(instance) = instance.filter(|instance| !instance.is_empty()) else {
+            return error_response(
+                StatusCode::BAD_REQUEST,
+                &format!("missing {} header", INSTANCE_HEADER),
+            );
+        };
+        if instance == self.instance {
+            return error_response(StatusCode::BAD_REQUEST, "instance pushed to itself");
+        }
+        match read_dump(body, timekeeper) {
+            Ok(dump) => {
+                self.peer_dumps
+                    .lock()
+                    .unwrap_or_else(|poison| poison.into_inner())
+                    .insert(instance.to_owned(), dump);
+                json_response(StatusCode::OK, r#"{"status":"success"}"#.to_owned())
+            }
+            Err(e) => error_response(StatusCode::BAD_REQUEST, &format!("invalid dump: {}", e)),
+        }
+    }
+
+    /// Answers a peer that pulls the dump of this instance.
+    pub fn handle_pull(
+        &self,
+        authorization: Option<&str>,
+        servers: &Mutex<Servers>,
+        timekeeper: Timekeeper,
+    ) -> Response<String> {
+        if !is_authorized(&self.secret, authorization) {
+            return error_response(StatusCode::UNAUTHORIZED, "invalid replication secret");
+        }
+        json_response(StatusCode::OK, own_dump(servers, timekeeper))
+    }
+
+    /// Dumps of all peers that are not outdated.
+    pub fn current_peer_dumps(&self, now: crate::Timestamp) -> Vec<Dump<'static>> {
+        let mut peer_dumps = self
+            .peer_dumps
+            .lock()
+            .unwrap_or_else(|poison| poison.into_inner());
+        peer_dumps.retain(|_, dump| dump.now >= now.minus_seconds(SERVER_TIMEOUT_SECONDS));
+        peer_dumps.values().cloned().collect()
+    }
+}
+
+async fn push(
+    client: &reqwest::Client,
+    replication: &Replication,
+    peer: &str,
+    dump: String,
+) -> Result<(), reqwest::Error> {
+    client
+        .post(format!("{}/ddnet/15/replicate", peer.trim_end_matches('/')))
+        .bearer_auth(&replication.secret)
+        .header(INSTANCE_HEADER, &replication.instance)
+        .header("Content-Type", "application/json")
+        .body(dump)
+        .send()
+        .await?
+        .error_for_status()?;
+    Ok(())
+}
+
+async fn pull(
+    client: &reqwest::Client,
+    replication: &Replication,
+    peer: &str,
+    timekeeper: Timekeeper,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let body = client
+        .get(format!("{}/ddnet/15/dump", peer.trim_end_matches('/')))
+        .bearer_auth(&replication.secret)
+        .send()
+        .await?
+        .error_for_status()?
+        .bytes()
+        .await?;
+    let dump = read_dump(&body, timekeeper)?;
+    replication
+        .peer_dumps
+        .lock()
+        .unwrap_or_else(|poison| poison.into_inner())
+        .insert(peer.to_owned(), dump);
+    Ok(())
+}
+
+pub async fn handle_periodic_replication(
+    replication: Arc<Replication>,
+    servers: Arc<Mutex<Servers>>,
+    timekeeper: Timekeeper,
+) {
+    if replication.push_peers.is_empty() && replication.pull_peers.is_empty() {
+        return;
+    }
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(5))
+        .build()
+        .unwrap();
+    let mut interval = time::interval(Duration::from_secs(1));
+    interval.set_missed_tick_behavior(time::MissedTickBehavior::Delay);
+    loop {
+        interval.tick().await;
+        // Talk to all peers concurrently, so a dead peer doesn't delay the others.
+        let mut tasks = Vec::new();
+        if !replication.push_peers.is_empty() {
+            let dump = own_dump(&servers, timekeeper);
+            for peer in replication.push_peers.iter().cloned() {
+                let (client, replication, dump) =
+                    (client.clone(), replication.clone(), dump.clone());
+                tasks.push(tokio::spawn(async move {
+                    if let Err(e) = push(&client, &replication, &peer, dump).await {
+                        warn!("pushing dump to {} failed: {}", peer, e);
+                    }
+                }));
+            }
+        }
+        for peer in replication.pull_peers.iter().cloned() {
+            let (client, replication) = (client.clone(), replication.clone());
+            tasks.push(tokio::spawn(async move {
+                if let Err(e) = pull(&client, &replication, &peer, timekeeper).await {
+                    warn!("pulling dump from {} failed: {}", peer, e);
+                }
+            }));
+        }
+        for task in tasks {
+            let _ = task.await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::is_authorized;
+    use super::Replication;
+    use crate::Servers;
+    use crate::Timekeeper;
+    use crate::Timestamp;
+    use crate::SERVER_TIMEOUT_SECONDS;
+    use warp::http::StatusCode;
+
+    const DUMP: &str = r#"{
+        "now": 1000,
+        "addresses": {
+            "tw-0.6+udp://127.0.0.1:8303": {
+                "kind": "mastersrv",
+                "ping_time": 1000,
+                "secret": "server"
+            }
+        },
+        "servers": {
+            "server": { "info_serial": 0, "info": {} }
+        }
+    }"#;
+
+    #[test]
+    fn authorization() {
+        assert!(is_authorized("secret", Some("Bearer secret")));
+        assert!(!is_authorized("secret", Some("Bearer secre")));
+        assert!(!is_authorized("secret", Some("secret")));
+        assert!(!is_authorized("secret", None));
+    }
+
+    #[test]
+    fn push_and_expire() {
+        let replication = Replication {
+            secret: "secret".to_owned(),
+            instance: "a".to_owned(),
+            push_peers: Vec::new(),
+            pull_peers: Vec::new(),
+            peer_dumps: Default::default(),
+        };
+        let timekeeper = Timekeeper::new();
+        let auth = Some("Bearer secret");
+        let push = |auth, instance| {
+            replication
+                .handle_push(auth, instance, DUMP.as_bytes(), timekeeper)
+                .status()
+        };
+
+        assert_eq!(
+            push(Some("Bearer wrong"), Some("b")),
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(push(auth, None), StatusCode::BAD_REQUEST);
+        assert_eq!(push(auth, Some("")), StatusCode::BAD_REQUEST);
+        assert_eq!(push(auth, Some("a")), StatusCode::BAD_REQUEST);
+        assert!(replication.current_peer_dumps(timekeeper.now()).is_empty());
+
+        assert_eq!(push(auth, Some("b")), StatusCode::OK);
+        let dumps = replication.current_peer_dumps(timekeeper.now());
+        assert_eq!(dumps.len(), 1);
+        let mut servers = Servers::new();
+        servers.merge(&dumps[0]);
+        assert_eq!(servers.addresses.len(), 1);
+        assert!(servers.servers.contains_key("server"));
+
+        // The dump is relative to the time it was received.
+        let pushed = dumps[0].now;
+        let timeout = SERVER_TIMEOUT_SECONDS as i64 * 1_000;
+        let dumps = replication.current_peer_dumps(Timestamp(pushed.0 + timeout));
+        assert_eq!(dumps.len(), 1);
+        let dumps = replication.current_peer_dumps(Timestamp(pushed.0 + timeout + 1));
+        assert!(dumps.is_empty());
+        assert!(replication.peer_dumps.lock().unwrap().is_empty());
+    }
+}
@@ -4,7 +4,7 @@ use std::{
 };
 
 use clap::Parser;
-use client_extra::particles_split::Particles06Part;
+use client_extra::particles_split::{Particles06ConvertResult, Particles06Part};
 use tar::Header;
 
 #[derive(Parser, Debug)]
@@ -17,6 +17,10 @@ struct Args {
     /// Put the resulting assets into a tar archieve.
     #[arg(short, long, default_value_t = false, action = clap::ArgAction::Set)]
     tar: bool,
+    /// Pack the split directory `file` back into
+    /// a 0.6 texture, written to the file `output`.
+    #[arg(short, long, default_value_t = false, action = clap::ArgAction::Set)]
+    reverse: bool,
 }
 
 struct TarFile {
@@ -62,9 +66,48 @@ fn write_part(write_mode: &mut WriteMode<'_>, part: Particles06Part, output: &Pa
     }
 }
 
+fn read_part(input: &Path, name: &str) -> Particles06Part {
+    let file = std::fs::read(input.join(format!("{name}.png")))
+        .unwrap_or_else(|err| panic!("failed to read {name} in {input:?}: {err}"));
+    let mut mem: Vec<u8> = Default::default();
+    let img = image_utils::png::load_png_image_as_rgba(&file, |width, height, bytes_per_pixel| {
+        mem.resize(width * height * bytes_per_pixel, Default::default());
+        &mut mem
+    })
+    .unwrap()
+    .to_persistent();
+    Particles06Part {
+        data: img.data,
+        width: img.width,
+        height: img.height,
+    }
+}
+
+fn pack(input: &Path, output: &Path) {
+    let converted = Particles06ConvertResult {
+        slice: read_part(input, "slice_001"),
+        ball: read_part(input, "ball_001"),
+        splat: std::array::from_fn(|index| read_part(input, &format!("splat_{:03}", index + 1))),
+
+        smoke: read_part(input, "smoke_001"),
+        shell: read_part(input, "shell_001"),
+        explosion: [read_part(input, "explosion_001")],
+        airjump: read_part(input, "airjump_001"),
+        hit: [read_part(input, "hit_001")],
+    };
+    let packed = client_extra::particles_split::pack_06_particles(&converted).unwrap();
+    let png = image_utils::png::save_png_image(&packed.data, packed.width, packed.height).unwrap();
+    std::fs::write(output, png).unwrap();
+}
+
 fn main() {
     let args = Args::parse();
 
+    if args.reverse {
+        pack(Path::new(&args.file), &args.output);
+        return;
+    }
+
     let file = std::fs::read(args.file).unwrap();
     let mut mem: Vec<u8> = Default::default();
     let img: image_utils::png::PngResult<'_> =
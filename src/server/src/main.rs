@@ -2,7 +2,7 @@ use std::sync::{atomic::AtomicBool, Arc};
 
 use base::system::System;
 use game_base::local_server_info::LocalServerInfo;
-use game_server::server::ddnet_server_main;
+use game_server::{econ::EconLogger, server::ddnet_server_main};
 use network::network::utils::create_certifified_keys;
 
 fn main() {
@@ -10,7 +10,10 @@ fn main() {
     if std::env::var("RUST_LOG").is_err() {
         unsafe { std::env::set_var("RUST_LOG", "info") };
     }
-    env_logger::init();
+    // the log is also streamed to the external console
+    let logger = env_logger::Builder::from_default_env().build();
+    let max_level = logger.filter();
+    EconLogger::init(Box::new(logger), max_level).unwrap();
 
     let mut args: Vec<_> = std::env::args().collect();
     // TODO: don't rely on first arg being executable
@@ -1,5 +1,7 @@
+use std::path::Path;
+
 use clap::Parser;
-use client_extra::skin_split::Skin06Part;
+use client_extra::skin_split::{Skin06ConvertResult, Skin06Part};
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
@@ -8,6 +10,10 @@ struct Args {
     file: String,
     /// output path (directory)
     output: String,
+    /// Pack the split skin directory `file` back into
+    /// a 0.6 skin, written to the file `output`.
+    #[arg(short, long, default_value_t = false, action = clap::ArgAction::Set)]
+    reverse: bool,
 }
 
 fn write_part(part: Skin06Part, output: &str, name: &str) {
@@ -15,9 +21,58 @@ fn write_part(part: Skin06Part, output: &str, name: &str) {
     std::fs::write(output.to_string() + "/" + name + ".png", png).unwrap();
 }
 
+fn read_part(input: &Path, name: &str) -> Skin06Part {
+    let file = std::fs::read(input.join(format!("{name}.png")))
+        .unwrap_or_else(|err| panic!("failed to read {name} in {input:?}: {err}"));
+    let mut mem: Vec<u8> = Default::default();
+    let img = image_utils::png::load_png_image_as_rgba(&file, |width, height, bytes_per_pixel| {
+        mem.resize(width * height * bytes_per_pixel, Default::default());
+        &mut mem
+    })
+    .unwrap()
+    .to_persistent();
+    Skin06Part {
+        data: img.data,
+        width: img.width,
+        height: img.height,
+    }
+}
+
+/// 0.6 skins only have a single hand, foot & set of eyes,
+/// so the left parts of the split skin are used.
+fn pack(input: &Path, output: &str) {
+    let skin = Skin06ConvertResult {
+        body: read_part(input, "body"),
+        body_outline: read_part(input, "body_outline"),
+
+        hand: read_part(input, "hand_left"),
+        hand_outline: read_part(input, "hand_left_outline"),
+
+        foot: read_part(input, "foot_left"),
+        foot_outline: read_part(input, "foot_left_outline"),
+
+        eye_normal: read_part(input, "eyes_left/normal"),
+        eye_angry: read_part(input, "eyes_left/angry"),
+        eye_pain: read_part(input, "eyes_left/pain"),
+        eye_happy: read_part(input, "eyes_left/happy"),
+        eye_dead: read_part(input, "eyes_left/dead"),
+        eye_surprised: read_part(input, "eyes_left/surprised"),
+
+        watermark: read_part(input, "watermark"),
+    };
+    let packed = client_extra::skin_split::pack_06_skin(&skin).unwrap();
+    let png = image_utils::png::save_png_image(&packed.data, packed.width, packed.height).unwrap();
+    std::fs::write(output, png).unwrap();
+}
+
 fn main() {
     let args = Args::parse();
 
+    if args.reverse {
+        pack(Path::new(&args.file), &args.output);
+        return;
+    }
+
     let file = std::fs::read(args.file).unwrap();
     let mut mem: Vec<u8> = Default::default();
     let img: image_utils::png::PngResult<'_> =
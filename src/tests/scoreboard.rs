@@ -88,6 +88,7 @@ pub fn test_scoreboard(
             score_limit: 50,
             time_limit: Some(Duration::from_secs(60 * 60)),
         },
+        match_over: false,
     };
 
     let gen = IdGenerator::new();
@@ -105,6 +106,7 @@ pub fn test_scoreboard(
                         ping: Duration::from_millis(999),
                         ..Default::default()
                     }),
+                    stats: None,
                 });
 
                 if let Some(stages) = (i % 3 == 0).then_some(stages.as_deref_mut()).flatten() {